@@ -0,0 +1,61 @@
+//! 빌드 정보를 컴파일 시점 환경 변수로 주입합니다.
+//!
+//! 변수 이름은 `vergen` 크레이트와 같은 이름(`VERGEN_*`)을 사용합니다.
+//! git이나 `.git` 디렉터리가 없는 환경(예: Docker 빌드)에서는 `unknown`으로 채웁니다.
+
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+use time::format_description::well_known::Rfc3339;
+use time::OffsetDateTime;
+
+fn command_output(program: &str, args: &[&str]) -> Option<String> {
+    let output = Command::new(program).args(args).output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    String::from_utf8(output.stdout).ok().map(|s| s.trim().to_string())
+}
+
+/// 커밋이 바뀌면 다시 빌드되도록 `.git`이 있을 때만 감시 경로를 출력합니다.
+///
+/// 브랜치에 커밋하면 `.git/HEAD`는 그대로이고 HEAD가 가리키는 ref 파일만 바뀌므로 ref 파일도 감시합니다.
+fn emit_git_rerun_hints() {
+    let git_dir = Path::new(".git");
+    let Ok(head) = fs::read_to_string(git_dir.join("HEAD")) else {
+        return;
+    };
+
+    println!("cargo:rerun-if-changed=.git/HEAD");
+    println!("cargo:rerun-if-changed=.git/index");
+    if let Some(reference) = head.trim().strip_prefix("ref: ") {
+        if git_dir.join(reference).exists() {
+            println!("cargo:rerun-if-changed=.git/{}", reference);
+        } else {
+            // `git gc` 이후에는 ref가 packed-refs에만 남음
+            println!("cargo:rerun-if-changed=.git/packed-refs");
+        }
+    }
+}
+
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+    emit_git_rerun_hints();
+
+    let git_sha = command_output("git", &["rev-parse", "HEAD"])
+        .unwrap_or_else(|| "unknown".to_string());
+    let build_timestamp = OffsetDateTime::now_utc()
+        .format(&Rfc3339)
+        .unwrap_or_else(|_| "unknown".to_string());
+    let rustc = std::env::var("RUSTC").unwrap_or_else(|_| "rustc".to_string());
+    // "rustc 1.85.0 (4d91de4e4 2025-02-17)" → "1.85.0"
+    let rustc_semver = command_output(&rustc, &["--version"])
+        .and_then(|version| version.split_whitespace().nth(1).map(str::to_string))
+        .unwrap_or_else(|| "unknown".to_string());
+    let target = std::env::var("TARGET").unwrap_or_else(|_| "unknown".to_string());
+
+    println!("cargo:rustc-env=VERGEN_GIT_SHA={}", git_sha);
+    println!("cargo:rustc-env=VERGEN_BUILD_TIMESTAMP={}", build_timestamp);
+    println!("cargo:rustc-env=VERGEN_RUSTC_SEMVER={}", rustc_semver);
+    println!("cargo:rustc-env=VERGEN_CARGO_TARGET_TRIPLE={}", target);
+}
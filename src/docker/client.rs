@@ -1,10 +1,14 @@
 use async_trait::async_trait;
 use bollard::container::ListContainersOptions;
-use bollard::models::{ContainerSummary, EventMessage};
+use bollard::models::{ContainerInspectResponse, ContainerSummary, EventMessage};
+use bollard::network::InspectNetworkOptions;
 use bollard::system::EventsOptions;
 use futures_util::{Stream, StreamExt};
+use std::env;
 use std::pin::Pin;
+use tracing::debug;
 use crate::docker::DockerError;
+use crate::settings::DockerSettings;
 
 #[async_trait]
 pub trait DockerClient: Send + Sync {
@@ -19,6 +23,23 @@ pub trait DockerClient: Send + Sync {
         &self, 
         options: Option<EventsOptions<String>>
     ) -> Pin<Box<dyn Stream<Item = Result<EventMessage, DockerError>> + Send>>;
+
+    /// 컨테이너 상세 정보(HEALTHCHECK 설정, 환경 변수 등)를 조회합니다.
+    async fn inspect_container(&self, id: &str) -> Result<ContainerInspectResponse, DockerError>;
+
+    /// 프록시 자신이 연결된 네트워크를 찾습니다.
+    ///
+    /// 컨테이너 안에서 실행 중이 아니거나 감지할 수 없으면 `None`을 반환합니다.
+    async fn detect_network(&self) -> Result<Option<String>, DockerError> {
+        Ok(None)
+    }
+
+    /// Docker 데몬의 API 버전을 조회합니다. (예: `1.43`)
+    ///
+    /// 조회를 지원하지 않으면 `None`을 반환합니다.
+    async fn api_version(&self) -> Result<Option<String>, DockerError> {
+        Ok(None)
+    }
 }
 
 impl Clone for Box<dyn DockerClient> {
@@ -33,6 +54,9 @@ pub struct BollardDockerClient {
 }
 
 impl BollardDockerClient {
+    /// Docker 요청 제한 시간 (초, bollard 기본값과 동일)
+    const TIMEOUT_SECS: u64 = 120;
+
     pub async fn new() -> Result<Self, DockerError> {
         let docker = bollard::Docker::connect_with_local_defaults()
             .map_err(|e| DockerError::ConnectionError {
@@ -42,6 +66,83 @@ impl BollardDockerClient {
         
         Ok(Self { inner: docker })
     }
+
+    /// `docker.host` 설정에 맞는 방식으로 Docker 데몬에 연결합니다.
+    ///
+    /// TLS 연결은 `docker.cert_path` 디렉터리의 `ca.pem`, `cert.pem`, `key.pem`을 사용합니다.
+    pub async fn from_settings(settings: &DockerSettings) -> Result<Self, DockerError> {
+        let host = DockerHost::parse(settings.host.as_deref(), settings.tls_verify)?;
+        let version = bollard::API_DEFAULT_VERSION;
+        let docker = match &host {
+            DockerHost::LocalDefaults => bollard::Docker::connect_with_local_defaults(),
+            DockerHost::Unix(path) => bollard::Docker::connect_with_unix(path, Self::TIMEOUT_SECS, version),
+            DockerHost::Tcp(addr) => bollard::Docker::connect_with_http(addr, Self::TIMEOUT_SECS, version),
+            DockerHost::TcpTls(addr) => {
+                let cert_dir = settings.cert_path.as_ref().ok_or_else(|| DockerError::InvalidHost {
+                    host: addr.clone(),
+                    reason: "TLS 연결에는 인증서 디렉터리(cert_path)가 필요합니다".to_string(),
+                })?;
+                let cert_dir = cert_dir.as_path();
+                bollard::Docker::connect_with_ssl(
+                    addr,
+                    &cert_dir.join("key.pem"),
+                    &cert_dir.join("cert.pem"),
+                    &cert_dir.join("ca.pem"),
+                    Self::TIMEOUT_SECS,
+                    version,
+                )
+            }
+        };
+
+        let docker = docker.map_err(|e| DockerError::ConnectionError {
+            source: e,
+            context: format!("Docker 데몬 연결 실패: {:?}", host),
+        })?;
+        debug!(host = ?host, "Docker 클라이언트 생성");
+        Ok(Self { inner: docker })
+    }
+}
+
+/// Docker 데몬 연결 방식
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DockerHost {
+    /// `DOCKER_HOST` 또는 로컬 기본 소켓
+    LocalDefaults,
+    /// Unix 소켓 경로
+    Unix(String),
+    /// 평문 TCP 주소
+    Tcp(String),
+    /// TLS TCP 주소
+    TcpTls(String),
+}
+
+impl DockerHost {
+    /// Docker 데몬 주소를 해석합니다.
+    ///
+    /// `tcp://` 주소는 `tls_verify`가 켜져 있으면 TLS로 연결합니다.
+    /// bollard가 SSH 전송을 지원하지 않으므로 `ssh://` 주소는 거부합니다.
+    pub fn parse(host: Option<&str>, tls_verify: bool) -> Result<Self, DockerError> {
+        let Some(host) = host.map(str::trim).filter(|host| !host.is_empty()) else {
+            return Ok(Self::LocalDefaults);
+        };
+        let invalid = |reason: &str| DockerError::InvalidHost {
+            host: host.to_string(),
+            reason: reason.to_string(),
+        };
+
+        let (scheme, rest) = host.split_once("://").ok_or_else(|| invalid("스킴이 없습니다"))?;
+        if rest.is_empty() {
+            return Err(invalid("주소가 비어 있습니다"));
+        }
+        match scheme {
+            "unix" => Ok(Self::Unix(rest.to_string())),
+            "tcp" if tls_verify => Ok(Self::TcpTls(rest.to_string())),
+            "tcp" | "http" => Ok(Self::Tcp(rest.to_string())),
+            "https" => Ok(Self::TcpTls(rest.to_string())),
+            "ssh" => Err(invalid("SSH 연결은 지원하지 않습니다. SSH 터널로 Unix 소켓이나 TCP 포트를 전달하세요")),
+            _ => Err(invalid("unix, tcp, http, https 스킴만 지원합니다")),
+        }
+    }
 }
 
 #[async_trait]
@@ -76,4 +177,152 @@ impl DockerClient for BollardDockerClient {
         
         Box::pin(stream)
     }
-}
\ No newline at end of file
+
+    async fn inspect_container(&self, id: &str) -> Result<ContainerInspectResponse, DockerError> {
+        self.inner.inspect_container(id, None)
+            .await
+            .map_err(|e| match e {
+                bollard::errors::Error::DockerResponseServerError { status_code: 404, .. } => {
+                    DockerError::ContainerConfigError {
+                        container_id: id.to_string(),
+                        reason: "컨테이너를 찾을 수 없음".to_string(),
+                        context: None,
+                    }
+                }
+                e => DockerError::ConnectionError {
+                    source: e,
+                    context: format!("컨테이너 {} 조회 실패", id),
+                },
+            })
+    }
+
+    async fn detect_network(&self) -> Result<Option<String>, DockerError> {
+        // 컨테이너의 HOSTNAME은 기본적으로 컨테이너 ID
+        let hostname = match env::var("HOSTNAME") {
+            Ok(hostname) if !hostname.is_empty() => hostname,
+            _ => return Ok(None),
+        };
+
+        let container = self.inspect_container(&hostname).await?;
+
+        let mut networks: Vec<String> = container.network_settings
+            .and_then(|settings| settings.networks)
+            .map(|networks| networks.into_keys().collect())
+            .unwrap_or_default();
+        networks.sort();
+
+        // 오버레이 네트워크를 우선하고, 없으면 첫 번째 사용자 정의 네트워크 사용
+        let mut fallback = None;
+        for name in networks {
+            if matches!(name.as_str(), "bridge" | "host" | "none") {
+                continue;
+            }
+
+            let driver = self.inner
+                .inspect_network(&name, None::<InspectNetworkOptions<String>>)
+                .await
+                .ok()
+                .and_then(|network| network.driver);
+            debug!(network = %name, driver = ?driver, "프록시 네트워크 확인");
+
+            if driver.as_deref() == Some("overlay") {
+                return Ok(Some(name));
+            }
+            fallback.get_or_insert(name);
+        }
+
+        Ok(fallback)
+    }
+
+    async fn api_version(&self) -> Result<Option<String>, DockerError> {
+        match self.inner.version().await {
+            Ok(version) => Ok(version.api_version),
+            // 데몬이 클라이언트 버전보다 오래되면 버전 조회 자체가 거부되므로 에러 메시지에서 최대 지원 버전을 찾음
+            Err(bollard::errors::Error::DockerResponseServerError { message, .. })
+                if max_supported_api_version(&message).is_some() =>
+            {
+                Ok(max_supported_api_version(&message))
+            }
+            Err(e) => Err(DockerError::ConnectionError {
+                source: e,
+                context: "Docker 버전 조회 실패".to_string(),
+            }),
+        }
+    }
+}
+
+/// "Maximum supported API version is 1.41" 형태의 메시지에서 버전을 추출합니다.
+fn max_supported_api_version(message: &str) -> Option<String> {
+    let marker = "Maximum supported API version is ";
+    let start = message.find(marker)? + marker.len();
+    let version: String = message[start..].chars()
+        .take_while(|c| c.is_ascii_digit() || *c == '.')
+        .collect();
+    Some(version.trim_end_matches('.').to_string()).filter(|v| !v.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_max_supported_api_version() {
+        let message = "client version 1.43 is too new. Maximum supported API version is 1.41";
+        assert_eq!(max_supported_api_version(message), Some("1.41".to_string()));
+        assert_eq!(max_supported_api_version("page not found"), None);
+    }
+
+    #[test]
+    fn test_parse_docker_host() {
+        assert_eq!(DockerHost::parse(None, false).unwrap(), DockerHost::LocalDefaults);
+        assert_eq!(DockerHost::parse(Some(" "), true).unwrap(), DockerHost::LocalDefaults);
+        assert_eq!(
+            DockerHost::parse(Some("unix:///var/run/docker.sock"), false).unwrap(),
+            DockerHost::Unix("/var/run/docker.sock".to_string())
+        );
+        assert_eq!(
+            DockerHost::parse(Some("tcp://remote:2375"), false).unwrap(),
+            DockerHost::Tcp("remote:2375".to_string())
+        );
+        assert_eq!(
+            DockerHost::parse(Some("tcp://remote:2376"), true).unwrap(),
+            DockerHost::TcpTls("remote:2376".to_string())
+        );
+        assert_eq!(
+            DockerHost::parse(Some("https://remote:2376"), false).unwrap(),
+            DockerHost::TcpTls("remote:2376".to_string())
+        );
+
+        for host in ["ssh://user@remote", "remote:2375", "tcp://", "npipe:////./pipe/docker"] {
+            assert!(matches!(
+                DockerHost::parse(Some(host), false),
+                Err(DockerError::InvalidHost { .. })
+            ), "{host}");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_from_settings() {
+        let mut settings = DockerSettings {
+            host: Some("unix:///var/run/docker.sock".to_string()),
+            ..Default::default()
+        };
+        assert!(BollardDockerClient::from_settings(&settings).await.is_ok());
+
+        settings.host = Some("tcp://127.0.0.1:2375".to_string());
+        assert!(BollardDockerClient::from_settings(&settings).await.is_ok());
+
+        // TLS는 인증서 디렉터리가 필요
+        settings.tls_verify = true;
+        assert!(matches!(
+            BollardDockerClient::from_settings(&settings).await,
+            Err(DockerError::InvalidHost { .. })
+        ));
+        let dir = tempfile::tempdir().unwrap();
+        settings.cert_path = Some(crate::settings::types::ValidPath::new(dir.path()).unwrap());
+        assert!(matches!(
+            BollardDockerClient::from_settings(&settings).await,
+            Err(DockerError::ConnectionError { .. })
+        ));
+    }
+}
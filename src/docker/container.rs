@@ -1,9 +1,13 @@
+use async_trait::async_trait;
 use bollard::models::ContainerSummary;
-use crate::{docker::DockerError, routing_v2::{BackendService, LoadBalancerStrategy, PathMatcher}};
-use std::net::SocketAddr;
+use crate::{docker::DockerError, routing_v2::{BackendService, LoadBalancerStrategy, PathMatcher, StickyCookieConfig, XAccelRedirect}};
+use std::collections::HashMap;
+use std::net::{IpAddr, SocketAddr};
 use crate::settings::docker::HealthCheckType;
+use crate::settings::types::ValidCidr;
+use ipnet::IpNet;
 use std::sync::atomic::AtomicUsize;
-use tracing::debug;
+use tracing::{debug, warn};
 
 // 불변 데이터 구조
 #[derive(Debug, Clone)]
@@ -17,8 +21,36 @@ pub struct ContainerInfo {
     /// 헬스 체크 설정
     pub health_check: Option<ContainerHealthCheck>,
     pub load_balancer: Option<LoadBalancerStrategy>,
+    /// HTTPS 패스스루(CONNECT 터널링) 여부
+    pub passthrough: bool,
+    /// 라우트 우선순위 (기본값: `BackendService::DOCKER_PRIORITY`)
+    pub priority: u32,
+    /// 업스트림 헤더로 전달할 환경 변수 이름 (`env.headers` 라벨)
+    pub env_header_names: Vec<String>,
+    /// 업스트림 헤더로 전달할 환경 변수 값
+    /// 컨테이너 inspect 결과에서 `env_header_names`에 해당하는 값만 채웁니다.
+    pub env_headers: HashMap<String, String>,
+    /// Docker Compose 프로젝트 이름 (`com.docker.compose.project` 라벨)
+    pub compose_project: Option<String>,
+    /// Docker Compose 서비스 이름 (`com.docker.compose.service` 라벨)
+    pub compose_service: Option<String>,
+    /// 로드밸런서 가중치 (`weight` 또는 `loadbalancer.server.weight` 라벨)
+    pub weight: Option<u32>,
+    /// `X-Forwarded-For` 체인을 신뢰할 클라이언트 대역 (`trusted_proxies` 라벨)
+    pub trusted_proxy_cidrs: Vec<IpNet>,
+    /// RFC 7239 `Forwarded` 헤더 추가 여부 (`rfc7239_forwarded` 라벨)
+    pub rfc7239_forwarded: bool,
+    /// 스티키 세션 쿠키 설정 (`loadbalancer.sticky.cookie.*` 라벨)
+    pub sticky_cookie: Option<StickyCookieConfig>,
+    /// X-Accel-Redirect 처리 설정 (`x_accel_redirect`, `x_accel_redirect.base_path` 라벨)
+    pub x_accel_redirect: Option<XAccelRedirect>,
 }
 
+/// Docker Compose가 컨테이너에 붙이는 프로젝트 라벨
+pub const COMPOSE_PROJECT_LABEL: &str = "com.docker.compose.project";
+/// Docker Compose가 컨테이너에 붙이는 서비스 라벨
+pub const COMPOSE_SERVICE_LABEL: &str = "com.docker.compose.service";
+
 #[derive(Debug, Clone)]
 pub struct ContainerHealthCheck {
     pub enabled: bool,
@@ -28,23 +60,47 @@ pub struct ContainerHealthCheck {
 }
 
 // 순수 함수들의 모음
+#[async_trait]
 pub trait ContainerInfoExtractor: Send + Sync {
     fn clone_box(&self) -> Box<dyn ContainerInfoExtractor>;
     // 부수 효과가 없는 순수 함수들
     fn extract_info(&self, container: &ContainerSummary) -> Result<ContainerInfo, DockerError>;
-    fn create_backend(&self, info: &ContainerInfo) -> Result<BackendService, DockerError>;
-    
-    // 새로운 메서드 추가 (반환 타입 명시)
-    fn parse_socket_addr(&self, ip: &str, port: u16) -> Result<SocketAddr, DockerError> {
-        let addr: SocketAddr = format!("{}:{}", ip, port)
-            .parse::<SocketAddr>()
-            .map_err(|e: std::net::AddrParseError| DockerError::ContainerConfigError {
-                container_id: "unknown".to_string(),
-                reason: format!("잘못된 소켓 주소: {}:{}", ip, port),
-                context: Some(e.to_string()),
-            })?;
-        Ok(addr)
+    async fn create_backend(&self, info: &ContainerInfo) -> Result<BackendService, DockerError>;
+}
+
+/// 백엔드 호스트와 포트를 소켓 주소로 변환합니다.
+///
+/// IP 리터럴은 그대로 파싱하고, `tasks.api` 같은 Docker DNS 이름은 조회합니다.
+pub async fn resolve_backend_addr(host: &str, port: u16) -> Result<SocketAddr, DockerError> {
+    if let Ok(ip) = host.parse::<IpAddr>() {
+        return Ok(SocketAddr::new(ip, port));
     }
+
+    let resolve_error = |context: String| DockerError::ContainerConfigError {
+        container_id: "unknown".to_string(),
+        reason: format!("백엔드 주소 해석 실패: {}:{}", host, port),
+        context: Some(context),
+    };
+
+    let mut addrs = tokio::net::lookup_host((host, port))
+        .await
+        .map_err(|e| resolve_error(e.to_string()))?;
+
+    let addr = addrs.next()
+        .ok_or_else(|| resolve_error("DNS 조회 결과 없음".to_string()))?;
+    debug!(host = %host, addr = %addr, "백엔드 주소 DNS 조회 완료");
+    Ok(addr)
+}
+
+/// 컨테이너 환경 변수(`KEY=VALUE` 목록)에서 지정된 이름의 값만 골라냅니다.
+///
+/// 컨테이너에 없는 변수는 건너뜁니다.
+pub fn select_env_vars(names: &[String], env: &[String]) -> HashMap<String, String> {
+    env.iter()
+        .filter_map(|entry| entry.split_once('='))
+        .filter(|(key, _)| names.iter().any(|name| name == key))
+        .map(|(key, value)| (key.to_string(), value.to_string()))
+        .collect()
 }
 
 impl Clone for Box<dyn ContainerInfoExtractor> {
@@ -57,6 +113,8 @@ impl Clone for Box<dyn ContainerInfoExtractor> {
 pub struct DefaultExtractor {
     network_name: String,
     label_prefix: String,
+    /// `label_prefix` 다음으로 시도할 추가 라벨 접두사
+    additional_label_prefixes: Vec<String>,
 }
 
 impl  DefaultExtractor {
@@ -153,15 +211,13 @@ impl  DefaultExtractor {
             .unwrap_or(80)
     }
 
-    fn parse_socket_addr(&self, ip: &str, port: u16) -> Result<SocketAddr, DockerError> {
-        format!("{}:{}", ip, port)
-            .parse()
-            .map_err(|_| DockerError::AddressParseError {
-                container_id: "unknown".to_string(),
-                address: format!("{}:{}", ip, port),
-                network: self.network_name.clone(),
-                context: None,
-            })
+    fn extract_weight(&self, labels: &Option<std::collections::HashMap<String, String>>) -> Option<u32> {
+        let labels = labels.as_ref()?;
+        labels.get(&format!("{}weight", self.label_prefix))
+            .or_else(|| labels.iter()
+                .find(|(k, _)| k.contains(".loadbalancer.server.weight"))
+                .map(|(_, v)| v))
+            .and_then(|v| v.trim().parse().ok())
     }
 
     fn extract_router_name(&self, labels: &Option<std::collections::HashMap<String, String>>) -> Option<String> {
@@ -188,6 +244,72 @@ impl  DefaultExtractor {
             })
     }
 
+    fn extract_passthrough(&self, labels: &Option<std::collections::HashMap<String, String>>, router_name: &str) -> bool {
+        labels
+            .as_ref()
+            .and_then(|l| l.get(&format!("{}http.routers.{}.passthrough", self.label_prefix, router_name)))
+            .map(|v| v.to_lowercase() == "true")
+            .unwrap_or(false)
+    }
+
+    fn extract_rfc7239_forwarded(&self, labels: &Option<std::collections::HashMap<String, String>>, router_name: &str) -> bool {
+        labels
+            .as_ref()
+            .and_then(|l| l.get(&format!("{}http.routers.{}.rfc7239_forwarded", self.label_prefix, router_name)))
+            .map(|v| v.to_lowercase() == "true")
+            .unwrap_or(false)
+    }
+
+    /// `x_accel_redirect=true`이면 같은 백엔드에 다시 요청하고, `x_accel_redirect.base_path`가 있으면
+    /// 그 디렉터리의 파일을 제공합니다.
+    fn extract_x_accel_redirect(&self, labels: &Option<std::collections::HashMap<String, String>>, router_name: &str) -> Option<XAccelRedirect> {
+        let labels = labels.as_ref()?;
+        let key = format!("{}http.routers.{}.x_accel_redirect", self.label_prefix, router_name);
+        let base_path = labels.get(&format!("{}.base_path", key))
+            .map(|v| v.trim().to_string())
+            .filter(|v| !v.is_empty());
+        let enabled = labels.get(&key).is_some_and(|v| v.to_lowercase() == "true");
+
+        (enabled || base_path.is_some()).then_some(XAccelRedirect { base_path })
+    }
+
+    fn extract_env_header_names(&self, labels: &Option<std::collections::HashMap<String, String>>) -> Vec<String> {
+        labels
+            .as_ref()
+            .and_then(|l| l.get(&format!("{}env.headers", self.label_prefix)))
+            .map(|v| v.split(',')
+                .map(str::trim)
+                .filter(|name| !name.is_empty())
+                .map(String::from)
+                .collect())
+            .unwrap_or_default()
+    }
+
+    fn extract_priority(&self, labels: &Option<std::collections::HashMap<String, String>>, router_name: &str) -> u32 {
+        labels
+            .as_ref()
+            .and_then(|l| l.get(&format!("{}http.routers.{}.priority", self.label_prefix, router_name)))
+            .and_then(|v| v.trim().parse().ok())
+            .unwrap_or(BackendService::DOCKER_PRIORITY)
+    }
+
+    fn extract_trusted_proxies(&self, labels: &Option<std::collections::HashMap<String, String>>, router_name: &str) -> Vec<IpNet> {
+        labels
+            .as_ref()
+            .and_then(|l| l.get(&format!("{}http.routers.{}.trusted_proxies", self.label_prefix, router_name)))
+            .map(|v| v.split(',')
+                .filter(|cidr| !cidr.trim().is_empty())
+                .filter_map(|cidr| match ValidCidr::new(cidr) {
+                    Ok(cidr) => Some(cidr.as_net()),
+                    Err(e) => {
+                        debug!(error = %e, "잘못된 신뢰 프록시 대역 무시");
+                        None
+                    }
+                })
+                .collect())
+            .unwrap_or_default()
+    }
+
     fn extract_health_check(&self, labels: &Option<std::collections::HashMap<String, String>>) -> Option<ContainerHealthCheck> {
         let labels = labels.as_ref()?;
         
@@ -215,6 +337,12 @@ impl  DefaultExtractor {
                     .and_then(|v| v.parse().ok())
                     .unwrap_or(200),
             }
+        } else if let Some(command) = labels.get(&format!("{}health.exec.command", self.label_prefix))
+            .map(|v| v.split_whitespace().map(String::from).collect::<Vec<_>>())
+            .filter(|command| !command.is_empty())
+        {
+            // 컨테이너 내부 명령 실행 체크
+            HealthCheckType::Exec { command }
         } else if let Some(port) = labels.get(&format!("{}health.tcp.port", self.label_prefix))
             .and_then(|v| v.parse().ok()) 
         {
@@ -249,6 +377,45 @@ impl  DefaultExtractor {
             .is_some()
     }
 
+    fn extract_sticky_cookie(&self, labels: &Option<std::collections::HashMap<String, String>>, service_name: &str) -> Option<StickyCookieConfig> {
+        let labels = labels.as_ref()?;
+        let label = |key: &str| labels.get(&format!(
+            "{}http.services.{}.loadbalancer.sticky.cookie.{}", self.label_prefix, service_name, key
+        ));
+
+        let cookie_name = label("name")?.trim();
+        if !StickyCookieConfig::is_valid_cookie_name(cookie_name) {
+            warn!(cookie_name = %cookie_name, "잘못된 스티키 쿠키 이름 무시");
+            return None;
+        }
+
+        let mut config = StickyCookieConfig::new(cookie_name);
+        if let Some(http_only) = label("httpOnly") {
+            config.http_only = http_only.to_lowercase() == "true";
+        }
+        if let Some(secure) = label("secure") {
+            config.secure = secure.to_lowercase() == "true";
+        }
+        if let Some(same_site) = label("sameSite") {
+            match same_site.parse() {
+                Ok(same_site) => config.same_site = same_site,
+                Err(e) => debug!(error = %e, "잘못된 SameSite 값 무시"),
+            }
+        }
+        Some(config)
+    }
+
+    /// 라우터가 사용하는 서비스 이름 (`http.routers.<라우터>.service` 라벨, 없으면 라우터 이름)
+    fn extract_service_name(&self, labels: &Option<std::collections::HashMap<String, String>>, router_name: &str) -> String {
+        labels
+            .as_ref()
+            .and_then(|l| l.get(&format!("{}http.routers.{}.service", self.label_prefix, router_name)))
+            .map(|v| v.trim())
+            .filter(|v| !v.is_empty())
+            .unwrap_or(router_name)
+            .to_string()
+    }
+
     // 기본적으로 라운드 로빈 전략 사용
     fn extract_load_balancer(&self, _labels: &Option<std::collections::HashMap<String, String>>, _service_name: &str) -> Option<LoadBalancerStrategy> {
         Some(LoadBalancerStrategy::RoundRobin {
@@ -256,7 +423,30 @@ impl  DefaultExtractor {
         })
     }
 
+    /// 설정된 접두사를 순서대로 시도해 컨테이너 정보를 추출합니다.
+    ///
+    /// 호스트를 찾을 수 있는 첫 번째 접두사가 사용되며, 모두 실패하면 기본 접두사의 에러를 반환합니다.
     fn extract_info(&self, container: &ContainerSummary) -> Result<ContainerInfo, DockerError> {
+        if self.additional_label_prefixes.is_empty() || self.extract_host(&container.labels).is_ok() {
+            return self.extract_info_with_prefix(container);
+        }
+
+        for prefix in &self.additional_label_prefixes {
+            let extractor = Self {
+                network_name: self.network_name.clone(),
+                label_prefix: prefix.clone(),
+                additional_label_prefixes: Vec::new(),
+            };
+            if extractor.extract_host(&container.labels).is_ok() {
+                debug!(prefix = %prefix, "추가 라벨 접두사로 컨테이너 정보 추출");
+                return extractor.extract_info_with_prefix(container);
+            }
+        }
+
+        self.extract_info_with_prefix(container)
+    }
+
+    fn extract_info_with_prefix(&self, container: &ContainerSummary) -> Result<ContainerInfo, DockerError> {
         let labels = &container.labels;
         
         // 먼저 로드밸런서 활성화 여부 확인
@@ -264,10 +454,34 @@ impl  DefaultExtractor {
         
         let host = self.extract_host(labels)?;
         let port = self.extract_port(labels);
-        let router_name = self.extract_router_name(labels);
+        let compose_project = labels.as_ref().and_then(|l| l.get(COMPOSE_PROJECT_LABEL)).cloned();
+        let compose_service = labels.as_ref().and_then(|l| l.get(COMPOSE_SERVICE_LABEL)).cloned();
+        // 라우터 라벨이 없으면 Compose 프로젝트/서비스 이름으로 기본 라우터 이름 생성
+        let router_name = self.extract_router_name(labels).or_else(|| {
+            Some(format!("{}-{}", compose_project.as_ref()?, compose_service.as_ref()?))
+        });
         let middlewares = router_name
             .as_ref()
             .and_then(|name| self.extract_middlewares(labels, name));
+        let passthrough = router_name
+            .as_ref()
+            .is_some_and(|name| self.extract_passthrough(labels, name));
+        let sticky_cookie = router_name
+            .as_ref()
+            .and_then(|name| self.extract_sticky_cookie(labels, &self.extract_service_name(labels, name)));
+        let rfc7239_forwarded = router_name
+            .as_ref()
+            .is_some_and(|name| self.extract_rfc7239_forwarded(labels, name));
+        let x_accel_redirect = router_name
+            .as_ref()
+            .and_then(|name| self.extract_x_accel_redirect(labels, name));
+        let priority = router_name
+            .as_ref()
+            .map_or(BackendService::DOCKER_PRIORITY, |name| self.extract_priority(labels, name));
+        let trusted_proxy_cidrs = router_name
+            .as_ref()
+            .map(|name| self.extract_trusted_proxies(labels, name))
+            .unwrap_or_default();
         
         let ip = self.extract_container_ip(container)?;
 
@@ -288,6 +502,17 @@ impl  DefaultExtractor {
             router_name,
             health_check: self.extract_health_check(labels),
             load_balancer,
+            passthrough,
+            priority,
+            env_header_names: self.extract_env_header_names(labels),
+            env_headers: HashMap::new(),
+            compose_project,
+            compose_service,
+            weight: self.extract_weight(labels),
+            trusted_proxy_cidrs,
+            rfc7239_forwarded,
+            sticky_cookie,
+            x_accel_redirect,
         })
     }
 
@@ -327,10 +552,18 @@ impl  DefaultExtractor {
         Self {
             network_name,
             label_prefix,
+            additional_label_prefixes: Vec::new(),
         }
     }
+
+    /// 기본 접두사에서 라벨을 찾지 못했을 때 시도할 추가 접두사를 설정합니다.
+    pub fn with_additional_prefixes(mut self, prefixes: Vec<String>) -> Self {
+        self.additional_label_prefixes = prefixes;
+        self
+    }
 }
 
+#[async_trait]
 impl ContainerInfoExtractor for DefaultExtractor {
     fn clone_box(&self) -> Box<dyn ContainerInfoExtractor> {
         Box::new(self.clone())
@@ -340,9 +573,18 @@ impl ContainerInfoExtractor for DefaultExtractor {
         DefaultExtractor::extract_info(self, container)
     }
 
-    fn create_backend(&self, info: &ContainerInfo) -> Result<BackendService, DockerError> {
-        let addr = self.parse_socket_addr(&info.ip, info.port)?;
+    async fn create_backend(&self, info: &ContainerInfo) -> Result<BackendService, DockerError> {
+        let addr = resolve_backend_addr(&info.ip, info.port).await?;
         let mut service = BackendService::with_router(addr, info.router_name.clone());
+        service.set_passthrough(info.passthrough);
+        service.set_priority(info.priority);
+        service.set_env_headers(info.env_headers.clone());
+        service.set_trusted_proxy_cidrs(info.trusted_proxy_cidrs.clone());
+        service.set_rfc7239_forwarded(info.rfc7239_forwarded);
+        service.set_sticky_cookie(info.sticky_cookie.clone());
+        if let Some(x_accel_redirect) = &info.x_accel_redirect {
+            service.enable_x_accel_redirect(x_accel_redirect.base_path.clone());
+        }
         
         // 미들웨어 설정
         if let Some(middlewares) = &info.middlewares {
@@ -354,6 +596,12 @@ impl ContainerInfoExtractor for DefaultExtractor {
             service.enable_load_balancer(strategy.clone());
         }
 
+        // 가중치 라벨이 있으면 가중치 기반 로드밸런서로 이 주소의 가중치 적용
+        if let Some(weight) = info.weight {
+            service.use_weighted_strategy();
+            service.set_weight(addr, weight)?;
+        }
+
         Ok(service)
     }
 } 
@@ -38,6 +38,16 @@ pub enum DockerError {
         container_id: String,
         error: String,
     },
+    /// Docker 데몬 API 버전이 요구 버전보다 낮음
+    IncompatibleApiVersion {
+        required: String,
+        found: String,
+    },
+    /// 지원하지 않거나 잘못된 Docker 데몬 주소
+    InvalidHost {
+        host: String,
+        reason: String,
+    },
 }
 
 impl fmt::Display for DockerError {
@@ -61,6 +71,11 @@ impl fmt::Display for DockerError {
                     container_id, network, context.as_deref().unwrap_or("No context provided"), reason),
             DockerError::BackendError { container_id, error } => 
                 write!(f, "백엔드 서비스 오류 (컨테이너 {}): {}", container_id, error),
+            DockerError::IncompatibleApiVersion { required, found } =>
+                write!(f, "Docker API 버전 {}은(는) 지원되지 않습니다. API {} 이상(Docker Engine 24.0 이상)으로 업그레이드하세요",
+                    found, required),
+            DockerError::InvalidHost { host, reason } =>
+                write!(f, "잘못된 Docker 데몬 주소 {}: {}", host, reason),
         }
     }
 }
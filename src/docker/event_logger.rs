@@ -0,0 +1,101 @@
+use std::fs::OpenOptions;
+use std::io::{self, Write};
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+use serde::Serialize;
+use tracing::warn;
+use super::DockerEvent;
+
+/// 표준 출력을 의미하는 로그 경로
+pub const STDOUT_PATH: &str = "-";
+
+/// 로그 한 줄에 기록되는 항목
+#[derive(Serialize)]
+struct EventLogEntry<'a> {
+    timestamp_unix: u64,
+    event: &'a DockerEvent,
+}
+
+/// Docker 이벤트를 JSON Lines 형식으로 기록합니다.
+///
+/// 외부 로그 수집기가 읽을 수 있도록 이벤트마다 한 줄의 JSON을 씁니다.
+pub struct DockerEventLogger {
+    writer: Mutex<Box<dyn Write + Send>>,
+}
+
+impl DockerEventLogger {
+    /// 임의의 출력 대상으로 로거를 생성합니다.
+    pub fn new(writer: Box<dyn Write + Send>) -> Self {
+        Self { writer: Mutex::new(writer) }
+    }
+
+    /// 파일 경로로 로거를 생성합니다. `-`이면 표준 출력에 기록합니다.
+    ///
+    /// 파일은 추가 모드로 열리며, 없으면 생성합니다.
+    pub fn from_path(path: &str) -> io::Result<Self> {
+        if path == STDOUT_PATH {
+            return Ok(Self::new(Box::new(io::stdout())));
+        }
+
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+        Ok(Self::new(Box::new(file)))
+    }
+
+    /// 이벤트를 기록합니다. 기록 실패는 경고만 남기고 무시합니다.
+    pub fn log(&self, event: &DockerEvent) {
+        if let Err(e) = self.write_event(event) {
+            warn!(error = %e, "Docker 이벤트 로그 기록 실패");
+        }
+    }
+
+    fn write_event(&self, event: &DockerEvent) -> io::Result<()> {
+        let entry = EventLogEntry {
+            timestamp_unix: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|d| d.as_secs())
+                .unwrap_or_default(),
+            event,
+        };
+        let mut line = serde_json::to_vec(&entry)?;
+        line.push(b'\n');
+
+        let mut writer = self.writer.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        writer.write_all(&line)?;
+        writer.flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::docker::HealthStatus;
+
+    #[test]
+    fn test_log_events_as_json_lines() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("events.log");
+        let logger = DockerEventLogger::from_path(path.to_str().unwrap()).unwrap();
+
+        logger.log(&DockerEvent::ContainerStopped {
+            container_id: "abc".to_string(),
+            host: "app.local".to_string(),
+        });
+        logger.log(&DockerEvent::ContainerHealthChanged {
+            container_id: "abc".to_string(),
+            status: HealthStatus::Unhealthy,
+            message: "timeout".to_string(),
+            host: "app.local".to_string(),
+            consecutive_failures: 3,
+        });
+
+        let content = std::fs::read_to_string(&path).unwrap();
+        let lines: Vec<serde_json::Value> = content.lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(lines.len(), 2);
+        assert!(lines[0]["timestamp_unix"].as_u64().unwrap() > 0);
+        assert_eq!(lines[0]["event"]["ContainerStopped"]["host"], "app.local");
+        assert_eq!(lines[1]["event"]["ContainerHealthChanged"]["status"], "Unhealthy");
+        assert_eq!(lines[1]["event"]["ContainerHealthChanged"]["consecutive_failures"], 3);
+    }
+}
@@ -1,10 +1,12 @@
 use std::collections::HashMap;
+use std::net::SocketAddr;
+use serde::Serialize;
 use crate::docker::DockerError;
 use crate::routing_v2::{BackendService, PathMatcher};
 use crate::middleware::MiddlewareConfig;
 
 /// 컨테이너 헬스 상태
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize)]
 pub enum HealthStatus {
     /// 상태 확인 전
     Unknown,
@@ -16,7 +18,13 @@ pub enum HealthStatus {
     Starting,
 }
 
-#[derive(Debug)]
+/// 라우팅 테이블 키 (호스트, 경로 매처)
+pub type RouteKey = (String, PathMatcher);
+
+/// Docker 이벤트
+///
+/// 이벤트 로그 스트리밍을 위해 JSON으로 직렬화할 수 있습니다. (`{"ContainerStopped": {...}}` 형식)
+#[derive(Debug, Serialize)]
 pub enum DockerEvent {
     /// 컨테이너 시작
     ContainerStarted {
@@ -39,9 +47,13 @@ pub enum DockerEvent {
         path_matcher: Option<PathMatcher>,
     },
     /// 에러 상황
-    Error(DockerError),
-    /// 라우팅 테이블 업데이트
-    RoutesUpdated(HashMap<(String, PathMatcher), BackendService>),
+    Error(#[serde(serialize_with = "serialize_display")] DockerError),
+    /// 라우팅 테이블 변경분
+    RoutesDiff {
+        added: Vec<(RouteKey, BackendService)>,
+        removed: Vec<RouteKey>,
+        changed: Vec<(RouteKey, BackendService)>,
+    },
     MiddlewareConfigsUpdated(Vec<(String, MiddlewareConfig)>),
     /// 컨테이너 헬스 상태 변경
     ContainerHealthChanged {
@@ -51,4 +63,51 @@ pub enum DockerEvent {
         host: String,
         consecutive_failures: u64,
     },
-}
\ No newline at end of file
+    /// 백엔드 가중치 변경 (`weight` 라벨 변경)
+    BackendWeightChanged {
+        host: String,
+        addr: SocketAddr,
+        new_weight: u32,
+    },
+}
+
+impl DockerEvent {
+    /// 이전 라우트와 새 라우트를 비교하여 `RoutesDiff` 이벤트를 만듭니다.
+    // `PathMatcher`의 해시와 동등성은 종류와 패턴만 사용하므로 내부 정규식은 키를 바꾸지 않음
+    #[allow(clippy::mutable_key_type)]
+    pub fn routes_diff(
+        previous: &HashMap<RouteKey, BackendService>,
+        current: HashMap<RouteKey, BackendService>,
+    ) -> Self {
+        let removed = previous.keys()
+            .filter(|key| !current.contains_key(*key))
+            .cloned()
+            .collect();
+
+        let mut added = Vec::new();
+        let mut changed = Vec::new();
+        for (key, service) in current {
+            match previous.get(&key) {
+                None => added.push((key, service)),
+                Some(old) if !old.same_config(&service) => changed.push((key, service)),
+                Some(_) => {}
+            }
+        }
+
+        DockerEvent::RoutesDiff { added, removed, changed }
+    }
+
+    /// 변경분이 없는 `RoutesDiff`인지 확인합니다.
+    pub fn is_empty_diff(&self) -> bool {
+        matches!(
+            self,
+            DockerEvent::RoutesDiff { added, removed, changed }
+                if added.is_empty() && removed.is_empty() && changed.is_empty()
+        )
+    }
+}
+
+/// `Display` 표현을 문자열로 직렬화합니다.
+fn serialize_display<T: std::fmt::Display, S: serde::Serializer>(value: &T, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.collect_str(value)
+}
@@ -9,7 +9,9 @@ use bytes::Bytes;
 use tokio::time::timeout;
 use tracing::debug;
 use tokio::net::TcpStream;
+use tokio::process::Command;
 use std::fmt;
+use std::process::Stdio;
 
 use crate::settings::docker::HealthCheckType;
 use super::{DockerError, events_types::HealthStatus};
@@ -145,6 +147,53 @@ impl HealthChecker for TcpHealthChecker {
     }
 }
 
+/// 컨테이너 내부 명령 실행 헬스 체커
+///
+/// `docker exec`로 컨테이너 네임스페이스에서 명령을 실행하고 종료 코드 0이면 정상으로 판단합니다.
+pub struct ExecHealthChecker {
+    container_id: String,
+    command: Vec<String>,
+    timeout_secs: u64,
+}
+
+impl ExecHealthChecker {
+    pub fn new(container_id: String, command: Vec<String>, timeout_secs: u64) -> Self {
+        Self {
+            container_id,
+            command,
+            timeout_secs,
+        }
+    }
+}
+
+#[async_trait]
+impl HealthChecker for ExecHealthChecker {
+    async fn check(&self) -> Result<HealthCheckResult, DockerError> {
+        debug!("Exec 헬스 체크 시작: {} {:?}", self.container_id, self.command);
+
+        let output = Command::new("docker")
+            .arg("exec")
+            .arg(&self.container_id)
+            .args(&self.command)
+            .stdin(Stdio::null())
+            .kill_on_drop(true)
+            .output();
+
+        match timeout(std::time::Duration::from_secs(self.timeout_secs), output).await {
+            Ok(Ok(output)) if output.status.success() => {
+                Ok(HealthCheckResult::healthy("명령 실행 성공 (exit 0)"))
+            }
+            Ok(Ok(output)) => {
+                let stderr = String::from_utf8_lossy(&output.stderr);
+                Ok(HealthCheckResult::unhealthy(format!("명령 실행 실패 ({}): {}",
+                    output.status, stderr.trim())))
+            }
+            Ok(Err(e)) => Ok(HealthCheckResult::unhealthy(format!("명령 실행 불가: {}", e))),
+            Err(_) => Ok(HealthCheckResult::unhealthy(format!("타임아웃 ({}초)", self.timeout_secs))),
+        }
+    }
+}
+
 /// 헬스 체커 팩토리
 pub struct HealthCheckerFactory;
 
@@ -159,8 +208,18 @@ impl HealthCheckerFactory {
                 TcpHealthChecker::new(addr, check_type, timeout_secs)
                     .map(|checker| Box::new(checker) as Box<dyn HealthChecker>)
             }
+            // 컨테이너 ID가 필요하므로 create_exec 사용
+            HealthCheckType::Exec { .. } => None,
         }
     }
+
+    /// 컨테이너 안에서 명령을 실행하는 헬스 체커를 생성합니다.
+    pub fn create_exec(container_id: String, command: &[String], timeout_secs: u64) -> Option<Box<dyn HealthChecker>> {
+        if command.is_empty() {
+            return None;
+        }
+        Some(Box::new(ExecHealthChecker::new(container_id, command.to_vec(), timeout_secs)))
+    }
 }
 
 /// 컨테이너 헬스 체크 상태 관리
@@ -211,4 +270,30 @@ impl ContainerHealth {
         self.last_check = Some(result);
         Ok(self.last_check.as_ref().unwrap())
     }
-} 
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_create_exec_requires_command() {
+        assert!(HealthCheckerFactory::create_exec("abc".to_string(), &[], 5).is_none());
+        assert!(HealthCheckerFactory::create_exec("abc".to_string(), &["true".to_string()], 5).is_some());
+
+        let exec = HealthCheckType::Exec { command: vec!["true".to_string()] };
+        assert!(HealthCheckerFactory::create("127.0.0.1:80".to_string(), &exec, 5).is_none());
+    }
+
+    #[tokio::test]
+    async fn test_exec_health_check_failure() {
+        // 존재하지 않는 컨테이너(또는 docker 미설치)에서는 비정상
+        let checker = ExecHealthChecker::new(
+            "nonexistent-container-for-test".to_string(),
+            vec!["true".to_string()],
+            5,
+        );
+        let result = checker.check().await.unwrap();
+        assert_eq!(result.status, HealthStatus::Unhealthy);
+    }
+}
@@ -4,23 +4,28 @@ mod retry;
 mod client;
 pub mod container;
 mod health;
+mod event_logger;
 
 pub use client::{BollardDockerClient, DockerClient};
 use container::ContainerInfo;
-pub use container::{ContainerInfoExtractor, DefaultExtractor};
+pub use container::{ContainerInfoExtractor, DefaultExtractor, resolve_backend_addr};
 pub use events_types::{DockerEvent, HealthStatus};
+use events_types::RouteKey;
+pub use event_logger::DockerEventLogger;
 pub use error_types::DockerError;
-pub use retry::{RetryPolicy, with_retry, ContainerRoutesRetry};
+pub use retry::{RetryPolicy, RetryStats, with_retry, with_retry_stats, ContainerRoutesRetry};
+pub use health::{HealthChecker, HttpHealthChecker};
 
 use bollard::container::ListContainersOptions;
 use bollard::models::{ContainerSummary, EventMessage};
 use bollard::system::EventsOptions;
 use futures_util::stream::StreamExt;
 use std::collections::HashMap;
+use std::net::SocketAddr;
 use tokio::sync::mpsc;
-use crate::settings::DockerSettings;
+use crate::settings::{docker::HealthCheckType, DockerSettings};
 use crate::routing_v2::{BackendService, PathMatcher};
-use tracing::{debug, error, info, warn};
+use tracing::{debug, error, info, info_span, warn, Instrument};
 use tokio::time::Duration;
 use std::sync::Arc;
 use crate::middleware::MiddlewareConfig;
@@ -29,15 +34,54 @@ use tokio::{
     task::JoinHandle,
 };
 use self::health::{ContainerHealth, HealthCheckerFactory};
-use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
 use crate::routing_v2::LoadBalancerStrategy;
 
+/// 필요한 최소 Docker API 버전 (bollard 기본 클라이언트 버전)
+pub const MIN_DOCKER_API_VERSION: &str = "1.43";
+
+/// `major.minor` 형식의 API 버전을 비교합니다. 파싱할 수 없으면 호환되지 않는 것으로 봅니다.
+fn is_api_version_at_least(found: &str, required: &str) -> bool {
+    let parse = |version: &str| -> Option<(u32, u32)> {
+        let (major, minor) = version.trim().split_once('.')?;
+        Some((major.parse().ok()?, minor.parse().ok()?))
+    };
+
+    match (parse(found), parse(required)) {
+        (Some(found), Some(required)) => found >= required,
+        _ => false,
+    }
+}
+
 #[derive(Clone)]
 pub struct DockerManager {
     client: Arc<Box<dyn DockerClient>>,
     extractor: Box<dyn ContainerInfoExtractor>,
     config: DockerSettings,
     health_checks: Arc<RwLock<HashMap<String, ContainerHealth>>>,
+    /// Docker 데몬 이벤트 스트림 연결 상태
+    connected: Arc<AtomicBool>,
+    /// 주기적인 헬스 체크 일시 중지 여부
+    health_checks_paused: Arc<AtomicBool>,
+    /// 컨테이너 라우트 조회에서 발생한 누적 재시도 횟수
+    retry_count: Arc<AtomicU64>,
+}
+
+impl std::fmt::Debug for DockerManager {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let health_checks = self.health_checks.try_read()
+            .map(|checks| format!("{} entries", checks.len()))
+            .unwrap_or_else(|_| "<locked>".to_string());
+        f.debug_struct("DockerManager")
+            .field("client", &"<dyn DockerClient>")
+            .field("extractor", &"<dyn ContainerInfoExtractor>")
+            .field("config", &self.config)
+            .field("health_checks", &format_args!("{}", health_checks))
+            .field("connected", &self.connected.load(Ordering::Relaxed))
+            .field("health_checks_paused", &self.health_checks_paused.load(Ordering::Relaxed))
+            .field("retry_count", &self.retry_count.load(Ordering::Relaxed))
+            .finish()
+    }
 }
 
 impl DockerManager {
@@ -52,30 +96,97 @@ impl DockerManager {
             extractor,
             config,
             health_checks: Arc::new(RwLock::new(HashMap::new())),
+            connected: Arc::new(AtomicBool::new(true)),
+            health_checks_paused: Arc::new(AtomicBool::new(false)),
+            retry_count: Arc::new(AtomicU64::new(0)),
         }
     }
 
+    /// 연결 상태 플래그를 반환합니다. 이벤트 스트림 상태에 따라 갱신됩니다.
+    pub fn connection_status(&self) -> Arc<AtomicBool> {
+        self.connected.clone()
+    }
+
+    /// 컨테이너 라우트 조회의 누적 재시도 횟수 카운터를 반환합니다.
+    pub fn retry_count_total(&self) -> Arc<AtomicU64> {
+        self.retry_count.clone()
+    }
+
     /// 기본 구현을 사용하는 팩토리 메서드
-    pub async fn with_defaults(settings: DockerSettings) -> Result<Self, DockerError> {
-        let client = BollardDockerClient::new().await?;
+    pub async fn with_defaults(mut settings: DockerSettings) -> Result<Self, DockerError> {
+        let client = BollardDockerClient::from_settings(&settings).await?;
+
+        if settings.auto_detect_network && settings.uses_default_network() {
+            match client.detect_network().await {
+                Ok(Some(network)) => {
+                    info!(network = %network, "프록시 네트워크 자동 감지");
+                    settings.network = network;
+                }
+                Ok(None) => debug!("프록시 네트워크를 감지하지 못해 기본 네트워크 사용"),
+                Err(e) => warn!(error = %e, "프록시 네트워크 자동 감지 실패, 기본 네트워크 사용"),
+            }
+        }
+
         let extractor = DefaultExtractor::new(
             settings.network.clone(),
-            settings.label_prefix.clone(),
-        );
+            settings.label_prefix.to_string(),
+        ).with_additional_prefixes(settings.additional_label_prefixes.clone());
 
-        Ok(Self::new(
+        let manager = Self::new(
             Box::new(client),
             Box::new(extractor),
             settings,
-        ).await)
+        ).await;
+        manager.check_api_compatibility().await?;
+
+        Ok(manager)
+    }
+
+    /// Docker 데몬 API 버전이 최소 요구 버전 이상인지 확인합니다.
+    ///
+    /// 버전을 알 수 없으면 확인을 건너뜁니다.
+    pub async fn check_api_compatibility(&self) -> Result<(), DockerError> {
+        let Some(found) = self.client.api_version().await? else {
+            debug!("Docker API 버전을 확인할 수 없어 호환성 검사 생략");
+            return Ok(());
+        };
+
+        if !is_api_version_at_least(&found, MIN_DOCKER_API_VERSION) {
+            error!(found = %found, required = MIN_DOCKER_API_VERSION, "지원되지 않는 Docker API 버전");
+            return Err(DockerError::IncompatibleApiVersion {
+                required: MIN_DOCKER_API_VERSION.to_string(),
+                found,
+            });
+        }
+
+        debug!(api_version = %found, "Docker API 버전 확인");
+        Ok(())
     }
 
     /// 컨테이너 라우트를 조회하고 실패 시 재시도합니다.
     pub async fn get_container_routes(&self) -> Result<HashMap<(String, PathMatcher), BackendService>, DockerError> {
+        self.get_container_routes_with_retry_stats().await.map(|(routes, _)| routes)
+    }
+
+    /// 컨테이너 라우트를 조회하고, 재시도 횟수와 소요 시간을 함께 반환합니다.
+    ///
+    /// 재시도 횟수는 조회 성공 여부와 관계없이 `retry_count_total` 카운터에 누적됩니다.
+    pub async fn get_container_routes_with_retry_stats(
+        &self,
+    ) -> Result<(HashMap<(String, PathMatcher), BackendService>, RetryStats), DockerError> {
         let retry_operation = ContainerRoutesRetry { docker_manager: self };
         let policy = RetryPolicy::from(&self.config.retry);
-        
-        with_retry(retry_operation, policy).await
+
+        let (result, stats) = with_retry_stats(retry_operation, policy).await;
+        self.retry_count.fetch_add(u64::from(stats.retries()), Ordering::Relaxed);
+        if stats.attempts > 1 {
+            debug!(
+                attempts = stats.attempts,
+                total_elapsed_ms = stats.total_elapsed.as_millis() as u64,
+                "컨테이너 라우트 조회 재시도 통계"
+            );
+        }
+        result.map(|routes| (routes, stats))
     }
 
     /// 실제 컨테이너 라우트 조회 로직
@@ -89,7 +200,7 @@ impl DockerManager {
         
         for infos in services.values() {
             if !infos.is_empty() {
-                match self.create_backend_service(infos) {
+                match self.create_backend_service(infos).await {
                     Ok((host, path_matcher, service)) => {
                         routes.insert((host, path_matcher), service);
                     }
@@ -133,15 +244,23 @@ impl DockerManager {
     }
 
     /// Docker 이벤트를 구독하고 라우팅 테이블 업데이트를 위한 이벤트를 전송합니다.
-    pub async fn subscribe_to_events(&self) -> mpsc::Receiver<DockerEvent> {
+    ///
+    /// 구독 직후 컨테이너 라우트를 다시 조회해 `current_routes`(라우팅 테이블의 현재 내용)와 비교한
+    /// `RoutesDiff`를 먼저 보냅니다. 연결이 끊긴 동안 사라진 컨테이너의 라우트는 `removed`로 전달됩니다.
+    #[allow(clippy::mutable_key_type)]
+    pub async fn subscribe_to_events(&self, current_routes: &HashMap<RouteKey, BackendService>) -> mpsc::Receiver<DockerEvent> {
         let (tx, rx) = mpsc::channel(32);
         let docker = self.client.clone();
         let config = self.config.clone();
         let health_checks = self.health_checks.clone();
+        let connected = self.connected.clone();
 
-        // 초기 라우트와 미들웨어 설정 전송
+        // 현재 라우팅 테이블과 비교한 라우트 변경분과 미들웨어 설정 전송
         if let Ok(routes) = self.try_get_container_routes().await {
-            let _ = tx.send(DockerEvent::RoutesUpdated(routes)).await;
+            let diff = DockerEvent::routes_diff(current_routes, routes);
+            if !diff.is_empty_diff() {
+                let _ = tx.send(diff).await;
+            }
         }
         
         // 미들웨어 설정도 초기에 전송
@@ -163,6 +282,7 @@ impl DockerManager {
             while let Some(event) = events.next().await {
                 match event {
                     Ok(event_msg) => {
+                        connected.store(true, Ordering::Relaxed);
                         if let Err(e) = Self::handle_container_event(
                             &docker, 
                             &config,
@@ -174,12 +294,14 @@ impl DockerManager {
                         }
                     }
                     Err(e) => {
+                        connected.store(false, Ordering::Relaxed);
                         let _ = tx.send(DockerEvent::Error(e)).await;
                     }
                 }
             }
 
             // 이벤트 스트림이 종료되면 헬스체크도 중단
+            connected.store(false, Ordering::Relaxed);
             health_check_handle.abort();
         });
 
@@ -202,47 +324,85 @@ impl DockerManager {
                 context: None,
             })?;
 
+        // 이벤트 단위로 로그를 묶기 위한 span
+        // (await를 가로질러 span.enter() 가드를 유지하면 안 되므로 instrument 사용)
+        let span = info_span!(
+            "docker_event",
+            action = %event.action.as_deref().unwrap_or("unknown"),
+            container_id = %container_id
+        );
+
         let manager = DockerManager { 
             client: docker.clone(),
             extractor: Box::new(DefaultExtractor::new(
                 config.network.clone(),
-                config.label_prefix.clone(),
-            )),
+                config.label_prefix.to_string(),
+            ).with_additional_prefixes(config.additional_label_prefixes.clone())),
             config: config.clone(),
             health_checks,
+            connected: Arc::new(AtomicBool::new(true)),
+            health_checks_paused: Arc::new(AtomicBool::new(false)),
+            retry_count: Arc::new(AtomicU64::new(0)),
         };
 
-        // 이벤트 처리 후 미들웨어 설정도 업데이트
-        let result = match event.action.as_deref() {
-            Some("start") => {
-                info!("컨테이너 시작 이벤트 감지: {}", container_id);
-                Self::handle_container_start(&manager, container_id, tx).await
-            }
-            Some("stop" | "die" | "destroy") => Self::handle_container_stop(&manager, container_id, tx).await,
-            Some("update") => Self::handle_container_update(&manager, container_id, tx).await,
-            action => {
-                debug!(
-                    container_id = %container_id,
-                    action = ?action,
-                    "처리되지 않는 컨테이너 이벤트"
-                );
-                Ok(())
+        async {
+            // 이벤트 처리 후 미들웨어 설정도 업데이트
+            let result = match event.action.as_deref() {
+                Some("start") => {
+                    info!("컨테이너 시작 이벤트 감지: {}", container_id);
+                    Self::handle_container_start(&manager, container_id, tx).await
+                }
+                Some("stop" | "die" | "destroy") => Self::handle_container_stop(&manager, container_id, tx).await,
+                Some("update") => Self::handle_container_update(&manager, container_id, tx).await,
+                action => {
+                    debug!(
+                        container_id = %container_id,
+                        action = ?action,
+                        "처리되지 않는 컨테이너 이벤트"
+                    );
+                    Ok(())
+                }
+            };
+
+            // 미들웨어 설정 업데이트
+            if let Ok(middleware_configs) = manager.get_middleware_configs().await {
+                tx.send(DockerEvent::MiddlewareConfigsUpdated(middleware_configs))
+                    .await
+                    .map_err(|_| Self::channel_send_error())?;
             }
-        };
 
-        // 미들웨어 설정 업데이트
-        if let Ok(middleware_configs) = manager.get_middleware_configs().await {
-            tx.send(DockerEvent::MiddlewareConfigsUpdated(middleware_configs))
-                .await
-                .map_err(|_| Self::channel_send_error())?;
+            result
         }
+        .instrument(span)
+        .await
+    }
 
-        result
+    /// 주기적인 헬스 체크를 일시 중지합니다.
+    ///
+    /// 진행 중인 헬스 체크가 있으면 끝날 때까지 기다리므로,
+    /// 반환 이후에는 `resume_health_checks` 전까지 헬스 체크 이벤트가 발생하지 않습니다.
+    pub async fn pause_health_checks(&self) {
+        self.health_checks_paused.store(true, Ordering::SeqCst);
+        // 헬스 체크 루프는 실행 중 쓰기 잠금을 보유하므로 잠금 획득으로 완료를 기다림
+        drop(self.health_checks.write().await);
+        debug!("헬스 체크 일시 중지");
+    }
+
+    /// 일시 중지된 헬스 체크를 다시 시작합니다.
+    pub fn resume_health_checks(&self) {
+        self.health_checks_paused.store(false, Ordering::SeqCst);
+        debug!("헬스 체크 재개");
+    }
+
+    /// 헬스 체크가 일시 중지되었는지 확인합니다.
+    pub fn health_checks_paused(&self) -> bool {
+        self.health_checks_paused.load(Ordering::SeqCst)
     }
 
     /// 주기적인 헬스 체크 시작
     pub async fn start_health_checks(&self, tx: mpsc::Sender<DockerEvent>) -> JoinHandle<()> {
         let health_checks = self.health_checks.clone();
+        let paused = self.health_checks_paused.clone();
         let interval = self.config.health_check.interval;
         let health_checks_ptr = format!("{:p}", &*health_checks.read().await);
         info!("start_health_checks - health_checks 위치: {}", health_checks_ptr);
@@ -252,6 +412,10 @@ impl DockerManager {
             loop {
                 interval.tick().await;
                 let mut checks = health_checks.write().await;
+                if paused.load(Ordering::SeqCst) {
+                    debug!("헬스 체크 일시 중지 상태, 이번 주기 건너뜀");
+                    continue;
+                }
                 let count = checks.len();
                 info!("헬스체크 실행 중... 컨테이너 수: {}, health_checks 위치: {}", count, health_checks_ptr);
                 for (container_id, health) in checks.iter_mut() {
@@ -376,6 +540,14 @@ impl DockerManager {
                 path_matcher,
             }).await.map_err(|_| Self::channel_send_error())?;
         }
+
+        // 가중치 라벨이 있으면 로드밸런서 가중치 갱신
+        if let Some((host, addr, new_weight)) = manager.get_container_weight(container_id).await? {
+            info!(container_id = %container_id, host = %host, addr = %addr, weight = new_weight, "백엔드 가중치 변경");
+            tx.send(DockerEvent::BackendWeightChanged { host, addr, new_weight })
+                .await
+                .map_err(|_| Self::channel_send_error())?;
+        }
         
         Ok(())
     }
@@ -409,13 +581,35 @@ impl DockerManager {
         match containers.first() {
             Some(container) => {
                 let info = self.extractor.extract_info(container)?;
-                let service = self.extractor.create_backend(&info)?;
+                let info = self.with_env_headers(container_id, info).await;
+                let service = self.extractor.create_backend(&info).await?;
                 Ok(Some((info.host, service, info.path_matcher)))
             }
             None => Ok(None),
         }
     }
 
+    /// 단일 컨테이너의 호스트, 백엔드 주소, 가중치 라벨 값을 가져옵니다.
+    /// 가중치 라벨이 없으면 `None`을 반환합니다.
+    async fn get_container_weight(&self, container_id: &str) -> Result<Option<(String, SocketAddr, u32)>, DockerError> {
+        let options = Some(ListContainersOptions::<String> {
+            all: true,
+            filters: HashMap::from([("id".to_string(), vec![container_id.to_string()])]),
+            ..Default::default()
+        });
+
+        let containers = self.client.list_containers(options).await?;
+        let Some(container) = containers.first() else {
+            return Ok(None);
+        };
+        let info = self.extractor.extract_info(container)?;
+        let Some(weight) = info.weight else {
+            return Ok(None);
+        };
+        let addr = resolve_backend_addr(&info.ip, info.port).await?;
+        Ok(Some((info.host, addr, weight)))
+    }
+
     // 컨테이너 라벨 조회 메서드 추가
     pub async fn get_container_labels(&self) -> Result<HashMap<String, String>, DockerError> {
         let containers = self.client.list_containers(None).await?;
@@ -430,16 +624,54 @@ impl DockerManager {
         Ok(all_labels)
     }
 
-    // 미들웨어 설정 조회 메서드 추가
+    /// 모든 컨테이너의 미들웨어 설정을 이름별로 하나씩 조회합니다.
+    ///
+    /// 여러 컨테이너가 같은 이름의 미들웨어를 정의하면 하나만 남깁니다.
+    /// 설정이 서로 다르면 가장 최근에 생성된 컨테이너의 설정을 사용하고 경고를 남깁니다.
     pub async fn get_middleware_configs(&self) -> Result<Vec<(String, MiddlewareConfig)>, DockerError> {
-        let labels = self.get_container_labels().await?;
-        
-        MiddlewareConfig::from_labels(&labels)
-            .map_err(|e| DockerError::ContainerConfigError {
-                container_id: "unknown".to_string(),
-                reason: format!("미들웨어 설정 파싱 실패: {}", e),
-                context: None,
-            })
+        let mut containers = self.client.list_containers(None).await?;
+        // 생성 시각 순으로 처리하여 나중에 생성된 컨테이너 설정이 남도록 함
+        containers.sort_by_key(|container| container.created.unwrap_or_default());
+
+        let mut configs: HashMap<String, (String, MiddlewareConfig)> = HashMap::new();
+        for container in containers {
+            let Some(labels) = container.labels else {
+                continue;
+            };
+            let container_id = container.id.unwrap_or_default();
+            let parsed = MiddlewareConfig::from_labels(&labels, &self.config.label_prefixes())
+                .map_err(|e| DockerError::ContainerConfigError {
+                    container_id: container_id.clone(),
+                    reason: format!("미들웨어 설정 파싱 실패: {}", e),
+                    context: None,
+                })?;
+
+            for (name, config) in parsed {
+                match configs.get_mut(&name) {
+                    Some((source, existing)) => {
+                        if *existing != config {
+                            warn!(
+                                middleware = %name,
+                                previous_container = %source,
+                                container_id = %container_id,
+                                "컨테이너 간 미들웨어 설정 충돌, 최근 컨테이너 설정 사용"
+                            );
+                            *source = container_id.clone();
+                            *existing = config;
+                        }
+                    }
+                    None => {
+                        configs.insert(name, (container_id.clone(), config));
+                    }
+                }
+            }
+        }
+
+        let mut configs: Vec<(String, MiddlewareConfig)> = configs.into_iter()
+            .map(|(name, (_, config))| (name, config))
+            .collect();
+        configs.sort_by(|(a, _), (b, _)| a.cmp(b));
+        Ok(configs)
     }
 
     /// 컨테이너 헬스 체크 설정
@@ -459,7 +691,14 @@ impl DockerManager {
         if let Some(health_check) = &info.health_check {
             let addr = format!("{}:{}", info.ip, info.port);
             
-            if let Some(checker) = HealthCheckerFactory::create(addr.clone(), &health_check.check_type, health_check.timeout) {
+            let checker = match &health_check.check_type {
+                HealthCheckType::Exec { command } => {
+                    HealthCheckerFactory::create_exec(container_id.clone(), command, health_check.timeout)
+                }
+                check_type => HealthCheckerFactory::create(addr.clone(), check_type, health_check.timeout),
+            };
+
+            if let Some(checker) = checker {
                 let container_health = ContainerHealth::new(container_id.clone(), info.host.clone(), checker);
                 self.health_checks.write().await.insert(container_id.clone(), container_health);
                 info!(
@@ -493,6 +732,10 @@ impl DockerManager {
         
         for container in containers {
             if let Ok(info) = self.extractor.extract_info(&container) {
+                let info = match container.id.as_deref() {
+                    Some(id) => self.with_env_headers(id, info).await,
+                    None => info,
+                };
                 let service_name = info.router_name.clone()
                     .unwrap_or_else(|| info.host.clone());
                 services.entry(service_name)
@@ -504,25 +747,60 @@ impl DockerManager {
         services
     }
 
+    /// `env.headers` 라벨에 지정된 환경 변수 값을 inspect 결과에서 채웁니다.
+    ///
+    /// 조회에 실패하면 헤더 없이 라우팅을 계속합니다.
+    async fn with_env_headers(&self, container_id: &str, mut info: ContainerInfo) -> ContainerInfo {
+        if info.env_header_names.is_empty() {
+            return info;
+        }
+
+        match self.client.inspect_container(container_id).await {
+            Ok(inspect) => {
+                let env = inspect.config
+                    .and_then(|config| config.env)
+                    .unwrap_or_default();
+                info.env_headers = container::select_env_vars(&info.env_header_names, &env);
+                debug!(
+                    container_id = %container_id,
+                    headers = ?info.env_headers.keys().collect::<Vec<_>>(),
+                    "업스트림 환경 변수 헤더 설정"
+                );
+            }
+            Err(e) => warn!(
+                container_id = %container_id,
+                error = %e,
+                "환경 변수 헤더 조회 실패, 헤더 없이 진행"
+            ),
+        }
+        info
+    }
+
     // 그룹화된 컨테이너들을 하나의 백엔드 서비스로 변환
-    fn create_backend_service(&self, infos: &[ContainerInfo]) -> Result<(String, PathMatcher, BackendService), DockerError> {
+    async fn create_backend_service(&self, infos: &[ContainerInfo]) -> Result<(String, PathMatcher, BackendService), DockerError> {
         let first = &infos[0];
         debug!("서비스 생성 시작: host={}, path={:?}", first.host, first.path_matcher);
         
-        let mut service = self.extractor.create_backend(first)?;
+        let mut service = self.extractor.create_backend(first).await?;
         
         // 여러 컨테이너가 있으면 로드밸런서 활성화
         if infos.len() > 1 {
             debug!("로드밸런서 활성화: 컨테이너 수={}", infos.len());
-            service.enable_load_balancer(LoadBalancerStrategy::RoundRobin {
-                current_index: AtomicUsize::new(0)
-            });
+            // 가중치 라벨이 하나라도 있으면 가중치 기반, 없으면 라운드로빈
+            if infos.iter().any(|info| info.weight.is_some()) {
+                service.use_weighted_strategy();
+            } else {
+                service.enable_load_balancer(LoadBalancerStrategy::RoundRobin {
+                    current_index: AtomicUsize::new(0)
+                });
+            }
             
             // 추가 컨테이너들의 주소 등록
             for info in &infos[1..] {
-                let addr = self.extractor.parse_socket_addr(&info.ip, info.port)?;
-                debug!("백엔드 주소 추가: {}", addr);
-                service.add_address(addr, 1)?;
+                let addr = resolve_backend_addr(&info.ip, info.port).await?;
+                let weight = info.weight.unwrap_or(1);
+                debug!("백엔드 주소 추가: {} (가중치 {})", addr, weight);
+                service.add_address(addr, weight as usize)?;
             }
         }
 
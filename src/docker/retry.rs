@@ -1,5 +1,5 @@
 use async_trait::async_trait;
-use tokio::time::{sleep, Duration};
+use tokio::time::{sleep, Duration, Instant};
 use crate::{docker::DockerManager, routing_v2::{BackendService, PathMatcher}};
 use std::collections::HashMap;
 use tracing::warn;
@@ -24,6 +24,22 @@ impl From<&RetrySettings> for RetryPolicy {
     }
 }
 
+/// 재시도 실행 통계
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetryStats {
+    /// 실행한 시도 횟수 (첫 시도 포함)
+    pub attempts: u32,
+    /// 첫 시도부터 마지막 시도가 끝날 때까지 걸린 시간 (재시도 대기 포함)
+    pub total_elapsed: Duration,
+}
+
+impl RetryStats {
+    /// 첫 시도 이후 재시도한 횟수
+    pub fn retries(&self) -> u32 {
+        self.attempts.saturating_sub(1)
+    }
+}
+
 /// 재시도 가능한 작업 특성
 #[async_trait::async_trait]
 pub trait RetryableOperation {
@@ -43,15 +59,27 @@ pub async fn with_retry<T: RetryableOperation>(
     operation: T,
     policy: RetryPolicy,
 ) -> Result<T::Output, DockerError> {
+    with_retry_stats(operation, policy).await.0
+}
+
+/// 재시도 로직을 실행하고 결과와 함께 시도 횟수와 소요 시간을 반환합니다.
+///
+/// 통계는 작업이 최종적으로 실패한 경우에도 반환됩니다.
+pub async fn with_retry_stats<T: RetryableOperation>(
+    operation: T,
+    policy: RetryPolicy,
+) -> (Result<T::Output, DockerError>, RetryStats) {
+    let start = Instant::now();
     let mut attempts = 0;
+    let stats = |attempts| RetryStats { attempts, total_elapsed: start.elapsed() };
     
     loop {
         attempts += 1;
         match operation.execute().await {
-            Ok(result) => return Ok(result),
+            Ok(result) => return (Ok(result), stats(attempts)),
             Err(error) => {
                 if attempts >= policy.max_attempts || !operation.should_retry(&error) {
-                    return Err(error);
+                    return (Err(error), stats(attempts));
                 }
                 
                 warn!(
@@ -32,7 +32,7 @@
 //!         "example.com".to_string(), 
 //!         backend,
 //!         Some(PathMatcher::from_str("/api*").unwrap())
-//!     );
+//!     )?;
 //!     Ok(())
 //! }
 //! ```
@@ -66,12 +66,12 @@
 //! // 첫 번째 백엔드 추가
 //! let addr1: SocketAddr = "127.0.0.1:8080".parse()?;
 //! let backend1 = BackendService::new(addr1);
-//! table.add_route("example.com".to_string(), backend1, None);
+//! table.add_route("example.com".to_string(), backend1, None)?;
 //! 
 //! // 동일 호스트에 두 번째 백엔드 추가 (자동으로 로드 밸런싱됨)
 //! let addr2: SocketAddr = "127.0.0.1:8081".parse()?;
 //! let backend2 = BackendService::new(addr2);
-//! table.add_route("example.com".to_string(), backend2, None);
+//! table.merge_route("example.com".to_string(), backend2, None)?;
 //! # Ok(())
 //! # }
 //! ```
@@ -155,14 +155,15 @@
 ///         SettingsError::InvalidConfig(msg) => {
 ///             eprintln!("잘못된 설정: {}", msg);
 ///         }
-///         SettingsError::DuplicateMiddleware(name) => {
-///             eprintln!("중복된 미들웨어: {}", name);
+///         SettingsError::DuplicateMiddleware { name, existing_type } => {
+///             eprintln!("중복된 미들웨어: {} ({:?})", name, existing_type);
 ///         }
 ///     }
 /// }
 ///  
 
 pub mod logging;
+pub mod metrics;
 pub mod proxy;
 pub mod tls;
 pub mod docker;
@@ -1,7 +1,7 @@
 use std::fs;
 use std::path::Path;
-use tracing::{info, warn, error, Level};
-use tracing_subscriber::{fmt, EnvFilter};
+use tracing::{info, warn, error, Event, Level, Subscriber};
+use tracing_subscriber::{fmt, EnvFilter, Layer, layer::Context, prelude::*};
 use tracing_appender::rolling::Rotation;
 use crate::settings::LogSettings;
 use crate::settings::logging::{LogFormat, LogOutput};
@@ -14,6 +14,29 @@ fn ensure_log_directory(path: &str) -> std::io::Result<()> {
     Ok(())
 }
 
+/// DEBUG 이하 이벤트를 주어진 비율로만 통과시키는 레이어
+///
+/// INFO 이상 이벤트는 항상 통과합니다.
+pub struct DebugSampler {
+    rate: f32,
+}
+
+impl DebugSampler {
+    pub fn new(rate: f32) -> Self {
+        Self { rate: rate.clamp(0.0, 1.0) }
+    }
+}
+
+impl<S: Subscriber> Layer<S> for DebugSampler {
+    fn event_enabled(&self, event: &Event<'_>, _ctx: Context<'_, S>) -> bool {
+        // tracing의 Level은 상세할수록 크다 (TRACE > DEBUG > INFO)
+        if *event.metadata().level() < Level::DEBUG {
+            return true;
+        }
+        fastrand::f32() < self.rate
+    }
+}
+
 pub fn init_logging(settings: &LogSettings) -> Result<(), Box<dyn std::error::Error>> {
     let env_filter = EnvFilter::new("")
         .add_directive(settings.level.into())
@@ -29,12 +52,16 @@ pub fn init_logging(settings: &LogSettings) -> Result<(), Box<dyn std::error::Er
         .with_ansi(matches!(settings.output, LogOutput::Stdout))
         .with_env_filter(env_filter);
 
+    // 샘플링 비율이 1.0 미만일 때만 DEBUG 샘플링 레이어 추가
+    let sampler = (settings.debug_sample_rate < 1.0)
+        .then(|| DebugSampler::new(settings.debug_sample_rate));
+
     // 출력 대상 설정
     match &settings.output {
         LogOutput::Stdout => {
             match settings.format {
-                LogFormat::Json => subscriber.json().init(),
-                LogFormat::Text => subscriber.init(),
+                LogFormat::Json => subscriber.json().finish().with(sampler).init(),
+                LogFormat::Text => subscriber.finish().with(sampler).init(),
             }
         }
         LogOutput::File(path) => {
@@ -45,14 +72,14 @@ pub fn init_logging(settings: &LogSettings) -> Result<(), Box<dyn std::error::Er
                 .build("logs")?;
 
             match settings.format {
-                LogFormat::Json => subscriber.json().with_writer(file_appender).init(),
-                LogFormat::Text => subscriber.with_writer(file_appender).init(),
+                LogFormat::Json => subscriber.json().with_writer(file_appender).finish().with(sampler).init(),
+                LogFormat::Text => subscriber.with_writer(file_appender).finish().with(sampler).init(),
             }
         }
     }
 
-    info!("로깅 초기화 완료: format={:?}, level={:?}, output={:?}", 
-        settings.format, settings.level, settings.output);
+    info!("로깅 초기화 완료: format={:?}, level={:?}, output={:?}, debug_sample_rate={}", 
+        settings.format, settings.level, settings.output, settings.debug_sample_rate);
 
     Ok(())
 }
@@ -65,6 +92,8 @@ pub struct RequestLog {
     pub host: String,
     pub status_code: u16,
     pub duration_ms: u64,
+    /// 업스트림 요청 시작부터 응답 헤더 수신까지 걸린 시간
+    pub upstream_response_ms: Option<u64>,
     pub backend_address: Option<String>,
     pub error: Option<String>,
 }
@@ -79,6 +108,7 @@ impl RequestLog {
             host: String::new(),
             status_code: 0,
             duration_ms: 0,
+            upstream_response_ms: None,
             backend_address: None,
             error: None,
         }
@@ -148,6 +178,7 @@ pub fn log_request(log: &RequestLog) {
     // 간소화된 로그 포맷
     match level {
         Level::ERROR => error!(
+            upstream_response_ms = log.upstream_response_ms,
             "{} {} {} -> {} ({}ms) [{}]{}",
             log.method,
             log.host,
@@ -158,6 +189,7 @@ pub fn log_request(log: &RequestLog) {
             log.error.as_ref().map(|e| format!(" error: {}", e)).unwrap_or_default()
         ),
         Level::WARN => warn!(
+            upstream_response_ms = log.upstream_response_ms,
             "{} {} {} -> {} ({}ms) [{}]",
             log.method,
             log.host,
@@ -167,6 +199,7 @@ pub fn log_request(log: &RequestLog) {
             log.backend_address.as_deref().unwrap_or("-")
         ),
         _ => info!(
+            upstream_response_ms = log.upstream_response_ms,
             "{} {} {} -> {} ({}ms) [{}]",
             log.method,
             log.host,
@@ -176,4 +209,53 @@ pub fn log_request(log: &RequestLog) {
             log.backend_address.as_deref().unwrap_or("-")
         ),
     }
-} 
\ No newline at end of file
+} 
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+    use tracing::debug;
+
+    struct CountingLayer {
+        debug: Arc<AtomicUsize>,
+        info: Arc<AtomicUsize>,
+    }
+
+    impl<S: Subscriber> Layer<S> for CountingLayer {
+        fn on_event(&self, event: &Event<'_>, _ctx: Context<'_, S>) {
+            match *event.metadata().level() {
+                Level::DEBUG => self.debug.fetch_add(1, Ordering::Relaxed),
+                _ => self.info.fetch_add(1, Ordering::Relaxed),
+            };
+        }
+    }
+
+    fn count_events(rate: f32) -> (usize, usize) {
+        let debug_count = Arc::new(AtomicUsize::new(0));
+        let info_count = Arc::new(AtomicUsize::new(0));
+        let subscriber = tracing_subscriber::registry()
+            .with(DebugSampler::new(rate))
+            .with(CountingLayer { debug: debug_count.clone(), info: info_count.clone() });
+
+        tracing::subscriber::with_default(subscriber, || {
+            for _ in 0..1000 {
+                debug!("sampled");
+                info!("kept");
+            }
+        });
+
+        (debug_count.load(Ordering::Relaxed), info_count.load(Ordering::Relaxed))
+    }
+
+    #[test]
+    fn test_debug_sampler() {
+        assert_eq!(count_events(1.0), (1000, 1000));
+        assert_eq!(count_events(0.0), (0, 1000));
+
+        // 샘플링 비율에 맞게 일부만 통과, INFO는 항상 통과
+        let (debug_count, info_count) = count_events(0.5);
+        assert!(debug_count > 300 && debug_count < 700, "debug_count = {}", debug_count);
+        assert_eq!(info_count, 1000);
+    }
+}
@@ -1,4 +1,5 @@
 mod docker;
+mod metrics;
 mod proxy;
 mod logging;
 mod tls;
@@ -0,0 +1,177 @@
+//! Prometheus 메트릭 수집
+//!
+//! 메트릭 경로(`/__roxy/metrics`)에서 텍스트 형식(0.0.4)으로 노출할 히스토그램을 보관합니다.
+//! 요청 처리 경로 어디서나 기록할 수 있도록 프로세스 전역 값으로 둡니다.
+
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::Duration;
+
+/// 히스토그램 버킷 상한 (초, Prometheus 클라이언트 기본값)
+const BUCKETS: [f64; 11] = [0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0];
+
+/// 미들웨어별 실행 시간 (`roxy_middleware_duration_seconds{middleware}`)
+pub static MIDDLEWARE_DURATION: LabeledHistogram = LabeledHistogram::new(
+    "roxy_middleware_duration_seconds",
+    "Time spent in each middleware while handling a request or response.",
+    "middleware",
+);
+
+/// 백엔드 응답 시간 (`roxy_upstream_response_duration_seconds`)
+pub static UPSTREAM_RESPONSE_DURATION: DurationHistogram = DurationHistogram::new();
+
+/// 실행 시간 히스토그램
+#[derive(Debug)]
+pub struct DurationHistogram {
+    /// 버킷별 관측 수 (누적되지 않은 값, 마지막 칸은 `+Inf`)
+    buckets: [AtomicU64; BUCKETS.len() + 1],
+    sum_nanos: AtomicU64,
+    count: AtomicU64,
+}
+
+impl Default for DurationHistogram {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl DurationHistogram {
+    pub const fn new() -> Self {
+        Self {
+            buckets: [const { AtomicU64::new(0) }; BUCKETS.len() + 1],
+            sum_nanos: AtomicU64::new(0),
+            count: AtomicU64::new(0),
+        }
+    }
+
+    /// 실행 시간 하나를 기록합니다.
+    pub fn observe(&self, elapsed: Duration) {
+        let seconds = elapsed.as_secs_f64();
+        let index = BUCKETS.iter().position(|bound| seconds <= *bound).unwrap_or(BUCKETS.len());
+        self.buckets[index].fetch_add(1, Ordering::Relaxed);
+        self.sum_nanos.fetch_add(elapsed.as_nanos() as u64, Ordering::Relaxed);
+        self.count.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// 기록된 관측 수
+    pub fn count(&self) -> u64 {
+        self.count.load(Ordering::Relaxed)
+    }
+
+    /// 레이블 없는 히스토그램을 Prometheus 텍스트 형식으로 기록합니다.
+    pub fn render(&self, out: &mut String, name: &str, help: &str) {
+        let _ = writeln!(out, "# HELP {} {}", name, help);
+        let _ = writeln!(out, "# TYPE {} histogram", name);
+        self.render_samples(out, name, "");
+    }
+
+    /// `_bucket`, `_sum`, `_count` 줄을 기록합니다. `labels`는 `key="value"` 형식이며 비어 있을 수 있습니다.
+    fn render_samples(&self, out: &mut String, name: &str, labels: &str) {
+        let separator = if labels.is_empty() { "" } else { "," };
+        let mut cumulative = 0;
+        for (index, bucket) in self.buckets.iter().enumerate() {
+            cumulative += bucket.load(Ordering::Relaxed);
+            let bound = BUCKETS.get(index).map_or_else(|| "+Inf".to_string(), f64::to_string);
+            let _ = writeln!(out, "{}_bucket{{{}{}le=\"{}\"}} {}", name, labels, separator, bound, cumulative);
+        }
+        let labels = if labels.is_empty() { String::new() } else { format!("{{{}}}", labels) };
+        let sum = Duration::from_nanos(self.sum_nanos.load(Ordering::Relaxed)).as_secs_f64();
+        let _ = writeln!(out, "{}_sum{} {}", name, labels, sum);
+        let _ = writeln!(out, "{}_count{} {}", name, labels, self.count());
+    }
+}
+
+/// 레이블 값 하나로 구분되는 실행 시간 히스토그램 모음
+#[derive(Debug)]
+pub struct LabeledHistogram {
+    name: &'static str,
+    help: &'static str,
+    label: &'static str,
+    histograms: Mutex<BTreeMap<&'static str, DurationHistogram>>,
+}
+
+impl LabeledHistogram {
+    pub const fn new(name: &'static str, help: &'static str, label: &'static str) -> Self {
+        Self {
+            name,
+            help,
+            label,
+            histograms: Mutex::new(BTreeMap::new()),
+        }
+    }
+
+    /// `value` 레이블의 히스토그램에 실행 시간을 기록합니다.
+    pub fn observe(&self, value: &'static str, elapsed: Duration) {
+        let mut histograms = self.histograms.lock().unwrap_or_else(|e| e.into_inner());
+        histograms.entry(value).or_default().observe(elapsed);
+    }
+
+    /// Prometheus 텍스트 형식으로 기록합니다.
+    pub fn render(&self, out: &mut String) {
+        let _ = writeln!(out, "# HELP {} {}", self.name, self.help);
+        let _ = writeln!(out, "# TYPE {} histogram", self.name);
+        let histograms = self.histograms.lock().unwrap_or_else(|e| e.into_inner());
+        for (value, histogram) in histograms.iter() {
+            let labels = format!("{}=\"{}\"", self.label, escape_label_value(value));
+            histogram.render_samples(out, self.name, &labels);
+        }
+    }
+}
+
+/// 레이블 값의 `\`, `"`, 줄바꿈을 이스케이프합니다.
+fn escape_label_value(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', "\\n")
+}
+
+/// 수집한 메트릭 전체를 Prometheus 텍스트 형식으로 반환합니다.
+pub fn render() -> String {
+    let mut out = String::new();
+    MIDDLEWARE_DURATION.render(&mut out);
+    UPSTREAM_RESPONSE_DURATION.render(
+        &mut out,
+        "roxy_upstream_response_duration_seconds",
+        "Time from sending a request to a backend until its response headers arrive.",
+    );
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_labeled_histogram_render() {
+        let histogram = LabeledHistogram::new("test_duration_seconds", "Test.", "middleware");
+        histogram.observe("cors", Duration::from_millis(3));
+        histogram.observe("cors", Duration::from_millis(200));
+        histogram.observe("cors", Duration::from_secs(20));
+
+        let mut out = String::new();
+        histogram.render(&mut out);
+        assert!(out.contains("# TYPE test_duration_seconds histogram\n"), "{}", out);
+        // 버킷 값은 누적
+        assert!(out.contains("test_duration_seconds_bucket{middleware=\"cors\",le=\"0.005\"} 1\n"), "{}", out);
+        assert!(out.contains("test_duration_seconds_bucket{middleware=\"cors\",le=\"0.25\"} 2\n"), "{}", out);
+        assert!(out.contains("test_duration_seconds_bucket{middleware=\"cors\",le=\"10\"} 2\n"), "{}", out);
+        assert!(out.contains("test_duration_seconds_bucket{middleware=\"cors\",le=\"+Inf\"} 3\n"), "{}", out);
+        assert!(out.contains("test_duration_seconds_sum{middleware=\"cors\"} 20.203\n"), "{}", out);
+        assert!(out.contains("test_duration_seconds_count{middleware=\"cors\"} 3\n"), "{}", out);
+    }
+
+    #[test]
+    fn test_duration_histogram_render() {
+        let histogram = DurationHistogram::new();
+        histogram.observe(Duration::from_millis(40));
+        assert_eq!(histogram.count(), 1);
+
+        let mut out = String::new();
+        histogram.render(&mut out, "test_upstream_seconds", "Test.");
+        assert!(out.contains("# TYPE test_upstream_seconds histogram\n"), "{}", out);
+        assert!(out.contains("test_upstream_seconds_bucket{le=\"0.025\"} 0\n"), "{}", out);
+        assert!(out.contains("test_upstream_seconds_bucket{le=\"0.05\"} 1\n"), "{}", out);
+        assert!(out.contains("test_upstream_seconds_sum 0.04\n"), "{}", out);
+        assert!(out.contains("test_upstream_seconds_count 1\n"), "{}", out);
+    }
+}
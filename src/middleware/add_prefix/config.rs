@@ -0,0 +1,63 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use crate::middleware::MiddlewareError;
+
+/// 경로 접두사 추가 설정
+///
+/// # Docker 라벨 예시
+/// ```yaml
+/// labels:
+///   - "rproxy.http.middlewares.api-v2.type=add-prefix"
+///   - "rproxy.http.middlewares.api-v2.addPrefix.prefix=/v2"
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
+pub struct AddPrefixConfig {
+    /// 요청 경로 앞에 붙일 접두사
+    pub prefix: String,
+}
+
+impl AddPrefixConfig {
+    /// Docker 라벨에서 설정을 파싱합니다.
+    pub fn from_labels(labels: &HashMap<String, String>) -> Result<Self, MiddlewareError> {
+        let prefix = labels.get("addPrefix.prefix")
+            .ok_or_else(|| MiddlewareError::Config {
+                message: "addPrefix.prefix is required".to_string(),
+            })?;
+
+        let prefix = prefix.trim().trim_end_matches('/');
+        if !prefix.starts_with('/') {
+            return Err(MiddlewareError::InvalidLabel {
+                key: "addPrefix.prefix".to_string(),
+                value: prefix.to_string(),
+                reason: "prefix must start with '/'".to_string(),
+            });
+        }
+
+        Ok(Self {
+            prefix: prefix.to_string(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_labels() {
+        let mut labels = HashMap::new();
+        labels.insert("addPrefix.prefix".to_string(), "/v2/".to_string());
+
+        let config = AddPrefixConfig::from_labels(&labels).unwrap();
+        assert_eq!(config.prefix, "/v2");
+    }
+
+    #[test]
+    fn test_invalid_prefix() {
+        let mut labels = HashMap::new();
+        assert!(AddPrefixConfig::from_labels(&labels).is_err());
+
+        labels.insert("addPrefix.prefix".to_string(), "v2".to_string());
+        assert!(AddPrefixConfig::from_labels(&labels).is_err());
+    }
+}
@@ -0,0 +1,72 @@
+use crate::middleware::{Middleware, MiddlewareError, Request, Response};
+use super::config::AddPrefixConfig;
+use async_trait::async_trait;
+use hyper::Uri;
+use tracing::debug;
+
+/// 경로 접두사 추가 미들웨어
+///
+/// 공개 경로가 `/api`이고 백엔드가 `/v2/api`를 기대하는 경우에 사용합니다.
+#[derive(Debug)]
+pub struct AddPrefixMiddleware {
+    config: AddPrefixConfig,
+}
+
+impl AddPrefixMiddleware {
+    pub fn new(config: AddPrefixConfig) -> Self {
+        Self { config }
+    }
+
+    /// 접두사가 추가된 URI를 생성합니다. 쿼리 문자열은 유지됩니다.
+    fn prefixed_uri(&self, uri: &Uri) -> Result<Uri, MiddlewareError> {
+        let path_and_query = uri.path_and_query()
+            .map(|pq| pq.as_str())
+            .unwrap_or("/");
+        let new_path_and_query = format!("{}{}", self.config.prefix, path_and_query);
+
+        let mut parts = uri.clone().into_parts();
+        parts.path_and_query = Some(new_path_and_query.parse()
+            .map_err(|e| MiddlewareError::InvalidRequest(format!("Invalid prefixed path: {}", e)))?);
+
+        Uri::from_parts(parts)
+            .map_err(|e| MiddlewareError::InvalidRequest(format!("Invalid prefixed uri: {}", e)))
+    }
+}
+
+#[async_trait]
+impl Middleware for AddPrefixMiddleware {
+    fn name(&self) -> &'static str {
+        "add-prefix"
+    }
+
+    async fn handle_request(&self, mut req: Request) -> Result<Request, MiddlewareError> {
+        let uri = self.prefixed_uri(req.uri())?;
+        debug!(from = %req.uri(), to = %uri, "경로 접두사 추가");
+        *req.uri_mut() = uri;
+        Ok(req)
+    }
+
+    async fn handle_response(&self, res: Response) -> Result<Response, MiddlewareError> {
+        Ok(res)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn middleware(prefix: &str) -> AddPrefixMiddleware {
+        AddPrefixMiddleware::new(AddPrefixConfig { prefix: prefix.to_string() })
+    }
+
+    #[test]
+    fn test_prefixed_uri() {
+        let middleware = middleware("/v2");
+
+        let uri: Uri = "/api/items?page=1".parse().unwrap();
+        assert_eq!(middleware.prefixed_uri(&uri).unwrap(), "/v2/api/items?page=1");
+
+        let uri: Uri = "/".parse().unwrap();
+        assert_eq!(middleware.prefixed_uri(&uri).unwrap(), "/v2/");
+    }
+}
@@ -0,0 +1,9 @@
+//! 경로 접두사 추가 미들웨어
+//! 
+//! 백엔드로 전달하기 전에 요청 경로 앞에 접두사를 붙입니다.
+
+mod config;
+mod middleware;
+
+pub use config::AddPrefixConfig;
+pub use middleware::AddPrefixMiddleware;
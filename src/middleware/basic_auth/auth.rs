@@ -1,7 +1,12 @@
 use std::collections::HashMap;
 use crate::middleware::MiddlewareError;
 use super::config::{AuthSource, BasicAuthConfig};
+use crate::settings::watcher::ConfigWatcher;
 use std::fs;
+use std::sync::{Arc, RwLock};
+use std::time::SystemTime;
+use tokio::task::JoinHandle;
+use tracing::{debug, info, warn};
 use bcrypt;
 /// Basic 인증을 위한 인증기 트레이트
 /// 
@@ -22,7 +27,9 @@ pub struct LabelAuthenticator {
 impl LabelAuthenticator {
     pub fn new(config: &BasicAuthConfig) -> Self {
         Self {
-            users: config.users.clone(),
+            users: config.users.iter()
+                .map(|(username, entry)| (username.clone(), entry.password_hash.clone()))
+                .collect(),
         }
     }
 }
@@ -42,6 +49,9 @@ impl Authenticator for LabelAuthenticator {
 
 /// .htpasswd 파일 기반 인증기
 /// 
+/// 파일의 수정 시간(mtime)이 바뀌면 자격증명을 다시 로드합니다.
+/// `watch`를 호출하면 `ConfigWatcher`로 파일 변경을 감시하며, 감시 작업은 인증기가 제거될 때 함께 종료됩니다.
+/// 
 /// # 예시
 /// ```text
 /// # .htpasswd 파일 형식
@@ -50,21 +60,109 @@ impl Authenticator for LabelAuthenticator {
 /// ```
 pub struct HtpasswdAuthenticator {
     path: String,
+    state: Arc<RwLock<HtpasswdState>>,
+    /// 파일 감시 작업 (`watch` 호출 시 생성)
+    watch_task: Option<JoinHandle<()>>,
+}
+
+/// 로드된 htpasswd 데이터
+#[derive(Default)]
+struct HtpasswdState {
     users: HashMap<String, String>,
+    modified: Option<SystemTime>,
 }
 
 impl HtpasswdAuthenticator {
     pub fn new(path: String) -> Self {
         Self {
             path,
-            users: HashMap::new(),
+            state: Arc::new(RwLock::new(HtpasswdState::default())),
+            watch_task: None,
+        }
+    }
+
+    /// 파일 변경 감시를 시작합니다.
+    ///
+    /// 이미 감시 중이면 기존 작업을 중단하고 다시 시작합니다.
+    /// Tokio 런타임 밖에서 호출되면 감시하지 않습니다.
+    pub fn watch(&mut self) {
+        let Ok(handle) = tokio::runtime::Handle::try_current() else {
+            warn!(path = %self.path, "Tokio 런타임이 없어 htpasswd 파일 감시를 건너뜁니다");
+            return;
+        };
+
+        let path = self.path.clone();
+        let state = Arc::downgrade(&self.state);
+        let task = handle.spawn(async move {
+            let mut watcher = ConfigWatcher::new();
+            // 생성 시 이미 htpasswd 파일을 읽었으므로 초기 이벤트 불필요
+            watcher.emit_initial_state = false;
+            watcher.add_path(&path);
+            if let Err(e) = watcher.start().await {
+                warn!(path = %path, error = %e, "htpasswd 파일 감시 시작 실패");
+                return;
+            }
+
+            while watcher.watch().await.is_some() {
+                let Some(state) = state.upgrade() else {
+                    debug!(path = %path, "인증기가 제거되어 htpasswd 파일 감시 종료");
+                    break;
+                };
+                match reload_htpasswd(&path, &state) {
+                    Ok(true) => info!(path = %path, "htpasswd 파일 다시 로드됨"),
+                    Ok(false) => {}
+                    Err(e) => warn!(path = %path, error = %e, "htpasswd 파일 다시 로드 실패"),
+                }
+            }
+        });
+        if let Some(previous) = self.watch_task.replace(task) {
+            previous.abort();
+        }
+    }
+}
+
+impl Drop for HtpasswdAuthenticator {
+    fn drop(&mut self) {
+        if let Some(task) = self.watch_task.take() {
+            debug!(path = %self.path, "인증기가 제거되어 htpasswd 파일 감시 종료");
+            task.abort();
         }
     }
 }
 
+/// htpasswd 파일 내용을 파싱합니다.
+fn parse_htpasswd(content: &str) -> HashMap<String, String> {
+    content.lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .filter_map(|line| line.split_once(':'))
+        .map(|(username, hash)| (username.to_string(), hash.to_string()))
+        .collect()
+}
+
+/// mtime이 바뀐 경우에만 htpasswd 파일을 다시 읽습니다.
+fn reload_htpasswd(path: &str, state: &RwLock<HtpasswdState>) -> Result<bool, MiddlewareError> {
+    let read_error = |e: std::io::Error| MiddlewareError::Runtime {
+        message: format!("Failed to read htpasswd file: {}", e),
+        source: None,
+    };
+
+    let modified = fs::metadata(path).and_then(|m| m.modified()).map_err(read_error)?;
+    if state.read().unwrap().modified == Some(modified) {
+        return Ok(false);
+    }
+
+    let content = fs::read_to_string(path).map_err(read_error)?;
+    let mut state = state.write().unwrap();
+    state.users = parse_htpasswd(&content);
+    state.modified = Some(modified);
+    Ok(true)
+}
+
 impl Authenticator for HtpasswdAuthenticator {
     fn verify_credentials(&self, username: &str, password: &str) -> bool {
-        if let Some(hash) = self.users.get(username) {
+        let state = self.state.read().unwrap();
+        if let Some(hash) = state.users.get(username) {
             verify_password(password, hash)
         } else {
             false
@@ -72,21 +170,34 @@ impl Authenticator for HtpasswdAuthenticator {
     }
 
     fn load_credentials(&mut self) -> Result<(), MiddlewareError> {
-        let content = fs::read_to_string(&self.path).map_err(|e| MiddlewareError::Runtime {
-            message: format!("Failed to read htpasswd file: {}", e),
-            source: None,
-        })?;
+        // mtime과 관계없이 강제로 다시 로드
+        self.state.write().unwrap().modified = None;
+        reload_htpasswd(&self.path, &self.state).map(|_| ())
+    }
+}
 
-        self.users.clear();
-        
-        // 직접 파일 파싱
-        for line in content.lines() {
-            if let Some((username, hash)) = line.split_once(':') {
-                self.users.insert(username.to_string(), hash.to_string());
-            }
-        }
+/// 여러 인증기를 순서대로 확인하는 인증기
+///
+/// 하나라도 자격증명을 승인하면 인증에 성공합니다.
+pub struct CompositeAuthenticator {
+    authenticators: Vec<Box<dyn Authenticator>>,
+}
 
-        Ok(())
+impl CompositeAuthenticator {
+    pub fn new(authenticators: Vec<Box<dyn Authenticator>>) -> Self {
+        Self { authenticators }
+    }
+}
+
+impl Authenticator for CompositeAuthenticator {
+    fn verify_credentials(&self, username: &str, password: &str) -> bool {
+        self.authenticators.iter()
+            .any(|authenticator| authenticator.verify_credentials(username, password))
+    }
+
+    fn load_credentials(&mut self) -> Result<(), MiddlewareError> {
+        self.authenticators.iter_mut()
+            .try_for_each(|authenticator| authenticator.load_credentials())
     }
 }
 
@@ -181,14 +292,31 @@ impl Authenticator for DockerSecretsAuthenticator {
     }
 }
 
+/// htpasswd 인증기를 생성하고 파일 감시를 시작합니다.
+fn create_htpasswd_authenticator(path: &str) -> Result<HtpasswdAuthenticator, MiddlewareError> {
+    let mut authenticator = HtpasswdAuthenticator::new(path.to_string());
+    authenticator.load_credentials()?;
+    authenticator.watch();
+    Ok(authenticator)
+}
+
 /// 인증기 팩토리
 pub fn create_authenticator(config: &BasicAuthConfig) -> Result<Box<dyn Authenticator>, MiddlewareError> {
     match &config.source {
-        AuthSource::Labels => Ok(Box::new(LabelAuthenticator::new(config))),
+        AuthSource::Labels => match &config.htpasswd_file {
+            // 인라인 사용자와 htpasswd 파일 사용자를 함께 허용
+            Some(path) => Ok(Box::new(CompositeAuthenticator::new(vec![
+                Box::new(LabelAuthenticator::new(config)),
+                Box::new(create_htpasswd_authenticator(path)?),
+            ]))),
+            None => Ok(Box::new(LabelAuthenticator::new(config))),
+        },
         AuthSource::HtpasswdFile(path) => {
-            let mut authenticator = HtpasswdAuthenticator::new(path.clone());
-            authenticator.load_credentials()?;
-            Ok(Box::new(authenticator))
+            let path = match (path.is_empty(), &config.htpasswd_file) {
+                (true, Some(file)) => file,
+                _ => path,
+            };
+            Ok(Box::new(create_htpasswd_authenticator(path)?))
         }
         AuthSource::EnvVar(prefix) => {
             let mut authenticator = EnvAuthenticator::new(prefix.clone());
@@ -206,6 +334,7 @@ pub fn create_authenticator(config: &BasicAuthConfig) -> Result<Box<dyn Authenti
 #[cfg(test)]
 mod tests {
     use super::*;
+    use super::super::config::UserEntry;
     use bcrypt::DEFAULT_COST;
     use std::io::Write;
     use tempfile::NamedTempFile;
@@ -215,12 +344,14 @@ mod tests {
         let mut users = HashMap::new();
         // bcrypt 해시로 테스트
         let hash = bcrypt::hash("test-password", DEFAULT_COST).unwrap();
-        users.insert("test".to_string(), hash);
+        users.insert("test".to_string(), UserEntry::new(hash));
 
         let config = BasicAuthConfig {
             users,
             realm: "Test Realm".to_string(),
             source: AuthSource::Labels,
+            htpasswd_file: None,
+            charset: None,
         };
 
         let authenticator = LabelAuthenticator::new(&config);
@@ -327,4 +458,72 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_htpasswd_reload_on_mtime_change() -> Result<(), Box<dyn std::error::Error>> {
+        let mut temp_file = NamedTempFile::new()?;
+        let hash = bcrypt::hash("old-password", DEFAULT_COST)?;
+        writeln!(temp_file, "user:{}", hash)?;
+
+        let mut authenticator = HtpasswdAuthenticator::new(temp_file.path().to_str().unwrap().to_string());
+        authenticator.load_credentials()?;
+        assert!(authenticator.verify_credentials("user", "old-password"));
+
+        // mtime이 같으면 다시 로드하지 않음
+        assert!(!reload_htpasswd(&authenticator.path, &authenticator.state)?);
+
+        // 파일 교체 후 mtime 변경
+        let hash = bcrypt::hash("new-password", DEFAULT_COST)?;
+        let file = temp_file.reopen()?;
+        file.set_len(0)?;
+        writeln!(&file, "user:{}", hash)?;
+        file.set_modified(SystemTime::now() + std::time::Duration::from_secs(10))?;
+
+        assert!(reload_htpasswd(&authenticator.path, &authenticator.state)?);
+        assert!(authenticator.verify_credentials("user", "new-password"));
+        assert!(!authenticator.verify_credentials("user", "old-password"));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_htpasswd_watch_stops_on_drop() -> Result<(), Box<dyn std::error::Error>> {
+        let temp_file = NamedTempFile::new()?;
+        let mut authenticator = HtpasswdAuthenticator::new(temp_file.path().to_str().unwrap().to_string());
+        authenticator.watch();
+        let task = authenticator.watch_task.as_ref().unwrap().abort_handle();
+        assert!(!task.is_finished());
+
+        // 인증기를 제거하면 파일 이벤트를 기다리지 않고 감시 작업이 종료됨
+        drop(authenticator);
+        tokio::time::timeout(std::time::Duration::from_secs(1), async {
+            while !task.is_finished() {
+                tokio::task::yield_now().await;
+            }
+        }).await?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_inline_users_with_htpasswd_file() -> Result<(), Box<dyn std::error::Error>> {
+        let mut temp_file = NamedTempFile::new()?;
+        writeln!(temp_file, "file-user:{}", bcrypt::hash("file-password", DEFAULT_COST)?)?;
+
+        let mut users = HashMap::new();
+        users.insert("inline-user".to_string(), UserEntry::new(bcrypt::hash("inline-password", DEFAULT_COST)?));
+
+        let config = BasicAuthConfig {
+            users,
+            htpasswd_file: Some(temp_file.path().to_str().unwrap().to_string()),
+            ..Default::default()
+        };
+
+        let authenticator = create_authenticator(&config)?;
+        assert!(authenticator.verify_credentials("inline-user", "inline-password"));
+        assert!(authenticator.verify_credentials("file-user", "file-password"));
+        assert!(!authenticator.verify_credentials("file-user", "inline-password"));
+
+        Ok(())
+    }
 }
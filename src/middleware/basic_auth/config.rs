@@ -1,6 +1,7 @@
 use std::collections::HashMap;
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize};
 use crate::middleware::MiddlewareError;
+use crate::routing_v2::PathMatcher;
 
 /// Basic 인증 소스 설정
 /// 
@@ -12,6 +13,8 @@ use crate::middleware::MiddlewareError;
 ///   - "rproxy.http.middlewares.my-auth.type=basic-auth"
 ///   - "rproxy.http.middlewares.my-auth.basicAuth.users=admin:$2y$05$..."
 ///   - "rproxy.http.middlewares.my-auth.basicAuth.realm=Restricted Area"
+///   # 사용자별 접근 가능 경로 (선택, 지정하지 않으면 모든 경로 허용)
+///   - "rproxy.http.middlewares.my-auth.basicAuth.allowedPaths.admin=/admin*,/api*"
 /// ```
 /// 
 /// ## Htpasswd 파일 소스
@@ -23,6 +26,14 @@ use crate::middleware::MiddlewareError;
 ///   - "rproxy.http.middlewares.my-auth.basicAuth.realm=Restricted Area"
 /// ```
 /// 
+/// ## 인라인 사용자 + Htpasswd 파일
+/// ```yaml
+/// labels:
+///   - "rproxy.http.middlewares.my-auth.type=basic-auth"
+///   - "rproxy.http.middlewares.my-auth.basicAuth.users=admin:$2y$05$..."
+///   - "rproxy.http.middlewares.my-auth.basicAuth.htpasswdFile=/etc/rproxy/.htpasswd"
+/// ```
+/// 
 /// ## 환경 변수 소스
 /// ```yaml
 /// labels:
@@ -59,12 +70,79 @@ impl Default for AuthSource {
     }
 }
 
-/// Basic 인증 설정
+/// 사용자별 인증 정보
 #[derive(Debug, Clone, Deserialize, Default)]
+pub struct UserEntry {
+    /// 해시된 비밀번호 (htpasswd 등 다른 소스의 사용자는 비어 있음)
+    #[serde(default)]
+    pub password_hash: String,
+
+    /// 접근 가능한 경로 목록. 비어 있으면 모든 경로를 허용합니다.
+    #[serde(default, deserialize_with = "deserialize_path_matchers")]
+    pub allowed_paths: Vec<PathMatcher>,
+}
+
+impl UserEntry {
+    pub fn new(password_hash: impl Into<String>) -> Self {
+        Self {
+            password_hash: password_hash.into(),
+            allowed_paths: Vec::new(),
+        }
+    }
+
+    /// 요청 경로가 허용 범위에 포함되는지 확인합니다.
+    ///
+    /// `..`, `.`, 연속된 `/`로 허용 범위를 벗어나지 못하도록 경로를 정규화한 뒤 비교하며,
+    /// 접두사 패턴(`/admin*`)은 경로 세그먼트 단위로 매칭합니다(`/administrator`는 불일치).
+    pub fn is_path_allowed(&self, path: &str) -> bool {
+        if self.allowed_paths.is_empty() {
+            return true;
+        }
+        let path = normalize_path(path);
+        self.allowed_paths.iter().any(|matcher| matcher.matches(&path))
+    }
+}
+
+/// 요청 경로의 빈 세그먼트와 `.`을 제거하고 `..`은 상위 세그먼트를 제거합니다.
+/// 퍼센트 인코딩된 점(`%2e`)도 점으로 취급하며, 끝의 `/`는 유지합니다.
+fn normalize_path(path: &str) -> String {
+    let is_dot = |segment: &str| segment == "." || segment.eq_ignore_ascii_case("%2e");
+    let is_dot_dot = |segment: &str| {
+        matches!(segment.to_ascii_lowercase().as_str(), ".." | ".%2e" | "%2e." | "%2e%2e")
+    };
+
+    let mut segments: Vec<&str> = Vec::new();
+    for segment in path.split('/') {
+        if segment.is_empty() || is_dot(segment) {
+            continue;
+        }
+        if is_dot_dot(segment) {
+            segments.pop();
+        } else {
+            segments.push(segment);
+        }
+    }
+
+    let mut normalized = format!("/{}", segments.join("/"));
+    if path.ends_with('/') && normalized != "/" {
+        normalized.push('/');
+    }
+    normalized
+}
+
+fn deserialize_path_matchers<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<PathMatcher>, D::Error> {
+    Vec::<String>::deserialize(deserializer)?
+        .iter()
+        .map(|pattern| PathMatcher::from_str(pattern).map_err(serde::de::Error::custom))
+        .collect()
+}
+
+/// Basic 인증 설정
+#[derive(Debug, Clone, Deserialize)]
 pub struct BasicAuthConfig {
-    /// 사용자 이름과 해시된 비밀번호 맵
+    /// 사용자 이름과 인증 정보 맵
     #[serde(default)]
-    pub users: HashMap<String, String>,
+    pub users: HashMap<String, UserEntry>,
     
     /// 인증 영역 (realm)
     #[serde(default = "default_realm")]
@@ -73,10 +151,32 @@ pub struct BasicAuthConfig {
     /// 인증 소스
     #[serde(default)]
     pub source: AuthSource,
+
+    /// htpasswd 형식 비밀번호 파일 경로
+    ///
+    /// 인라인 사용자와 함께 사용할 수 있으며, 파일이 변경되면 재시작 없이 다시 로드됩니다.
+    #[serde(default)]
+    pub htpasswd_file: Option<String>,
+
+    /// 자격증명 인코딩 (RFC 7617). 지정하면 `WWW-Authenticate`에 `charset="UTF-8"`을 추가합니다.
+    #[serde(default)]
+    pub charset: Option<String>,
+}
+
+impl Default for BasicAuthConfig {
+    fn default() -> Self {
+        Self {
+            users: HashMap::new(),
+            realm: default_realm(),
+            source: AuthSource::default(),
+            htpasswd_file: None,
+            charset: None,
+        }
+    }
 }
 
 fn default_realm() -> String {
-    "Restricted Area".to_string()
+    "Restricted".to_string()
 }
 
 impl BasicAuthConfig {
@@ -89,7 +189,9 @@ impl BasicAuthConfig {
                 "basicAuth.users" => {
                     for user_entry in value.split(',') {
                         if let Some((username, password)) = user_entry.split_once(':') {
-                            config.users.insert(username.trim().to_string(), password.trim().to_string());
+                            config.users.entry(username.trim().to_string())
+                                .or_default()
+                                .password_hash = password.trim().to_string();
                         } else {
                             return Err(MiddlewareError::InvalidLabel {
                                 key: key.clone(),
@@ -100,6 +202,36 @@ impl BasicAuthConfig {
                     }
                 }
                 "basicAuth.realm" => config.realm = value.clone(),
+                "basicAuth.charset" => {
+                    // RFC 7617은 UTF-8만 허용
+                    if !value.trim().eq_ignore_ascii_case("UTF-8") {
+                        return Err(MiddlewareError::InvalidLabel {
+                            key: key.clone(),
+                            value: value.clone(),
+                            reason: "Only UTF-8 charset is allowed".to_string(),
+                        });
+                    }
+                    config.charset = Some("UTF-8".to_string());
+                }
+                _ if key.starts_with("basicAuth.allowedPaths.") => {
+                    let username = key.trim_start_matches("basicAuth.allowedPaths.");
+                    let allowed_paths = value.split(',')
+                        .map(str::trim)
+                        .filter(|pattern| !pattern.is_empty())
+                        .map(PathMatcher::from_str)
+                        .collect::<Result<Vec<_>, _>>()
+                        .map_err(|e| MiddlewareError::InvalidLabel {
+                            key: key.clone(),
+                            value: value.clone(),
+                            reason: e.to_string(),
+                        })?;
+                    config.users.entry(username.to_string())
+                        .or_default()
+                        .allowed_paths = allowed_paths;
+                }
+                "basicAuth.htpasswdFile" | "basicAuth.htpasswd.path" => {
+                    config.htpasswd_file = Some(value.trim().to_string());
+                }
                 "basicAuth.source" => {
                     config.source = match value.to_lowercase().as_str() {
                         "htpasswd" => AuthSource::HtpasswdFile(String::new()),
@@ -114,6 +246,16 @@ impl BasicAuthConfig {
         
         Ok(config)
     }
+
+    /// 401 응답의 `WWW-Authenticate` 헤더 값을 생성합니다.
+    pub fn www_authenticate(&self) -> String {
+        // quoted-string 안의 `\`와 `"`는 이스케이프
+        let realm = self.realm.replace('\\', "\\\\").replace('"', "\\\"");
+        match &self.charset {
+            Some(charset) => format!("Basic realm=\"{}\", charset=\"{}\"", realm, charset),
+            None => format!("Basic realm=\"{}\"", realm),
+        }
+    }
 }
 
 #[cfg(test)]
@@ -136,7 +278,7 @@ mod tests {
         
         assert_eq!(config.realm, "My Realm");
         assert_eq!(
-            config.users.get("test").unwrap(),
+            config.users.get("test").unwrap().password_hash,
             "$apr1$H6uskkkW$IgXLP6ewTrSuBkTrqE8wj/"
         );
         assert_eq!(config.source, AuthSource::Labels);
@@ -161,4 +303,60 @@ mod tests {
             AuthSource::HtpasswdFile("/etc/nginx/.htpasswd".to_string())
         );
     }
+
+    #[test]
+    fn test_basic_auth_config_htpasswd_file() {
+        let mut labels = HashMap::new();
+        labels.insert("basicAuth.users".to_string(), "admin:$2y$05$hash".to_string());
+        labels.insert("basicAuth.htpasswdFile".to_string(), "/etc/rproxy/.htpasswd".to_string());
+
+        let config = BasicAuthConfig::from_labels(&labels).unwrap();
+
+        assert_eq!(config.source, AuthSource::Labels);
+        assert_eq!(config.htpasswd_file.as_deref(), Some("/etc/rproxy/.htpasswd"));
+        assert!(config.users.contains_key("admin"));
+    }
+
+    #[test]
+    fn test_basic_auth_config_allowed_paths() {
+        let mut labels = HashMap::new();
+        labels.insert("basicAuth.users".to_string(), "admin:hash1,dev:hash2".to_string());
+        labels.insert("basicAuth.allowedPaths.admin".to_string(), "/admin*".to_string());
+        labels.insert("basicAuth.allowedPaths.dev".to_string(), "/api*, ^/v[0-9]+/".to_string());
+
+        let config = BasicAuthConfig::from_labels(&labels).unwrap();
+
+        let admin = &config.users["admin"];
+        assert_eq!(admin.password_hash, "hash1");
+        assert!(admin.is_path_allowed("/admin/users"));
+        assert!(!admin.is_path_allowed("/api/items"));
+
+        // 세그먼트 경계와 정규화된 경로로 비교
+        assert!(admin.is_path_allowed("/admin"));
+        assert!(admin.is_path_allowed("//admin/./users/"));
+        assert!(!admin.is_path_allowed("/administrator"));
+        assert!(!admin.is_path_allowed("/admin/../secret"));
+        assert!(!admin.is_path_allowed("/admin/%2e%2e/secret"));
+
+        let dev = &config.users["dev"];
+        assert!(dev.is_path_allowed("/api/items"));
+        assert!(dev.is_path_allowed("/v2/status"));
+        assert!(!dev.is_path_allowed("/admin"));
+    }
+
+    #[test]
+    fn test_basic_auth_config_realm_and_charset() {
+        let config = BasicAuthConfig::default();
+        assert_eq!(config.www_authenticate(), "Basic realm=\"Restricted\"");
+
+        let mut labels = HashMap::new();
+        labels.insert("basicAuth.realm".to_string(), "Admin \"Area\"".to_string());
+        labels.insert("basicAuth.charset".to_string(), "utf-8".to_string());
+        let config = BasicAuthConfig::from_labels(&labels).unwrap();
+        assert_eq!(config.charset.as_deref(), Some("UTF-8"));
+        assert_eq!(config.www_authenticate(), "Basic realm=\"Admin \\\"Area\\\"\", charset=\"UTF-8\"");
+
+        labels.insert("basicAuth.charset".to_string(), "ISO-8859-1".to_string());
+        assert!(BasicAuthConfig::from_labels(&labels).is_err());
+    }
 }
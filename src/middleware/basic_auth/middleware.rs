@@ -58,14 +58,23 @@ impl BasicAuthMiddleware {
         ))
     }
 
+    /// 사용자에게 허용된 경로인지 확인합니다.
+    fn check_path_scope(&self, username: &str, path: &str) -> Result<(), MiddlewareError> {
+        match self.config.users.get(username) {
+            Some(entry) if !entry.is_path_allowed(path) => {
+                Err(MiddlewareError::Forbidden(format!(
+                    "User '{}' is not allowed to access {}", username, path
+                )))
+            }
+            _ => Ok(()),
+        }
+    }
+
     /// 401 Unauthorized 응답을 생성합니다.
     fn unauthorized_response(&self) -> Response {
         Response::builder()
             .status(StatusCode::UNAUTHORIZED)
-            .header(
-                header::WWW_AUTHENTICATE,
-                format!("Basic realm=\"{}\"", self.config.realm)
-            )
+            .header(header::WWW_AUTHENTICATE, self.config.www_authenticate())
             .body(Full::new(Bytes::from("Unauthorized")))
             .unwrap()
     }
@@ -73,6 +82,9 @@ impl BasicAuthMiddleware {
 
 #[async_trait]
 impl Middleware for BasicAuthMiddleware {
+    fn name(&self) -> &'static str {
+        "basic-auth"
+    }
 
     async fn handle_request(&self, req: Request) -> Result<Request, MiddlewareError> {
         // 자격증명 추출
@@ -80,6 +92,7 @@ impl Middleware for BasicAuthMiddleware {
             Ok((username, password)) => {
                 // 변경: 인증기를 통한 검증
                 if self.authenticator.verify_credentials(&username, &password) {
+                    self.check_path_scope(&username, req.uri().path())?;
                     Ok(req)
                 } else {
                     Err(MiddlewareError::Runtime {
@@ -106,13 +119,22 @@ impl Middleware for BasicAuthMiddleware {
 mod tests {
 
     use super::*;
+    use super::super::UserEntry;
+    use crate::routing_v2::PathMatcher;
     use std::collections::HashMap;
 
     fn create_test_middleware() -> BasicAuthMiddleware {
         let mut users = HashMap::new();
         users.insert(
             "test".to_string(),
-            "$apr1$H6uskkkW$IgXLP6ewTrSuBkTrqE8wj/".to_string()
+            UserEntry::new("$apr1$H6uskkkW$IgXLP6ewTrSuBkTrqE8wj/")
+        );
+        users.insert(
+            "admin".to_string(),
+            UserEntry {
+                password_hash: String::new(),
+                allowed_paths: vec![PathMatcher::from_str("/admin*").unwrap()],
+            }
         );
 
         let config = BasicAuthConfig {
@@ -130,6 +152,21 @@ mod tests {
         let response = middleware.unauthorized_response();
 
         assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
-        assert!(response.headers().contains_key(header::WWW_AUTHENTICATE));
+        assert_eq!(response.headers()[header::WWW_AUTHENTICATE], "Basic realm=\"Test Realm\"");
+    }
+
+    #[test]
+    fn test_path_scope() {
+        let middleware = create_test_middleware();
+
+        assert!(middleware.check_path_scope("admin", "/admin/users").is_ok());
+        assert!(matches!(
+            middleware.check_path_scope("admin", "/api/items"),
+            Err(MiddlewareError::Forbidden(_))
+        ));
+
+        // 경로 제한이 없는 사용자와 다른 소스의 사용자는 모든 경로 허용
+        assert!(middleware.check_path_scope("test", "/api/items").is_ok());
+        assert!(middleware.check_path_scope("file-user", "/api/items").is_ok());
     }
 }
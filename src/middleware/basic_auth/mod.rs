@@ -3,5 +3,5 @@ mod config;
 mod middleware;
 
 pub use auth::create_authenticator;
-pub use config::BasicAuthConfig;
+pub use config::{BasicAuthConfig, UserEntry};
 pub use middleware::BasicAuthMiddleware;
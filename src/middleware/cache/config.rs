@@ -0,0 +1,123 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::time::Duration;
+use crate::middleware::MiddlewareError;
+
+/// 응답 캐시 설정
+///
+/// # Docker 라벨 예시
+/// ```yaml
+/// labels:
+///   - "rproxy.http.middlewares.api-cache.type=cache"
+///   - "rproxy.http.middlewares.api-cache.cache.maxEntries=500"
+///   - "rproxy.http.middlewares.api-cache.cache.defaultTtlSecs=30"
+///   - "rproxy.http.middlewares.api-cache.cache.maxBodyBytes=1048576"
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct CacheConfig {
+    /// 최대 캐시 항목 수 (초과하면 가장 오래 사용하지 않은 항목부터 제거)
+    pub max_entries: usize,
+    /// 응답에 만료 시간이 없을 때 사용하는 TTL
+    pub default_ttl: Duration,
+    /// `Cache-Control` 헤더(no-store, no-cache, private, max-age)를 따를지 여부
+    pub respect_cache_control: bool,
+    /// `Authorization`/`Cookie` 헤더가 있는 요청도 캐시할지 여부
+    pub ignore_auth: bool,
+    /// 캐시할 응답 바디의 최대 크기 (초과하면 저장하지 않고 스트리밍)
+    pub max_body_bytes: usize,
+}
+
+impl Default for CacheConfig {
+    fn default() -> Self {
+        Self {
+            max_entries: 1000,
+            default_ttl: Duration::from_secs(60),
+            respect_cache_control: true,
+            ignore_auth: false,
+            max_body_bytes: 1024 * 1024,
+        }
+    }
+}
+
+impl CacheConfig {
+    /// Docker 라벨에서 설정을 파싱합니다.
+    pub fn from_labels(labels: &HashMap<String, String>) -> Result<Self, MiddlewareError> {
+        let mut config = Self::default();
+
+        for (key, value) in labels {
+            let invalid = |reason: &str| MiddlewareError::InvalidLabel {
+                key: key.clone(),
+                value: value.clone(),
+                reason: reason.to_string(),
+            };
+
+            match key.as_str() {
+                "cache.maxEntries" => {
+                    config.max_entries = value.trim().parse()
+                        .ok()
+                        .filter(|entries| *entries > 0)
+                        .ok_or_else(|| invalid("must be a positive integer"))?;
+                }
+                "cache.defaultTtlSecs" => {
+                    let secs: u64 = value.trim().parse()
+                        .map_err(|_| invalid("must be a number of seconds"))?;
+                    config.default_ttl = Duration::from_secs(secs);
+                }
+                "cache.respectCacheControl" => {
+                    config.respect_cache_control = value.trim().parse()
+                        .map_err(|_| invalid("must be true or false"))?;
+                }
+                "cache.ignoreAuth" => {
+                    config.ignore_auth = value.trim().parse()
+                        .map_err(|_| invalid("must be true or false"))?;
+                }
+                "cache.maxBodyBytes" => {
+                    config.max_body_bytes = value.trim().parse()
+                        .map_err(|_| invalid("must be a number of bytes"))?;
+                }
+                _ => continue,
+            }
+        }
+
+        Ok(config)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_labels() {
+        let config = CacheConfig::from_labels(&HashMap::new()).unwrap();
+        assert_eq!(config, CacheConfig::default());
+
+        let labels: HashMap<String, String> = [
+            ("cache.maxEntries", "10"),
+            ("cache.defaultTtlSecs", "5"),
+            ("cache.respectCacheControl", "false"),
+            ("cache.ignoreAuth", "true"),
+            ("cache.maxBodyBytes", "2048"),
+        ].into_iter().map(|(k, v)| (k.to_string(), v.to_string())).collect();
+
+        let config = CacheConfig::from_labels(&labels).unwrap();
+        assert_eq!(config.max_entries, 10);
+        assert_eq!(config.default_ttl, Duration::from_secs(5));
+        assert!(!config.respect_cache_control);
+        assert!(config.ignore_auth);
+        assert_eq!(config.max_body_bytes, 2048);
+    }
+
+    #[test]
+    fn test_invalid_labels() {
+        for (key, value) in [
+            ("cache.maxEntries", "0"),
+            ("cache.defaultTtlSecs", "soon"),
+            ("cache.ignoreAuth", "yes"),
+            ("cache.maxBodyBytes", "1MB"),
+        ] {
+            let labels = HashMap::from([(key.to_string(), value.to_string())]);
+            assert!(CacheConfig::from_labels(&labels).is_err(), "{}={}", key, value);
+        }
+    }
+}
@@ -0,0 +1,477 @@
+use crate::middleware::{Middleware, MiddlewareError, Request, Response, StreamedResponseBody};
+use super::config::CacheConfig;
+use async_trait::async_trait;
+use bytes::Bytes;
+use http_body_util::{BodyExt, Full};
+use hyper::header::HeaderName;
+use hyper::{header, HeaderMap, Method, StatusCode};
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tracing::debug;
+
+/// 캐시 적중 여부를 알리는 응답 헤더 (`HIT` 또는 `MISS`)
+pub const X_CACHE: &str = "x-cache";
+
+/// 캐시 키 (메서드, 호스트, 경로, 쿼리 문자열)
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct CacheKey {
+    method: Method,
+    host: String,
+    path: String,
+    query: Option<String>,
+}
+
+impl CacheKey {
+    fn from_request(req: &Request) -> Self {
+        let host = req.headers()
+            .get(header::HOST)
+            .and_then(|h| h.to_str().ok())
+            .or_else(|| req.uri().host())
+            .unwrap_or_default()
+            .to_ascii_lowercase();
+
+        Self {
+            method: req.method().clone(),
+            host,
+            path: req.uri().path().to_string(),
+            query: req.uri().query().map(String::from),
+        }
+    }
+}
+
+/// `Vary`에 나열된 요청 헤더 값 (헤더 순서대로, 없으면 `None`)
+type VaryValues = Vec<Option<Bytes>>;
+
+fn vary_values(names: &[HeaderName], headers: &HeaderMap) -> VaryValues {
+    names.iter()
+        .map(|name| {
+            let values: Vec<&[u8]> = headers.get_all(name).iter().map(|v| v.as_bytes()).collect();
+            (!values.is_empty()).then(|| Bytes::from(values.join(&b","[..])))
+        })
+        .collect()
+}
+
+/// 캐시 대상 요청 정보
+///
+/// 요청의 확장 데이터에 기록되어 응답 단계에서 저장 위치와 `Vary` 값 계산에 사용됩니다.
+#[derive(Debug, Clone)]
+struct PendingEntry {
+    key: CacheKey,
+    request_headers: HeaderMap,
+}
+
+/// 저장된 응답
+#[derive(Debug)]
+struct CachedResponse {
+    status: StatusCode,
+    headers: HeaderMap,
+    body: Bytes,
+    expires_at: Instant,
+    /// 마지막 사용 순번 (`LruCache::order`의 키)
+    last_used: u64,
+}
+
+/// 같은 캐시 키에 대해 `Vary` 요청 헤더 값별로 저장된 응답
+#[derive(Debug, Default)]
+struct Variants {
+    /// 응답의 `Vary` 헤더 이름 (소문자)
+    vary: Vec<HeaderName>,
+    responses: HashMap<VaryValues, CachedResponse>,
+}
+
+/// 최근 사용 순서로 항목을 제거하는 캐시
+#[derive(Debug, Default)]
+struct LruCache {
+    entries: HashMap<CacheKey, Variants>,
+    /// 사용 순번 → (키, `Vary` 값) (가장 작은 순번이 가장 오래 사용하지 않은 항목)
+    order: BTreeMap<u64, (CacheKey, VaryValues)>,
+    tick: u64,
+}
+
+impl LruCache {
+    fn next_tick(&mut self) -> u64 {
+        self.tick += 1;
+        self.tick
+    }
+
+    /// 저장된 응답 수 (`Vary` 값별 응답을 각각 셈)
+    fn len(&self) -> usize {
+        self.order.len()
+    }
+
+    /// 요청 헤더에 맞는 만료되지 않은 응답을 만들고 최근 사용으로 표시합니다.
+    fn get(&mut self, key: &CacheKey, request_headers: &HeaderMap, now: Instant) -> Option<Response> {
+        let variants = self.entries.get(key)?;
+        let values = vary_values(&variants.vary, request_headers);
+        if variants.responses.get(&values)?.expires_at <= now {
+            self.remove(key, &values);
+            return None;
+        }
+
+        let tick = self.next_tick();
+        let entry = self.entries.get_mut(key)?.responses.get_mut(&values)?;
+        self.order.remove(&entry.last_used);
+        self.order.insert(tick, (key.clone(), values));
+        entry.last_used = tick;
+
+        let mut response = Response::new(Full::new(entry.body.clone()));
+        *response.status_mut() = entry.status;
+        *response.headers_mut() = entry.headers.clone();
+        Some(response)
+    }
+
+    /// 항목을 저장합니다. 최대 항목 수를 넘으면 가장 오래 사용하지 않은 항목부터 제거합니다.
+    ///
+    /// 응답의 `Vary` 헤더 목록이 바뀌면 같은 키의 기존 응답은 모두 버립니다.
+    fn insert(
+        &mut self,
+        key: CacheKey,
+        vary: Vec<HeaderName>,
+        values: VaryValues,
+        mut entry: CachedResponse,
+        max_entries: usize,
+    ) {
+        if self.entries.get(&key).is_some_and(|variants| variants.vary != vary) {
+            self.remove_all(&key);
+        }
+        self.remove(&key, &values);
+        while self.len() >= max_entries {
+            let Some((_, (oldest, oldest_values))) = self.order.pop_first() else { break };
+            debug!(key = ?oldest, "캐시 항목 제거 (LRU)");
+            self.remove(&oldest, &oldest_values);
+        }
+
+        entry.last_used = self.next_tick();
+        self.order.insert(entry.last_used, (key.clone(), values.clone()));
+        let variants = self.entries.entry(key).or_default();
+        variants.vary = vary;
+        variants.responses.insert(values, entry);
+    }
+
+    fn remove(&mut self, key: &CacheKey, values: &VaryValues) {
+        let Some(variants) = self.entries.get_mut(key) else { return };
+        if let Some(entry) = variants.responses.remove(values) {
+            self.order.remove(&entry.last_used);
+        }
+        if variants.responses.is_empty() {
+            self.entries.remove(key);
+        }
+    }
+
+    fn remove_all(&mut self, key: &CacheKey) {
+        if let Some(variants) = self.entries.remove(key) {
+            for entry in variants.responses.values() {
+                self.order.remove(&entry.last_used);
+            }
+        }
+    }
+}
+
+/// 응답 캐시 미들웨어
+///
+/// `GET`/`HEAD` 요청의 200 응답을 메모리 LRU 캐시에 저장합니다.
+/// 캐시 적중 시 백엔드와 이후 미들웨어를 거치지 않고 저장된 응답을 반환합니다.
+/// 응답의 `Vary` 헤더에 나열된 요청 헤더 값이 다르면 별도 항목으로 저장합니다.
+#[derive(Debug)]
+pub struct ResponseCacheMiddleware {
+    config: CacheConfig,
+    cache: Mutex<LruCache>,
+}
+
+impl ResponseCacheMiddleware {
+    pub fn new(config: CacheConfig) -> Self {
+        Self {
+            config,
+            cache: Mutex::new(LruCache::default()),
+        }
+    }
+
+    fn cache(&self) -> std::sync::MutexGuard<'_, LruCache> {
+        self.cache.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    /// 저장된 항목 수를 반환합니다.
+    pub fn len(&self) -> usize {
+        self.cache().len()
+    }
+
+    /// 저장된 항목이 없는지 확인합니다.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// `Cache-Control` 지시어 목록 (소문자)
+    fn cache_control(headers: &HeaderMap) -> Vec<String> {
+        headers.get_all(header::CACHE_CONTROL)
+            .iter()
+            .filter_map(|value| value.to_str().ok())
+            .flat_map(|value| value.split(','))
+            .map(|directive| directive.trim().to_ascii_lowercase())
+            .collect()
+    }
+
+    /// 응답의 `Vary` 헤더 이름 목록 (`Vary: *`이면 저장할 수 없으므로 `None`)
+    fn vary(headers: &HeaderMap) -> Option<Vec<HeaderName>> {
+        let mut names = Vec::new();
+        for name in headers.get_all(header::VARY)
+            .iter()
+            .filter_map(|value| value.to_str().ok())
+            .flat_map(|value| value.split(','))
+            .map(str::trim)
+            .filter(|name| !name.is_empty())
+        {
+            if name == "*" {
+                return None;
+            }
+            if let Ok(name) = HeaderName::from_bytes(name.as_bytes()) {
+                if !names.contains(&name) {
+                    names.push(name);
+                }
+            }
+        }
+        Some(names)
+    }
+
+    /// 응답을 저장할 기간을 계산합니다. 저장하지 않아야 하면 `None`입니다.
+    fn response_ttl(&self, headers: &HeaderMap) -> Option<Duration> {
+        if !self.config.respect_cache_control {
+            return Some(self.config.default_ttl);
+        }
+
+        let directives = Self::cache_control(headers);
+        if directives.iter().any(|d| matches!(d.as_str(), "no-store" | "no-cache" | "private")) {
+            return None;
+        }
+
+        let max_age = |name: &str| directives.iter()
+            .find_map(|d| d.strip_prefix(name)?.strip_prefix('=')?.trim_matches('"').parse().ok());
+        Some(max_age("s-maxage")
+            .or_else(|| max_age("max-age"))
+            .map(Duration::from_secs)
+            .unwrap_or(self.config.default_ttl))
+    }
+}
+
+#[async_trait]
+impl Middleware for ResponseCacheMiddleware {
+    fn name(&self) -> &'static str {
+        "cache"
+    }
+
+    fn response_body_limit(&self, parts: &hyper::http::response::Parts) -> Option<usize> {
+        // 캐시 대상 요청의 200 응답만 바디를 모음
+        (parts.status == StatusCode::OK && parts.extensions.get::<PendingEntry>().is_some())
+            .then_some(self.config.max_body_bytes)
+    }
+
+    async fn handle_request(&self, mut req: Request) -> Result<Request, MiddlewareError> {
+        if !matches!(*req.method(), Method::GET | Method::HEAD) {
+            return Ok(req);
+        }
+        // 사용자별 응답일 수 있으므로 인증 정보나 쿠키가 있는 요청은 캐시하지 않음
+        if !self.config.ignore_auth
+            && (req.headers().contains_key(header::AUTHORIZATION) || req.headers().contains_key(header::COOKIE))
+        {
+            debug!("Authorization/Cookie 헤더가 있는 요청은 캐시하지 않음");
+            return Ok(req);
+        }
+
+        let directives = if self.config.respect_cache_control {
+            Self::cache_control(req.headers())
+        } else {
+            Vec::new()
+        };
+        // no-store 요청은 저장하지도 않음
+        if directives.iter().any(|d| d == "no-store") {
+            return Ok(req);
+        }
+
+        let key = CacheKey::from_request(&req);
+        // no-cache 요청은 저장된 응답을 쓰지 않고 새 응답으로 갱신
+        if !directives.iter().any(|d| d == "no-cache") {
+            if let Some(mut response) = self.cache().get(&key, req.headers(), Instant::now()) {
+                debug!(key = ?key, "캐시 적중");
+                response.headers_mut().insert(X_CACHE, "HIT".parse().unwrap());
+                return Err(MiddlewareError::CachedResponse(response));
+            }
+        }
+
+        let request_headers = req.headers().clone();
+        req.extensions_mut().insert(PendingEntry { key, request_headers });
+        Ok(req)
+    }
+
+    async fn handle_response(&self, res: Response) -> Result<Response, MiddlewareError> {
+        let Some(pending) = res.extensions().get::<PendingEntry>().cloned() else {
+            return Ok(res);
+        };
+
+        let (mut parts, body) = res.into_parts();
+        let body = match body.collect().await {
+            Ok(collected) => collected.to_bytes(),
+            Err(never) => match never {},
+        };
+
+        // 바디가 한도를 넘어 스트리밍된 응답은 저장할 바디가 없음
+        let streamed = parts.extensions.get::<StreamedResponseBody>().is_some();
+        let vary = Self::vary(&parts.headers);
+        let ttl = if parts.status == StatusCode::OK && !streamed && !parts.headers.contains_key(header::SET_COOKIE) {
+            self.response_ttl(&parts.headers).filter(|ttl| !ttl.is_zero())
+        } else {
+            None
+        };
+
+        if let (Some(ttl), Some(vary)) = (ttl, vary) {
+            debug!(key = ?pending.key, ttl_secs = ttl.as_secs(), "응답 캐시 저장");
+            let mut headers = parts.headers.clone();
+            for hop_by_hop in [header::CONNECTION, header::TRANSFER_ENCODING] {
+                headers.remove(hop_by_hop);
+            }
+            let values = vary_values(&vary, &pending.request_headers);
+            self.cache().insert(pending.key, vary, values, CachedResponse {
+                status: parts.status,
+                headers,
+                body: body.clone(),
+                expires_at: Instant::now() + ttl,
+                last_used: 0,
+            }, self.config.max_entries);
+        }
+
+        parts.headers.insert(X_CACHE, "MISS".parse().unwrap());
+        Ok(Response::from_parts(parts, Full::new(body)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn middleware(config: CacheConfig) -> ResponseCacheMiddleware {
+        ResponseCacheMiddleware::new(config)
+    }
+
+    fn key(path: &str) -> CacheKey {
+        CacheKey {
+            method: Method::GET,
+            host: "example.com".to_string(),
+            path: path.to_string(),
+            query: None,
+        }
+    }
+
+    fn entry(body: &'static str, ttl: Duration) -> CachedResponse {
+        CachedResponse {
+            status: StatusCode::OK,
+            headers: HeaderMap::new(),
+            body: Bytes::from(body),
+            expires_at: Instant::now() + ttl,
+            last_used: 0,
+        }
+    }
+
+    fn body(response: Response) -> Bytes {
+        futures_util::FutureExt::now_or_never(response.into_body().collect())
+            .unwrap()
+            .unwrap()
+            .to_bytes()
+    }
+
+    #[test]
+    fn test_lru_eviction() {
+        let mut cache = LruCache::default();
+        let ttl = Duration::from_secs(60);
+        let none = HeaderMap::new();
+        cache.insert(key("/a"), vec![], vec![], entry("a", ttl), 2);
+        cache.insert(key("/b"), vec![], vec![], entry("b", ttl), 2);
+
+        // /a를 사용하면 /b가 가장 오래 사용하지 않은 항목이 됨
+        assert!(cache.get(&key("/a"), &none, Instant::now()).is_some());
+        cache.insert(key("/c"), vec![], vec![], entry("c", ttl), 2);
+
+        assert!(cache.get(&key("/a"), &none, Instant::now()).is_some());
+        assert!(cache.get(&key("/b"), &none, Instant::now()).is_none());
+        assert!(cache.get(&key("/c"), &none, Instant::now()).is_some());
+        assert_eq!(cache.entries.len(), cache.order.len());
+    }
+
+    #[test]
+    fn test_expired_entry_removed() {
+        let mut cache = LruCache::default();
+        let none = HeaderMap::new();
+        cache.insert(key("/a"), vec![], vec![], entry("a", Duration::from_secs(1)), 10);
+
+        assert!(cache.get(&key("/a"), &none, Instant::now() + Duration::from_secs(2)).is_none());
+        assert!(cache.entries.is_empty());
+        assert!(cache.order.is_empty());
+    }
+
+    #[test]
+    fn test_vary_variants() {
+        let mut cache = LruCache::default();
+        let ttl = Duration::from_secs(60);
+        let vary = vec![header::ACCEPT_ENCODING];
+        let request = |encoding: Option<&str>| {
+            let mut headers = HeaderMap::new();
+            if let Some(encoding) = encoding {
+                headers.insert(header::ACCEPT_ENCODING, encoding.parse().unwrap());
+            }
+            headers
+        };
+
+        for (encoding, body) in [(Some("gzip"), "gzip"), (None, "plain")] {
+            let values = vary_values(&vary, &request(encoding));
+            cache.insert(key("/a"), vary.clone(), values, entry(body, ttl), 10);
+        }
+        assert_eq!(cache.len(), 2);
+
+        // Vary 헤더 값에 맞는 응답만 반환
+        let hit = |cache: &mut LruCache, encoding| cache.get(&key("/a"), &request(encoding), Instant::now()).map(body);
+        assert_eq!(hit(&mut cache, Some("gzip")), Some(Bytes::from("gzip")));
+        assert_eq!(hit(&mut cache, None), Some(Bytes::from("plain")));
+        assert_eq!(hit(&mut cache, Some("br")), None);
+
+        // Vary 목록이 바뀌면 기존 변형은 모두 버림
+        cache.insert(key("/a"), vec![], vec![], entry("any", ttl), 10);
+        assert_eq!(cache.len(), 1);
+        assert_eq!(hit(&mut cache, Some("gzip")), Some(Bytes::from("any")));
+    }
+
+    #[test]
+    fn test_vary_header() {
+        let headers = |value: &str| {
+            let mut headers = HeaderMap::new();
+            headers.insert(header::VARY, value.parse().unwrap());
+            headers
+        };
+
+        assert_eq!(ResponseCacheMiddleware::vary(&HeaderMap::new()), Some(vec![]));
+        assert_eq!(
+            ResponseCacheMiddleware::vary(&headers("Accept-Encoding, accept-language, Accept-Encoding")),
+            Some(vec![header::ACCEPT_ENCODING, header::ACCEPT_LANGUAGE])
+        );
+        assert_eq!(ResponseCacheMiddleware::vary(&headers("Accept, *")), None);
+    }
+
+    #[test]
+    fn test_response_ttl() {
+        let middleware = middleware(CacheConfig::default());
+        let headers = |value: &str| {
+            let mut headers = HeaderMap::new();
+            headers.insert(header::CACHE_CONTROL, value.parse().unwrap());
+            headers
+        };
+
+        assert_eq!(middleware.response_ttl(&HeaderMap::new()), Some(Duration::from_secs(60)));
+        assert_eq!(middleware.response_ttl(&headers("public, max-age=10")), Some(Duration::from_secs(10)));
+        assert_eq!(middleware.response_ttl(&headers("max-age=10, s-maxage=20")), Some(Duration::from_secs(20)));
+        assert_eq!(middleware.response_ttl(&headers("no-store")), None);
+        assert_eq!(middleware.response_ttl(&headers("private, max-age=10")), None);
+
+        let middleware = ResponseCacheMiddleware::new(CacheConfig {
+            respect_cache_control: false,
+            ..CacheConfig::default()
+        });
+        assert_eq!(middleware.response_ttl(&headers("no-store")), Some(Duration::from_secs(60)));
+    }
+}
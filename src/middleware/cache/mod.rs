@@ -0,0 +1,9 @@
+//! 응답 캐시 미들웨어
+//! 
+//! `GET`/`HEAD` 요청의 200 응답을 메모리에 저장하고, 같은 요청에는 백엔드를 거치지 않고 응답합니다.
+
+mod config;
+mod middleware;
+
+pub use config::CacheConfig;
+pub use middleware::{ResponseCacheMiddleware, X_CACHE};
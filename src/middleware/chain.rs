@@ -1,62 +1,265 @@
 use super::{Middleware, Request, Response, MiddlewareError};
+use crate::metrics;
+use std::env;
 use std::sync::Arc;
-use tracing::debug;
+use std::time::{Duration, Instant};
+use tracing::{debug, warn};
+
+/// 느린 미들웨어 체인 경고 임계값 기본값 (밀리초)
+const DEFAULT_SLOW_MIDDLEWARE_THRESHOLD_MS: u64 = 100;
+
+/// 미들웨어별 실행 시간 목록 (미들웨어 이름, 실행 시간)
+pub type MiddlewareTimings = Vec<(&'static str, Duration)>;
 
 /// 미들웨어 체인
-/// 
+///
 /// 여러 미들웨어를 순서대로 실행합니다.
-#[derive(Default, Clone)]
+///
+/// 미들웨어는 `Arc`로 공유되므로 복제 비용이 낮고, 요청 처리 태스크마다
+/// 복제한 체인을 소유할 수 있습니다. 복제된 체인은 미들웨어 상태(예: Rate Limit 카운터)를 공유합니다.
+#[derive(Clone)]
 pub struct MiddlewareChain {
     middlewares: Vec<Arc<dyn Middleware>>,
+    /// 이 시간을 초과한 체인 실행은 WARN 레벨로 기록됩니다.
+    slow_middleware_threshold_ms: u64,
+}
+
+impl Default for MiddlewareChain {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// `PROXY_SLOW_MIDDLEWARE_THRESHOLD_MS` 환경변수에서 임계값을 읽습니다.
+fn default_slow_middleware_threshold_ms() -> u64 {
+    env::var("PROXY_SLOW_MIDDLEWARE_THRESHOLD_MS")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(DEFAULT_SLOW_MIDDLEWARE_THRESHOLD_MS)
 }
 
 impl MiddlewareChain {
     pub fn new() -> Self {
         Self {
             middlewares: Vec::new(),
+            slow_middleware_threshold_ms: default_slow_middleware_threshold_ms(),
         }
     }
 
+    /// 느린 체인 경고 임계값을 설정합니다.
+    pub fn with_slow_middleware_threshold_ms(mut self, threshold_ms: u64) -> Self {
+        self.slow_middleware_threshold_ms = threshold_ms;
+        self
+    }
+
     /// 미들웨어를 체인에 추가합니다.
+    /// 미들웨어 이름(`Middleware::name`)은 실행 시간 기록과 로깅에 사용됩니다.
     pub fn add_boxed(&mut self, middleware: Box<dyn Middleware>) {
-        let arc: Arc<dyn Middleware> = Arc::from(middleware);
-        self.middlewares.push(arc);
+        self.middlewares.push(Arc::from(middleware));
     }
 
     /// 요청 체인을 실행합니다.
-    pub async fn handle_request(&self, mut req: Request) -> Result<Request, MiddlewareError> {
+    pub async fn handle_request(&self, req: Request) -> Result<Request, MiddlewareError> {
+        self.handle_request_timed(req).await.map(|(req, _)| req)
+    }
+
+    /// 요청 체인을 실행하고 미들웨어별 실행 시간을 함께 반환합니다.
+    pub async fn handle_request_timed(&self, mut req: Request) -> Result<(Request, MiddlewareTimings), MiddlewareError> {
         debug!("미들웨어 체인 요청 처리 시작 - 미들웨어 수: {}", self.middlewares.len());
+        let mut timings = Vec::with_capacity(self.middlewares.len());
         for (index, middleware) in self.middlewares.iter().enumerate() {
-            debug!("요청 미들웨어 실행 #{} - 타입: {:?}", index, std::any::type_name::<dyn Middleware>());
-            req = middleware.handle_request(req).await?;
+            let name = middleware.name();
+            debug!("요청 미들웨어 실행 #{} - 타입: {}", index, name);
+            let start = Instant::now();
+            let result = middleware.handle_request(req).await;
+            timings.push((name, start.elapsed()));
+            req = match result {
+                Ok(req) => req,
+                Err(e) => {
+                    debug!(middleware = name, error = %e, "요청 미들웨어 처리 실패");
+                    self.report_timings("요청", &timings);
+                    return Err(e);
+                }
+            };
         }
+        self.report_timings("요청", &timings);
         debug!("미들웨어 체인 요청 처리 완료");
-        Ok(req)
+        Ok((req, timings))
     }
 
     /// 응답 체인을 실행합니다.
-    pub async fn handle_response(&self, mut res: Response) -> Result<Response, MiddlewareError> {
+    pub async fn handle_response(&self, res: Response) -> Result<Response, MiddlewareError> {
+        self.handle_response_timed(res).await.map(|(res, _)| res)
+    }
+
+    /// 응답 체인을 실행하고 미들웨어별 실행 시간을 함께 반환합니다.
+    pub async fn handle_response_timed(&self, mut res: Response) -> Result<(Response, MiddlewareTimings), MiddlewareError> {
         debug!("미들웨어 체인 응답 처리 시작 - 미들웨어 수: {}", self.middlewares.len());
+        let mut timings = Vec::with_capacity(self.middlewares.len());
         // 응답은 역순으로 처리
         for (index, middleware) in self.middlewares.iter().rev().enumerate() {
-            debug!("응답 미들웨어 실행 #{} - 타입: {:?}", index, std::any::type_name::<dyn Middleware>());
-            res = middleware.handle_response(res).await?;
+            let name = middleware.name();
+            debug!("응답 미들웨어 실행 #{} - 타입: {}", index, name);
+            let start = Instant::now();
+            let result = middleware.handle_response(res).await;
+            timings.push((name, start.elapsed()));
+            res = match result {
+                Ok(res) => res,
+                Err(e) => {
+                    debug!(middleware = name, error = %e, "응답 미들웨어 처리 실패");
+                    self.report_timings("응답", &timings);
+                    return Err(e);
+                }
+            };
         }
+        self.report_timings("응답", &timings);
         debug!("미들웨어 체인 응답 처리 완료 - 최종 헤더: {:?}", res.headers());
-        Ok(res)
+        Ok((res, timings))
     }
 
-    pub fn middleware_count(&self) -> usize {
+    /// 미들웨어별 실행 시간을 메트릭에 기록하고, 체인 전체 실행 시간이 임계값을 넘으면 WARN 레벨로 기록합니다.
+    fn report_timings(&self, phase: &str, timings: &[(&'static str, Duration)]) {
+        for (name, elapsed) in timings {
+            metrics::MIDDLEWARE_DURATION.observe(name, *elapsed);
+        }
+        let total: Duration = timings.iter().map(|(_, elapsed)| *elapsed).sum();
+        if total > Duration::from_millis(self.slow_middleware_threshold_ms) {
+            warn!(
+                phase = %phase,
+                total_ms = total.as_millis() as u64,
+                threshold_ms = self.slow_middleware_threshold_ms,
+                timings = ?timings,
+                "느린 미들웨어 체인 감지"
+            );
+        } else {
+            debug!(phase = %phase, timings = ?timings, "미들웨어 실행 시간");
+        }
+    }
+
+    /// 응답 바디를 모을 최대 크기를 반환합니다. (`Middleware::response_body_limit` 중 가장 큰 값)
+    ///
+    /// 바디가 필요한 미들웨어가 없으면 `None`입니다.
+    pub fn response_body_limit(&self, parts: &hyper::http::response::Parts) -> Option<usize> {
+        self.middlewares.iter()
+            .filter_map(|middleware| middleware.response_body_limit(parts))
+            .max()
+    }
+
+    /// 체인에 등록된 미들웨어 수를 반환합니다.
+    pub fn len(&self) -> usize {
         self.middlewares.len()
     }
 
+    /// 등록된 미들웨어가 없는지 확인합니다.
+    pub fn is_empty(&self) -> bool {
+        self.middlewares.is_empty()
+    }
+
     pub fn middleware_types(&self) -> Option<Vec<&'static str>> {
         if self.middlewares.is_empty() {
             None
         } else {
             Some(self.middlewares.iter()
-                .map(|m| std::any::type_name::<dyn Middleware>())
+                .map(|middleware| middleware.name())
                 .collect())
         }
     }
-} 
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use async_trait::async_trait;
+    use http_body_util::Full;
+    use bytes::Bytes;
+
+    struct SleepMiddleware(&'static str, Duration);
+
+    #[async_trait]
+    impl Middleware for SleepMiddleware {
+        fn name(&self) -> &'static str {
+            self.0
+        }
+
+        async fn handle_request(&self, req: Request) -> Result<Request, MiddlewareError> {
+            tokio::time::sleep(self.1).await;
+            Ok(req)
+        }
+
+        async fn handle_response(&self, res: Response) -> Result<Response, MiddlewareError> {
+            tokio::time::sleep(self.1).await;
+            Ok(res)
+        }
+    }
+
+    struct CountingMiddleware(std::sync::atomic::AtomicUsize);
+
+    #[async_trait]
+    impl Middleware for CountingMiddleware {
+        fn name(&self) -> &'static str {
+            "counting"
+        }
+
+        async fn handle_request(&self, req: Request) -> Result<Request, MiddlewareError> {
+            Ok(req)
+        }
+
+        async fn handle_response(&self, res: Response) -> Result<Response, MiddlewareError> {
+            self.0.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            Ok(res)
+        }
+    }
+
+    #[tokio::test]
+    async fn test_cloned_chain_in_parallel_tasks() {
+        let counter = Arc::new(CountingMiddleware(Default::default()));
+        let mut chain = MiddlewareChain::new();
+        chain.middlewares.push(counter.clone());
+
+        let tasks: Vec<_> = (0..4)
+            .map(|_| {
+                let chain = chain.clone();
+                tokio::spawn(async move {
+                    let res = Response::new(Full::new(Bytes::new()));
+                    chain.handle_response(res).await.unwrap();
+                })
+            })
+            .collect();
+        for task in tasks {
+            task.await.unwrap();
+        }
+
+        // 복제된 체인은 같은 미들웨어 인스턴스를 공유
+        assert_eq!(counter.0.load(std::sync::atomic::Ordering::SeqCst), 4);
+        assert_eq!(chain.middleware_types(), Some(vec!["counting"]));
+    }
+
+    #[tokio::test]
+    async fn test_response_timings_per_middleware() {
+        let mut chain = MiddlewareChain::new().with_slow_middleware_threshold_ms(0);
+        chain.add_boxed(Box::new(SleepMiddleware("fast", Duration::from_millis(0))));
+        chain.add_boxed(Box::new(SleepMiddleware("slow", Duration::from_millis(20))));
+
+        let res = Response::new(Full::new(Bytes::from("ok")));
+        let (_, timings) = chain.handle_response_timed(res).await.unwrap();
+
+        // 응답은 역순으로 실행됨
+        assert_eq!(timings.len(), 2);
+        assert_eq!(timings[0].0, "slow");
+        assert_eq!(timings[1].0, "fast");
+        assert!(timings[0].1 >= Duration::from_millis(20));
+        assert_eq!(chain.middleware_types(), Some(vec!["fast", "slow"]));
+    }
+
+    #[test]
+    fn test_len_and_is_empty() {
+        let mut chain = MiddlewareChain::new();
+        assert!(chain.is_empty());
+        assert_eq!(chain.len(), 0);
+
+        chain.add_boxed(Box::new(SleepMiddleware("first", Duration::ZERO)));
+        chain.add_boxed(Box::new(SleepMiddleware("second", Duration::ZERO)));
+        assert!(!chain.is_empty());
+        assert_eq!(chain.len(), 2);
+    }
+}
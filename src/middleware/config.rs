@@ -1,7 +1,10 @@
 use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
 use std::collections::HashMap;
 use std::str::FromStr;
-use tracing::debug;
+use tracing::{debug, warn};
+use crate::settings::types::ValidMiddlewareId;
+use super::MiddlewareError;
 
 /// 미들웨어 설정을 위한 공통 인터페이스
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -11,7 +14,11 @@ pub enum MiddlewareType {
     Headers,
     Cors,
     RateLimit,
-    // 추후 추가될 미들웨어 타입들...
+    AddPrefix,
+    Redirect,
+    Cache,
+    /// `PluginRegistry`에 등록된 플러그인 미들웨어 (플러그인 이름)
+    Custom(String),
 }
 
 impl FromStr for MiddlewareType {
@@ -23,12 +30,37 @@ impl FromStr for MiddlewareType {
             "basic-auth" => Ok(MiddlewareType::BasicAuth),
             "cors" => Ok(MiddlewareType::Cors),
             "ratelimit" => Ok(MiddlewareType::RateLimit),
+            "add-prefix" => Ok(MiddlewareType::AddPrefix),
+            "redirect" => Ok(MiddlewareType::Redirect),
+            "cache" => Ok(MiddlewareType::Cache),
+            custom if custom.starts_with(Self::CUSTOM_PREFIX) => {
+                Ok(MiddlewareType::Custom(custom[Self::CUSTOM_PREFIX.len()..].to_string()))
+            }
             unknown => Err(format!("Unknown middleware type: {}", unknown)),
         }
     }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+impl MiddlewareType {
+    /// 플러그인 미들웨어 타입 이름 접두사 (`custom:<플러그인 이름>`)
+    pub const CUSTOM_PREFIX: &'static str = "custom:";
+
+    /// 라벨에서 사용하는 미들웨어 타입 이름을 반환합니다.
+    pub fn as_str(&self) -> Cow<'static, str> {
+        match self {
+            MiddlewareType::Headers => Cow::Borrowed("headers"),
+            MiddlewareType::BasicAuth => Cow::Borrowed("basic-auth"),
+            MiddlewareType::Cors => Cow::Borrowed("cors"),
+            MiddlewareType::RateLimit => Cow::Borrowed("ratelimit"),
+            MiddlewareType::AddPrefix => Cow::Borrowed("add-prefix"),
+            MiddlewareType::Redirect => Cow::Borrowed("redirect"),
+            MiddlewareType::Cache => Cow::Borrowed("cache"),
+            MiddlewareType::Custom(name) => Cow::Owned(format!("{}{}", Self::CUSTOM_PREFIX, name)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct MiddlewareConfig {
     /// 미들웨어 타입
     pub middleware_type: MiddlewareType,
@@ -42,6 +74,7 @@ pub struct MiddlewareConfig {
     pub order: i32,
     
     /// 미들웨어별 설정
+    #[serde(default)]
     pub settings: HashMap<String, String>,
 }
 
@@ -71,11 +104,27 @@ impl MiddlewareConfig {
     }
 
     /// Docker 라벨에서 미들웨어 설정을 파싱합니다.
-    pub fn from_labels(labels: &HashMap<String, String>) -> Result<Vec<(String, Self)>, String> {
+    ///
+    /// `prefixes`(예: `rproxy.`)를 순서대로 시도해 `<접두사>http.middlewares.*` 라벨을 읽으며,
+    /// 같은 이름의 미들웨어가 여러 접두사에 있으면 앞선 접두사의 설정을 사용합니다.
+    pub fn from_labels(labels: &HashMap<String, String>, prefixes: &[&str]) -> Result<Vec<(String, Self)>, String> {
+        let mut configs = HashMap::new();
+        for prefix in prefixes {
+            for (name, config) in Self::from_labels_with_prefix(labels, prefix)? {
+                configs.entry(name).or_insert(config);
+            }
+        }
+
+        debug!("최종 설정: {:?}", configs);
+        Ok(configs.into_iter().collect())
+    }
+
+    fn from_labels_with_prefix(labels: &HashMap<String, String>, prefix: &str) -> Result<HashMap<String, Self>, String> {
+        let middleware_prefix = format!("{}http.middlewares.", prefix);
         let mut configs = HashMap::new();
         
         for (key, value) in labels {
-            if let Some(middleware_name) = key.strip_prefix("rproxy.http.middlewares.") {
+            if let Some(middleware_name) = key.strip_prefix(&middleware_prefix) {
                 debug!("미들웨어 라벨 파싱: key={}, value={}", key, value);
                 
                 let parts: Vec<&str> = middleware_name.split('.').collect();
@@ -83,7 +132,13 @@ impl MiddlewareConfig {
                     continue;
                 }
 
-                let name = parts[0].to_string();
+                let name = match ValidMiddlewareId::new(parts[0]) {
+                    Ok(id) => id.to_string(),
+                    Err(e) => {
+                        warn!("미들웨어 라벨 무시: key={}, error={}", key, e);
+                        continue;
+                    }
+                };
                 let config = configs.entry(name.clone())
                     .or_insert_with(|| Self::new(MiddlewareType::Headers));
 
@@ -102,8 +157,35 @@ impl MiddlewareConfig {
             }
         }
 
-        debug!("최종 설정: {:?}", configs);
-        Ok(configs.into_iter().collect())
+        Ok(configs)
+    }
+
+    /// 두 설정을 병합합니다. `other`가 우선합니다.
+    ///
+    /// - 같은 키의 `settings`는 `other` 값으로 덮어씁니다.
+    /// - `enabled`는 `other` 값을 따릅니다.
+    /// - `order`는 `other`가 기본값(0)이 아닐 때만 덮어씁니다.
+    /// - 미들웨어 타입이 다르면 에러를 반환합니다.
+    pub fn merge(&self, other: &MiddlewareConfig) -> Result<MiddlewareConfig, MiddlewareError> {
+        if self.middleware_type != other.middleware_type {
+            return Err(MiddlewareError::Config {
+                message: format!(
+                    "미들웨어 타입이 달라 병합할 수 없습니다: {} != {}",
+                    self.middleware_type.as_str(),
+                    other.middleware_type.as_str()
+                ),
+            });
+        }
+
+        let mut settings = self.settings.clone();
+        settings.extend(other.settings.iter().map(|(k, v)| (k.clone(), v.clone())));
+
+        Ok(MiddlewareConfig {
+            middleware_type: self.middleware_type.clone(),
+            enabled: other.enabled,
+            order: if other.order != 0 { other.order } else { self.order },
+            settings,
+        })
     }
 
     /// TOML 설정에서 미들웨어 설정을 파싱합니다.
@@ -134,7 +216,7 @@ mod tests {
             "value".to_string(),
         );
 
-        let configs = MiddlewareConfig::from_labels(&labels).unwrap();
+        let configs = MiddlewareConfig::from_labels(&labels, &["rproxy."]).unwrap();
         assert_eq!(configs.len(), 1);
         
         let (name, config) = &configs[0];
@@ -144,4 +226,53 @@ mod tests {
         assert_eq!(config.order, 0);
         assert!(config.settings.contains_key("headers.customResponseHeaders.X-Custom-Header"));
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_parse_docker_labels_skips_invalid_names() {
+        let labels: HashMap<String, String> = [
+            ("rproxy.http.middlewares.auth@docker.type", "basic-auth"),
+            ("rproxy.http.middlewares.인증.type", "basic-auth"),
+            ("rproxy.http.middlewares.bad,name.type", "cors"),
+        ]
+        .into_iter()
+        .map(|(k, v)| (k.to_string(), v.to_string()))
+        .collect();
+
+        let mut names: Vec<_> = MiddlewareConfig::from_labels(&labels, &["rproxy."]).unwrap()
+            .into_iter()
+            .map(|(name, _)| name)
+            .collect();
+        names.sort();
+        assert_eq!(names, ["auth@docker", "인증"]);
+    }
+
+    #[test]
+    fn test_merge_configs() {
+        let mut base = MiddlewareConfig::new(MiddlewareType::Headers);
+        base.order = 10;
+        base.settings.insert("headers.customRequestHeaders.X-A".to_string(), "json".to_string());
+        base.settings.insert("headers.customRequestHeaders.X-B".to_string(), "json".to_string());
+
+        let mut label = MiddlewareConfig::new(MiddlewareType::Headers);
+        label.enabled = false;
+        label.settings.insert("headers.customRequestHeaders.X-B".to_string(), "label".to_string());
+
+        let merged = base.merge(&label).unwrap();
+        assert!(!merged.enabled);
+        assert_eq!(merged.order, 10);
+        assert_eq!(merged.settings["headers.customRequestHeaders.X-A"], "json");
+        assert_eq!(merged.settings["headers.customRequestHeaders.X-B"], "label");
+
+        // 타입이 다르면 병합 불가
+        let cors = MiddlewareConfig::new(MiddlewareType::Cors);
+        assert!(matches!(base.merge(&cors), Err(MiddlewareError::Config { .. })));
+    }
+
+    #[test]
+    fn test_custom_middleware_type() {
+        let middleware_type: MiddlewareType = "custom:geoip".parse().unwrap();
+        assert_eq!(middleware_type, MiddlewareType::Custom("geoip".to_string()));
+        assert_eq!(middleware_type.as_str(), "custom:geoip");
+        assert!("geoip".parse::<MiddlewareType>().is_err());
+    }
+}
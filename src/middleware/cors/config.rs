@@ -1,4 +1,5 @@
-use serde::{Deserialize, Serialize};
+use hyper::header::HeaderName;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use std::collections::HashMap;
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
@@ -15,9 +16,9 @@ pub struct CorsConfig {
     #[serde(default)]
     pub allow_headers: Vec<String>,
     
-    /// 노출할 헤더 목록
-    #[serde(default)]
-    pub expose_headers: Vec<String>,
+    /// 브라우저에 노출할 응답 헤더 목록 (Access-Control-Expose-Headers)
+    #[serde(default, with = "header_names")]
+    pub expose_headers: Vec<HeaderName>,
     
     /// preflight 요청 캐시 시간 (초)
     #[serde(default)]
@@ -57,9 +58,7 @@ impl CorsConfig {
                         .collect();
                 },
                 ["cors", "exposeHeaders"] => {
-                    config.expose_headers = value.split(',')
-                        .map(|s| s.trim().to_string())
-                        .collect();
+                    config.expose_headers = parse_header_names(value)?;
                 },
                 ["cors", "maxAge"] => {
                     config.max_age = value.parse().ok();
@@ -73,4 +72,60 @@ impl CorsConfig {
         
         Ok(config)
     }
-} 
\ No newline at end of file
+}
+
+/// 쉼표로 구분된 헤더 이름 목록을 파싱합니다.
+fn parse_header_names(value: &str) -> Result<Vec<HeaderName>, serde_json::Error> {
+    value.split(',')
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .map(|s| {
+            HeaderName::from_bytes(s.as_bytes()).map_err(|_| {
+                <serde_json::Error as serde::de::Error>::custom(
+                    format!("잘못된 헤더 이름: {}", s)
+                )
+            })
+        })
+        .collect()
+}
+
+/// `Vec<HeaderName>`을 문자열 목록으로 직렬화/역직렬화합니다.
+mod header_names {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(names: &[HeaderName], serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(names.iter().map(HeaderName::as_str))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<HeaderName>, D::Error> {
+        Vec::<String>::deserialize(deserializer)?
+            .into_iter()
+            .map(|s| HeaderName::from_bytes(s.as_bytes()).map_err(serde::de::Error::custom))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_expose_headers_from_labels() {
+        let mut labels = HashMap::new();
+        labels.insert("cors.exposeHeaders".to_string(), "X-Request-Id, X-Custom-Header".to_string());
+
+        let config = CorsConfig::from_labels(&labels).unwrap();
+        assert_eq!(config.expose_headers, vec![
+            HeaderName::from_static("x-request-id"),
+            HeaderName::from_static("x-custom-header"),
+        ]);
+    }
+
+    #[test]
+    fn test_invalid_expose_header_name() {
+        let mut labels = HashMap::new();
+        labels.insert("cors.exposeHeaders".to_string(), "X-Valid, Bad Header".to_string());
+
+        assert!(CorsConfig::from_labels(&labels).is_err());
+    }
+}
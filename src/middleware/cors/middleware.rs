@@ -1,7 +1,7 @@
 use crate::middleware::{Middleware, MiddlewareError, Request, Response};
 use super::config::CorsConfig;
 use async_trait::async_trait;
-use hyper::{header, Method, HeaderMap};
+use hyper::{header, Method, HeaderMap, StatusCode};
 use tracing::{debug, instrument};
 use http_body_util::Full;
 use bytes::Bytes;
@@ -50,13 +50,28 @@ impl CorsMiddleware {
         if !self.config.expose_headers.is_empty() {
             headers.insert(
                 header::ACCESS_CONTROL_EXPOSE_HEADERS,
-                self.config.expose_headers.join(", ").parse().unwrap()
+                self.config.expose_headers.iter()
+                    .map(|name| name.as_str())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+                    .parse()
+                    .unwrap()
             );
         }
     }
 
+    /// Preflight 요청 여부
+    ///
+    /// `Access-Control-Request-Method` 헤더가 있는 `OPTIONS` 요청만 preflight로 봅니다.
+    fn is_preflight<B>(req: &hyper::Request<B>) -> bool {
+        req.method() == Method::OPTIONS
+            && req.headers().contains_key(header::ACCESS_CONTROL_REQUEST_METHOD)
+    }
+
     /// Preflight 요청 처리
-    fn handle_preflight(&self, req: &Request) -> Result<Response, MiddlewareError> {
+    ///
+    /// 업스트림으로 전달하지 않고 204 응답을 직접 생성합니다.
+    fn handle_preflight<B>(&self, req: &hyper::Request<B>) -> Result<Response, MiddlewareError> {
         let origin = req.headers()
             .get(header::ORIGIN)
             .and_then(|v| v.to_str().ok())
@@ -66,7 +81,8 @@ impl CorsMiddleware {
             return Err(MiddlewareError::InvalidRequest("Origin not allowed".into()));
         }
 
-        let mut response = Response::new(Full::new(Bytes::from("")));
+        let mut response = Response::new(Full::new(Bytes::new()));
+        *response.status_mut() = StatusCode::NO_CONTENT;
         let headers = response.headers_mut();
 
         // 기본 CORS 헤더 설정
@@ -100,10 +116,14 @@ impl CorsMiddleware {
 
 #[async_trait]
 impl Middleware for CorsMiddleware {
+    fn name(&self) -> &'static str {
+        "cors"
+    }
+
     #[instrument(skip(self, req))]
     async fn handle_request(&self, req: Request) -> Result<Request, MiddlewareError> {
-        // OPTIONS 요청은 preflight로 처리
-        if req.method() == Method::OPTIONS {
+        // preflight 요청은 업스트림으로 전달하지 않고 바로 응답
+        if Self::is_preflight(&req) {
             debug!("Handling CORS preflight request");
             return Err(MiddlewareError::PreflightResponse(self.handle_preflight(&req)?));
         }
@@ -133,4 +153,65 @@ impl Middleware for CorsMiddleware {
         
         Ok(res)
     }
-} 
\ No newline at end of file
+} 
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cors_middleware() -> CorsMiddleware {
+        CorsMiddleware::new(CorsConfig {
+            allow_origins: vec!["https://app.example.com".to_string()],
+            allow_methods: vec!["GET".to_string(), "POST".to_string()],
+            allow_headers: vec!["Content-Type".to_string()],
+            max_age: Some(600),
+            ..CorsConfig::default()
+        })
+    }
+
+    #[test]
+    fn test_preflight_detection() {
+        let preflight = hyper::Request::builder()
+            .method(Method::OPTIONS)
+            .header(header::ORIGIN, "https://app.example.com")
+            .header(header::ACCESS_CONTROL_REQUEST_METHOD, "POST")
+            .body(())
+            .unwrap();
+        assert!(CorsMiddleware::is_preflight(&preflight));
+
+        // Access-Control-Request-Method가 없는 OPTIONS 요청은 업스트림으로 전달
+        let plain_options = hyper::Request::builder()
+            .method(Method::OPTIONS)
+            .header(header::ORIGIN, "https://app.example.com")
+            .body(())
+            .unwrap();
+        assert!(!CorsMiddleware::is_preflight(&plain_options));
+    }
+
+    #[test]
+    fn test_preflight_response() {
+        let req = hyper::Request::builder()
+            .method(Method::OPTIONS)
+            .header(header::ORIGIN, "https://app.example.com")
+            .header(header::ACCESS_CONTROL_REQUEST_METHOD, "POST")
+            .body(())
+            .unwrap();
+
+        let response = cors_middleware().handle_preflight(&req).unwrap();
+        assert_eq!(response.status(), StatusCode::NO_CONTENT);
+        let headers = response.headers();
+        assert_eq!(headers[header::ACCESS_CONTROL_ALLOW_ORIGIN], "https://app.example.com");
+        assert_eq!(headers[header::ACCESS_CONTROL_ALLOW_METHODS], "GET, POST");
+        assert_eq!(headers[header::ACCESS_CONTROL_ALLOW_HEADERS], "Content-Type");
+        assert_eq!(headers[header::ACCESS_CONTROL_MAX_AGE], "600");
+
+        // 허용되지 않은 Origin은 거부
+        let req = hyper::Request::builder()
+            .method(Method::OPTIONS)
+            .header(header::ORIGIN, "https://evil.example.com")
+            .header(header::ACCESS_CONTROL_REQUEST_METHOD, "POST")
+            .body(())
+            .unwrap();
+        assert!(cors_middleware().handle_preflight(&req).is_err());
+    }
+}
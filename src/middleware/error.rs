@@ -24,9 +24,15 @@ pub enum MiddlewareError {
         reason: String,
     },
     InvalidRequest(String),
+    /// 인증은 성공했지만 접근 권한이 없음
+    Forbidden(String),
     PreflightResponse(Response<Full<Bytes>>),
     /// Rate limit 초과 에러
     TooManyRequests(Response<Full<Bytes>>),
+    /// 리다이렉트 응답
+    Redirect(Response<Full<Bytes>>),
+    /// 캐시에 저장된 응답
+    CachedResponse(Response<Full<Bytes>>),
 }
 
 impl fmt::Display for MiddlewareError {
@@ -50,12 +56,21 @@ impl fmt::Display for MiddlewareError {
             Self::InvalidRequest(message) => {
                 write!(f, "요청 오류: {}", message)
             }
+            Self::Forbidden(message) => {
+                write!(f, "권한 오류: {}", message)
+            }
             Self::PreflightResponse(_) => {
                 write!(f, "Preflight 응답 오류")
             }
             Self::TooManyRequests(_) => {
                 write!(f, "Rate limit exceeded")
             }
+            Self::Redirect(_) => {
+                write!(f, "Redirect")
+            }
+            Self::CachedResponse(_) => {
+                write!(f, "Cached response")
+            }
         }
     }
 }
@@ -1,8 +1,12 @@
 use hyper::header::{HeaderName, HeaderValue};
+use hyper::StatusCode;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::fmt;
 use tracing::{debug, error};
 use std::str::FromStr;
+use super::context::HeaderContext;
+
 /// 헤더 수정 작업 설정
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct HeaderModification {
@@ -22,6 +26,18 @@ pub struct HeaderModification {
 impl HeaderModification {
     /// 헤더 맵에 설정된 수정사항을 적용합니다.
     pub fn apply_to_headers(&self, headers: &mut hyper::HeaderMap) {
+        self.apply_to_headers_with_context(headers, None);
+    }
+
+    /// 헤더 맵에 수정사항을 적용하며, 컨텍스트가 있으면 값의 `$변수`를 치환합니다.
+    pub fn apply_to_headers_with_context(&self, headers: &mut hyper::HeaderMap, context: Option<&HeaderContext>) {
+        let expand = |value: &'_ str| -> String {
+            match context {
+                Some(context) => context.expand(value).into_owned(),
+                None => value.to_string(),
+            }
+        };
+
         debug!("헤더 수정 시작: add={:?}, remove={:?}, set={:?}", self.add, self.remove, self.set);
         
         // 1. 먼저 삭제할 헤더 처리
@@ -36,7 +52,8 @@ impl HeaderModification {
 
         // 2. set으로 덮어쓸 헤더 처리
         for (name, value) in &self.set {
-            match (HeaderName::from_str(name), HeaderValue::from_str(value)) {
+            let value = expand(value);
+            match (HeaderName::from_str(name), HeaderValue::from_str(&value)) {
                 (Ok(name), Ok(value)) => {
                     debug!("헤더 설정: {:?}={:?}", name, value);
                     headers.insert(name, value);
@@ -47,7 +64,8 @@ impl HeaderModification {
 
         // 3. 마지막으로 추가할 헤더 처리
         for (name, value) in &self.add {
-            match (HeaderName::from_str(name), HeaderValue::from_str(value)) {
+            let value = expand(value);
+            match (HeaderName::from_str(name), HeaderValue::from_str(&value)) {
                 (Ok(name), Ok(value)) => {
                     debug!("헤더 추가: {:?}={:?}", name, value);
                     headers.append(name, value);
@@ -60,6 +78,62 @@ impl HeaderModification {
     }
 }
 
+/// HTTP 상태 코드 범위
+///
+/// `5xx` 같은 클래스, `404` 같은 단일 코드, `500-504` 같은 구간을 지원합니다.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StatusRange {
+    start: u16,
+    end: u16,
+}
+
+impl StatusRange {
+    /// 상태 코드가 범위에 포함되는지 확인합니다.
+    pub fn contains(&self, status: StatusCode) -> bool {
+        (self.start..=self.end).contains(&status.as_u16())
+    }
+}
+
+impl FromStr for StatusRange {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || format!("잘못된 상태 코드 범위: {}", s);
+        let code = |value: &str| value.parse::<u16>()
+            .ok()
+            .filter(|code| (100..=599).contains(code))
+            .ok_or_else(invalid);
+
+        let lower = s.to_ascii_lowercase();
+        let (start, end) = if let Some(class) = lower.strip_suffix("xx") {
+            let class = class.parse::<u16>().ok().filter(|c| (1..=5).contains(c)).ok_or_else(invalid)?;
+            (class * 100, class * 100 + 99)
+        } else if let Some((start, end)) = lower.split_once('-') {
+            (code(start)?, code(end)?)
+        } else {
+            let code = code(&lower)?;
+            (code, code)
+        };
+
+        if start > end {
+            return Err(invalid());
+        }
+        Ok(Self { start, end })
+    }
+}
+
+impl fmt::Display for StatusRange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.start == self.end {
+            write!(f, "{}", self.start)
+        } else if self.start.is_multiple_of(100) && self.end == self.start + 99 {
+            write!(f, "{}xx", self.start / 100)
+        } else {
+            write!(f, "{}-{}", self.start, self.end)
+        }
+    }
+}
+
 /// 헤더 미들웨어 설정
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct HeadersConfig {
@@ -70,6 +144,10 @@ pub struct HeadersConfig {
     /// 응답 헤더 수정 설정
     #[serde(default)]
     pub response: HeaderModification,
+
+    /// 응답 상태 코드가 범위에 포함될 때만 설정하는 헤더
+    #[serde(skip)]
+    pub conditional_response_headers: Vec<(StatusRange, HeaderName, HeaderValue)>,
 }
 
 impl HeadersConfig {
@@ -94,10 +172,94 @@ impl HeadersConfig {
                 ["headers", "response", "set", header_name] => {
                     config.response.set.insert(header_name.to_string(), value.clone());
                 },
+                ["headers", "conditionalResponseHeaders", range, header_name] => {
+                    match (range.parse::<StatusRange>(), HeaderName::from_str(header_name), HeaderValue::from_str(value)) {
+                        (Ok(range), Ok(name), Ok(value)) => {
+                            config.conditional_response_headers.push((range, name, value));
+                        }
+                        _ => error!("잘못된 조건부 응답 헤더 설정: {}={}", key, value),
+                    }
+                },
                 _ => continue,
             }
         }
         debug!("최종 헤더 설정: {:?}", config);
         Ok(config)
     }
-} 
\ No newline at end of file
+
+    /// 상태 코드에 해당하는 조건부 응답 헤더를 설정합니다.
+    pub fn apply_conditional_response_headers(&self, status: StatusCode, headers: &mut hyper::HeaderMap) {
+        for (range, name, value) in &self.conditional_response_headers {
+            if range.contains(status) {
+                debug!(status = %status, range = %range, header = %name, "조건부 응답 헤더 설정");
+                headers.insert(name.clone(), value.clone());
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_apply_with_context() {
+        let mut modification = HeaderModification::default();
+        modification.add.insert("X-Real-IP".to_string(), "$remote_addr".to_string());
+        modification.set.insert("X-Forwarded-Host".to_string(), "$host".to_string());
+
+        let context = HeaderContext {
+            remote_addr: "10.0.0.1".to_string(),
+            host: "example.com".to_string(),
+            ..Default::default()
+        };
+
+        let mut headers = hyper::HeaderMap::new();
+        modification.apply_to_headers_with_context(&mut headers, Some(&context));
+        assert_eq!(headers["x-real-ip"], "10.0.0.1");
+        assert_eq!(headers["x-forwarded-host"], "example.com");
+
+        // 컨텍스트가 없으면 값을 그대로 사용
+        let mut headers = hyper::HeaderMap::new();
+        modification.apply_to_headers(&mut headers);
+        assert_eq!(headers["x-real-ip"], "$remote_addr");
+    }
+
+    #[test]
+    fn test_status_range() {
+        let server_error: StatusRange = "5xx".parse().unwrap();
+        assert!(server_error.contains(StatusCode::BAD_GATEWAY));
+        assert!(!server_error.contains(StatusCode::NOT_FOUND));
+        assert_eq!(server_error.to_string(), "5xx");
+
+        let not_found: StatusRange = "404".parse().unwrap();
+        assert!(not_found.contains(StatusCode::NOT_FOUND));
+        assert!(!not_found.contains(StatusCode::GONE));
+
+        let gateway: StatusRange = "502-504".parse().unwrap();
+        assert!(gateway.contains(StatusCode::GATEWAY_TIMEOUT));
+        assert!(!gateway.contains(StatusCode::INTERNAL_SERVER_ERROR));
+
+        for invalid in ["6xx", "abc", "99", "504-502"] {
+            assert!(invalid.parse::<StatusRange>().is_err(), "{}", invalid);
+        }
+    }
+
+    #[test]
+    fn test_conditional_response_headers() {
+        let settings = HashMap::from([(
+            "headers.conditionalResponseHeaders.5xx.X-Error-Source".to_string(),
+            "upstream".to_string(),
+        )]);
+        let config = HeadersConfig::from_flat_map(&settings).unwrap();
+        assert_eq!(config.conditional_response_headers.len(), 1);
+
+        let mut headers = hyper::HeaderMap::new();
+        config.apply_conditional_response_headers(StatusCode::BAD_GATEWAY, &mut headers);
+        assert_eq!(headers["x-error-source"], "upstream");
+
+        let mut headers = hyper::HeaderMap::new();
+        config.apply_conditional_response_headers(StatusCode::OK, &mut headers);
+        assert!(headers.get("x-error-source").is_none());
+    }
+}
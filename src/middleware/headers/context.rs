@@ -0,0 +1,138 @@
+use crate::middleware::ConnectionInfo;
+use std::borrow::Cow;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// 헤더 값 변수 치환에 사용하는 요청 정보
+///
+/// # 지원하는 변수
+/// - `$remote_addr`: 클라이언트 IP
+/// - `$host`: 요청 Host 헤더
+/// - `$request_uri`: 경로와 쿼리 문자열
+/// - `$scheme`: http 또는 https
+/// - `$timestamp_unix`: Unix epoch 초
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct HeaderContext {
+    pub remote_addr: String,
+    pub host: String,
+    pub request_uri: String,
+    pub scheme: String,
+    pub timestamp_unix: u64,
+}
+
+impl HeaderContext {
+    /// 요청에서 치환 컨텍스트를 생성합니다.
+    pub fn from_request<B>(req: &hyper::Request<B>) -> Self {
+        let connection = req.extensions().get::<ConnectionInfo>();
+
+        let host = req.headers()
+            .get(hyper::header::HOST)
+            .and_then(|h| h.to_str().ok())
+            .or_else(|| req.uri().authority().map(|a| a.as_str()))
+            .unwrap_or_default()
+            .to_string();
+
+        let scheme = match (connection, req.uri().scheme_str()) {
+            (Some(connection), _) => connection.scheme(),
+            (None, Some(scheme)) => scheme,
+            (None, None) => "http",
+        };
+
+        Self {
+            remote_addr: connection
+                .map(|c| c.remote_addr.ip().to_string())
+                .unwrap_or_default(),
+            host,
+            request_uri: req.uri()
+                .path_and_query()
+                .map(|pq| pq.as_str().to_string())
+                .unwrap_or_else(|| req.uri().path().to_string()),
+            scheme: scheme.to_string(),
+            timestamp_unix: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|d| d.as_secs())
+                .unwrap_or_default(),
+        }
+    }
+
+    /// 변수 값을 반환합니다. 알 수 없는 변수는 None을 반환합니다.
+    fn lookup(&self, name: &str) -> Option<Cow<'_, str>> {
+        match name {
+            "remote_addr" => Some(Cow::Borrowed(&self.remote_addr)),
+            "host" => Some(Cow::Borrowed(&self.host)),
+            "request_uri" => Some(Cow::Borrowed(&self.request_uri)),
+            "scheme" => Some(Cow::Borrowed(&self.scheme)),
+            "timestamp_unix" => Some(Cow::Owned(self.timestamp_unix.to_string())),
+            _ => None,
+        }
+    }
+
+    /// 헤더 값의 `$변수`를 치환합니다. 알 수 없는 변수는 그대로 둡니다.
+    pub fn expand<'a>(&self, value: &'a str) -> Cow<'a, str> {
+        if !value.contains('$') {
+            return Cow::Borrowed(value);
+        }
+
+        let mut result = String::with_capacity(value.len());
+        let mut rest = value;
+        while let Some(pos) = rest.find('$') {
+            result.push_str(&rest[..pos]);
+            let after = &rest[pos + 1..];
+            let name_len = after
+                .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+                .unwrap_or(after.len());
+
+            match self.lookup(&after[..name_len]) {
+                Some(replacement) => result.push_str(&replacement),
+                None => result.push_str(&rest[pos..pos + 1 + name_len]),
+            }
+            rest = &after[name_len..];
+        }
+        result.push_str(rest);
+
+        Cow::Owned(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_request() {
+        let mut req = hyper::Request::builder()
+            .uri("/api/items?page=2")
+            .header("host", "example.com")
+            .body(())
+            .unwrap();
+        req.extensions_mut().insert(ConnectionInfo {
+            remote_addr: "10.0.0.1:54321".parse().unwrap(),
+            local_addr: None,
+            is_tls: true,
+        });
+
+        let context = HeaderContext::from_request(&req);
+        assert_eq!(context.remote_addr, "10.0.0.1");
+        assert_eq!(context.host, "example.com");
+        assert_eq!(context.request_uri, "/api/items?page=2");
+        assert_eq!(context.scheme, "https");
+        assert!(context.timestamp_unix > 0);
+    }
+
+    #[test]
+    fn test_expand_variables() {
+        let context = HeaderContext {
+            remote_addr: "10.0.0.1".to_string(),
+            host: "example.com".to_string(),
+            request_uri: "/api".to_string(),
+            scheme: "http".to_string(),
+            timestamp_unix: 1700000000,
+        };
+
+        assert_eq!(context.expand("$remote_addr"), "10.0.0.1");
+        assert_eq!(context.expand("$scheme://$host$request_uri"), "http://example.com/api");
+        assert_eq!(context.expand("t=$timestamp_unix"), "t=1700000000");
+        // 알 수 없는 변수와 단독 '$'는 그대로 유지
+        assert_eq!(context.expand("$unknown-$"), "$unknown-$");
+        assert_eq!(context.expand("plain"), "plain");
+    }
+}
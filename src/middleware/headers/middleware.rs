@@ -1,5 +1,6 @@
 use crate::middleware::{Middleware, MiddlewareError, Request, Response};
 use super::config::HeadersConfig;
+use super::context::HeaderContext;
 use async_trait::async_trait;
 use hyper::header::{HeaderName, HeaderValue};
 use tracing::{debug, instrument};
@@ -41,12 +42,20 @@ impl HeadersMiddleware {
 
 #[async_trait]
 impl Middleware for HeadersMiddleware {
+    fn name(&self) -> &'static str {
+        "headers"
+    }
+
     #[instrument(skip(self, req), fields(req_headers = ?req.headers()))]
     async fn handle_request(&self, mut req: Request) -> Result<Request, MiddlewareError> {
         debug!("헤더 요청 헤더 처리 시작: {:?}", self.config.request);
-        // request HeaderModification 사용
-        self.config.request.apply_to_headers(req.headers_mut());
+        // 변수 치환 컨텍스트 생성 후 request HeaderModification 적용
+        let context = HeaderContext::from_request(&req);
+        self.config.request.apply_to_headers_with_context(req.headers_mut(), Some(&context));
         debug!("요청 헤더 수정 완료: {:?}", req.headers());
+
+        // 응답 헤더 치환에도 사용할 수 있도록 요청에 기록
+        req.extensions_mut().insert(context);
         Ok(req)
     }
 
@@ -58,7 +67,10 @@ impl Middleware for HeadersMiddleware {
         self.apply_security_headers(res.headers_mut());
         
         // response HeaderModification 사용
-        self.config.response.apply_to_headers(res.headers_mut());
+        let context = res.extensions().get::<HeaderContext>().cloned();
+        self.config.response.apply_to_headers_with_context(res.headers_mut(), context.as_ref());
+        let status = res.status();
+        self.config.apply_conditional_response_headers(status, res.headers_mut());
         
         debug!(modified_headers = ?res.headers(), "응답 헤더 수정 완료");
         Ok(res)
@@ -3,7 +3,9 @@
 //! HTTP 요청 및 응답의 헤더를 수정하는 미들웨어를 제공합니다.
 
 mod config;
+mod context;
 mod middleware;
 
-pub use config::HeadersConfig;
+pub use config::{HeadersConfig, StatusRange};
+pub use context::HeaderContext;
 pub use middleware::HeadersMiddleware;
@@ -1,17 +1,25 @@
-use tracing::debug;
+use tracing::{debug, warn};
 use crate::middleware::basic_auth::{BasicAuthConfig, BasicAuthMiddleware};
+use crate::middleware::add_prefix::{AddPrefixConfig, AddPrefixMiddleware};
 use crate::middleware::cors::{CorsConfig, CorsMiddleware};
+use crate::middleware::redirect::{RedirectConfig, RedirectMiddleware};
+use crate::middleware::cache::{CacheConfig, ResponseCacheMiddleware};
 use crate::middleware::headers::{HeadersConfig, HeadersMiddleware};
-use crate::middleware::rate_limit::{RateLimitConfig, RateLimitMiddleware, store::memory::MemoryStore};
-use super::{Middleware, MiddlewareChain, MiddlewareConfig, MiddlewareError, Request, Response};
+use crate::middleware::rate_limit::{RateLimitConfig, RateLimitMiddleware, RateLimitStrategy, StoreBackend, store::{memory::MemoryStore, redis::RedisStore}};
+use super::{Middleware, MiddlewareChain, MiddlewareConfig, MiddlewareError, PluginRegistry, Request, Response};
 use super::config::MiddlewareType;
 use std::collections::HashMap;
+use std::sync::Arc;
+use hyper::header::HeaderName;
+
+/// 테넌트별 미들웨어 체인을 고르는 기본 요청 헤더
+pub const DEFAULT_TENANT_HEADER: &str = "x-tenant-id";
 
 /// 미들웨어 설정으로부터 미들웨어 인스턴스를 생성합니다.
-fn create_middleware(config: &MiddlewareConfig) -> Result<Box<dyn Middleware>, MiddlewareError> {
-    debug!("미들웨어 생성 시작: type={:?}, settings={:?}", config.middleware_type, config.settings);
+fn create_middleware(config: &MiddlewareConfig, registry: &PluginRegistry) -> Result<Box<dyn Middleware>, MiddlewareError> {
+    debug!("미들웨어 생성 시작: type={}, settings={:?}", config.middleware_type.as_str(), config.settings);
     
-    match config.middleware_type {
+    match &config.middleware_type {
         MiddlewareType::BasicAuth => {
             let auth_config = BasicAuthConfig::from_labels(&config.settings)?;
             Ok(Box::new(BasicAuthMiddleware::new(auth_config)?))
@@ -30,44 +38,146 @@ fn create_middleware(config: &MiddlewareConfig) -> Result<Box<dyn Middleware>, M
         MiddlewareType::RateLimit => {
             let rate_limit_config = RateLimitConfig::from_labels(&config.settings)
                 .map_err(|e| MiddlewareError::Config { message: e })?;
-            let store = MemoryStore::new();
-            Ok(Box::new(RateLimitMiddleware::new(rate_limit_config, store)))
+            if rate_limit_config.strategy != RateLimitStrategy::TokenBucket {
+                warn!(
+                    "Rate limit 전략 {}은(는) 아직 지원하지 않아 저장소 기본 방식으로 동작합니다",
+                    rate_limit_config.strategy
+                );
+            }
+            match (rate_limit_config.store_backend, rate_limit_config.redis_url.clone()) {
+                (StoreBackend::Redis, Some(url)) => {
+                    let store = RedisStore::new(&url);
+                    Ok(Box::new(RateLimitMiddleware::new(rate_limit_config, store)))
+                }
+                _ => {
+                    let store = MemoryStore::new();
+                    Ok(Box::new(RateLimitMiddleware::new(rate_limit_config, store)))
+                }
+            }
         }
+        MiddlewareType::AddPrefix => {
+            let add_prefix_config = AddPrefixConfig::from_labels(&config.settings)?;
+            Ok(Box::new(AddPrefixMiddleware::new(add_prefix_config)))
+        }
+        MiddlewareType::Redirect => {
+            let redirect_config = RedirectConfig::from_labels(&config.settings)?;
+            Ok(Box::new(RedirectMiddleware::new(redirect_config)?))
+        }
+        MiddlewareType::Cache => {
+            let cache_config = CacheConfig::from_labels(&config.settings)?;
+            Ok(Box::new(ResponseCacheMiddleware::new(cache_config)))
+        }
+        MiddlewareType::Custom(name) => registry.create_middleware(name, config),
     }
 }
 
-#[derive(Default, Clone)]
+#[derive(Clone)]
 pub struct MiddlewareManager {
-    router_chains: HashMap<String, MiddlewareChain>,  // 라우터 이름 -> 체인
+    router_chains: HashMap<String, MiddlewareChain>,  // 라우터 이름(또는 `라우터:테넌트`) -> 체인
+    plugins: Arc<PluginRegistry>,
+    /// 테넌트 ID를 담은 요청 헤더
+    tenant_header: HeaderName,
+}
+
+impl std::fmt::Debug for MiddlewareManager {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MiddlewareManager")
+            .field("router_chains", &format_args!("{} entries", self.router_chains.len()))
+            .field("plugins", &self.plugins)
+            .field("tenant_header", &self.tenant_header)
+            .finish()
+    }
+}
+
+impl Default for MiddlewareManager {
+    fn default() -> Self {
+        Self {
+            router_chains: HashMap::new(),
+            plugins: Arc::default(),
+            tenant_header: HeaderName::from_static(DEFAULT_TENANT_HEADER),
+        }
+    }
 }
 
 impl MiddlewareManager {
     pub fn new(
         middleware_configs: &HashMap<String, MiddlewareConfig>,
         router_middlewares: &HashMap<String, Vec<String>>
+    ) -> Self {
+        Self::with_plugins(middleware_configs, router_middlewares, Arc::default())
+    }
+
+    /// 플러그인 등록소를 사용하는 미들웨어 매니저를 생성합니다.
+    ///
+    /// `MiddlewareType::Custom` 미들웨어는 등록소의 팩토리로 생성합니다.
+    pub fn with_plugins(
+        middleware_configs: &HashMap<String, MiddlewareConfig>,
+        router_middlewares: &HashMap<String, Vec<String>>,
+        plugins: Arc<PluginRegistry>,
     ) -> Self {
         let mut router_chains = HashMap::new();
         
         for (router_name, middleware_names) in router_middlewares {
-            let chain = Self::create_middleware_chain(middleware_names, middleware_configs);
-            if chain.middleware_count() > 0 {
+            let chain = Self::create_middleware_chain(middleware_names, middleware_configs, &plugins);
+            if !chain.is_empty() {
                 router_chains.insert(router_name.clone(), chain);
             }
         }
         
-        Self { router_chains }
+        Self { router_chains, plugins, ..Self::default() }
+    }
+
+    /// 테넌트별 체인을 고를 요청 헤더를 설정합니다.
+    pub fn with_tenant_header(mut self, header: HeaderName) -> Self {
+        self.tenant_header = header;
+        self
+    }
+
+    /// 미들웨어 생성에 사용하는 플러그인 등록소
+    pub fn plugins(&self) -> &Arc<PluginRegistry> {
+        &self.plugins
+    }
+
+    /// 테넌트별 체인을 고르는 요청 헤더
+    pub fn tenant_header(&self) -> &HeaderName {
+        &self.tenant_header
+    }
+
+    /// 요청에 적용할 라우터의 미들웨어 체인을 반환합니다.
+    ///
+    /// 테넌트 헤더가 있으면 `라우터:테넌트` 체인을 먼저 찾고, 없으면 라우터 체인을 사용합니다.
+    /// 테넌트 체인은 `router_middlewares`에 `api:acme`처럼 등록합니다.
+    pub fn chain_for_request<B>(&self, req: &Request<B>, route_id: &str) -> Option<&MiddlewareChain> {
+        let tenant_chain = req.headers()
+            .get(&self.tenant_header)
+            .and_then(|value| value.to_str().ok())
+            .filter(|tenant| !tenant.is_empty())
+            .and_then(|tenant| self.router_chains.get(&format!("{}:{}", route_id, tenant)));
+
+        if let Some(chain) = tenant_chain {
+            debug!(router = %route_id, "테넌트 미들웨어 체인 선택");
+            return Some(chain);
+        }
+        self.router_chains.get(route_id)
     }
 
     fn create_middleware_chain(
         middleware_names: &[String],
-        configs: &HashMap<String, MiddlewareConfig>
+        configs: &HashMap<String, MiddlewareConfig>,
+        plugins: &PluginRegistry,
     ) -> MiddlewareChain {
         let mut chain = MiddlewareChain::new();
         
         let middlewares = middleware_names.iter()
             .filter_map(|name| configs.get(name))
             .filter(|config| config.enabled)
-            .filter_map(|config| create_middleware(config).ok());
+            .filter_map(|config| match create_middleware(config, plugins) {
+                Ok(middleware) => Some(middleware),
+                Err(e) => {
+                    warn!(middleware_type = %config.middleware_type.as_str(), error = %e, "미들웨어 생성 실패");
+                    None
+                }
+            });
 
         for middleware in middlewares {
             chain.add_boxed(middleware);
@@ -94,6 +204,13 @@ impl MiddlewareManager {
         }
     }
 
+    /// 라우터에 실행할 미들웨어가 있는지 확인합니다.
+    pub fn has_middlewares(&self, router_name: Option<&str>) -> bool {
+        router_name
+            .and_then(|name| self.router_chains.get(name))
+            .is_some_and(|chain| !chain.is_empty())
+    }
+
     pub async fn handle_request(&self, router_name: Option<&str>, req: Request) -> Result<Request, MiddlewareError> {
         self.handle_chain(router_name, req, |chain, req| Box::pin(chain.handle_request(req))).await
     }
@@ -113,7 +230,7 @@ impl MiddlewareManager {
                 let router_name = name.split('-').next()?;
                 debug!("미들웨어 체인 업데이트 - 라우터: {}, 타입: {:?}", router_name, config.middleware_type);
                 
-                let middleware = match create_middleware(config) {
+                let middleware = match create_middleware(config, &self.plugins) {
                     Ok(m) => m,
                     Err(_) => return None,
                 };
@@ -140,7 +257,7 @@ impl MiddlewareManager {
                 for (router_name, chain) in &self.router_chains {
                     debug!(
                         router = %router_name,
-                        middlewares = %chain.middleware_count(),
+                        middlewares = %chain.len(),
                         "라우터 체인 정보"
                     );
                     // 체인 내부의 미들웨어 타입 정보도 출력
@@ -173,7 +290,7 @@ impl MiddlewareManager {
             
             // 각 체인별 정보 로깅 (디버깅 목적)
             for (router_name, chain) in &self.router_chains {
-                debug!("라우터 '{}' 미들웨어 체인 미들웨어 수: {}", router_name, chain.middleware_count());
+                debug!("라우터 '{}' 미들웨어 체인 미들웨어 수: {}", router_name, chain.len());
             }
         }
         
@@ -13,15 +13,53 @@ mod response;
 pub mod parser;
 mod cors;
 pub mod rate_limit;
+pub mod add_prefix;
+pub mod redirect;
+pub mod cache;
+mod plugin;
 
 pub use chain::MiddlewareChain;
 pub use config::MiddlewareConfig;
 pub use error::MiddlewareError;
 pub use traits::Middleware;
 pub use manager::MiddlewareManager;
+pub use plugin::{MiddlewareFactory, PluginRegistry};
 
 // 재사용 가능한 타입 별칭
 pub type Request<B = hyper::body::Incoming> = hyper::Request<B>;
 pub type Response<B = http_body_util::Full<bytes::Bytes>> = hyper::Response<B>;
 
 pub use response::handle_middleware_error;
+
+/// 요청이 수신된 연결 정보
+///
+/// 서버가 요청 확장 데이터(extensions)에 넣어 미들웨어에 전달합니다.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ConnectionInfo {
+    /// 클라이언트 주소
+    pub remote_addr: std::net::SocketAddr,
+    /// 연결을 수락한 프록시 자신의 주소
+    pub local_addr: Option<std::net::SocketAddr>,
+    /// TLS 연결 여부
+    pub is_tls: bool,
+}
+
+impl ConnectionInfo {
+    /// 연결 스킴 (http/https)
+    pub fn scheme(&self) -> &'static str {
+        if self.is_tls { "https" } else { "http" }
+    }
+}
+
+/// 신뢰하는 프록시 대역을 고려해 결정한 실제 클라이언트 IP
+///
+/// 서버가 라우팅 후 요청 확장 데이터에 넣습니다. 신뢰하지 않는 연결에서 온
+/// `X-Forwarded-For`/`X-Real-IP`는 반영되지 않으므로 클라이언트 식별에 사용할 수 있습니다.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ClientIp(pub std::net::IpAddr);
+
+/// 응답 바디가 미들웨어를 거치지 않고 스트리밍되었음을 나타냅니다.
+///
+/// 서버가 응답 확장 데이터에 넣으며, 이 경우 `handle_response`가 받는 바디는 비어 있습니다.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StreamedResponseBody;
@@ -0,0 +1,44 @@
+use std::collections::HashMap;
+use super::{Middleware, MiddlewareConfig, MiddlewareError};
+
+/// 플러그인 미들웨어 생성기
+///
+/// `MiddlewareType::Custom(이름)` 설정으로부터 미들웨어 인스턴스를 만듭니다.
+pub trait MiddlewareFactory: Send + Sync {
+    fn create(&self, config: &MiddlewareConfig) -> Result<Box<dyn Middleware>, MiddlewareError>;
+}
+
+/// 플러그인 미들웨어 등록소
+///
+/// 코어 코드를 수정하지 않고 사용자 정의 미들웨어 타입을 추가할 수 있습니다.
+#[derive(Default)]
+pub struct PluginRegistry {
+    pub middlewares: HashMap<String, Box<dyn MiddlewareFactory>>,
+}
+
+impl PluginRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 플러그인 미들웨어 팩토리를 등록합니다. 같은 이름이 있으면 대체합니다.
+    pub fn register_middleware(&mut self, name: impl Into<String>, factory: Box<dyn MiddlewareFactory>) {
+        self.middlewares.insert(name.into(), factory);
+    }
+
+    /// 등록된 팩토리로 플러그인 미들웨어를 생성합니다.
+    pub fn create_middleware(&self, name: &str, config: &MiddlewareConfig) -> Result<Box<dyn Middleware>, MiddlewareError> {
+        let factory = self.middlewares.get(name).ok_or_else(|| MiddlewareError::Config {
+            message: format!("등록되지 않은 플러그인 미들웨어: {}", name),
+        })?;
+        factory.create(config)
+    }
+}
+
+impl std::fmt::Debug for PluginRegistry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PluginRegistry")
+            .field("middlewares", &self.middlewares.keys().collect::<Vec<_>>())
+            .finish()
+    }
+}
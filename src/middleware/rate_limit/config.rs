@@ -1,11 +1,14 @@
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::fmt;
+use std::str::FromStr;
 use std::time::Duration;
+use crate::settings::types::ValidCidr;
 
 /// Rate Limit 설정
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RateLimitConfig {
-    /// 초당 평균 요청 수
+    /// 측정 기간당 평균 요청 수 (0이면 보충 없이 `burst`만큼만 허용)
     #[serde(default = "default_average")]
     pub average: u32,
     
@@ -16,6 +19,75 @@ pub struct RateLimitConfig {
     /// 측정 기간
     #[serde(default = "default_period")]
     pub period: Duration,
+
+    /// 속도 제한 알고리즘
+    #[serde(default)]
+    pub strategy: RateLimitStrategy,
+
+    /// 카운터 저장소 종류
+    #[serde(default)]
+    pub store_backend: StoreBackend,
+
+    /// Redis 저장소 사용 시 접속 URL (예: redis://127.0.0.1:6379)
+    #[serde(default)]
+    pub redis_url: Option<String>,
+
+    /// 속도 제한을 적용하지 않을 클라이언트 IP 대역
+    #[serde(default)]
+    pub bypass_ips: Vec<ValidCidr>,
+}
+
+/// Rate Limit 카운터 저장소 종류
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum StoreBackend {
+    /// 인스턴스별 메모리 저장소
+    #[default]
+    Memory,
+    /// 여러 인스턴스가 공유하는 Redis 저장소
+    Redis,
+}
+
+/// Rate Limit 알고리즘
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RateLimitStrategy {
+    /// 토큰 버킷 (`average`/`period` 속도로 보충, 최대 `burst`)
+    #[default]
+    TokenBucket,
+    /// 슬라이딩 윈도우
+    SlidingWindow,
+    /// 고정 윈도우
+    FixedWindow,
+}
+
+impl RateLimitStrategy {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::TokenBucket => "token_bucket",
+            Self::SlidingWindow => "sliding_window",
+            Self::FixedWindow => "fixed_window",
+        }
+    }
+}
+
+impl FromStr for RateLimitStrategy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "token_bucket" => Ok(Self::TokenBucket),
+            "sliding_window" => Ok(Self::SlidingWindow),
+            "fixed_window" => Ok(Self::FixedWindow),
+            _ => Err(format!("Invalid rate limit strategy: {}", s)),
+        }
+    }
+}
+
+impl fmt::Display for RateLimitStrategy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
 }
 
 fn default_average() -> u32 {
@@ -37,6 +109,10 @@ impl RateLimitConfig {
             average: default_average(),
             burst: default_burst(),
             period: default_period(),
+            strategy: RateLimitStrategy::default(),
+            store_backend: StoreBackend::default(),
+            redis_url: None,
+            bypass_ips: Vec::new(),
         };
 
         for (key, value) in labels {
@@ -47,12 +123,49 @@ impl RateLimitConfig {
                 "rateLimit.burst" => {
                     config.burst = value.parse().map_err(|_| "Invalid burst value")?;
                 }
+                "rateLimit.period" => {
+                    let secs: u64 = value.parse().map_err(|_| "Invalid period value")?;
+                    if secs == 0 {
+                        return Err("rateLimit.period must be greater than 0".to_string());
+                    }
+                    config.period = Duration::from_secs(secs);
+                }
+                "rateLimit.strategy" => {
+                    config.strategy = value.parse()?;
+                }
+                "rateLimit.storeBackend" => {
+                    config.store_backend = match value.to_lowercase().as_str() {
+                        "memory" => StoreBackend::Memory,
+                        "redis" => StoreBackend::Redis,
+                        _ => return Err(format!("Invalid store backend: {}", value)),
+                    };
+                }
+                "rateLimit.redisUrl" => {
+                    config.redis_url = Some(value.clone());
+                }
+                "rateLimit.bypassIps" => {
+                    config.bypass_ips = ValidCidr::parse_list(value).map_err(|e| e.to_string())?;
+                }
                 _ => continue,
             }
         }
 
+        // average 없이 burst만 지정하면 보충 없이 burst만큼만 허용
+        if labels.contains_key("rateLimit.burst") && !labels.contains_key("rateLimit.average") {
+            config.average = 0;
+        }
+
+        if config.store_backend == StoreBackend::Redis && config.redis_url.is_none() {
+            return Err("rateLimit.redisUrl is required for redis store backend".to_string());
+        }
+
         Ok(config)
     }
+
+    /// 초당 토큰 보충 속도
+    pub fn rate_per_sec(&self) -> f64 {
+        self.average as f64 / self.period.as_secs_f64()
+    }
 }
 
 #[cfg(test)]
@@ -65,11 +178,48 @@ mod tests {
             average: default_average(),
             burst: default_burst(),
             period: default_period(),
+            strategy: RateLimitStrategy::default(),
+            store_backend: StoreBackend::default(),
+            redis_url: None,
+            bypass_ips: Vec::new(),
         };
 
         assert_eq!(config.average, 100);
         assert_eq!(config.burst, 50);
         assert_eq!(config.period, Duration::from_secs(1));
+        assert_eq!(config.store_backend, StoreBackend::Memory);
+        assert_eq!(config.strategy, RateLimitStrategy::TokenBucket);
+    }
+
+    #[test]
+    fn test_burst_only_labels() {
+        let mut labels = HashMap::new();
+        labels.insert("rateLimit.burst".to_string(), "10".to_string());
+
+        let config = RateLimitConfig::from_labels(&labels).unwrap();
+        assert_eq!(config.burst, 10);
+        assert_eq!(config.average, 0);
+        assert_eq!(config.rate_per_sec(), 0.0);
+    }
+
+    #[test]
+    fn test_period_and_strategy_labels() {
+        let mut labels = HashMap::new();
+        labels.insert("rateLimit.average".to_string(), "60".to_string());
+        labels.insert("rateLimit.period".to_string(), "60".to_string());
+        labels.insert("rateLimit.strategy".to_string(), "fixed_window".to_string());
+
+        let config = RateLimitConfig::from_labels(&labels).unwrap();
+        assert_eq!(config.period, Duration::from_secs(60));
+        assert_eq!(config.rate_per_sec(), 1.0);
+        assert_eq!(config.strategy, RateLimitStrategy::FixedWindow);
+
+        labels.insert("rateLimit.strategy".to_string(), "leaky_bucket".to_string());
+        assert!(RateLimitConfig::from_labels(&labels).is_err());
+
+        labels.insert("rateLimit.strategy".to_string(), "token_bucket".to_string());
+        labels.insert("rateLimit.period".to_string(), "0".to_string());
+        assert!(RateLimitConfig::from_labels(&labels).is_err());
     }
 
     #[test]
@@ -90,4 +240,32 @@ mod tests {
 
         assert!(RateLimitConfig::from_labels(&labels).is_err());
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_redis_backend_labels() {
+        let mut labels = HashMap::new();
+        labels.insert("rateLimit.storeBackend".to_string(), "redis".to_string());
+        labels.insert("rateLimit.redisUrl".to_string(), "redis://127.0.0.1:6379".to_string());
+
+        let config = RateLimitConfig::from_labels(&labels).unwrap();
+        assert_eq!(config.store_backend, StoreBackend::Redis);
+        assert_eq!(config.redis_url.as_deref(), Some("redis://127.0.0.1:6379"));
+
+        // URL 없이 redis 저장소를 지정하면 오류
+        labels.remove("rateLimit.redisUrl");
+        assert!(RateLimitConfig::from_labels(&labels).is_err());
+    }
+
+    #[test]
+    fn test_bypass_ips_labels() {
+        let mut labels = HashMap::new();
+        labels.insert("rateLimit.bypassIps".to_string(), "10.0.0.0/8, 127.0.0.1".to_string());
+
+        let config = RateLimitConfig::from_labels(&labels).unwrap();
+        assert_eq!(config.bypass_ips.len(), 2);
+        assert!(config.bypass_ips[0].contains(&"10.20.30.40".parse().unwrap()));
+
+        labels.insert("rateLimit.bypassIps".to_string(), "10.0.0.0/40".to_string());
+        assert!(RateLimitConfig::from_labels(&labels).is_err());
+    }
+}
@@ -1,5 +1,8 @@
-use crate::middleware::{Middleware, MiddlewareError, Request, Response};
-use super::{config::RateLimitConfig, store::RateLimitStore};
+use crate::middleware::{ClientIp, ConnectionInfo, Middleware, MiddlewareError, Request, Response};
+use super::{config::RateLimitConfig, store::{RateLimitStatus, RateLimitStore}};
+use hyper::HeaderMap;
+use std::net::IpAddr;
+use std::time::Duration;
 use async_trait::async_trait;
 use hyper::StatusCode;
 use http_body_util::Full;
@@ -18,55 +21,95 @@ impl<S: RateLimitStore> RateLimitMiddleware<S> {
     }
 
     /// 클라이언트 식별자를 추출합니다.
-    fn get_client_id(req: &Request) -> String {
-        // X-Forwarded-For 헤더 확인
-        if let Some(forwarded) = req.headers()
-            .get("x-forwarded-for")
-            .and_then(|h| h.to_str().ok())
-        {
-            if let Some(ip) = forwarded.split(',').next() {
-                return ip.trim().to_string();
-            }
+    ///
+    /// 클라이언트가 임의로 보낼 수 있는 `X-Forwarded-For`/`X-Real-IP`는 직접 읽지 않습니다.
+    /// 서버가 신뢰하는 프록시 대역을 고려해 기록한 `ClientIp`를 우선 사용하고,
+    /// 없으면 연결 주소를 사용합니다.
+    fn get_client_id<B>(req: &Request<B>) -> String {
+        if let Some(ClientIp(ip)) = req.extensions().get::<ClientIp>() {
+            return ip.to_string();
         }
 
-        // X-Real-IP 헤더 확인
-        if let Some(real_ip) = req.headers()
-            .get("x-real-ip")
-            .and_then(|h| h.to_str().ok())
-        {
-            return real_ip.to_string();
+        if let Some(connection) = req.extensions().get::<ConnectionInfo>() {
+            return connection.remote_addr.ip().to_canonical().to_string();
         }
 
-        // 헤더가 없는 경우 기본값 사용
+        // 연결 정보가 없는 경우 기본값 사용
         "unknown".to_string()
     }
 
+    /// 속도 제한 예외 대역에 속한 클라이언트인지 확인합니다.
+    fn is_bypassed(&self, client_id: &str) -> bool {
+        client_id.parse::<IpAddr>()
+            .map(|ip| self.config.bypass_ips.iter().any(|cidr| cidr.contains(&ip)))
+            .unwrap_or(false)
+    }
+
     /// Rate Limit 초과 응답을 생성합니다.
+    /// 허용량이 다시 생기지 않는 경우(버스트 전용)에는 `Retry-After`를 보내지 않습니다.
     async fn create_limit_exceeded_response(&self, key: &str) -> Response {
-        let wait_time = self.store.time_to_next_request(key).await
-            .unwrap_or_default();
+        let wait_time = self.store.time_to_next_request(key).await;
+        let status = self.status(key).await;
 
-        Response::builder()
+        let mut response = Response::builder()
             .status(StatusCode::TOO_MANY_REQUESTS)
-            .header("X-RateLimit-Limit", self.config.average.to_string())
-            .header("X-RateLimit-Reset", wait_time.as_secs().to_string())
-            .header("Retry-After", wait_time.as_secs().to_string())
             .body(Full::new(Bytes::from("Rate limit exceeded")))
-            .unwrap()
+            .unwrap();
+        if let Some(wait_time) = wait_time {
+            response.headers_mut().insert("Retry-After", ceil_secs(wait_time).into());
+        }
+        self.set_rate_limit_headers(response.headers_mut(), &RateLimitStatus {
+            remaining: 0,
+            ..status
+        });
+        response
+    }
+
+    async fn status(&self, key: &str) -> RateLimitStatus {
+        self.store.status(
+            key,
+            self.config.rate_per_sec(),
+            self.config.burst as f64
+        ).await
+    }
+
+    /// X-RateLimit-* 헤더를 설정합니다.
+    fn set_rate_limit_headers(&self, headers: &mut HeaderMap, status: &RateLimitStatus) {
+        headers.insert("X-RateLimit-Limit", self.config.average.into());
+        headers.insert("X-RateLimit-Remaining", status.remaining.into());
+        headers.insert("X-RateLimit-Reset", ceil_secs(status.reset).into());
     }
 }
 
+/// 초 단위로 올림합니다. 1초 미만의 대기 시간이 0으로 표시되지 않도록 합니다.
+fn ceil_secs(duration: Duration) -> u64 {
+    duration.as_secs() + u64::from(duration.subsec_nanos() > 0)
+}
+
 #[async_trait]
 impl<S: RateLimitStore> Middleware for RateLimitMiddleware<S> {
+    fn name(&self) -> &'static str {
+        "ratelimit"
+    }
+
     async fn handle_request(&self, req: Request) -> Result<Request, MiddlewareError> {
         let client_id = Self::get_client_id(&req);
         debug!("Rate limit check for client: {}", client_id);
 
+        if self.is_bypassed(&client_id) {
+            debug!("Rate limit bypassed for client: {}", client_id);
+            return Ok(req);
+        }
+
         if self.store.check_rate(
             &client_id,
-            self.config.average as f64,
+            self.config.rate_per_sec(),
             self.config.burst as f64
         ).await {
+            // 응답 미들웨어에서 헤더를 설정할 수 있도록 상태를 요청에 기록
+            let status = self.status(&client_id).await;
+            let mut req = req;
+            req.extensions_mut().insert(status);
             Ok(req)
         } else {
             let response = self.create_limit_exceeded_response(&client_id).await;
@@ -74,7 +117,99 @@ impl<S: RateLimitStore> Middleware for RateLimitMiddleware<S> {
         }
     }
 
-    async fn handle_response(&self, res: Response) -> Result<Response, MiddlewareError> {
+    async fn handle_response(&self, mut res: Response) -> Result<Response, MiddlewareError> {
+        if let Some(status) = res.extensions().get::<RateLimitStatus>().copied() {
+            self.set_rate_limit_headers(res.headers_mut(), &status);
+        }
         Ok(res)
     }
-} 
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::middleware::rate_limit::store::memory::MemoryStore;
+    use std::collections::HashMap;
+
+    fn middleware() -> RateLimitMiddleware<MemoryStore> {
+        let config = RateLimitConfig::from_labels(&HashMap::new()).unwrap();
+        RateLimitMiddleware::new(config, MemoryStore::new())
+    }
+
+    #[tokio::test]
+    async fn test_response_headers_from_status() {
+        let middleware = middleware();
+        let mut res = Response::new(Full::new(Bytes::from("ok")));
+        res.extensions_mut().insert(RateLimitStatus {
+            remaining: 7,
+            reset: Duration::from_millis(1500),
+        });
+
+        let res = middleware.handle_response(res).await.unwrap();
+        assert_eq!(res.headers()["X-RateLimit-Limit"], "100");
+        assert_eq!(res.headers()["X-RateLimit-Remaining"], "7");
+        assert_eq!(res.headers()["X-RateLimit-Reset"], "2");
+    }
+
+    #[tokio::test]
+    async fn test_limit_exceeded_response_headers() {
+        let middleware = middleware();
+        for _ in 0..50 {
+            assert!(middleware.store.check_rate("client", 100.0, 50.0).await);
+        }
+        assert!(!middleware.store.check_rate("client", 100.0, 50.0).await);
+
+        let res = middleware.create_limit_exceeded_response("client").await;
+        assert_eq!(res.status(), StatusCode::TOO_MANY_REQUESTS);
+        assert_eq!(res.headers()["Retry-After"], "1");
+        assert_eq!(res.headers()["X-RateLimit-Remaining"], "0");
+    }
+
+    #[tokio::test]
+    async fn test_burst_only_limit_has_no_retry_after() {
+        let middleware = middleware();
+        assert!(middleware.store.check_rate("client", 0.0, 1.0).await);
+        assert!(!middleware.store.check_rate("client", 0.0, 1.0).await);
+
+        let res = middleware.create_limit_exceeded_response("client").await;
+        assert_eq!(res.status(), StatusCode::TOO_MANY_REQUESTS);
+        assert!(!res.headers().contains_key("Retry-After"));
+    }
+
+    #[test]
+    fn test_client_id_ignores_spoofed_forwarded_for() {
+        let request = |ip: Option<&str>| {
+            let mut req = hyper::Request::builder()
+                .header("x-forwarded-for", "10.0.0.1")
+                .header("x-real-ip", "10.0.0.2")
+                .body(())
+                .unwrap();
+            req.extensions_mut().insert(ConnectionInfo {
+                remote_addr: "203.0.113.7:40000".parse().unwrap(),
+                local_addr: None,
+                is_tls: false,
+            });
+            if let Some(ip) = ip {
+                req.extensions_mut().insert(ClientIp(ip.parse().unwrap()));
+            }
+            req
+        };
+
+        // 위조된 헤더 대신 연결 주소로 식별
+        assert_eq!(RateLimitMiddleware::<MemoryStore>::get_client_id(&request(None)), "203.0.113.7");
+        // 서버가 신뢰 대역을 확인해 기록한 클라이언트 IP는 사용
+        assert_eq!(RateLimitMiddleware::<MemoryStore>::get_client_id(&request(Some("10.0.0.1"))), "10.0.0.1");
+    }
+
+    #[test]
+    fn test_bypass_ips() {
+        let mut labels = HashMap::new();
+        labels.insert("rateLimit.bypassIps".to_string(), "10.0.0.0/8".to_string());
+        let config = RateLimitConfig::from_labels(&labels).unwrap();
+        let middleware = RateLimitMiddleware::new(config, MemoryStore::new());
+
+        assert!(middleware.is_bypassed("10.1.2.3"));
+        assert!(!middleware.is_bypassed("192.168.0.1"));
+        assert!(!middleware.is_bypassed("unknown"));
+    }
+}
@@ -6,5 +6,5 @@ mod config;
 pub mod store;
 mod middleware;
 
-pub use config::RateLimitConfig;
+pub use config::{RateLimitConfig, RateLimitStrategy, StoreBackend};
 pub use middleware::RateLimitMiddleware; 
\ No newline at end of file
@@ -47,14 +47,33 @@ impl TokenBucket {
         }
     }
 
-    /// 다음 토큰이 사용 가능할 때까지 남은 시간을 반환합니다.
-    fn time_to_next_token(&self) -> Duration {
-        if self.tokens > 0.0 {
+    /// 현재 시점 기준 상태를 계산합니다. 버킷은 변경하지 않습니다.
+    fn status(&self) -> RateLimitStatus {
+        let tokens = (self.tokens + self.last_update.elapsed().as_secs_f64() * self.rate)
+            .min(self.capacity);
+        let reset = if self.rate > 0.0 {
+            Duration::from_secs_f64((self.capacity - tokens) / self.rate)
+        } else {
             Duration::from_secs(0)
+        };
+
+        RateLimitStatus {
+            remaining: tokens.floor() as u32,
+            reset,
+        }
+    }
+
+    /// 다음 토큰이 사용 가능할 때까지 남은 시간을 반환합니다.
+    /// 보충 속도가 0인 빈 버킷은 기다려도 토큰이 생기지 않으므로 `None`을 반환합니다.
+    fn time_to_next_token(&self) -> Option<Duration> {
+        if self.tokens >= 1.0 {
+            Some(Duration::from_secs(0))
+        } else if self.rate <= 0.0 {
+            None
         } else {
-            let tokens_needed = 1.0;
+            let tokens_needed = 1.0 - self.tokens;
             let time_needed = tokens_needed / self.rate;
-            Duration::from_secs_f64(time_needed)
+            Some(Duration::from_secs_f64(time_needed))
         }
     }
 }
@@ -87,10 +106,19 @@ impl TokenBucketStore {
     /// 다음 요청까지 대기해야 하는 시간을 반환합니다.
     pub async fn time_to_next_request(&self, key: &str) -> Option<Duration> {
         let buckets = self.buckets.read().await;
-        buckets.get(key).map(|bucket| bucket.time_to_next_token())
+        buckets.get(key).and_then(|bucket| bucket.time_to_next_token())
     }
 }
 
+/// 클라이언트별 속도 제한 상태
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RateLimitStatus {
+    /// 현재 남은 요청 수
+    pub remaining: u32,
+    /// 허용량이 모두 회복될 때까지 남은 시간
+    pub reset: Duration,
+}
+
 /// 속도 제한 저장소 trait
 #[async_trait]
 pub trait RateLimitStore: Send + Sync {
@@ -98,7 +126,11 @@ pub trait RateLimitStore: Send + Sync {
     async fn check_rate(&self, key: &str, rate: f64, capacity: f64) -> bool;
     
     /// 다음 요청까지 대기해야 하는 시간을 반환합니다.
+    /// 알 수 없거나 허용량이 다시 생기지 않으면(버스트 전용) `None`을 반환합니다.
     async fn time_to_next_request(&self, key: &str) -> Option<Duration>;
+
+    /// 현재 속도 제한 상태를 반환합니다.
+    async fn status(&self, key: &str, rate: f64, capacity: f64) -> RateLimitStatus;
 }
 
 /// 메모리 기반 저장소 구현을 위한 모듈
@@ -148,7 +180,210 @@ pub mod memory {
 
         async fn time_to_next_request(&self, key: &str) -> Option<Duration> {
             let buckets = self.buckets.read().await;
-            buckets.get(key).map(|bucket| bucket.time_to_next_token())
+            buckets.get(key).and_then(|bucket| bucket.time_to_next_token())
+        }
+
+        async fn status(&self, key: &str, _rate: f64, capacity: f64) -> RateLimitStatus {
+            let buckets = self.buckets.read().await;
+            buckets.get(key)
+                .map(|bucket| bucket.status())
+                .unwrap_or(RateLimitStatus {
+                    remaining: capacity as u32,
+                    reset: Duration::from_secs(0),
+                })
+        }
+    }
+}
+
+/// Redis 기반 저장소 구현을 위한 모듈
+///
+/// 여러 프록시 인스턴스가 같은 Redis를 바라보면 클라이언트별 카운터가 공유됩니다.
+/// Redis에 접속할 수 없으면 인스턴스 로컬 메모리 저장소로 대체합니다.
+pub mod redis {
+    use super::*;
+    use super::memory::MemoryStore;
+    use ::redis::aio::MultiplexedConnection;
+    use tokio::sync::Mutex;
+    use tracing::warn;
+
+    /// Redis 키 접두사
+    const KEY_PREFIX: &str = "roxy:ratelimit:";
+    /// Redis 접속 타임아웃
+    const CONNECT_TIMEOUT: Duration = Duration::from_secs(1);
+    /// 접속 실패 후 재접속을 시도하기까지 대기 시간
+    const RECONNECT_INTERVAL: Duration = Duration::from_secs(5);
+
+    #[derive(Default)]
+    struct ConnectionState {
+        connection: Option<MultiplexedConnection>,
+        last_failure: Option<Instant>,
+    }
+
+    /// Redis 기반 저장소
+    ///
+    /// `INCR` + `PEXPIRE`로 고정 윈도우 카운터를 구현합니다.
+    /// 윈도우 길이는 버킷이 가득 차는 데 걸리는 시간(capacity / rate)이며,
+    /// 윈도우마다 최대 capacity개의 요청을 허용합니다.
+    /// 보충 속도가 0(버스트 전용)이면 만료 시간을 두지 않아 허용량이 다시 생기지 않습니다.
+    #[derive(Clone)]
+    pub struct RedisStore {
+        client: Option<::redis::Client>,
+        state: Arc<Mutex<ConnectionState>>,
+        fallback: MemoryStore,
+    }
+
+    impl RedisStore {
+        pub fn new(url: &str) -> Self {
+            let client = match ::redis::Client::open(url) {
+                Ok(client) => Some(client),
+                Err(e) => {
+                    warn!("잘못된 Redis URL, 메모리 저장소로 대체합니다: {}", e);
+                    None
+                }
+            };
+
+            Self {
+                client,
+                state: Arc::new(Mutex::new(ConnectionState::default())),
+                fallback: MemoryStore::new(),
+            }
+        }
+
+        /// 연결을 가져옵니다. 접속할 수 없으면 None을 반환합니다.
+        async fn connection(&self) -> Option<MultiplexedConnection> {
+            let client = self.client.as_ref()?;
+            let mut state = self.state.lock().await;
+
+            if let Some(conn) = state.connection.as_ref() {
+                return Some(conn.clone());
+            }
+
+            if let Some(last_failure) = state.last_failure {
+                if last_failure.elapsed() < RECONNECT_INTERVAL {
+                    return None;
+                }
+            }
+
+            match tokio::time::timeout(CONNECT_TIMEOUT, client.get_multiplexed_tokio_connection()).await {
+                Ok(Ok(conn)) => {
+                    debug!("Redis 연결 성공");
+                    state.connection = Some(conn.clone());
+                    state.last_failure = None;
+                    Some(conn)
+                }
+                Ok(Err(e)) => {
+                    warn!("Redis 연결 실패, 메모리 저장소로 대체합니다: {}", e);
+                    state.last_failure = Some(Instant::now());
+                    None
+                }
+                Err(_) => {
+                    warn!("Redis 연결 타임아웃, 메모리 저장소로 대체합니다");
+                    state.last_failure = Some(Instant::now());
+                    None
+                }
+            }
+        }
+
+        /// 명령 실패 시 연결을 버리고 재접속 대기 상태로 전환합니다.
+        async fn mark_failed(&self, error: ::redis::RedisError) {
+            warn!("Redis 명령 실패, 메모리 저장소로 대체합니다: {}", error);
+            let mut state = self.state.lock().await;
+            state.connection = None;
+            state.last_failure = Some(Instant::now());
+        }
+
+        fn redis_key(key: &str) -> String {
+            format!("{}{}", KEY_PREFIX, key)
+        }
+
+        /// 카운터 윈도우 길이 (밀리초), 버스트 전용이면 `None`
+        pub(super) fn window_ms(rate: f64, capacity: f64) -> Option<u64> {
+            if rate <= 0.0 {
+                return None;
+            }
+            Some(((capacity / rate) * 1000.0).ceil().max(1.0) as u64)
+        }
+    }
+
+    #[async_trait]
+    impl RateLimitStore for RedisStore {
+        async fn check_rate(&self, key: &str, rate: f64, capacity: f64) -> bool {
+            let Some(mut conn) = self.connection().await else {
+                return self.fallback.check_rate(key, rate, capacity).await;
+            };
+
+            let redis_key = Self::redis_key(key);
+            let result: ::redis::RedisResult<(u64, i64)> = ::redis::pipe()
+                .atomic()
+                .cmd("INCR").arg(&redis_key)
+                .cmd("PTTL").arg(&redis_key)
+                .query_async(&mut conn)
+                .await;
+
+            match result {
+                Ok((count, ttl)) => {
+                    // 새 윈도우이거나 TTL이 누락된 경우 만료 시간 설정
+                    if let (true, Some(window_ms)) = (ttl < 0, Self::window_ms(rate, capacity)) {
+                        let expire: ::redis::RedisResult<()> = ::redis::cmd("PEXPIRE")
+                            .arg(&redis_key)
+                            .arg(window_ms)
+                            .query_async(&mut conn)
+                            .await;
+                        if let Err(e) = expire {
+                            self.mark_failed(e).await;
+                        }
+                    }
+                    count as f64 <= capacity
+                }
+                Err(e) => {
+                    self.mark_failed(e).await;
+                    self.fallback.check_rate(key, rate, capacity).await
+                }
+            }
+        }
+
+        async fn time_to_next_request(&self, key: &str) -> Option<Duration> {
+            let Some(mut conn) = self.connection().await else {
+                return self.fallback.time_to_next_request(key).await;
+            };
+
+            let result: ::redis::RedisResult<i64> = ::redis::cmd("PTTL")
+                .arg(Self::redis_key(key))
+                .query_async(&mut conn)
+                .await;
+
+            match result {
+                Ok(ttl) if ttl > 0 => Some(Duration::from_millis(ttl as u64)),
+                Ok(_) => None,
+                Err(e) => {
+                    self.mark_failed(e).await;
+                    self.fallback.time_to_next_request(key).await
+                }
+            }
+        }
+
+        async fn status(&self, key: &str, rate: f64, capacity: f64) -> RateLimitStatus {
+            let Some(mut conn) = self.connection().await else {
+                return self.fallback.status(key, rate, capacity).await;
+            };
+
+            let redis_key = Self::redis_key(key);
+            let result: ::redis::RedisResult<(Option<u64>, i64)> = ::redis::pipe()
+                .cmd("GET").arg(&redis_key)
+                .cmd("PTTL").arg(&redis_key)
+                .query_async(&mut conn)
+                .await;
+
+            match result {
+                Ok((count, ttl)) => RateLimitStatus {
+                    remaining: (capacity as u64).saturating_sub(count.unwrap_or(0)) as u32,
+                    reset: Duration::from_millis(ttl.max(0) as u64),
+                },
+                Err(e) => {
+                    self.mark_failed(e).await;
+                    self.fallback.status(key, rate, capacity).await
+                }
+            }
         }
     }
 }
@@ -204,4 +439,48 @@ mod tests {
         // cleanup 후에는 버킷이 제거됨
         assert!(store.time_to_next_request(key).await.is_none());
     }
-} 
\ No newline at end of file
+
+    #[tokio::test]
+    async fn test_redis_store_falls_back_to_memory() {
+        // 접속할 수 없는 Redis 주소
+        let store = redis::RedisStore::new("redis://127.0.0.1:1");
+        let key = "test";
+
+        assert!(store.check_rate(key, 1.0, 2.0).await);
+        assert!(store.check_rate(key, 1.0, 2.0).await);
+        assert!(!store.check_rate(key, 1.0, 2.0).await);
+        assert!(store.time_to_next_request(key).await.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_burst_only_never_refills() {
+        let store = MemoryStore::new();
+        let key = "test";
+
+        assert!(store.check_rate(key, 0.0, 1.0).await);
+        assert!(!store.check_rate(key, 0.0, 1.0).await);
+        // 기다려도 토큰이 생기지 않으므로 대기 시간을 알려주지 않음
+        assert_eq!(store.time_to_next_request(key).await, None);
+
+        assert_eq!(redis::RedisStore::window_ms(0.0, 10.0), None);
+        assert_eq!(redis::RedisStore::window_ms(2.0, 10.0), Some(5000));
+    }
+
+    #[tokio::test]
+    async fn test_status_reports_remaining() {
+        let store = MemoryStore::new();
+        let key = "test";
+
+        // 버킷이 없으면 전체 허용량이 남아 있음
+        let status = store.status(key, 1.0, 3.0).await;
+        assert_eq!(status.remaining, 3);
+        assert_eq!(status.reset, Duration::from_secs(0));
+
+        assert!(store.check_rate(key, 1.0, 3.0).await);
+        assert!(store.check_rate(key, 1.0, 3.0).await);
+
+        let status = store.status(key, 1.0, 3.0).await;
+        assert_eq!(status.remaining, 1);
+        assert!(status.reset > Duration::from_secs(1));
+    }
+}
@@ -0,0 +1,101 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use crate::middleware::MiddlewareError;
+
+/// 허용되는 리다이렉트 상태 코드
+pub const REDIRECT_STATUSES: [u16; 4] = [301, 302, 307, 308];
+
+/// 리다이렉트 설정
+///
+/// # Docker 라벨 예시
+/// ```yaml
+/// labels:
+///   - "rproxy.http.middlewares.to-https.type=redirect"
+///   - "rproxy.http.middlewares.to-https.redirect.from=^http://([^/:]+)(.*)$"
+///   - "rproxy.http.middlewares.to-https.redirect.to=https://${1}${2}"
+///   - "rproxy.http.middlewares.to-https.redirect.status=301"
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct RedirectConfig {
+    /// 전체 요청 URL(`scheme://host/path?query`)과 비교할 정규식
+    pub from_pattern: String,
+
+    /// 리다이렉트 대상 템플릿 (`$1`, `${name}` 캡처 그룹 사용 가능)
+    pub to_template: String,
+
+    /// 응답 상태 코드 (301, 302, 307, 308)
+    #[serde(default = "default_status")]
+    pub status: u16,
+}
+
+fn default_status() -> u16 {
+    302
+}
+
+impl RedirectConfig {
+    /// Docker 라벨에서 설정을 파싱합니다.
+    pub fn from_labels(labels: &HashMap<String, String>) -> Result<Self, MiddlewareError> {
+        let required = |key: &str| {
+            labels.get(key).cloned().ok_or_else(|| MiddlewareError::Config {
+                message: format!("{} is required", key),
+            })
+        };
+
+        let status = match labels.get("redirect.status") {
+            Some(value) => value.parse().map_err(|_| MiddlewareError::InvalidLabel {
+                key: "redirect.status".to_string(),
+                value: value.clone(),
+                reason: "status must be a number".to_string(),
+            })?,
+            None => default_status(),
+        };
+
+        let config = Self {
+            from_pattern: required("redirect.from")?,
+            to_template: required("redirect.to")?,
+            status,
+        };
+        config.validate()?;
+        Ok(config)
+    }
+
+    /// 상태 코드가 리다이렉트 코드인지 검증합니다.
+    pub fn validate(&self) -> Result<(), MiddlewareError> {
+        if !REDIRECT_STATUSES.contains(&self.status) {
+            return Err(MiddlewareError::InvalidLabel {
+                key: "redirect.status".to_string(),
+                value: self.status.to_string(),
+                reason: "status must be one of 301, 302, 307, 308".to_string(),
+            });
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_labels() {
+        let mut labels = HashMap::new();
+        labels.insert("redirect.from".to_string(), "^http://(.*)$".to_string());
+        labels.insert("redirect.to".to_string(), "https://$1".to_string());
+
+        let config = RedirectConfig::from_labels(&labels).unwrap();
+        assert_eq!(config.status, 302);
+
+        labels.insert("redirect.status".to_string(), "308".to_string());
+        assert_eq!(RedirectConfig::from_labels(&labels).unwrap().status, 308);
+    }
+
+    #[test]
+    fn test_invalid_status() {
+        let mut labels = HashMap::new();
+        labels.insert("redirect.from".to_string(), "^http://(.*)$".to_string());
+        labels.insert("redirect.to".to_string(), "https://$1".to_string());
+        labels.insert("redirect.status".to_string(), "200".to_string());
+
+        assert!(RedirectConfig::from_labels(&labels).is_err());
+    }
+}
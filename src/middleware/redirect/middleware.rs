@@ -0,0 +1,147 @@
+use crate::middleware::{Middleware, MiddlewareError, Request, Response};
+use crate::middleware::headers::HeaderContext;
+use super::config::RedirectConfig;
+use async_trait::async_trait;
+use bytes::Bytes;
+use http_body_util::Full;
+use hyper::{header, StatusCode};
+use regex_lite::Regex;
+use tracing::debug;
+
+/// 리다이렉트 미들웨어
+///
+/// 주요 용도는 `http://example.com` → `https://example.com` 리다이렉트입니다.
+#[derive(Debug, Clone)]
+pub struct RedirectMiddleware {
+    from_pattern: Regex,
+    to_template: String,
+    status: u16,
+}
+
+impl RedirectMiddleware {
+    pub fn new(config: RedirectConfig) -> Result<Self, MiddlewareError> {
+        config.validate()?;
+        let from_pattern = Regex::new(&config.from_pattern).map_err(|e| MiddlewareError::Config {
+            message: format!("Invalid redirect pattern '{}': {}", config.from_pattern, e),
+        })?;
+
+        Ok(Self {
+            from_pattern,
+            to_template: config.to_template,
+            status: config.status,
+        })
+    }
+
+    /// HTTP 요청을 같은 호스트의 HTTPS 포트로 영구 리다이렉트하는 규칙을 생성합니다.
+    pub fn https_upgrade(https_port: u16) -> Self {
+        let to_template = if https_port == 443 {
+            "https://${1}${2}".to_string()
+        } else {
+            format!("https://${{1}}:{}${{2}}", https_port)
+        };
+
+        Self::new(RedirectConfig {
+            from_pattern: r"^http://([^/:]+)(?::\d+)?(.*)$".to_string(),
+            to_template,
+            status: 308,
+        }).expect("HTTPS 리다이렉트 규칙은 항상 유효합니다")
+    }
+
+    /// 요청 URL이 패턴과 일치하면 리다이렉트 대상 URL을 반환합니다.
+    pub fn redirect_location(&self, url: &str) -> Option<String> {
+        self.from_pattern.captures(url).map(|captures| {
+            let mut location = String::new();
+            captures.expand(&self.to_template, &mut location);
+            location
+        })
+    }
+
+    /// 요청이 리다이렉트 대상이면 리다이렉트 응답을 생성합니다.
+    pub fn redirect_response<B>(&self, req: &hyper::Request<B>) -> Option<Response> {
+        let context = HeaderContext::from_request(req);
+        let url = format!("{}://{}{}", context.scheme, context.host, context.request_uri);
+        let location = self.redirect_location(&url)?;
+        debug!(from = %url, to = %location, status = self.status, "요청 리다이렉트");
+
+        Response::builder()
+            .status(StatusCode::from_u16(self.status).unwrap_or(StatusCode::FOUND))
+            .header(header::LOCATION, location)
+            .body(Full::new(Bytes::new()))
+            .ok()
+    }
+}
+
+#[async_trait]
+impl Middleware for RedirectMiddleware {
+    fn name(&self) -> &'static str {
+        "redirect"
+    }
+
+    async fn handle_request(&self, req: Request) -> Result<Request, MiddlewareError> {
+        match self.redirect_response(&req) {
+            Some(response) => Err(MiddlewareError::Redirect(response)),
+            None => Ok(req),
+        }
+    }
+
+    async fn handle_response(&self, res: Response) -> Result<Response, MiddlewareError> {
+        Ok(res)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::middleware::ConnectionInfo;
+
+    #[test]
+    fn test_redirect_location() {
+        let middleware = RedirectMiddleware::new(RedirectConfig {
+            from_pattern: r"^https?://www\.(.+)$".to_string(),
+            to_template: "https://$1".to_string(),
+            status: 301,
+        }).unwrap();
+
+        assert_eq!(
+            middleware.redirect_location("http://www.example.com/a?b=1").as_deref(),
+            Some("https://example.com/a?b=1")
+        );
+        assert_eq!(middleware.redirect_location("http://example.com/"), None);
+    }
+
+    #[test]
+    fn test_https_upgrade() {
+        assert_eq!(
+            RedirectMiddleware::https_upgrade(443)
+                .redirect_location("http://example.com:80/path?q=1").as_deref(),
+            Some("https://example.com/path?q=1")
+        );
+        assert_eq!(
+            RedirectMiddleware::https_upgrade(8443)
+                .redirect_location("http://example.com/path").as_deref(),
+            Some("https://example.com:8443/path")
+        );
+        assert_eq!(
+            RedirectMiddleware::https_upgrade(443).redirect_location("https://example.com/"),
+            None
+        );
+    }
+
+    #[test]
+    fn test_redirect_response() {
+        let mut req = hyper::Request::builder()
+            .uri("/login")
+            .header("host", "example.com")
+            .body(())
+            .unwrap();
+        req.extensions_mut().insert(ConnectionInfo {
+            remote_addr: "127.0.0.1:5000".parse().unwrap(),
+            local_addr: None,
+            is_tls: false,
+        });
+
+        let response = RedirectMiddleware::https_upgrade(443).redirect_response(&req).unwrap();
+        assert_eq!(response.status(), StatusCode::PERMANENT_REDIRECT);
+        assert_eq!(response.headers()[header::LOCATION], "https://example.com/login");
+    }
+}
@@ -0,0 +1,9 @@
+//! 리다이렉트 미들웨어
+//! 
+//! 요청 URL이 패턴과 일치하면 백엔드로 전달하지 않고 리다이렉트 응답을 반환합니다.
+
+mod config;
+mod middleware;
+
+pub use config::RedirectConfig;
+pub use middleware::RedirectMiddleware;
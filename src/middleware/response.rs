@@ -9,6 +9,8 @@ pub fn handle_middleware_error(err: MiddlewareError) -> Response<Full<Bytes>> {
         // 직접 Response를 반환하는 에러들
         MiddlewareError::PreflightResponse(response) => response,
         MiddlewareError::TooManyRequests(response) => response,
+        MiddlewareError::Redirect(response) => response,
+        MiddlewareError::CachedResponse(response) => response,
         
         // 상태 코드와 메시지를 생성하는 에러들
         _ => {
@@ -34,6 +36,9 @@ pub fn handle_middleware_error(err: MiddlewareError) -> Response<Full<Bytes>> {
                 MiddlewareError::InvalidRequest(message) => {
                     (StatusCode::BAD_REQUEST, message)
                 }
+                MiddlewareError::Forbidden(message) => {
+                    (StatusCode::FORBIDDEN, message)
+                }
                 _ => (
                     StatusCode::INTERNAL_SERVER_ERROR,
                     "Internal Server Error".to_string()
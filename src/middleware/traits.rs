@@ -6,9 +6,20 @@ use async_trait::async_trait;
 /// HTTP 요청과 응답을 수정할 수 있는 인터페이스를 정의합니다.
 #[async_trait]
 pub trait Middleware: Send + Sync {
+    /// 로깅과 실행 시간 기록에 사용하는 미들웨어 이름입니다.
+    fn name(&self) -> &'static str;
+
     /// HTTP 요청을 처리합니다.
     async fn handle_request(&self, req: Request) -> Result<Request, MiddlewareError>;
 
     /// HTTP 응답을 처리합니다.
     async fn handle_response(&self, res: Response) -> Result<Response, MiddlewareError>;
+
+    /// `handle_response`에서 이 응답의 바디가 필요하면 메모리에 모을 최대 크기(바이트)를 반환합니다.
+    ///
+    /// 기본값은 `None`이며, 이 경우 응답 바디는 미들웨어를 거치지 않고 클라이언트로 스트리밍됩니다.
+    /// 바디가 한도를 넘으면 모으지 않고 스트리밍하며, 응답 확장 데이터에 `StreamedResponseBody`를 기록합니다.
+    fn response_body_limit(&self, _parts: &hyper::http::response::Parts) -> Option<usize> {
+        None
+    }
 }
\ No newline at end of file
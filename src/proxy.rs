@@ -1,41 +1,129 @@
 use hyper::{Response, StatusCode};
-use hyper::body::Bytes;
-use http_body_util::{BodyExt, Full};
+use hyper::body::{Body, Bytes};
+use http_body_util::{BodyExt, Empty, Full};
+use http_body_util::combinators::UnsyncBoxBody;
 use hyper_util::client::legacy;
-use hyper_util::client::legacy::connect::HttpConnector;
-use hyper_util::rt::TokioExecutor;
+use hyper_util::rt::{TokioExecutor, TokioIo};
 use crate::logging::{RequestLog, log_request};
-use crate::routing_v2::BackendService;
+use crate::metrics;
+use crate::middleware::ConnectionInfo;
+use crate::routing_v2::{BackendService, KeepaliveConfig};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
 use uuid::Uuid;
-use tracing::{info, error, instrument, Level};
+use tracing::{debug, info, error, Level};
+use upstream::{ConnectorService, TcpConnector, UpstreamConnector};
+
+pub mod upstream;
+
+/// 내부 리다이렉트 경로를 담는 백엔드 응답 헤더
+pub const X_ACCEL_REDIRECT: &str = "x-accel-redirect";
+
+/// 업스트림 응답 시간(요청 시작부터 응답 헤더 수신까지)을 알리는 응답 헤더
+pub const X_UPSTREAM_RESPONSE_TIME: &str = "x-upstream-response-time";
+
+/// 클라이언트와 중간 프록시 IP 체인을 전달하는 요청 헤더
+pub const X_FORWARDED_FOR: &str = "x-forwarded-for";
+
+/// 클라이언트와 프록시 정보를 전달하는 RFC 7239 요청 헤더
+pub const FORWARDED: &str = "forwarded";
+
+/// 업스트림으로 전달하는 요청 바디 (원본 바디 또는 일부를 읽은 뒤 재구성한 바디)
+pub type UpstreamBody = UnsyncBoxBody<Bytes, hyper::Error>;
+
+/// 클라이언트로 보내는 응답 바디
+///
+/// 백엔드 응답은 메모리에 모으지 않고 도착하는 대로 전달하며,
+/// 프록시가 직접 만드는 응답은 `full_response`로 변환합니다.
+pub type ResponseBody = UnsyncBoxBody<Bytes, hyper::Error>;
+
+/// 백엔드가 보낸 응답임을 표시하는 응답 확장
+///
+/// 백엔드 응답의 본문 길이는 프록시가 알 수 없으므로 HEAD 응답에서 `Content-Length`를 추측하지 않습니다.
+#[derive(Debug, Clone, Copy)]
+pub struct UpstreamResponse {
+    /// 응답을 보낸 백엔드 주소 (X-Accel-Redirect 내부 요청도 같은 주소로 보냄)
+    pub address: std::net::SocketAddr,
+}
+
+/// 메모리에 있는 응답을 클라이언트 응답 바디 타입으로 변환합니다.
+pub fn full_response(response: Response<Full<Bytes>>) -> Response<ResponseBody> {
+    response.map(|body| body.map_err(|never| match never {}).boxed_unsync())
+}
+
+type UpstreamClient = legacy::Client<ConnectorService, UpstreamBody>;
 
 // 프록시 요청을 위한 불변 설정 구조체
 #[derive(Clone)]
 pub struct ProxyConfig {
-    client: legacy::Client<HttpConnector, hyper::body::Incoming>,
+    /// 백엔드 연결에 사용하는 커넥터
+    connector: Arc<dyn UpstreamConnector>,
+    /// 기본 keep-alive 설정을 사용하는 백엔드용 클라이언트
+    client: UpstreamClient,
+    /// 기본값과 다른 keep-alive 설정별 클라이언트 (설정이 같은 백엔드끼리 연결 풀 공유)
+    keepalive_clients: Arc<Mutex<HashMap<KeepaliveConfig, UpstreamClient>>>,
+    /// 프록시가 직접 만드는 요청(내부 리다이렉트 등)용 클라이언트
+    fetch_client: legacy::Client<ConnectorService, Empty<Bytes>>,
 }
 
 impl ProxyConfig {
     pub fn new() -> Self {
-        let connector = HttpConnector::new();
-        let client = legacy::Client::builder(TokioExecutor::new())
-            .build::<_, hyper::body::Incoming>(connector);
-        
-        Self { client }
+        Self::with_connector(Arc::new(TcpConnector))
     }
+
+    /// 지정한 커넥터로 백엔드에 연결하는 설정을 생성합니다.
+    pub fn with_connector(connector: Arc<dyn UpstreamConnector>) -> Self {
+        let client = build_upstream_client(&connector, &KeepaliveConfig::default());
+        let fetch_client = legacy::Client::builder(TokioExecutor::new())
+            .build::<_, Empty<Bytes>>(ConnectorService(connector.clone()));
+
+        Self {
+            connector,
+            client,
+            keepalive_clients: Arc::default(),
+            fetch_client,
+        }
+    }
+
+    /// 백엔드의 keep-alive 설정에 맞는 업스트림 클라이언트를 반환합니다.
+    fn client_for(&self, keepalive: &KeepaliveConfig) -> UpstreamClient {
+        if *keepalive == KeepaliveConfig::default() {
+            return self.client.clone();
+        }
+
+        self.keepalive_clients.lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .entry(keepalive.clone())
+            .or_insert_with(|| {
+                debug!(keepalive = ?keepalive, "업스트림 클라이언트 생성");
+                build_upstream_client(&self.connector, keepalive)
+            })
+            .clone()
+    }
+}
+
+/// keep-alive 설정에 맞춰 연결 풀을 구성한 업스트림 클라이언트를 생성합니다.
+fn build_upstream_client(connector: &Arc<dyn UpstreamConnector>, keepalive: &KeepaliveConfig) -> UpstreamClient {
+    let max_idle = if keepalive.enabled { keepalive.max_idle_connections } else { 0 };
+    legacy::Client::builder(TokioExecutor::new())
+        .pool_idle_timeout(keepalive.idle_timeout)
+        .pool_max_idle_per_host(max_idle)
+        .build(ConnectorService(connector.clone()))
 }
 
 //프록시 요청 핸들러
-#[instrument(skip(config, backend))]
+//
+// 요청 span은 RequestHandler가 생성하며, 여기서는 선택된 upstream만 기록합니다.
 pub async fn proxy_request(
     config: &ProxyConfig,
     backend: &BackendService,
-    req: hyper::Request<hyper::body::Incoming>,
-) -> Result<hyper::Response<http_body_util::Full<hyper::body::Bytes>>, ProxyError> {
+    req: hyper::Request<UpstreamBody>,
+) -> Result<hyper::Response<ResponseBody>, ProxyError> {
     // --- 부수 효과가 포함된 임페리티브 처리 영역 ---
     // UUID 생성 및 트레이싱 설정
+    let request_span = tracing::Span::current();
     let request_id = Uuid::new_v4().to_string();
-    let _span = tracing::span!(Level::INFO, "request", request_id = %request_id);
+    let _span = tracing::span!(Level::INFO, "proxy", request_id = %request_id);
     let _enter = _span.enter();
     let start_time = std::time::Instant::now();
     
@@ -43,30 +131,53 @@ pub async fn proxy_request(
     let mut log = RequestLog::new(request_id);
     log.with_request(&req);
 
-    // 백엔드 주소 획득
-    let address = backend.get_next_address().map_err(|e| {
-        let err = ProxyError::BackendRequestFailed {
-            backend: "unknown".to_string(),
-            error: e.to_string(),
-        };
-        error!(error = %err, "백엔드 주소 획득 실패");
-        err
-    })?;
+    // 백엔드 주소 획득 (스티키 세션 쿠키가 유효하면 로드밸런서를 거치지 않음)
+    let sticky_address = backend.sticky_address(req.headers());
+    let address = match sticky_address {
+        Some(address) => address,
+        None => backend.get_next_address().map_err(|e| {
+            let err = ProxyError::BackendRequestFailed {
+                backend: "unknown".to_string(),
+                error: e.to_string(),
+            };
+            error!(error = %err, "백엔드 주소 획득 실패");
+            err
+        })?,
+    };
+    let connection = backend.track_connection(address);
     log.with_backend(address);
+    request_span.record("upstream", tracing::field::display(address));
     info!(backend = %address, "백엔드로 요청 프록시");
 
     // --- 순수 함수 호출 영역 ---
     // 원래 요청을 분리하여 순수 함수로 요청 빌드
     let (parts, body) = req.into_parts();
-    let proxied_req = pure_build_proxied_request(address, parts.method, parts.uri.path(), body)
+    // 요청 미들웨어가 남긴 확장 데이터를 응답 미들웨어에서 사용할 수 있도록 보관
+    let request_extensions = parts.extensions;
+    let mut proxied_req = pure_build_proxied_request(address, parts.method, parts.uri.path(), body)
         .map_err(|e| {
             let err = ProxyError::RequestBuildError { reason: e };
             error!(error = %err, "요청 빌드 실패");
             err
         })?;
+    pure_apply_env_headers(proxied_req.headers_mut(), backend);
+    pure_apply_keepalive(proxied_req.headers_mut(), &backend.keepalive);
+    if let Some(connection) = request_extensions.get::<ConnectionInfo>() {
+        pure_apply_forwarded_for(proxied_req.headers_mut(), &parts.headers, connection.remote_addr.ip(), backend);
+        if backend.use_rfc7239_forwarded {
+            pure_apply_rfc7239_forwarded(proxied_req.headers_mut(), &parts.headers, connection, backend);
+        }
+    }
+    // 기능이 꺼져 있어도 신뢰하지 않는 클라이언트가 보낸 `Forwarded` 헤더는 백엔드로 넘기지 않음
+    let trusted_client = request_extensions.get::<ConnectionInfo>()
+        .is_some_and(|connection| backend.is_trusted_proxy(&connection.remote_addr.ip()));
+    if !backend.use_rfc7239_forwarded && !trusted_client {
+        proxied_req.headers_mut().remove(FORWARDED);
+    }
 
     // --- 부수 효과: 네트워크 요청 및 응답 처리 ---
-    let response = config.client.request(proxied_req).await.map_err(|e| {
+    let upstream_start = std::time::Instant::now();
+    let response = config.client_for(&backend.keepalive).request(proxied_req).await.map_err(|e| {
         let err = ProxyError::BackendRequestFailed {
             backend: address.to_string(),
             error: e.to_string(),
@@ -75,25 +186,187 @@ pub async fn proxy_request(
         err
     })?;
 
+    let upstream_elapsed = upstream_start.elapsed();
+    metrics::UPSTREAM_RESPONSE_DURATION.observe(upstream_elapsed);
+
     let status = response.status();
     log.with_response(status);
+    log.upstream_response_ms = Some(upstream_elapsed.as_millis() as u64);
+
+    let (mut parts, body) = response.into_parts();
+    parts.extensions.extend(request_extensions);
+    parts.extensions.insert(UpstreamResponse { address });
+    pure_apply_upstream_response_time(&mut parts.headers, upstream_elapsed);
+    // 새로 선택된 주소는 다음 요청부터 같은 백엔드로 보내도록 쿠키에 기록
+    if sticky_address.is_none() {
+        pure_apply_sticky_cookie(&mut parts.headers, backend, address);
+    }
+
+    log.duration_ms = start_time.elapsed().as_millis() as u64;
+    log_request(&log);
+
+    // 바디는 클라이언트로 스트리밍하며, 전송이 끝날 때까지 진행 중인 연결로 기록
+    let body = body
+        .map_frame(move |frame| {
+            let _connection = &connection;
+            frame
+        })
+        .boxed_unsync();
+    debug!(size_hint = ?body.size_hint().exact(), "응답 바디 스트리밍 시작");
+
+    Ok(hyper::Response::from_parts(parts, body))
+}
+
+/// 백엔드 응답의 `X-Accel-Redirect` 헤더를 처리합니다.
+///
+/// 백엔드에 X-Accel-Redirect가 활성화되어 있고 헤더가 있으면 원래 응답 바디를 버리고
+/// 리다이렉트 경로의 로컬 파일 또는 같은 백엔드의 응답으로 대체합니다.
+/// 대체된 응답의 X-Accel-Redirect는 다시 따라가지 않습니다.
+pub async fn follow_x_accel_redirect(
+    config: &ProxyConfig,
+    backend: &BackendService,
+    response: Response<ResponseBody>,
+) -> Result<Response<ResponseBody>, ProxyError> {
+    let Some(settings) = &backend.x_accel_redirect else {
+        return Ok(response);
+    };
+    let Some(target) = response.headers().get(X_ACCEL_REDIRECT) else {
+        return Ok(response);
+    };
+
+    let target = target.to_str()
+        .ok()
+        .filter(|path| path.starts_with('/'))
+        .map(String::from)
+        .ok_or_else(|| {
+            let err = ProxyError::ResponseError {
+                backend: backend.address.to_string(),
+                error: format!("잘못된 X-Accel-Redirect 경로: {:?}", target),
+            };
+            error!(error = %err, "내부 리다이렉트 실패");
+            err
+        })?;
+    info!(target = %target, "X-Accel-Redirect 내부 리다이렉트");
+
+    let (mut parts, _) = response.into_parts();
+    parts.headers.remove(X_ACCEL_REDIRECT);
+
+    match &settings.base_path {
+        Some(base_path) => {
+            let body = read_redirect_file(base_path, &target).await?;
+            parts.headers.remove(hyper::header::CONTENT_LENGTH);
+            parts.headers.remove(hyper::header::TRANSFER_ENCODING);
+            parts.extensions.remove::<UpstreamResponse>();
+            parts.status = StatusCode::OK;
+            Ok(full_response(Response::from_parts(parts, Full::new(body))))
+        }
+        None => {
+            // 원래 응답을 보낸 복제본으로 다시 요청 (로드밸런서를 거치면 다른 복제본으로 갈 수 있음)
+            let address = match parts.extensions.get::<UpstreamResponse>() {
+                Some(upstream) => upstream.address,
+                None => backend.get_next_address().map_err(|e| ProxyError::BackendRequestFailed {
+                    backend: "unknown".to_string(),
+                    error: e.to_string(),
+                })?,
+            };
+            let connection = backend.track_connection(address);
+            let uri: hyper::Uri = format!("http://{}{}", address, target)
+                .parse()
+                .map_err(|e| ProxyError::RequestBuildError { reason: format!("URI 파싱 실패: {}", e) })?;
+            let request = hyper::Request::get(uri)
+                .body(Empty::<Bytes>::new())
+                .map_err(|e| ProxyError::RequestBuildError { reason: e.to_string() })?;
+
+            let redirected = config.fetch_client.request(request).await.map_err(|e| {
+                let err = ProxyError::BackendRequestFailed {
+                    backend: address.to_string(),
+                    error: e.to_string(),
+                };
+                error!(error = %err, "내부 리다이렉트 요청 실패");
+                err
+            })?;
+
+            let (mut redirected_parts, body) = redirected.into_parts();
+            redirected_parts.headers.remove(X_ACCEL_REDIRECT);
+            // 응답 미들웨어가 사용하는 요청 확장 데이터 유지
+            redirected_parts.extensions = parts.extensions;
+            // 바디 전송이 끝날 때까지 진행 중인 연결로 기록
+            let body = body
+                .map_frame(move |frame| {
+                    let _connection = &connection;
+                    frame
+                })
+                .boxed_unsync();
+            Ok(Response::from_parts(redirected_parts, body))
+        }
+    }
+}
+
+/// 기준 디렉터리 아래의 리다이렉트 대상 파일을 읽습니다. 상위 경로(`..`) 접근은 거부합니다.
+async fn read_redirect_file(base_path: &str, target: &str) -> Result<Bytes, ProxyError> {
+    let path = target.split('?').next().unwrap_or_default();
+    if path.split('/').any(|segment| segment == "..") {
+        return Err(ProxyError::RedirectTargetNotFound { path: path.to_string() });
+    }
 
-    let (parts, body) = response.into_parts();
-    let collected = body.collect().await.map_err(|e| {
-        let err = ProxyError::ResponseError {
+    let file_path = std::path::Path::new(base_path).join(path.trim_start_matches('/'));
+    tokio::fs::read(&file_path).await
+        .map(Bytes::from)
+        .map_err(|e| {
+            debug!(path = %file_path.display(), error = %e, "내부 리다이렉트 파일 읽기 실패");
+            ProxyError::RedirectTargetNotFound { path: path.to_string() }
+        })
+}
+
+/// CONNECT 요청에 대해 클라이언트와 백엔드 사이에 TCP 터널을 생성합니다.
+///
+/// 백엔드 연결에 성공하면 200 응답을 반환하고, 연결이 업그레이드되면
+/// 바이트를 그대로 양방향 전달합니다. TLS는 백엔드가 직접 종료합니다.
+pub async fn tunnel_request(
+    config: &ProxyConfig,
+    backend: &BackendService,
+    req: hyper::Request<hyper::body::Incoming>,
+) -> Result<hyper::Response<http_body_util::Full<hyper::body::Bytes>>, ProxyError> {
+    let address = backend.get_next_address().map_err(|e| {
+        let err = ProxyError::BackendRequestFailed {
+            backend: "unknown".to_string(),
+            error: e.to_string(),
+        };
+        error!(error = %err, "백엔드 주소 획득 실패");
+        err
+    })?;
+    tracing::Span::current().record("upstream", tracing::field::display(address));
+
+    // 200 응답 전에 백엔드 연결을 확인하여 실패 시 502 반환
+    let mut backend_stream = config.connector.connect(address).await.map_err(|e| {
+        let err = ProxyError::BackendRequestFailed {
             backend: address.to_string(),
             error: e.to_string(),
         };
-        error!(error = %err, "응답 처리 실패");
+        error!(error = %err, "CONNECT 백엔드 연결 실패");
         err
     })?;
-    let bytes = collected.to_bytes();
-    info!(bytes_size = bytes.len(), "응답 바디 수집 완료");
+    info!(backend = %address, target = %req.uri(), "CONNECT 터널 생성");
 
-    log.duration_ms = start_time.elapsed().as_millis() as u64;
-    log_request(&log);
+    // 터널이 닫힐 때까지 진행 중인 연결로 기록
+    let connection = backend.track_connection(address);
+    tokio::spawn(async move {
+        let _connection = connection;
+        match hyper::upgrade::on(req).await {
+            Ok(upgraded) => {
+                let mut client_stream = TokioIo::new(upgraded);
+                match tokio::io::copy_bidirectional(&mut client_stream, &mut backend_stream).await {
+                    Ok((sent, received)) => {
+                        debug!(backend = %address, sent, received, "CONNECT 터널 종료");
+                    }
+                    Err(e) => debug!(backend = %address, error = %e, "CONNECT 터널 오류로 종료"),
+                }
+            }
+            Err(e) => error!(backend = %address, error = %e, "CONNECT 연결 업그레이드 실패"),
+        }
+    });
 
-    Ok(hyper::Response::from_parts(parts, http_body_util::Full::new(bytes)))
+    Ok(Response::new(Full::new(Bytes::new())))
 }
 
 // 에러 응답 생성 헬퍼 함수
@@ -104,6 +377,8 @@ pub fn error_response(error: &ProxyError) -> Response<Full<Bytes>> {
         ProxyError::BackendRequestFailed { .. } | 
         ProxyError::ResponseError { .. } => 
             (StatusCode::BAD_GATEWAY, error.to_string()),
+        ProxyError::RedirectTargetNotFound { .. } =>
+            (StatusCode::NOT_FOUND, error.to_string()),
     };
 
     Response::builder()
@@ -131,6 +406,10 @@ pub enum ProxyError {
     RequestBuildError {
         reason: String,
     },
+    /// 내부 리다이렉트 대상 파일 없음
+    RedirectTargetNotFound {
+        path: String,
+    },
 }
 
 impl std::fmt::Display for ProxyError {
@@ -142,19 +421,117 @@ impl std::fmt::Display for ProxyError {
                 write!(f, "백엔드 {} 응답 처리 실패: {}", backend, error),
             ProxyError::RequestBuildError { reason } => 
                 write!(f, "요청 빌드 실패: {}", reason),
+            ProxyError::RedirectTargetNotFound { path } =>
+                write!(f, "내부 리다이렉트 대상을 찾을 수 없음: {}", path),
         }
     }
 }
 
 impl std::error::Error for ProxyError {}
 
+// 백엔드에 설정된 컨테이너 환경 변수를 `X-App-*` 헤더로 추가
+// 헤더로 쓸 수 없는 이름이나 값은 건너뜁니다.
+pub fn pure_apply_env_headers(headers: &mut hyper::HeaderMap, backend: &BackendService) {
+    for (name, value) in backend.upstream_env_headers() {
+        match (
+            hyper::header::HeaderName::from_bytes(name.as_bytes()),
+            hyper::header::HeaderValue::from_str(value),
+        ) {
+            (Ok(name), Ok(value)) => {
+                headers.insert(name, value);
+            }
+            _ => debug!(header = %name, "헤더로 변환할 수 없는 환경 변수 무시"),
+        }
+    }
+}
+
+// keep-alive가 비활성화된 백엔드에는 요청마다 연결을 닫도록 요청
+pub fn pure_apply_keepalive(headers: &mut hyper::HeaderMap, keepalive: &KeepaliveConfig) {
+    if !keepalive.enabled {
+        headers.insert(hyper::header::CONNECTION, hyper::header::HeaderValue::from_static("close"));
+    }
+}
+
+// 클라이언트 IP를 `X-Forwarded-For`에 기록
+// 신뢰하는 프록시 대역에서 온 요청만 기존 체인 뒤에 덧붙이고, 그 외에는 클라이언트가 보낸 값을 대체합니다.
+pub fn pure_apply_forwarded_for(
+    headers: &mut hyper::HeaderMap,
+    incoming: &hyper::HeaderMap,
+    client_ip: std::net::IpAddr,
+    backend: &BackendService,
+) {
+    let trusted = backend.is_trusted_proxy(&client_ip);
+    let client_ip = client_ip.to_canonical().to_string();
+    let mut chain: Vec<&str> = if trusted {
+        incoming.get_all(X_FORWARDED_FOR).iter()
+            .filter_map(|value| value.to_str().ok())
+            .collect()
+    } else {
+        Vec::new()
+    };
+    chain.push(&client_ip);
+    if let Ok(value) = hyper::header::HeaderValue::from_str(&chain.join(", ")) {
+        headers.insert(X_FORWARDED_FOR, value);
+    }
+}
+
+// 연결 정보를 RFC 7239 `Forwarded` 헤더에 기록 (`for="클라이언트 IP";proto=https;by="프록시 주소"`)
+// `X-Forwarded-For`와 마찬가지로 신뢰하는 프록시 대역에서 온 요청만 기존 값 뒤에 덧붙입니다.
+pub fn pure_apply_rfc7239_forwarded(
+    headers: &mut hyper::HeaderMap,
+    incoming: &hyper::HeaderMap,
+    connection: &ConnectionInfo,
+    backend: &BackendService,
+) {
+    let client_ip = connection.remote_addr.ip();
+    // IPv6 주소는 대괄호로 감싸고, 콜론이 들어가는 값은 따옴표로 감싸야 함
+    let for_node = match client_ip.to_canonical() {
+        std::net::IpAddr::V4(ip) => ip.to_string(),
+        std::net::IpAddr::V6(ip) => format!("[{}]", ip),
+    };
+    let mut element = format!("for=\"{}\";proto={}", for_node, connection.scheme());
+    if let Some(local_addr) = connection.local_addr {
+        element.push_str(&format!(";by=\"{}\"", local_addr));
+    }
+
+    let mut elements: Vec<&str> = if backend.is_trusted_proxy(&client_ip) {
+        incoming.get_all(FORWARDED).iter()
+            .filter_map(|value| value.to_str().ok())
+            .collect()
+    } else {
+        Vec::new()
+    };
+    elements.push(&element);
+    if let Ok(value) = hyper::header::HeaderValue::from_str(&elements.join(", ")) {
+        headers.insert(FORWARDED, value);
+    }
+}
+
+// 스티키 세션이 설정된 백엔드면 선택된 주소를 `Set-Cookie`로 기록
+pub fn pure_apply_sticky_cookie(headers: &mut hyper::HeaderMap, backend: &BackendService, address: std::net::SocketAddr) {
+    let Some(sticky_cookie) = &backend.sticky_cookie else {
+        return;
+    };
+    if let Ok(value) = hyper::header::HeaderValue::from_str(&sticky_cookie.set_cookie_value(address)) {
+        headers.append(hyper::header::SET_COOKIE, value);
+    }
+}
+
+// 업스트림 응답 시간을 `45ms` 형식으로 응답 헤더에 기록
+pub fn pure_apply_upstream_response_time(headers: &mut hyper::HeaderMap, elapsed: std::time::Duration) {
+    let value = format!("{}ms", elapsed.as_millis());
+    if let Ok(value) = hyper::header::HeaderValue::from_str(&value) {
+        headers.insert(X_UPSTREAM_RESPONSE_TIME, value);
+    }
+}
+
 // 순수 함수로 분리한 요청 빌드 함수
 pub fn pure_build_proxied_request(
     address: std::net::SocketAddr,
     method: hyper::Method,
     path: &str,
-    body: hyper::body::Incoming,
-) -> Result<hyper::Request<hyper::body::Incoming>, String> {
+    body: UpstreamBody,
+) -> Result<hyper::Request<UpstreamBody>, String> {
     let uri: hyper::Uri = format!("http://{}{}", address, path)
         .parse()
         .map_err(|e| format!("URI 파싱 실패: {}", e))?;
@@ -0,0 +1,108 @@
+//! 업스트림 연결 추상화
+//!
+//! 프록시가 백엔드에 연결하는 방법을 `UpstreamConnector`로 분리합니다.
+//! 테스트에서는 실제 네트워크 대신 미리 준비한 응답을 돌려주는 커넥터를 주입할 수 있습니다.
+
+use std::future::Future;
+use std::io;
+use std::net::SocketAddr;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use async_trait::async_trait;
+use hyper::Uri;
+use hyper_util::client::legacy::connect::{Connected, Connection};
+use hyper_util::rt::TokioIo;
+use tokio::io::{AsyncRead, AsyncWrite};
+use tokio::net::TcpStream;
+
+/// 읽기와 쓰기가 모두 가능한 업스트림 연결 스트림
+pub trait AsyncReadWrite: AsyncRead + AsyncWrite + Send + Unpin {}
+
+impl<T: AsyncRead + AsyncWrite + Send + Unpin> AsyncReadWrite for T {}
+
+/// 백엔드 주소로 연결을 여는 커넥터
+///
+/// 일반 프록시 요청(연결 풀 포함), 내부 리다이렉트, CONNECT 터널이 모두 이 커넥터를 사용합니다.
+#[async_trait]
+pub trait UpstreamConnector: Send + Sync {
+    async fn connect(&self, addr: SocketAddr) -> io::Result<Box<dyn AsyncReadWrite>>;
+}
+
+/// TCP로 백엔드에 연결하는 기본 커넥터
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TcpConnector;
+
+#[async_trait]
+impl UpstreamConnector for TcpConnector {
+    async fn connect(&self, addr: SocketAddr) -> io::Result<Box<dyn AsyncReadWrite>> {
+        Ok(Box::new(TcpStream::connect(addr).await?))
+    }
+}
+
+/// `UpstreamConnector`를 hyper 클라이언트의 커넥터로 사용하기 위한 어댑터
+#[derive(Clone)]
+pub(crate) struct ConnectorService(pub(crate) Arc<dyn UpstreamConnector>);
+
+impl tower_service::Service<Uri> for ConnectorService {
+    type Response = UpstreamStream;
+    type Error = io::Error;
+    type Future = Pin<Box<dyn Future<Output = io::Result<UpstreamStream>> + Send>>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, uri: Uri) -> Self::Future {
+        let connector = self.0.clone();
+        Box::pin(async move {
+            let addr = upstream_addr(&uri)?;
+            let stream = connector.connect(addr).await?;
+            Ok(UpstreamStream(TokioIo::new(stream)))
+        })
+    }
+}
+
+/// 업스트림 요청 URI의 `IP:포트` 부분을 소켓 주소로 변환합니다.
+/// 프록시가 만드는 요청 URI는 항상 백엔드 소켓 주소를 authority로 사용합니다.
+fn upstream_addr(uri: &Uri) -> io::Result<SocketAddr> {
+    uri.authority()
+        .and_then(|authority| authority.as_str().parse().ok())
+        .ok_or_else(|| io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("업스트림 소켓 주소가 아닌 URI: {}", uri),
+        ))
+}
+
+/// hyper 클라이언트가 사용하는 업스트림 연결
+pub(crate) struct UpstreamStream(TokioIo<Box<dyn AsyncReadWrite>>);
+
+impl hyper::rt::Read for UpstreamStream {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: hyper::rt::ReadBufCursor<'_>,
+    ) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.get_mut().0).poll_read(cx, buf)
+    }
+}
+
+impl hyper::rt::Write for UpstreamStream {
+    fn poll_write(self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &[u8]) -> Poll<io::Result<usize>> {
+        Pin::new(&mut self.get_mut().0).poll_write(cx, buf)
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.get_mut().0).poll_flush(cx)
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.get_mut().0).poll_shutdown(cx)
+    }
+}
+
+impl Connection for UpstreamStream {
+    fn connected(&self) -> Connected {
+        Connected::new()
+    }
+}
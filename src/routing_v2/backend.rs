@@ -1,6 +1,13 @@
-use std::net::SocketAddr;
+use std::collections::HashMap;
+use std::future::Future;
+use std::net::{IpAddr, SocketAddr};
 use std::sync::atomic::AtomicUsize;
 use std::sync::atomic::Ordering;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::sync::Notify;
+use base64::{Engine as _, engine::general_purpose::URL_SAFE_NO_PAD};
+use ipnet::IpNet;
 
 use crate::routing_v2::error::BackendError;
 
@@ -19,6 +26,174 @@ pub struct BackendService {
     /// 라우터 이름입니다.
     /// 동일한 라우터 이름을 가진 서비스들이 하나의 로드밸런싱 그룹을 형성합니다.
     pub router_name: Option<String>,
+    /// HTTPS 패스스루 여부입니다.
+    /// 활성화된 경우 CONNECT 요청을 복호화 없이 백엔드로 TCP 터널링합니다.
+    pub passthrough: bool,
+    /// 라우트 우선순위입니다.
+    /// 같은 (호스트, 경로)에 여러 제공자가 라우트를 등록하면 높은 쪽이 우선합니다.
+    pub priority: u32,
+    /// X-Accel-Redirect 처리 설정입니다.
+    /// 활성화된 경우 백엔드 응답의 `X-Accel-Redirect` 경로로 다시 조회한 결과를 응답합니다.
+    pub x_accel_redirect: Option<XAccelRedirect>,
+    /// 주소별 진행 중인 요청 수와 상태입니다.
+    /// 복제된 서비스끼리 공유되며, 백엔드 교체 시 드레인 완료를 판단하는 데 사용됩니다.
+    pub connections: Arc<ConnectionTracker>,
+    /// 업스트림 요청에 `X-App-{이름}` 헤더로 추가할 컨테이너 환경 변수입니다.
+    pub env_headers: HashMap<String, String>,
+    /// 업스트림 연결 keep-alive 설정입니다.
+    pub keepalive: KeepaliveConfig,
+    /// 주소별 메타데이터 라벨입니다. (예: 데이터센터, 가용 영역)
+    /// 어피니티 기반 라우팅에서 특정 라벨을 가진 주소를 찾는 데 사용됩니다.
+    pub address_labels: HashMap<SocketAddr, HashMap<String, String>>,
+    /// `X-Forwarded-For` 체인을 신뢰할 클라이언트 대역입니다.
+    /// 이 대역 밖의 클라이언트가 보낸 `X-Forwarded-For`는 버리고 클라이언트 IP로 대체합니다.
+    pub trusted_proxy_cidrs: Vec<IpNet>,
+    /// 업스트림 요청에 RFC 7239 `Forwarded` 헤더를 추가할지 여부입니다.
+    /// `X-Forwarded-*` 헤더는 이 설정과 관계없이 항상 추가됩니다.
+    pub use_rfc7239_forwarded: bool,
+    /// 스티키 세션 쿠키 설정입니다.
+    /// 설정된 경우 선택된 백엔드 주소를 쿠키에 기록하고, 이후 요청은 로드밸런서 대신 같은 주소로 보냅니다.
+    pub sticky_cookie: Option<StickyCookieConfig>,
+}
+
+/// 업스트림 HTTP keep-alive 설정입니다.
+///
+/// 같은 설정을 가진 백엔드끼리 연결 풀을 공유합니다.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct KeepaliveConfig {
+    /// 비활성화하면 요청마다 `Connection: close`를 보내고 유휴 연결을 보관하지 않습니다.
+    pub enabled: bool,
+    /// 유휴 연결을 풀에 보관하는 시간
+    pub idle_timeout: Duration,
+    /// 업스트림 호스트별 최대 유휴 연결 수
+    pub max_idle_connections: usize,
+}
+
+impl Default for KeepaliveConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            idle_timeout: Duration::from_secs(90),
+            max_idle_connections: usize::MAX,
+        }
+    }
+}
+
+/// nginx 스타일 내부 리다이렉트(`X-Accel-Redirect`) 설정입니다.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct XAccelRedirect {
+    /// 로컬 파일 기준 디렉터리입니다.
+    /// 지정된 경우 리다이렉트 경로의 파일을 제공하고, 없으면 같은 백엔드에 경로를 다시 요청합니다.
+    pub base_path: Option<String>,
+}
+
+/// 쿠키의 `SameSite` 속성입니다.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub enum SameSite {
+    Strict,
+    #[default]
+    Lax,
+    None,
+}
+
+impl SameSite {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SameSite::Strict => "Strict",
+            SameSite::Lax => "Lax",
+            SameSite::None => "None",
+        }
+    }
+}
+
+impl std::str::FromStr for SameSite {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "strict" => Ok(SameSite::Strict),
+            "lax" => Ok(SameSite::Lax),
+            "none" => Ok(SameSite::None),
+            _ => Err(format!("잘못된 SameSite 값: {}", s)),
+        }
+    }
+}
+
+/// 스티키 세션 쿠키 설정입니다.
+///
+/// 쿠키 값은 백엔드 주소(`ip:port`)를 URL-safe base64로 인코딩한 문자열입니다.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct StickyCookieConfig {
+    /// 쿠키 이름
+    pub cookie_name: String,
+    /// `HttpOnly` 속성 여부
+    #[serde(default = "default_http_only")]
+    pub http_only: bool,
+    /// `Secure` 속성 여부
+    #[serde(default)]
+    pub secure: bool,
+    /// `SameSite` 속성
+    #[serde(default)]
+    pub same_site: SameSite,
+}
+
+fn default_http_only() -> bool {
+    true
+}
+
+impl StickyCookieConfig {
+    /// 기본 속성(`HttpOnly`, `SameSite=Lax`)으로 설정을 생성합니다.
+    pub fn new(cookie_name: impl Into<String>) -> Self {
+        Self {
+            cookie_name: cookie_name.into(),
+            http_only: default_http_only(),
+            secure: false,
+            same_site: SameSite::default(),
+        }
+    }
+
+    /// 쿠키 이름이 RFC 6265 토큰인지 확인합니다. 공백, 제어 문자, `;`, `=` 등 구분자는 허용하지 않습니다.
+    pub fn is_valid_cookie_name(name: &str) -> bool {
+        !name.is_empty()
+            && name.bytes().all(|b| b.is_ascii_graphic() && !b"()<>@,;:\\\"/[]?={}".contains(&b))
+    }
+
+    /// 백엔드 주소를 쿠키 값으로 인코딩합니다.
+    pub fn encode_address(addr: SocketAddr) -> String {
+        URL_SAFE_NO_PAD.encode(addr.to_string())
+    }
+
+    /// 쿠키 값을 백엔드 주소로 디코딩합니다. 형식이 잘못되면 `None`입니다.
+    pub fn decode_address(value: &str) -> Option<SocketAddr> {
+        let decoded = URL_SAFE_NO_PAD.decode(value).ok()?;
+        std::str::from_utf8(&decoded).ok()?.parse().ok()
+    }
+
+    /// `Cookie` 헤더들에서 이 설정의 쿠키 값을 찾습니다.
+    pub fn find_cookie<'a>(&self, headers: &'a hyper::HeaderMap) -> Option<&'a str> {
+        headers.get_all(hyper::header::COOKIE)
+            .iter()
+            .filter_map(|value| value.to_str().ok())
+            .flat_map(|value| value.split(';'))
+            .find_map(|pair| {
+                let (name, value) = pair.trim().split_once('=')?;
+                (name == self.cookie_name).then_some(value)
+            })
+    }
+
+    /// 백엔드 주소를 기록하는 `Set-Cookie` 헤더 값을 생성합니다.
+    pub fn set_cookie_value(&self, addr: SocketAddr) -> String {
+        let mut value = format!("{}={}; Path=/", self.cookie_name, Self::encode_address(addr));
+        if self.http_only {
+            value.push_str("; HttpOnly");
+        }
+        if self.secure {
+            value.push_str("; Secure");
+        }
+        value.push_str("; SameSite=");
+        value.push_str(self.same_site.as_str());
+        value
+    }
 }
 
 impl Clone for BackendService {
@@ -28,11 +203,57 @@ impl Clone for BackendService {
             load_balancer: self.load_balancer.clone(),
             middlewares: self.middlewares.clone(),
             router_name: self.router_name.clone(),
+            passthrough: self.passthrough,
+            priority: self.priority,
+            x_accel_redirect: self.x_accel_redirect.clone(),
+            connections: self.connections.clone(),
+            env_headers: self.env_headers.clone(),
+            keepalive: self.keepalive.clone(),
+            address_labels: self.address_labels.clone(),
+            trusted_proxy_cidrs: self.trusted_proxy_cidrs.clone(),
+            use_rfc7239_forwarded: self.use_rfc7239_forwarded,
+            sticky_cookie: self.sticky_cookie.clone(),
         }
     }
 }
 
+/// 설정 내보내기용 백엔드 서비스 스냅샷입니다.
+///
+/// 요청 카운터와 연결 추적 같은 런타임 상태는 제외하고, 주소와 가중치, 로드밸런싱 전략, 라우터 이름만 담습니다.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct BackendServiceSnapshot {
+    /// 백엔드 주소와 가중치 목록
+    pub addresses: Vec<(SocketAddr, usize)>,
+    /// 로드밸런싱 전략 이름 (로드밸런서가 비활성화되면 없음)
+    pub strategy: Option<String>,
+    /// 라우터 이름
+    pub router_name: Option<String>,
+}
+
+/// 이벤트 로그 등 외부 출력용 직렬화
+///
+/// 로드밸런서 내부 상태(카운터)는 제외하고 백엔드 주소와 가중치만 포함합니다.
+impl serde::Serialize for BackendService {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeStruct;
+
+        let mut state = serializer.serialize_struct("BackendService", 6)?;
+        state.serialize_field("address", &self.address)?;
+        state.serialize_field("addresses", &self.load_balancer.as_ref().map(|lb| &lb.addresses))?;
+        state.serialize_field("middlewares", &self.middlewares)?;
+        state.serialize_field("router_name", &self.router_name)?;
+        state.serialize_field("passthrough", &self.passthrough)?;
+        state.serialize_field("priority", &self.priority)?;
+        state.end()
+    }
+}
+
 impl BackendService {
+    /// Docker 라벨로 등록된 라우트의 기본 우선순위
+    pub const DOCKER_PRIORITY: u32 = 100;
+    /// TOML/환경 변수 등 정적 설정으로 등록된 라우트의 기본 우선순위
+    pub const ENV_PRIORITY: u32 = 0;
+
     /// 새로운 백엔드 서비스를 생성합니다.
     /// 기본적으로 로드밸런서와 미들웨어는 비활성화됩니다.
     pub fn new(addr: SocketAddr) -> Self {
@@ -41,6 +262,16 @@ impl BackendService {
             load_balancer: None,
             middlewares: None,
             router_name: None,
+            passthrough: false,
+            priority: Self::ENV_PRIORITY,
+            x_accel_redirect: None,
+            connections: Arc::default(),
+            env_headers: HashMap::new(),
+            keepalive: KeepaliveConfig::default(),
+            address_labels: HashMap::new(),
+            trusted_proxy_cidrs: Vec::new(),
+            use_rfc7239_forwarded: false,
+            sticky_cookie: None,
         }
     }
 
@@ -51,6 +282,16 @@ impl BackendService {
             load_balancer: None,
             middlewares: Some(vec![middleware]),
             router_name: None,
+            passthrough: false,
+            priority: Self::ENV_PRIORITY,
+            x_accel_redirect: None,
+            connections: Arc::default(),
+            env_headers: HashMap::new(),
+            keepalive: KeepaliveConfig::default(),
+            address_labels: HashMap::new(),
+            trusted_proxy_cidrs: Vec::new(),
+            use_rfc7239_forwarded: false,
+            sticky_cookie: None,
         }
     }
 
@@ -62,9 +303,117 @@ impl BackendService {
             load_balancer: None,
             middlewares: None,
             router_name,
+            passthrough: false,
+            priority: Self::ENV_PRIORITY,
+            x_accel_redirect: None,
+            connections: Arc::default(),
+            env_headers: HashMap::new(),
+            keepalive: KeepaliveConfig::default(),
+            address_labels: HashMap::new(),
+            trusted_proxy_cidrs: Vec::new(),
+            use_rfc7239_forwarded: false,
+            sticky_cookie: None,
         }
     }
 
+    /// HTTPS 패스스루(CONNECT 터널링) 여부를 설정합니다.
+    pub fn set_passthrough(&mut self, passthrough: bool) {
+        self.passthrough = passthrough;
+    }
+
+    /// X-Accel-Redirect 처리를 활성화합니다.
+    /// `base_path`가 있으면 로컬 파일을, 없으면 같은 백엔드의 리다이렉트 경로를 응답합니다.
+    pub fn enable_x_accel_redirect(&mut self, base_path: Option<String>) {
+        self.x_accel_redirect = Some(XAccelRedirect { base_path });
+    }
+
+    /// 라우트 우선순위를 설정합니다.
+    pub fn set_priority(&mut self, priority: u32) {
+        self.priority = priority;
+    }
+
+    /// 업스트림 keep-alive 설정을 변경합니다.
+    pub fn set_keepalive(&mut self, keepalive: KeepaliveConfig) {
+        self.keepalive = keepalive;
+    }
+
+    /// 업스트림 헤더로 전달할 환경 변수를 설정합니다.
+    pub fn set_env_headers(&mut self, env_headers: HashMap<String, String>) {
+        self.env_headers = env_headers;
+    }
+
+    /// `X-Forwarded-For` 체인을 신뢰할 클라이언트 대역을 설정합니다.
+    pub fn set_trusted_proxy_cidrs(&mut self, cidrs: Vec<IpNet>) {
+        self.trusted_proxy_cidrs = cidrs;
+    }
+
+    /// RFC 7239 `Forwarded` 헤더 추가 여부를 설정합니다.
+    pub fn set_rfc7239_forwarded(&mut self, enabled: bool) {
+        self.use_rfc7239_forwarded = enabled;
+    }
+
+    /// 스티키 세션 쿠키 설정을 변경합니다. `None`이면 스티키 세션을 사용하지 않습니다.
+    pub fn set_sticky_cookie(&mut self, sticky_cookie: Option<StickyCookieConfig>) {
+        self.sticky_cookie = sticky_cookie;
+    }
+
+    /// 요청 쿠키에 기록된 백엔드 주소를 반환합니다.
+    /// 주소가 이 서비스에 없거나 드레인 중이면 `None`을 반환해 일반 선택으로 넘깁니다.
+    pub fn sticky_address(&self, headers: &hyper::HeaderMap) -> Option<SocketAddr> {
+        let sticky_cookie = self.sticky_cookie.as_ref()?;
+        let addr = StickyCookieConfig::decode_address(sticky_cookie.find_cookie(headers)?)?;
+        let available = self.addresses().contains(&addr)
+            && self.connections.state(addr) == BackendState::Healthy;
+        available.then_some(addr)
+    }
+
+    /// 클라이언트가 신뢰하는 프록시 대역에 속하는지 확인합니다.
+    /// IPv4-mapped IPv6 주소는 IPv4 주소로 비교합니다.
+    pub fn is_trusted_proxy(&self, client_ip: &IpAddr) -> bool {
+        let client_ip = client_ip.to_canonical();
+        self.trusted_proxy_cidrs.iter().any(|cidr| cidr.contains(&client_ip))
+    }
+
+    /// 신뢰하는 프록시 대역을 고려해 실제 클라이언트 IP를 결정합니다.
+    ///
+    /// 연결한 주소가 신뢰 대역이 아니면 헤더를 무시하고 연결 주소를 사용합니다.
+    /// 신뢰 대역이면 `X-Forwarded-For` 체인을 오른쪽부터 거슬러 올라가 처음 만나는
+    /// 신뢰하지 않는 주소를 클라이언트로 보고, 체인이 없으면 `X-Real-IP`를 사용합니다.
+    pub fn client_ip(&self, remote_ip: IpAddr, headers: &hyper::HeaderMap) -> IpAddr {
+        let remote_ip = remote_ip.to_canonical();
+        if !self.is_trusted_proxy(&remote_ip) {
+            return remote_ip;
+        }
+
+        let chain: Vec<IpAddr> = headers.get_all("x-forwarded-for")
+            .iter()
+            .filter_map(|value| value.to_str().ok())
+            .flat_map(|value| value.split(','))
+            .filter_map(|ip| ip.trim().parse::<IpAddr>().ok())
+            .map(|ip| ip.to_canonical())
+            .collect();
+        if chain.is_empty() {
+            return headers.get("x-real-ip")
+                .and_then(|value| value.to_str().ok())
+                .and_then(|ip| ip.trim().parse::<IpAddr>().ok())
+                .map_or(remote_ip, |ip| ip.to_canonical());
+        }
+
+        chain.iter()
+            .rev()
+            .find(|ip| !self.is_trusted_proxy(ip))
+            .or(chain.first())
+            .copied()
+            .unwrap_or(remote_ip)
+    }
+
+    /// 업스트림 요청에 추가할 `(헤더 이름, 값)` 목록을 반환합니다.
+    /// `APP_VERSION`은 `X-App-App-Version`처럼 변환됩니다.
+    pub fn upstream_env_headers(&self) -> impl Iterator<Item = (String, &str)> + '_ {
+        self.env_headers.iter()
+            .map(|(name, value)| (env_header_name(name), value.as_str()))
+    }
+
     pub fn set_middlewares(&mut self, middlewares: Vec<String>) {
         self.middlewares = Some(middlewares);
     }
@@ -89,12 +438,189 @@ impl BackendService {
         }
     }
 
+    /// 현재 등록된 모든 백엔드 주소를 등록 순서대로 반환합니다.
+    /// 드레인 중인 주소도 포함하는 스냅샷입니다.
+    pub fn addresses(&self) -> Vec<SocketAddr> {
+        match &self.load_balancer {
+            Some(lb) => lb.addresses.iter().map(|(addr, _)| *addr).collect(),
+            None => vec![self.address],
+        }
+    }
+
+    /// 설정 내보내기용 스냅샷을 생성합니다.
+    /// 로드밸런서가 비활성화된 경우 기본 주소를 가중치 1로 포함합니다.
+    pub fn snapshot(&self) -> BackendServiceSnapshot {
+        BackendServiceSnapshot {
+            addresses: match &self.load_balancer {
+                Some(lb) => lb.addresses.clone(),
+                None => vec![(self.address, 1)],
+            },
+            strategy: self.load_balancer.as_ref().map(|lb| lb.strategy.name().to_string()),
+            router_name: self.router_name.clone(),
+        }
+    }
+
     /// 로드밸런서를 활성화합니다.
     /// 지정된 전략(라운드로빈/가중치)으로 요청이 분산됩니다.
     pub fn enable_load_balancer(&mut self, strategy: LoadBalancerStrategy) {
         self.load_balancer = Some(LoadBalancer::new(self.address, strategy));
     }
 
+    /// 주소와 가중치를 유지한 채 가중치 기반 전략으로 전환합니다.
+    /// 로드밸런서가 비활성화된 경우 기본 주소 하나(가중치 1)로 활성화합니다.
+    pub fn use_weighted_strategy(&mut self) {
+        match &mut self.load_balancer {
+            Some(lb) => lb.use_weighted(),
+            None => self.enable_load_balancer(LoadBalancerStrategy::Weighted {
+                current_index: AtomicUsize::new(0),
+                total_weight: 1,
+            }),
+        }
+    }
+
+    /// 가중치 기반 전략을 사용하는지 확인합니다.
+    pub fn is_weighted(&self) -> bool {
+        self.load_balancer.as_ref()
+            .is_some_and(|lb| matches!(lb.strategy, LoadBalancerStrategy::Weighted { .. }))
+    }
+
+    /// 주소의 가중치를 반환합니다.
+    /// 로드밸런서가 비활성화된 경우 기본 주소의 가중치는 1입니다.
+    pub fn address_weight(&self, addr: SocketAddr) -> Option<usize> {
+        match &self.load_balancer {
+            Some(lb) => lb.addresses.iter()
+                .find(|(existing, _)| *existing == addr)
+                .map(|(_, weight)| *weight),
+            None => (self.address == addr).then_some(1),
+        }
+    }
+
+    /// 같은 라우트의 이전 서비스에서 로드밸런서의 다른 백엔드 주소를 이어받습니다.
+    ///
+    /// 컨테이너 하나의 설정이 바뀌어도 같은 그룹의 다른 컨테이너가 빠지지 않도록 할 때 사용합니다.
+    /// 이 서비스의 기본 주소는 이 서비스의 가중치를 사용하고, 나머지 주소는 이전 가중치와
+    /// 메타데이터 라벨을 유지합니다. 이전 서비스에 로드밸런서가 없으면 아무것도 하지 않습니다.
+    pub fn inherit_addresses(&mut self, previous: &BackendService) {
+        let Some(mut lb) = previous.load_balancer.clone() else {
+            return;
+        };
+        let weight = self.address_weight(self.address).unwrap_or(1);
+        if lb.set_weight(self.address, weight).is_err() {
+            lb.add_address(self.address, weight);
+        }
+        if self.is_weighted() {
+            lb.use_weighted();
+        }
+        self.load_balancer = Some(lb);
+        for (addr, labels) in &previous.address_labels {
+            self.address_labels.entry(*addr).or_insert_with(|| labels.clone());
+        }
+        self.connections = previous.connections.clone();
+    }
+
+    /// 로드밸런서의 분배 순서를 처음으로 되돌립니다.
+    /// 테스트에서 이전 요청의 인덱스 상태가 남지 않도록 할 때 사용합니다.
+    pub fn reset_load_balancer_state(&self) {
+        if let Some(lb) = &self.load_balancer {
+            lb.reset_index();
+        }
+    }
+
+    /// 선택된 백엔드 주소로의 요청이 끝날 때까지 진행 중인 요청으로 기록합니다.
+    pub fn track_connection(&self, addr: SocketAddr) -> ConnectionGuard {
+        self.connections.acquire(addr)
+    }
+
+    /// 블루-그린 배포를 위해 백엔드 주소 목록을 한 번에 교체합니다.
+    ///
+    /// 새 주소는 즉시 `Healthy` 상태로 트래픽을 받고, 기존 주소는 `Draining`으로 표시됩니다.
+    /// 반환된 Future는 기존 주소의 진행 중인 요청이 모두 끝나면 완료되며,
+    /// `timeout` 안에 끝나지 않으면 `BackendError::DrainTimeout`을 반환합니다.
+    ///
+    /// 주소 교체는 호출 시점에 끝나므로, 라우팅 테이블 쓰기 잠금 안에서 호출한 뒤
+    /// 잠금을 해제하고 Future를 기다리면 됩니다.
+    pub fn drain_and_replace(
+        &mut self,
+        new_backends: Vec<(SocketAddr, u32)>,
+        timeout: Duration,
+    ) -> impl Future<Output = Result<(), BackendError>> + Send + 'static {
+        let old_addresses = self.addresses();
+        let replaced = self.replace_addresses(&new_backends);
+
+        let connections = self.connections.clone();
+        let draining: Vec<SocketAddr> = old_addresses.into_iter()
+            .filter(|addr| !new_backends.iter().any(|(new_addr, _)| new_addr == addr))
+            .collect();
+        if replaced.is_ok() {
+            for (addr, _) in &new_backends {
+                connections.set_state(*addr, BackendState::Healthy);
+            }
+            for addr in &draining {
+                connections.set_state(*addr, BackendState::Draining);
+            }
+        }
+
+        async move {
+            replaced?;
+            let result = connections.wait_idle(&draining, timeout).await;
+            connections.forget(&draining);
+            result
+        }
+    }
+
+    /// 주소 목록을 교체합니다. 여러 주소면 가중치 기반 로드밸런서를 사용합니다.
+    fn replace_addresses(&mut self, backends: &[(SocketAddr, u32)]) -> Result<(), BackendError> {
+        let (first, _) = backends.first().ok_or(BackendError::NoAddresses)?;
+        self.address = *first;
+        self.address_labels.retain(|addr, _| backends.iter().any(|(new_addr, _)| new_addr == addr));
+
+        if backends.len() == 1 && self.load_balancer.is_none() {
+            return Ok(());
+        }
+
+        let addresses: Vec<(SocketAddr, usize)> = backends.iter()
+            .map(|(addr, weight)| (*addr, (*weight as usize).max(1)))
+            .collect();
+        let strategy = LoadBalancerStrategy::Weighted {
+            current_index: AtomicUsize::new(0),
+            total_weight: addresses.iter().map(|(_, weight)| weight).sum(),
+        };
+        self.load_balancer = Some(LoadBalancer { addresses, strategy });
+        Ok(())
+    }
+
+    /// 두 서비스가 같은 백엔드 구성을 가리키는지 비교합니다.
+    /// 로드밸런서의 현재 인덱스 같은 런타임 상태는 비교하지 않습니다.
+    pub fn same_config(&self, other: &BackendService) -> bool {
+        let addresses = |service: &BackendService| match &service.load_balancer {
+            Some(lb) => lb.addresses.clone(),
+            None => vec![(service.address, 1)],
+        };
+
+        addresses(self) == addresses(other)
+            && self.middlewares == other.middlewares
+            && self.router_name == other.router_name
+            && self.passthrough == other.passthrough
+            && self.priority == other.priority
+            && self.x_accel_redirect == other.x_accel_redirect
+            && self.env_headers == other.env_headers
+            && self.keepalive == other.keepalive
+            && self.address_labels == other.address_labels
+            && self.trusted_proxy_cidrs == other.trusted_proxy_cidrs
+            && self.use_rfc7239_forwarded == other.use_rfc7239_forwarded
+            && self.sticky_cookie == other.sticky_cookie
+    }
+
+    /// 모든 백엔드 가중치를 같은 배율로 조정합니다.
+    /// 예를 들어 서비스 상태가 저하되면 `0.5`를 넘겨 가중치를 절반으로 줄입니다.
+    /// 로드밸런서가 비활성화된 경우 아무것도 하지 않습니다.
+    pub fn weight_factor(&mut self, factor: f64) -> &mut Self {
+        if let Some(lb) = &mut self.load_balancer {
+            lb.scale_weights(factor);
+        }
+        self
+    }
+
     /// 로드밸런서에 새로운 백엔드 주소를 추가합니다.
     /// 로드밸런서가 비활성화된 경우 에러를 반환합니다.
     pub fn add_address(&mut self, addr: SocketAddr, weight: usize) -> Result<(), BackendError> {
@@ -106,6 +632,140 @@ impl BackendService {
             None => Err(BackendError::LoadBalancerNotEnabled),
         }
     }
+
+    /// 로드밸런서에 등록된 백엔드 주소의 가중치를 변경합니다.
+    /// 가중치는 가중치 기반 전략에서만 분배에 영향을 주며, 최소값은 1입니다.
+    pub fn set_weight(&mut self, addr: SocketAddr, weight: u32) -> Result<(), BackendError> {
+        match &mut self.load_balancer {
+            Some(lb) => lb.set_weight(addr, weight as usize),
+            None => Err(BackendError::LoadBalancerNotEnabled),
+        }
+    }
+
+    /// 메타데이터 라벨과 함께 로드밸런서에 새로운 백엔드 주소를 추가합니다.
+    /// 로드밸런서가 비활성화된 경우 에러를 반환합니다.
+    pub fn add_address_with_metadata(
+        &mut self,
+        addr: SocketAddr,
+        weight: u32,
+        labels: HashMap<String, String>,
+    ) -> Result<(), BackendError> {
+        self.add_address(addr, weight as usize)?;
+        self.address_labels.insert(addr, labels);
+        Ok(())
+    }
+
+    /// 주어진 라벨 값을 가진 첫 번째 백엔드 주소를 반환합니다.
+    /// 주소는 등록 순서대로 검사합니다.
+    pub fn find_address_by_label(&self, key: &str, value: &str) -> Option<SocketAddr> {
+        self.addresses().into_iter().find(|addr| {
+            self.address_labels.get(addr)
+                .and_then(|labels| labels.get(key))
+                .is_some_and(|label| label == value)
+        })
+    }
+}
+
+/// 백엔드 주소의 트래픽 수신 상태입니다.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BackendState {
+    /// 새 요청을 받는 상태
+    Healthy,
+    /// 새 요청을 받지 않고 진행 중인 요청이 끝나기를 기다리는 상태
+    Draining,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct AddressEntry {
+    state: BackendState,
+    active: usize,
+}
+
+/// 백엔드 주소별 진행 중인 요청 수를 추적합니다.
+#[derive(Debug, Default)]
+pub struct ConnectionTracker {
+    entries: Mutex<HashMap<SocketAddr, AddressEntry>>,
+    released: Notify,
+}
+
+impl ConnectionTracker {
+    fn entries(&self) -> std::sync::MutexGuard<'_, HashMap<SocketAddr, AddressEntry>> {
+        self.entries.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    /// 주소로의 요청을 시작합니다. 반환된 가드가 해제되면 요청이 끝난 것으로 봅니다.
+    pub fn acquire(self: &Arc<Self>, addr: SocketAddr) -> ConnectionGuard {
+        self.entries()
+            .entry(addr)
+            .or_insert(AddressEntry { state: BackendState::Healthy, active: 0 })
+            .active += 1;
+        ConnectionGuard { tracker: self.clone(), addr }
+    }
+
+    /// 주소로 진행 중인 요청 수
+    pub fn active(&self, addr: SocketAddr) -> usize {
+        self.entries().get(&addr).map_or(0, |entry| entry.active)
+    }
+
+    /// 주소의 상태 (기록이 없으면 `Healthy`)
+    pub fn state(&self, addr: SocketAddr) -> BackendState {
+        self.entries().get(&addr).map_or(BackendState::Healthy, |entry| entry.state)
+    }
+
+    fn set_state(&self, addr: SocketAddr, state: BackendState) {
+        self.entries()
+            .entry(addr)
+            .or_insert(AddressEntry { state, active: 0 })
+            .state = state;
+    }
+
+    fn release(&self, addr: SocketAddr) {
+        if let Some(entry) = self.entries().get_mut(&addr) {
+            entry.active = entry.active.saturating_sub(1);
+        }
+        self.released.notify_waiters();
+    }
+
+    fn forget(&self, addrs: &[SocketAddr]) {
+        let mut entries = self.entries();
+        for addr in addrs {
+            if entries.get(addr).is_some_and(|entry| entry.active == 0) {
+                entries.remove(addr);
+            }
+        }
+    }
+
+    /// 주어진 주소들의 진행 중인 요청이 모두 끝날 때까지 기다립니다.
+    async fn wait_idle(&self, addrs: &[SocketAddr], timeout: Duration) -> Result<(), BackendError> {
+        let remaining = || addrs.iter().map(|addr| self.active(*addr)).sum::<usize>();
+        let wait = async {
+            loop {
+                // 확인 전에 알림을 등록해야 그 사이의 해제를 놓치지 않음
+                let released = self.released.notified();
+                if remaining() == 0 {
+                    return;
+                }
+                released.await;
+            }
+        };
+
+        tokio::time::timeout(timeout, wait)
+            .await
+            .map_err(|_| BackendError::DrainTimeout { remaining: remaining() })
+    }
+}
+
+/// 진행 중인 요청 표시. 해제되면 요청 수가 줄어듭니다.
+#[derive(Debug)]
+pub struct ConnectionGuard {
+    tracker: Arc<ConnectionTracker>,
+    addr: SocketAddr,
+}
+
+impl Drop for ConnectionGuard {
+    fn drop(&mut self) {
+        self.tracker.release(self.addr);
+    }
 }
 
 /// 로드밸런싱 전략을 정의하는 열거형입니다.
@@ -125,6 +785,16 @@ pub enum LoadBalancerStrategy {
     },
 }
 
+impl LoadBalancerStrategy {
+    /// 설정에서 사용하는 전략 이름 (`roundrobin`, `weighted`)
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::RoundRobin { .. } => "roundrobin",
+            Self::Weighted { .. } => "weighted",
+        }
+    }
+}
+
 impl Clone for LoadBalancerStrategy {
     fn clone(&self) -> Self {
         match self {
@@ -169,6 +839,51 @@ impl LoadBalancer {
         }
     }
 
+    /// 주소 하나의 가중치를 변경합니다.
+    /// 가중치 기반 전략을 사용하는 경우 전체 가중치도 함께 갱신됩니다.
+    pub fn set_weight(&mut self, addr: SocketAddr, weight: usize) -> Result<(), BackendError> {
+        let entry = self.addresses.iter_mut()
+            .find(|(existing, _)| *existing == addr)
+            .ok_or(BackendError::AddressNotFound { addr })?;
+        entry.1 = weight.max(1);
+        if let LoadBalancerStrategy::Weighted { total_weight, .. } = &mut self.strategy {
+            *total_weight = self.addresses.iter().map(|(_, weight)| weight).sum();
+        }
+        Ok(())
+    }
+
+    /// 주소와 가중치를 유지한 채 가중치 기반 전략으로 전환합니다.
+    /// 이미 가중치 기반 전략이면 아무것도 하지 않습니다.
+    pub fn use_weighted(&mut self) {
+        if let LoadBalancerStrategy::RoundRobin { current_index } = &self.strategy {
+            self.strategy = LoadBalancerStrategy::Weighted {
+                current_index: AtomicUsize::new(current_index.load(Ordering::Relaxed)),
+                total_weight: self.addresses.iter().map(|(_, weight)| weight).sum(),
+            };
+        }
+    }
+
+    /// 모든 주소의 가중치에 같은 배율을 곱합니다.
+    /// 결과는 반올림하며 최소 가중치는 1입니다.
+    pub fn scale_weights(&mut self, factor: f64) {
+        for (_, weight) in &mut self.addresses {
+            *weight = ((*weight as f64 * factor).round() as usize).max(1);
+        }
+        if let LoadBalancerStrategy::Weighted { total_weight, .. } = &mut self.strategy {
+            *total_weight = self.addresses.iter().map(|(_, weight)| weight).sum();
+        }
+    }
+
+    /// 분배 인덱스를 0으로 되돌려 다음 요청이 첫 번째 주소부터 선택되게 합니다.
+    pub fn reset_index(&self) {
+        match &self.strategy {
+            LoadBalancerStrategy::RoundRobin { current_index }
+            | LoadBalancerStrategy::Weighted { current_index, .. } => {
+                current_index.store(0, Ordering::Relaxed);
+            }
+        }
+    }
+
     /// 다음 요청을 처리할 백엔드 주소를 선택합니다.
     /// 설정된 전략(라운드로빈/가중치)에 따라 적절한 주소를 반환합니다.
     pub fn get_next_address(&self) -> Result<SocketAddr, BackendError> {
@@ -196,4 +911,20 @@ impl LoadBalancer {
             }
         }
     }
-} 
\ No newline at end of file
+} 
+/// 환경 변수 이름을 `X-App-` 헤더 이름으로 변환합니다.
+/// 밑줄로 나눈 각 단어의 첫 글자만 대문자로 만듭니다.
+fn env_header_name(env_name: &str) -> String {
+    let words: Vec<String> = env_name.split('_')
+        .filter(|word| !word.is_empty())
+        .map(|word| {
+            let lower = word.to_ascii_lowercase();
+            let mut chars = lower.chars();
+            match chars.next() {
+                Some(first) => first.to_ascii_uppercase().to_string() + chars.as_str(),
+                None => String::new(),
+            }
+        })
+        .collect();
+    format!("X-App-{}", words.join("-"))
+}
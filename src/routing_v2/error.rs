@@ -30,6 +30,11 @@ pub enum RoutingError {
         pattern: String,
         reason: String,
     },
+    /// 이미 등록된 (호스트, 경로) 라우트
+    DuplicateRoute {
+        host: String,
+        path: String,
+    },
 }
 
 impl fmt::Display for RoutingError {
@@ -47,6 +52,8 @@ impl fmt::Display for RoutingError {
                 write!(f, "{} 헤더 파싱 실패: {}", header_name, error),
             RoutingError::InvalidPathPattern { pattern, reason } => 
                 write!(f, "잘못된 경로 패턴: {} ({})", pattern, reason),
+            RoutingError::DuplicateRoute { host, path } => 
+                write!(f, "이미 등록된 라우트: {}{}", host, path),
         }
     }
 }
@@ -58,6 +65,10 @@ pub enum BackendError {
     NoAddresses,
     IndexOutOfBounds { index: usize, len: usize },
     LoadBalancerNotEnabled,
+    /// 드레인 대기 시간 초과 (남은 진행 중 요청 수)
+    DrainTimeout { remaining: usize },
+    /// 로드밸런서에 등록되지 않은 주소
+    AddressNotFound { addr: std::net::SocketAddr },
 }
 
 impl std::fmt::Display for BackendError {
@@ -68,6 +79,10 @@ impl std::fmt::Display for BackendError {
                 write!(f, "백엔드 주소 인덱스 범위 초과: index={}, len={}", index, len),
             BackendError::LoadBalancerNotEnabled =>
                 write!(f, "로드밸런서가 활성화되지 않음"),
+            BackendError::DrainTimeout { remaining } =>
+                write!(f, "백엔드 드레인 시간 초과: 진행 중인 요청 {}개", remaining),
+            BackendError::AddressNotFound { addr } =>
+                write!(f, "로드밸런서에 등록되지 않은 백엔드 주소: {}", addr),
         }
     }
 }
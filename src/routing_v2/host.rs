@@ -1,3 +1,4 @@
+use std::time::Duration;
 use crate::routing_v2::error::RoutingError;
 
 /// 호스트 정보를 담는 불변 데이터 구조입니다.
@@ -86,4 +87,30 @@ impl HostInfo {
             }),
         }
     }
-} 
\ No newline at end of file
+}
+
+/// 호스트 단위 설정입니다.
+///
+/// 라우팅 테이블에 호스트 이름별로 저장되며, 해당 호스트의 모든 경로 라우트에 적용됩니다.
+///
+/// # 필드
+///
+/// * `max_connections` - 동시 연결 수 제한 (None이면 제한 없음)
+/// * `timeout_override` - 백엔드 요청 타임아웃 재정의 (None이면 기본값 사용)
+/// * `access_log_enabled` - 접근 로그 기록 여부
+#[derive(Clone, Debug, PartialEq)]
+pub struct HostConfig {
+    pub max_connections: Option<u32>,
+    pub timeout_override: Option<Duration>,
+    pub access_log_enabled: bool,
+}
+
+impl Default for HostConfig {
+    fn default() -> Self {
+        Self {
+            max_connections: None,
+            timeout_override: None,
+            access_log_enabled: true,
+        }
+    }
+}
@@ -1,4 +1,6 @@
+use std::fmt;
 use std::hash::Hash;
+use std::sync::atomic::{AtomicU64, Ordering};
 use regex_lite as regex;
 use crate::routing_v2::error::RoutingError;
 
@@ -14,8 +16,15 @@ pub struct PathMatcher {
     pub kind: PathMatcherKind,
     pub pattern: String,
     regex: Option<regex::Regex>,
+    /// 생성 순서 (우선순위가 같은 정규식 매처는 먼저 만들어진 쪽이 우선)
+    ///
+    /// 동등성 비교와 해시에는 포함되지 않으므로, 라우팅 테이블은 처음 등록된 키의 순서를 유지합니다.
+    order: u64,
 }
 
+/// 다음 매처에 부여할 생성 순서
+static NEXT_ORDER: AtomicU64 = AtomicU64::new(0);
+
 impl PathMatcher {
     pub fn from_str(pattern: &str) -> Result<Self, RoutingError> {
         if pattern.starts_with("^") {
@@ -29,6 +38,7 @@ impl PathMatcher {
                 kind: PathMatcherKind::Regex,
                 pattern: pattern.to_string(),
                 regex: Some(re),
+                order: NEXT_ORDER.fetch_add(1, Ordering::Relaxed),
             })
         } else {
             // '*' 패턴을 제거하고 모두 Prefix로 처리
@@ -40,10 +50,32 @@ impl PathMatcher {
                 },
                 pattern: pattern.trim_end_matches('*').to_string(),
                 regex: None,
+                order: NEXT_ORDER.fetch_add(1, Ordering::Relaxed),
             })
         }
     }
 
+    /// 여러 매처가 같은 경로에 매칭될 때 사용하는 우선순위입니다.
+    ///
+    /// 정확/접두사 매칭은 패턴 길이를 사용하므로 `/api/v2/*`가 `/api/*`보다 우선합니다.
+    /// 접두사의 끝 `/`는 길이에서 제외하여, 같은 경로의 정확 매칭보다 높아지지 않게 합니다.
+    /// 정규식은 `1`로 모든 정확/접두사 매칭보다 낮고, 모든 경로에 매칭되는 `/`는 가장 낮은 `0`입니다.
+    pub fn priority(&self) -> u32 {
+        if self.pattern == "/" {
+            return 0;
+        }
+        match self.kind {
+            PathMatcherKind::Exact => self.pattern.len() as u32,
+            PathMatcherKind::Prefix => self.pattern.trim_end_matches('/').len() as u32,
+            PathMatcherKind::Regex => 1,
+        }
+    }
+
+    /// 매처 생성 순서
+    pub fn order(&self) -> u64 {
+        self.order
+    }
+
     pub fn matches(&self, path: &str) -> bool {
         if self.pattern == "/" {
             return true;
@@ -64,6 +96,30 @@ impl PathMatcher {
     }
 }
 
+/// 라우팅 테이블 출력용 표현
+///
+/// `PREFIX:/api/*`, `EXACT:/api`, `REGEX:/api/.*` 형식이며, 모든 경로에 매칭되는 `/`는 `ANY`로 표시합니다.
+impl fmt::Display for PathMatcher {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.pattern == "/" {
+            return write!(f, "ANY");
+        }
+
+        match self.kind {
+            PathMatcherKind::Exact => write!(f, "EXACT:{}", self.pattern),
+            PathMatcherKind::Prefix => write!(f, "PREFIX:{}*", self.pattern),
+            PathMatcherKind::Regex => write!(f, "REGEX:{}", self.pattern.trim_start_matches('^')),
+        }
+    }
+}
+
+/// 이벤트 로그 등 외부 출력용으로 `Display` 표현을 문자열로 직렬화합니다.
+impl serde::Serialize for PathMatcher {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
 impl PartialEq for PathMatcher {
     fn eq(&self, other: &Self) -> bool {
         self.kind == other.kind && self.pattern == other.pattern
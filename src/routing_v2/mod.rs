@@ -6,8 +6,8 @@ mod host;
 pub mod matcher;
 mod table;
 
-pub use backend::{BackendService, LoadBalancerStrategy};
+pub use backend::{BackendService, BackendServiceSnapshot, KeepaliveConfig, LoadBalancerStrategy, SameSite, StickyCookieConfig, XAccelRedirect};
 pub use error::{RoutingError, BackendError};
-pub use host::HostInfo;
+pub use host::{HostConfig, HostInfo};
 pub use matcher::PathMatcher;
-pub use table::RoutingTable; 
\ No newline at end of file
+pub use table::{RouteChange, RouteSnapshot, RoutingTable}; 
\ No newline at end of file
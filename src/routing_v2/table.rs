@@ -1,46 +1,262 @@
 use std::collections::HashMap;
+use std::net::SocketAddr;
 use hyper::header;
 use tracing::{debug, info, warn};
 use std::sync::atomic::AtomicUsize;
+use tokio::sync::broadcast;
 
 use crate::routing_v2::{
+    HostConfig,
     HostInfo,
     BackendService,
     PathMatcher,
     RoutingError,
 };
+use crate::routing_v2::backend::BackendServiceSnapshot;
 
 use super::backend::LoadBalancerStrategy;
+use super::matcher::PathMatcherKind;
+
+/// 라우트 변경 이벤트
+///
+/// `RoutingTable::subscribe`로 구독하며, `path`는 경로 매처 패턴입니다.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RouteChange {
+    Added { host: String, path: String },
+    Removed { host: String, path: String },
+    Updated { host: String, path: String },
+}
+
+impl RouteChange {
+    fn added(key: &(String, PathMatcher)) -> Self {
+        Self::Added { host: key.0.clone(), path: key.1.pattern.clone() }
+    }
+
+    fn removed(key: &(String, PathMatcher)) -> Self {
+        Self::Removed { host: key.0.clone(), path: key.1.pattern.clone() }
+    }
+
+    fn updated(key: &(String, PathMatcher)) -> Self {
+        Self::Updated { host: key.0.clone(), path: key.1.pattern.clone() }
+    }
+}
+
+/// 설정 내보내기용 라우트 스냅샷
+///
+/// `path`는 `PathMatcher`의 출력 형식(`PREFIX:/api/*`, `ANY` 등)입니다.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct RouteSnapshot {
+    pub host: String,
+    pub path: String,
+    pub backend: BackendServiceSnapshot,
+}
 
 /// 라우팅 테이블을 관리하는 구조체입니다.
 #[derive(Clone)]
 pub struct RoutingTable {
     // (host, PathMatcher)를 키로 사용
     pub routes: HashMap<(String, PathMatcher), BackendService>,
+    /// 호스트별 설정 (라우트 변경과 무관하게 유지)
+    host_configs: HashMap<String, HostConfig>,
+    /// 라우터 이름별 매칭 우선순위 (지정하지 않은 라우터는 경로 구체성 사용)
+    router_priorities: HashMap<String, u32>,
+    /// 라우트 변경 이벤트 송신자 (복제한 테이블과 공유)
+    changes: broadcast::Sender<RouteChange>,
+    /// 등록되지 않은 호스트 요청을 받을 기본 백엔드와 모든 경로 매처
+    default_backend: Option<(BackendService, PathMatcher)>,
+}
+
+/// 라우트 내용 대신 개수만 표시합니다.
+impl std::fmt::Debug for RoutingTable {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RoutingTable")
+            .field("routes", &format_args!("{} entries", self.routes.len()))
+            .field("host_configs", &format_args!("{} entries", self.host_configs.len()))
+            .field("router_priorities", &format_args!("{} entries", self.router_priorities.len()))
+            .field("default_backend", &self.default_backend.as_ref().map(|(service, _)| service.address))
+            .finish()
+    }
 }
 
 impl RoutingTable {
+    /// 구독자가 따라잡지 못해도 보관하는 최대 변경 이벤트 수
+    const CHANGE_CAPACITY: usize = 256;
+
     /// 새로운 라우팅 테이블을 생성합니다.
     pub fn new() -> Self {
         RoutingTable {
             routes: HashMap::new(),
+            host_configs: HashMap::new(),
+            router_priorities: HashMap::new(),
+            changes: broadcast::channel(Self::CHANGE_CAPACITY).0,
+            default_backend: None,
         }
     }
 
+    /// 라우트 변경 이벤트를 구독합니다.
+    ///
+    /// 구독 이후의 변경만 전달되며, 늦게 읽는 구독자는 `RecvError::Lagged`를 받을 수 있습니다.
+    pub fn subscribe(&self) -> broadcast::Receiver<RouteChange> {
+        self.changes.subscribe()
+    }
+
+    fn notify(&self, change: RouteChange) {
+        // 구독자가 없으면 전송 실패는 무시
+        let _ = self.changes.send(change);
+    }
+
     /// 라우팅 테이블에서 호스트를 제거합니다.
-    pub fn remove_route(&mut self, host: &str) {
-        self.routes.retain(|k, _| k.0 != host);
+    ///
+    /// 제거된 라우트 수를 반환합니다. 등록된 라우트가 없으면 0입니다.
+    pub fn remove_route(&mut self, host: &str) -> usize {
+        let mut removed = Vec::new();
+        self.routes.retain(|k, _| {
+            let keep = k.0 != host;
+            if !keep {
+                removed.push(RouteChange::removed(k));
+            }
+            keep
+        });
+        let count = removed.len();
+        for change in removed {
+            self.notify(change);
+        }
+        count
+    }
+
+    /// 특정 (호스트, 경로) 라우트만 제거합니다.
+    pub fn remove_path_route(&mut self, host: &str, path_matcher: &PathMatcher) -> Option<BackendService> {
+        let key = (host.to_string(), path_matcher.clone());
+        let removed = self.routes.remove(&key);
+        if removed.is_some() {
+            self.notify(RouteChange::removed(&key));
+        }
+        removed
     }
 
     /// 라우팅 테이블에 새로운 라우트를 추가합니다.
-    pub fn add_route(&mut self, host: String, service: BackendService, path_matcher: Option<PathMatcher>) {
-        let matcher = path_matcher.unwrap_or_else(|| PathMatcher::from_str("/").unwrap());
-        let key = (host, matcher);
-        
+    ///
+    /// 동일한 (호스트, 경로)에 다른 라우터의 라우트가 이미 있으면 우선순위를 비교합니다.
+    /// 새 라우트의 우선순위가 더 높으면 교체하고, 같거나 낮으면 `RoutingError::DuplicateRoute`를 반환합니다.
+    /// 같은 라우터가 다시 등록한 라우트는 우선순위와 관계없이 교체합니다.
+    pub fn add_route(&mut self, host: String, service: BackendService, path_matcher: Option<PathMatcher>) -> Result<(), RoutingError> {
+        let key = Self::route_key(host, path_matcher);
+        self.check_priority(&key, &service)?;
+
+        let change = match self.routes.contains_key(&key) {
+            true => RouteChange::updated(&key),
+            false => RouteChange::added(&key),
+        };
+        self.routes.insert(key, service);
+        self.notify(change);
+        Ok(())
+    }
+
+    /// 라우트를 추가하거나 기존 라우트를 덮어씁니다.
+    ///
+    /// `add_route`와 같이 다른 라우터의 라우트보다 우선순위가 높지 않으면 `RoutingError::DuplicateRoute`를 반환합니다.
+    pub fn upsert_route(&mut self, host: String, service: BackendService, path_matcher: Option<PathMatcher>) -> Result<(), RoutingError> {
+        let key = Self::route_key(host, path_matcher);
+        self.check_priority(&key, &service)?;
+        self.insert_route(key, service);
+        Ok(())
+    }
+
+    /// 우선순위를 비교하지 않고 라우트를 추가하거나 덮어씁니다.
+    ///
+    /// 같은 제공자가 자신의 라우트를 갱신할 때 사용합니다. 우선순위 라벨을 낮춘 경우에도
+    /// 이전 라우트가 남지 않도록 기존 라우트와 비교하지 않습니다.
+    pub fn replace_route(&mut self, host: String, service: BackendService, path_matcher: Option<PathMatcher>) {
+        let key = Self::route_key(host, path_matcher);
+        self.insert_route(key, service);
+    }
+
+    /// 컨테이너 하나의 설정 변경을 라우트에 반영합니다.
+    ///
+    /// `replace_route`처럼 우선순위를 비교하지 않지만, 기존 로드밸런서의 다른 백엔드 주소는
+    /// `BackendService::inherit_addresses`로 유지합니다.
+    pub fn update_route(&mut self, host: String, mut service: BackendService, path_matcher: Option<PathMatcher>) {
+        let key = Self::route_key(host, path_matcher);
+        if let Some(existing) = self.routes.get(&key) {
+            service.inherit_addresses(existing);
+        }
+        self.insert_route(key, service);
+    }
+
+    fn insert_route(&mut self, key: (String, PathMatcher), service: BackendService) {
+        if self.routes.insert(key.clone(), service).is_some() {
+            debug!(host = %key.0, path = %key.1.pattern, "기존 라우트 덮어씀");
+            self.notify(RouteChange::updated(&key));
+        } else {
+            self.notify(RouteChange::added(&key));
+        }
+    }
+
+    /// 같은 (호스트, 경로)에 다른 라우터의 라우트가 있고 우선순위가 더 높지 않으면
+    /// `RoutingError::DuplicateRoute`를 반환합니다.
+    ///
+    /// 라우터 이름이 같으면 같은 제공자의 라우트로 보고 우선순위를 비교하지 않습니다.
+    fn check_priority(&self, key: &(String, PathMatcher), service: &BackendService) -> Result<(), RoutingError> {
+        let Some(existing) = self.routes.get(key) else {
+            return Ok(());
+        };
+        if existing.router_name == service.router_name {
+            return Ok(());
+        }
+        if service.priority <= existing.priority {
+            return Err(RoutingError::DuplicateRoute {
+                host: key.0.clone(),
+                path: key.1.pattern.clone(),
+            });
+        }
+        debug!(
+            host = %key.0,
+            path = %key.1.pattern,
+            old_priority = existing.priority,
+            new_priority = service.priority,
+            "우선순위에 따라 기존 라우트 교체"
+        );
+        Ok(())
+    }
+
+    /// 호스트의 라우트 중 `addr`을 가진 백엔드의 가중치를 변경하고 변경된 라우트 수를 반환합니다.
+    ///
+    /// 가중치가 분배에 반영되도록 해당 라우트의 로드밸런서는 가중치 기반 전략으로 전환됩니다.
+    pub fn set_backend_weight(&mut self, host: &str, addr: SocketAddr, weight: u32) -> usize {
+        let mut updated = Vec::new();
+        for (key, service) in self.routes.iter_mut().filter(|((h, _), _)| h == host) {
+            if service.set_weight(addr, weight).is_ok() {
+                // 라운드로빈은 가중치를 무시하므로 가중치 기반으로 전환
+                service.use_weighted_strategy();
+                updated.push(RouteChange::updated(key));
+            }
+        }
+        let count = updated.len();
+        for change in updated {
+            self.notify(change);
+        }
+        count
+    }
+
+    /// 라우트를 추가하되, 이미 있으면 기존 서비스에 백엔드 주소를 추가하여 로드밸런싱합니다.
+    ///
+    /// 다른 라우터의 라우트보다 우선순위가 높지 않으면 `RoutingError::DuplicateRoute`를 반환하고,
+    /// 기존 라우트보다 우선순위가 높으면 병합하지 않고 교체합니다.
+    pub fn merge_route(&mut self, host: String, service: BackendService, path_matcher: Option<PathMatcher>) -> Result<(), RoutingError> {
+        let key = Self::route_key(host, path_matcher);
+        self.check_priority(&key, &service)?;
+
         match self.routes.get_mut(&key) {
+            Some(existing_service) if service.priority > existing_service.priority => {
+                *existing_service = service;
+                self.notify(RouteChange::updated(&key));
+            }
             Some(existing_service) => {
                 // 기존 서비스가 있는 경우
-                if existing_service.load_balancer.is_none() {
+                if service.is_weighted() {
+                    // 새 서비스에 가중치가 있으면 가중치 기반으로 전환
+                    existing_service.use_weighted_strategy();
+                } else if existing_service.load_balancer.is_none() {
                     // 로드밸런서가 없으면 라운드로빈으로 활성화
                     existing_service.enable_load_balancer(
                         LoadBalancerStrategy::RoundRobin {
@@ -48,14 +264,23 @@ impl RoutingTable {
                         }
                     );
                 }
-                // 새 주소 추가 (기본 가중치 1)
-                let _ = existing_service.add_address(service.address, 1);
+                // 새 주소를 새 서비스의 가중치로 추가 (기본 가중치 1)
+                let weight = service.address_weight(service.address).unwrap_or(1);
+                let _ = existing_service.add_address(service.address, weight);
+                self.notify(RouteChange::updated(&key));
             }
             None => {
                 // 새로운 서비스 추가
+                self.notify(RouteChange::added(&key));
                 self.routes.insert(key, service);
             }
         }
+        Ok(())
+    }
+
+    fn route_key(host: String, path_matcher: Option<PathMatcher>) -> (String, PathMatcher) {
+        let matcher = path_matcher.unwrap_or_else(|| PathMatcher::from_str("/").unwrap());
+        (host, matcher)
     }
 
     /// HTTP 요청에서 호스트 정보를 추출하고 해당하는 백엔드 서비스를 찾습니다.
@@ -108,29 +333,86 @@ impl RoutingTable {
         Ok(host_info)
     }
 
-    /// 호스트 정보를 기반으로 백엔드 서비스를 찾습니다.
-    pub fn find_backend(&self, host_info: &HostInfo) -> Result<&BackendService, RoutingError> {
+    /// 호스트와 경로가 일치하는 백엔드 서비스와 매칭된 경로 매처를 찾습니다.
+    ///
+    /// 여러 매처가 일치하면 매칭 우선순위가 가장 높은 라우트를 선택합니다.
+    /// 라우터 우선순위가 지정되지 않은 라우트는 `PathMatcher::priority`(경로 구체성)를 사용하며,
+    /// 결과적으로 다음 순서로 선택됩니다.
+    ///
+    /// 1. 정확 매칭
+    /// 2. 가장 긴 접두사 매칭
+    /// 3. 정규식 매칭 (먼저 등록된 매처 우선)
+    /// 4. 모든 경로에 매칭되는 `/`
+    ///
+    /// 우선순위가 같으면 정확 매칭, 먼저 등록된 매처, 패턴 순서로 결정합니다.
+    /// 경로 접두사를 다루는 미들웨어가 매칭된 부분을 알 수 있도록 `PathMatcher`를 함께 반환합니다.
+    ///
+    /// 호스트가 라우트에 하나도 등록되지 않았으면 기본 백엔드(`set_default_backend`)를 반환합니다.
+    /// 등록된 호스트의 경로가 일치하지 않는 경우에는 기본 백엔드로 넘기지 않습니다.
+    pub fn lookup(&self, host_info: &HostInfo) -> Option<(&BackendService, &PathMatcher)> {
+        if !self.has_host(&host_info.name) {
+            return self.default_backend.as_ref().map(|(backend, matcher)| (backend, matcher));
+        }
+
         let request_path = host_info.path.as_deref().unwrap_or("/");
 
-        // 먼저 호스트와 경로가 일치하는 백엔드를 찾음
-        let matching_backend = self.routes.iter()
-            .find(|((host, matcher), _)| {
+        self.routes.iter()
+            .filter(|((host, matcher), _)| {
                 host == &host_info.name && matcher.matches(request_path)
             })
-            .map(|(_, backend)| backend);
+            .max_by(|((_, a), a_backend), ((_, b), b_backend)| {
+                self.match_priority(a_backend, a).cmp(&self.match_priority(b_backend, b))
+                    .then_with(|| (a.kind == PathMatcherKind::Exact).cmp(&(b.kind == PathMatcherKind::Exact)))
+                    .then_with(|| b.order().cmp(&a.order()))
+                    .then_with(|| b.pattern.cmp(&a.pattern))
+            })
+            .map(|((_, matcher), backend)| (backend, matcher))
+    }
+
+    /// `lookup`과 같지만 스티키 세션 쿠키가 가리키는 백엔드 주소를 함께 반환합니다.
+    ///
+    /// 쿠키가 없거나, 디코딩할 수 없거나, 주소가 정상 상태가 아니면 주소는 `None`이며
+    /// 이 경우 호출자는 로드밸런서로 주소를 선택해야 합니다.
+    pub fn lookup_with_cookie(
+        &self,
+        host_info: &HostInfo,
+        headers: &hyper::HeaderMap,
+    ) -> Option<(&BackendService, &PathMatcher, Option<SocketAddr>)> {
+        self.lookup(host_info)
+            .map(|(backend, matcher)| (backend, matcher, backend.sticky_address(headers)))
+    }
+
+    /// 호스트에 등록된 라우트가 있는지 확인합니다.
+    fn has_host(&self, host: &str) -> bool {
+        self.routes.keys().any(|(route_host, _)| route_host == host)
+    }
+
+    /// 라우트의 매칭 우선순위 (라우터 우선순위가 있으면 우선 사용)
+    fn match_priority(&self, backend: &BackendService, matcher: &PathMatcher) -> u32 {
+        backend.router_name.as_ref()
+            .and_then(|name| self.router_priorities.get(name))
+            .copied()
+            .unwrap_or_else(|| matcher.priority())
+    }
+
+    /// 호스트 정보를 기반으로 백엔드 서비스를 찾습니다.
+    pub fn find_backend(&self, host_info: &HostInfo) -> Result<&BackendService, RoutingError> {
+        // 먼저 호스트와 경로가 일치하는 백엔드를 찾음
+        let matching_backend = self.lookup(host_info).map(|(backend, _)| backend);
 
         let backend = match matching_backend {
             Some(backend) => backend,
             None => return Err(RoutingError::BackendNotFound {
                 host: host_info.name.clone(),
                 available_routes: self.routes.keys()
-                    .map(|(host, matcher)| format!("{}:{:?}", host, matcher))
+                    .map(|(host, matcher)| format!("{}:{}", host, matcher))
                     .collect(),
             }),
         };
 
-        // 포트가 지정된 경우에만 포트 일치 여부 확인
-        if let Some(port) = &host_info.port {
+        // 포트가 지정된 경우에만 포트 일치 여부 확인 (기본 백엔드는 모든 포트 허용)
+        let is_default = !self.has_host(&host_info.name);
+        if let (Some(port), false) = (&host_info.port, is_default) {
             let port_matches = match &backend.load_balancer {
                 Some(lb) => lb.addresses.iter().any(|(addr, _)| addr.port() == *port),
                 None => backend.address.port() == *port,
@@ -140,7 +422,7 @@ impl RoutingTable {
                 return Err(RoutingError::BackendNotFound {
                     host: host_info.name.clone(),
                     available_routes: self.routes.keys()
-                        .map(|(host, matcher)| format!("{}:{:?}", host, matcher))
+                        .map(|(host, matcher)| format!("{}:{}", host, matcher))
                         .collect(),
                 });
             }
@@ -151,6 +433,108 @@ impl RoutingTable {
 
     /// Docker 컨테이너로부터 라우팅 규칙을 업데이트합니다.
     pub fn sync_docker_routes(&mut self, routes: HashMap<(String, PathMatcher), BackendService>) {
-        self.routes = routes;
+        self.replace(routes);
+    }
+
+    /// 호스트 설정을 지정합니다. 기존 설정은 대체됩니다.
+    pub fn set_host_config(&mut self, host: &str, config: HostConfig) {
+        debug!(host = %host, config = ?config, "호스트 설정 변경");
+        self.host_configs.insert(host.to_string(), config);
+    }
+
+    /// 호스트 설정을 반환합니다.
+    pub fn host_config(&self, host: &str) -> Option<&HostConfig> {
+        self.host_configs.get(host)
+    }
+
+    /// 호스트 설정을 제거합니다.
+    pub fn remove_host_config(&mut self, host: &str) -> Option<HostConfig> {
+        self.host_configs.remove(host)
+    }
+
+    /// 등록되지 않은 호스트의 요청을 받을 기본 백엔드를 지정합니다. 기존 설정은 대체됩니다.
+    ///
+    /// 개발 환경처럼 모든 요청을 하나의 백엔드로 보내는 catch-all 용도입니다.
+    pub fn set_default_backend(&mut self, service: BackendService) {
+        info!(address = %service.address, "기본 백엔드 설정");
+        let matcher = PathMatcher::from_str("/").expect("루트 경로 매처는 항상 유효함");
+        self.default_backend = Some((service, matcher));
+    }
+
+    /// 기본 백엔드를 반환합니다.
+    pub fn default_backend(&self) -> Option<&BackendService> {
+        self.default_backend.as_ref().map(|(service, _)| service)
+    }
+
+    /// 기본 백엔드를 제거합니다. 이후 등록되지 않은 호스트는 다시 오류를 반환합니다.
+    pub fn clear_default_backend(&mut self) -> Option<BackendService> {
+        self.default_backend.take().map(|(service, _)| service)
+    }
+
+    /// 라우터별 매칭 우선순위를 설정합니다. 기존 설정은 모두 대체됩니다.
+    pub fn set_router_priorities(&mut self, priorities: HashMap<String, u32>) {
+        debug!(priorities = ?priorities, "라우터 우선순위 변경");
+        self.router_priorities = priorities;
+    }
+
+    /// 모든 라우트의 스냅샷을 호스트와 경로 순으로 반환합니다.
+    ///
+    /// 기본 백엔드가 있으면 호스트가 `*`인 항목으로 마지막에 포함합니다.
+    pub fn snapshot(&self) -> Vec<RouteSnapshot> {
+        let mut routes: Vec<RouteSnapshot> = self.routes.iter()
+            .map(|((host, matcher), service)| RouteSnapshot {
+                host: host.clone(),
+                path: matcher.to_string(),
+                backend: service.snapshot(),
+            })
+            .collect();
+        routes.sort_by(|a, b| (&a.host, &a.path).cmp(&(&b.host, &b.path)));
+        if let Some((service, matcher)) = &self.default_backend {
+            routes.push(RouteSnapshot {
+                host: "*".to_string(),
+                path: matcher.to_string(),
+                backend: service.snapshot(),
+            });
+        }
+        routes
+    }
+
+    /// 등록된 라우트 수를 반환합니다.
+    pub fn len(&self) -> usize {
+        self.routes.len()
+    }
+
+    /// 등록된 라우트가 없는지 확인합니다.
+    pub fn is_empty(&self) -> bool {
+        self.routes.is_empty()
+    }
+
+    /// 모든 라우트를 제거합니다.
+    pub fn clear(&mut self) {
+        for key in std::mem::take(&mut self.routes).keys() {
+            self.notify(RouteChange::removed(key));
+        }
+    }
+
+    /// 라우팅 테이블 전체를 새 라우트로 교체합니다.
+    ///
+    /// 호출자가 잡은 쓰기 잠금 한 번 안에서 맵을 통째로 바꾸므로,
+    /// 교체 도중 일부만 갱신된 라우트가 노출되지 않습니다.
+    // `PathMatcher`의 해시와 동등성은 종류와 패턴만 사용하므로 내부 정규식은 키를 바꾸지 않음
+    #[allow(clippy::mutable_key_type)]
+    pub fn replace(&mut self, routes: HashMap<(String, PathMatcher), BackendService>) {
+        let previous = std::mem::replace(&mut self.routes, routes);
+        debug!(previous = previous.len(), current = self.routes.len(), "라우팅 테이블 교체");
+
+        for (key, old) in &previous {
+            match self.routes.get(key) {
+                None => self.notify(RouteChange::removed(key)),
+                Some(new) if !new.same_config(old) => self.notify(RouteChange::updated(key)),
+                Some(_) => {}
+            }
+        }
+        for key in self.routes.keys().filter(|key| !previous.contains_key(*key)) {
+            self.notify(RouteChange::added(key));
+        }
     }
 } 
\ No newline at end of file
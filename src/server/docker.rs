@@ -1,6 +1,6 @@
 use std::sync::Arc;
 use tokio::sync::RwLock;
-use tracing::{error, info, warn};
+use tracing::{debug, error, info, warn};
 use crate::{
     docker::{DockerEvent, HealthStatus},
     routing_v2::RoutingTable,
@@ -30,14 +30,21 @@ impl DockerEventHandler {
             DockerEvent::ContainerStarted { container_id, host, service, path_matcher } => {
                 match service.get_next_address() {
                     Ok(addr) => {
-                        table.add_route(host.clone(), service, path_matcher.clone());
-                        info!(
-                            container_id = %container_id,
-                            host = %host,
-                            address = ?addr,
-                            path_matcher = ?path_matcher,
-                            "컨테이너 시작"
-                        );
+                        match table.merge_route(host.clone(), service, path_matcher.clone()) {
+                            Ok(()) => info!(
+                                container_id = %container_id,
+                                host = %host,
+                                address = ?addr,
+                                path_matcher = ?path_matcher,
+                                "컨테이너 시작"
+                            ),
+                            Err(e) => warn!(
+                                error = %e,
+                                container_id = %container_id,
+                                host = %host,
+                                "우선순위가 더 높은 라우트가 있어 컨테이너 라우트 무시"
+                            ),
+                        }
                     }
                     Err(e) => {
                         error!(
@@ -51,22 +58,44 @@ impl DockerEventHandler {
             }
             
             DockerEvent::ContainerStopped { container_id, host } => {
-                table.remove_route(&host);
-                info!(container_id = %container_id, host = %host, "컨테이너 중지");
+                // 헬스 체크 실패 등으로 등록되지 못한 컨테이너는 제거할 라우트가 없음
+                if table.remove_route(&host) == 0 {
+                    debug!(container_id = %container_id, host = %host, "컨테이너 중지: 제거할 라우트 없음");
+                } else {
+                    info!(container_id = %container_id, host = %host, "컨테이너 중지");
+                }
             }
             
-            DockerEvent::RoutesUpdated(routes) => {
-                table.sync_docker_routes(routes);
-                info!("라우팅 테이블 업데이트");
+            DockerEvent::RoutesDiff { added, removed, changed } => {
+                info!(
+                    added = added.len(),
+                    removed = removed.len(),
+                    changed = changed.len(),
+                    "라우팅 테이블 업데이트"
+                );
+                for (host, path_matcher) in removed {
+                    table.remove_path_route(&host, &path_matcher);
+                }
+                for ((host, path_matcher), service) in added {
+                    if let Err(e) = table.upsert_route(host, service, Some(path_matcher)) {
+                        warn!(error = %e, "우선순위가 더 높은 라우트가 있어 라우트 추가 무시");
+                    }
+                }
+                // 변경된 라우트는 같은 컨테이너의 이전 라우트이므로 우선순위와 관계없이 교체
+                for ((host, path_matcher), service) in changed {
+                    table.replace_route(host, service, Some(path_matcher));
+                }
             }
             
             DockerEvent::ContainerUpdated { container_id, old_host, new_host, service, path_matcher } => {
-                if let Some(old) = old_host {
+                // 호스트가 바뀐 경우에만 이전 호스트의 라우트를 제거
+                if let Some(old) = old_host.filter(|old| new_host.as_ref() != Some(old)) {
                     table.remove_route(&old);
                 }
                 if let Some(host) = new_host {
                     if let Some(svc) = service {
-                        table.add_route(host.clone(), svc, path_matcher.clone());
+                        // 같은 그룹의 다른 컨테이너 주소는 유지하고 이 컨테이너의 설정만 교체
+                        table.update_route(host.clone(), svc, path_matcher.clone());
                         info!(
                             container_id = %container_id,
                             host = %host,
@@ -127,6 +156,15 @@ impl DockerEventHandler {
                 }
             }
             
+            DockerEvent::BackendWeightChanged { host, addr, new_weight } => {
+                let updated = table.set_backend_weight(&host, addr, new_weight);
+                if updated == 0 {
+                    warn!(host = %host, addr = %addr, "가중치를 변경할 로드밸런서 주소를 찾을 수 없음");
+                } else {
+                    info!(host = %host, addr = %addr, weight = new_weight, "백엔드 가중치 변경");
+                }
+            }
+
             DockerEvent::Error(e) => {
                 error!(error = %e, "Docker 이벤트 처리 오류");
                 return Err(e.into());
@@ -9,6 +9,7 @@ pub enum Error {
     Other(Box<dyn std::error::Error>),
     Configuration(String),
     ConfigWatchError(String),
+    StartupProbeFailed(String),
 }
 
 impl From<std::io::Error> for Error {
@@ -44,6 +45,7 @@ impl fmt::Display for Error {
             Error::Other(e) => write!(f, "Error: {}", e),
             Error::Configuration(msg) => write!(f, "Configuration Error: {}", msg),
             Error::ConfigWatchError(msg) => write!(f, "Config Watch Error: {}", msg),
+            Error::StartupProbeFailed(msg) => write!(f, "Startup Probe Failed: {}", msg),
         }
     }
 }
@@ -54,7 +56,10 @@ impl std::error::Error for Error {
             Error::IoError(e) => Some(e),
             Error::DockerError(e) => Some(e),
             Error::Other(e) => Some(e.as_ref()),
-            Error::ConfigError(_) | Error::Configuration(_) | Error::ConfigWatchError(_) => None,
+            Error::ConfigError(_)
+            | Error::Configuration(_)
+            | Error::ConfigWatchError(_)
+            | Error::StartupProbeFailed(_) => None,
         }
     }
 }
\ No newline at end of file
@@ -1,23 +1,47 @@
 use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 use tokio::sync::RwLock;
-use hyper::{Request, Response, StatusCode};
-use http_body_util::Full;
-use hyper::body::{Bytes, Incoming};
+use hyper::{Method, Request, Response, StatusCode};
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use http_body_util::{BodyExt, BodyStream, Full};
+use hyper::body::{Body, Bytes, Frame, Incoming, SizeHint};
+use futures_util::{Stream, StreamExt};
 use crate::{
-    routing_v2::{RoutingTable, RoutingError},
-    middleware::{MiddlewareManager, handle_middleware_error},
-    proxy::{self, ProxyConfig},
+    metrics,
+    routing_v2::{HostInfo, RoutingTable, RoutingError},
+    middleware::{ClientIp, ConnectionInfo, MiddlewareManager, StreamedResponseBody, handle_middleware_error},
+    middleware::redirect::RedirectMiddleware,
+    proxy::{self, ProxyConfig, ResponseBody, UpstreamBody, UpstreamResponse, full_response, upstream::UpstreamConnector},
 };
-use tracing::error;
+use tracing::{error, info_span, trace, Instrument, Level};
 use hyper::server::conn::http1;
 use hyper::service::service_fn;
 use tracing::debug;
 
+/// 준비 상태 프로브 경로 (라우팅 테이블과 Docker 연결이 준비되면 200)
+pub const READINESS_PATH: &str = "/__roxy/ready";
+/// 생존 프로브 경로 (핸들러가 응답할 수 있으면 항상 200)
+pub const LIVENESS_PATH: &str = "/__roxy/live";
+/// 버전 및 빌드 정보 경로
+pub const VERSION_PATH: &str = "/__roxy/version";
+/// Prometheus 텍스트 형식 메트릭 경로
+pub const METRICS_PATH: &str = "/__roxy/metrics";
 
 pub struct RequestHandler {
     routing_table: Arc<RwLock<RoutingTable>>,
     middleware_manager: MiddlewareManager,
     proxy_config: ProxyConfig,
+    /// 평문 HTTP 연결에 적용할 리다이렉트 규칙
+    http_redirect: Option<RedirectMiddleware>,
+    /// Docker 연결 상태 (없으면 준비 상태 판단에서 제외)
+    docker_connected: Option<Arc<AtomicBool>>,
+    /// Docker 컨테이너 라우트 조회의 누적 재시도 횟수 (없으면 0으로 노출)
+    docker_retry_count: Option<Arc<AtomicU64>>,
+    /// TRACE 레벨에서 기록할 요청 바디 최대 바이트 수 (0이면 기록하지 않음)
+    request_body_log_max_bytes: usize,
+    /// 요청 헤더 이름과 값의 바이트 수 합계 최대값 (0이면 제한 없음)
+    max_request_header_size: u64,
 }
 
 impl RequestHandler {
@@ -29,60 +53,379 @@ impl RequestHandler {
             routing_table,
             middleware_manager,
             proxy_config: ProxyConfig::new(),
+            http_redirect: None,
+            docker_connected: None,
+            docker_retry_count: None,
+            request_body_log_max_bytes: 0,
+            max_request_header_size: 0,
         }
     }
 
+    /// 평문 HTTP 연결로 들어온 요청에 적용할 리다이렉트 규칙을 설정합니다.
+    pub fn with_http_redirect(mut self, redirect: RedirectMiddleware) -> Self {
+        self.http_redirect = Some(redirect);
+        self
+    }
+
+    /// 준비 상태 프로브에서 확인할 Docker 연결 상태를 설정합니다.
+    pub fn with_docker_status(mut self, connected: Arc<AtomicBool>) -> Self {
+        self.docker_connected = Some(connected);
+        self
+    }
+
+    /// 메트릭 경로에서 노출할 Docker 재시도 횟수 카운터를 설정합니다.
+    pub fn with_docker_retry_count(mut self, retry_count: Arc<AtomicU64>) -> Self {
+        self.docker_retry_count = Some(retry_count);
+        self
+    }
+
+    /// TRACE 레벨에서 요청 바디를 최대 `max_bytes`까지 기록하도록 설정합니다.
+    pub fn with_request_body_logging(mut self, max_bytes: usize) -> Self {
+        self.request_body_log_max_bytes = max_bytes;
+        self
+    }
+
+    /// 요청 헤더 크기 합계가 `max_bytes`를 넘으면 431로 거부하도록 설정합니다.
+    pub fn with_max_request_header_size(mut self, max_bytes: u64) -> Self {
+        self.max_request_header_size = max_bytes;
+        self
+    }
+
+    /// 백엔드 연결에 사용할 커넥터를 설정합니다. 기본값은 TCP 커넥터입니다.
+    pub fn with_upstream_connector(mut self, connector: Arc<dyn UpstreamConnector>) -> Self {
+        self.proxy_config = ProxyConfig::with_connector(connector);
+        self
+    }
+
     pub async fn handle_request(
         &self,
         req: Request<Incoming>,
-    ) -> Result<Response<Full<Bytes>>, std::convert::Infallible> {
+    ) -> Result<Response<ResponseBody>, std::convert::Infallible> {
+        // 요청 단위 루트 span (upstream은 백엔드 주소 선택 후 기록)
+        let host = req.headers()
+            .get(hyper::header::HOST)
+            .and_then(|h| h.to_str().ok())
+            .or_else(|| req.uri().host())
+            .unwrap_or_default()
+            .to_string();
+        let span = info_span!(
+            "request",
+            host = %host,
+            path = %req.uri().path(),
+            method = %req.method(),
+            upstream = tracing::field::Empty,
+        );
+
+        let is_head = req.method() == Method::HEAD;
+        let response = self.process_request(req).instrument(span).await?;
+
+        // HEAD 응답은 GET과 같은 헤더에 본문 없이 전송 (RFC 7231 §4.3.2)
+        Ok(if is_head { Self::strip_body(response) } else { response })
+    }
+
+    /// 응답 본문을 제거합니다. 프록시가 만든 응답의 본문 길이는 `Content-Length` 헤더로 보존하고,
+    /// 백엔드 응답은 백엔드가 보낸 헤더를 그대로 둡니다.
+    fn strip_body(response: Response<ResponseBody>) -> Response<ResponseBody> {
+        let (mut parts, body) = response.into_parts();
+        let is_upstream = parts.extensions.get::<UpstreamResponse>().is_some();
+        if !is_upstream && !parts.headers.contains_key(hyper::header::CONTENT_LENGTH) {
+            if let Some(len) = body.size_hint().exact() {
+                parts.headers.insert(hyper::header::CONTENT_LENGTH, len.into());
+            }
+        }
+        full_response(Response::from_parts(parts, Full::new(Bytes::new())))
+    }
+
+    async fn process_request(
+        &self,
+        req: Request<Incoming>,
+    ) -> Result<Response<ResponseBody>, std::convert::Infallible> {
+        // 프로브 요청은 리다이렉트와 라우팅을 거치지 않음
+        if matches!(*req.method(), Method::GET | Method::HEAD) {
+            match req.uri().path() {
+                LIVENESS_PATH => return Ok(full_response(Self::probe_response(StatusCode::OK, "alive"))),
+                READINESS_PATH => return Ok(full_response(self.readiness_response().await)),
+                VERSION_PATH => return Ok(full_response(Self::version_response())),
+                METRICS_PATH => return Ok(full_response(self.metrics_response())),
+                _ => {}
+            }
+        }
+
+        // 0. 요청 헤더 크기 제한
+        if self.max_request_header_size > 0 {
+            let header_size = Self::request_header_size(&req);
+            if header_size > self.max_request_header_size {
+                debug!(header_size, limit = self.max_request_header_size, "요청 헤더 크기 초과");
+                return Ok(full_response(Self::probe_response(
+                    StatusCode::REQUEST_HEADER_FIELDS_TOO_LARGE,
+                    "Request Header Fields Too Large",
+                )));
+            }
+        }
+
+        // HTTP → HTTPS 리다이렉트
+        if let Some(redirect) = &self.http_redirect {
+            let is_tls = req.extensions().get::<ConnectionInfo>().is_some_and(|c| c.is_tls);
+            if !is_tls {
+                if let Some(response) = redirect.redirect_response(&req) {
+                    return Ok(full_response(response));
+                }
+            }
+        }
+
+        // CONNECT 요청은 미들웨어 체인을 거치지 않고 터널링
+        if req.method() == Method::CONNECT {
+            return Ok(full_response(self.handle_connect(req).await));
+        }
+
         // 1. 라우팅
         let table = self.routing_table.read().await;
         let backend = match table.route_request(&req) {
             Ok(backend) => backend,
             Err(e) => {
                 error!(error = %e, "라우팅 실패");
-                return Ok(self.create_routing_error_response(e));
+                return Ok(full_response(self.create_routing_error_response(e)));
             }
         };
 
+        // 미들웨어가 신뢰할 수 있는 클라이언트 IP를 사용하도록 기록
+        let mut req = req;
+        if let Some(connection) = req.extensions().get::<ConnectionInfo>().copied() {
+            let client_ip = backend.client_ip(connection.remote_addr.ip(), req.headers());
+            req.extensions_mut().insert(ClientIp(client_ip));
+        }
+
+        // 테넌트 헤더를 고려해 체인을 한 번 선택하고, 미들웨어가 없는 라우트는 체인 실행을 건너뜀
+        let chain = backend.router_name.as_deref()
+            .and_then(|router_name| self.middleware_manager.chain_for_request(&req, router_name))
+            .filter(|chain| !chain.is_empty());
+
         // 2. 요청 미들웨어 처리 - 라우터 이름 로깅 추가
-        debug!("미들웨어 처리 시작 - 라우터: {:?}", backend.router_name);
-        let req = match self.middleware_manager
-            .handle_request(backend.router_name.as_deref(), req).await 
-        {
-            Ok(req) => req,
-            Err(e) => {
-                error!(error = %e, "요청 미들웨어 처리 실패");
-                return Ok(handle_middleware_error(e));
+        let req = if let Some(chain) = chain {
+            debug!("미들웨어 처리 시작 - 라우터: {:?}", backend.router_name);
+            match chain.handle_request(req).await {
+                Ok(req) => req,
+                Err(e) => {
+                    error!(error = %e, "요청 미들웨어 처리 실패");
+                    return Ok(full_response(handle_middleware_error(e)));
+                }
             }
+        } else {
+            req
         };
 
         // 3. 프록시 요청
+        let req = self.trace_request_body(req).await;
         let response = match proxy::proxy_request(&self.proxy_config, backend, req).await {
             Ok(response) => response,
             Err(e) => {
                 error!(error = %e, "프록시 요청 실패");
-                return Ok(proxy::error_response(&e));
+                return Ok(full_response(proxy::error_response(&e)));
             }
         };
 
+        // 3-1. X-Accel-Redirect 내부 리다이렉트
+        let response = match proxy::follow_x_accel_redirect(&self.proxy_config, backend, response).await {
+            Ok(response) => response,
+            Err(e) => {
+                error!(error = %e, "내부 리다이렉트 처리 실패");
+                return Ok(full_response(proxy::error_response(&e)));
+            }
+        };
+
+        let Some(chain) = chain else {
+            return Ok(response);
+        };
+
         // 4. 응답 미들웨어 처리 - 상세 로깅 추가
+        // 바디가 필요한 미들웨어가 없거나 바디가 한도를 넘으면 헤더만 전달하고 백엔드 바디는 스트리밍으로 유지
         debug!("응답 미들웨어 처리 시작 - 라우터: {:?}", backend.router_name);
-        match self.middleware_manager
-            .handle_response(backend.router_name.as_deref(), response).await 
-        {
+        let (mut parts, body) = response.into_parts();
+        let collected = match chain.response_body_limit(&parts) {
+            Some(limit) => collect_response_body(body, limit).await,
+            None => Ok(CollectedBody::Streaming(body)),
+        };
+        // 스트리밍할 바디 (바디를 모은 경우 None)
+        let (middleware_body, stream_body) = match collected {
+            Ok(CollectedBody::Buffered(bytes)) => (Full::new(bytes), None),
+            Ok(CollectedBody::Streaming(body)) => {
+                parts.extensions.insert(StreamedResponseBody);
+                (Full::new(Bytes::new()), Some(body))
+            }
+            Err(e) => {
+                let err = proxy::ProxyError::ResponseError {
+                    backend: backend.address.to_string(),
+                    error: e.to_string(),
+                };
+                error!(error = %err, "응답 바디 수집 실패");
+                return Ok(full_response(proxy::error_response(&err)));
+            }
+        };
+        match chain.handle_response(Response::from_parts(parts, middleware_body)).await {
             Ok(response) => {
                 debug!("응답 미들웨어 처리 완료 - 최종 헤더: {:?}", response.headers());
-                Ok(response)
+                // 바디를 모았거나 미들웨어가 바디를 새로 채웠으면 그 바디를 사용
+                match stream_body {
+                    Some(body) if response.body().size_hint().exact() == Some(0) => {
+                        let (parts, _) = response.into_parts();
+                        Ok(Response::from_parts(parts, body))
+                    }
+                    _ => Ok(full_response(response)),
+                }
             }
             Err(e) => {
                 error!(error = %e, "응답 미들웨어 처리 실패");
-                Ok(handle_middleware_error(e))
+                Ok(full_response(handle_middleware_error(e)))
             }
         }
     }
 
+    /// CONNECT 요청을 패스스루 백엔드로 터널링합니다.
+    async fn handle_connect(&self, req: Request<Incoming>) -> Response<Full<Bytes>> {
+        // CONNECT 대상은 authority 형식 (예: example.com:443)
+        let host_info = match req.uri().authority() {
+            Some(authority) => HostInfo {
+                name: authority.host().to_string(),
+                port: None,
+                path: None,
+            },
+            None => return self.create_routing_error_response(RoutingError::MissingHost),
+        };
+
+        let table = self.routing_table.read().await;
+        let backend = match table.find_backend(&host_info) {
+            Ok(backend) => backend,
+            Err(e) => {
+                error!(error = %e, "CONNECT 라우팅 실패");
+                return self.create_routing_error_response(e);
+            }
+        };
+
+        if !backend.passthrough {
+            debug!(host = %host_info.name, "패스스루가 비활성화된 백엔드로의 CONNECT 요청 거부");
+            return Response::builder()
+                .status(StatusCode::METHOD_NOT_ALLOWED)
+                .body(Full::new(Bytes::from("CONNECT is only allowed for passthrough backends")))
+                .unwrap();
+        }
+
+        match proxy::tunnel_request(&self.proxy_config, backend, req).await {
+            Ok(response) => response,
+            Err(e) => {
+                error!(error = %e, "CONNECT 터널 생성 실패");
+                proxy::error_response(&e)
+            }
+        }
+    }
+
+    /// TRACE 레벨이면 텍스트 요청 바디의 앞부분을 16진수로 기록합니다.
+    ///
+    /// 읽은 프레임은 남은 바디 앞에 다시 붙여 업스트림에 그대로 전달합니다.
+    async fn trace_request_body(&self, req: Request<Incoming>) -> Request<UpstreamBody> {
+        let max_bytes = self.request_body_log_max_bytes;
+        let (parts, body) = req.into_parts();
+        if max_bytes == 0 || !tracing::enabled!(Level::TRACE) || !is_text_content(&parts.headers) {
+            return Request::from_parts(parts, body.boxed_unsync());
+        }
+
+        let size_hint = body.size_hint();
+        let mut stream = BodyStream::new(body);
+        let mut frames = Vec::new();
+        let mut captured = Vec::new();
+        while captured.len() <= max_bytes {
+            match stream.next().await {
+                Some(Ok(frame)) => {
+                    if let Some(data) = frame.data_ref() {
+                        captured.extend_from_slice(data);
+                    }
+                    frames.push(Ok(frame));
+                }
+                Some(Err(e)) => {
+                    frames.push(Err(e));
+                    break;
+                }
+                None => break,
+            }
+        }
+
+        let truncated = captured.len() > max_bytes;
+        captured.truncate(max_bytes);
+        trace!(bytes = captured.len(), truncated = truncated, body_hex = %hex_encode(&captured), "요청 바디");
+
+        let body = ReplayBody {
+            frames: futures_util::stream::iter(frames).chain(stream),
+            size_hint,
+        };
+        Request::from_parts(parts, body.boxed_unsync())
+    }
+
+    /// 라우팅 테이블이 채워지고 Docker가 연결되어 있으면 200, 아니면 503을 반환합니다.
+    async fn readiness_response(&self) -> Response<Full<Bytes>> {
+        let has_routes = !self.routing_table.read().await.is_empty();
+        let docker_connected = self.docker_connected
+            .as_ref()
+            .is_none_or(|connected| connected.load(Ordering::Relaxed));
+
+        if has_routes && docker_connected {
+            Self::probe_response(StatusCode::OK, "ready")
+        } else {
+            debug!(has_routes = has_routes, docker_connected = docker_connected, "준비되지 않은 상태");
+            Self::probe_response(StatusCode::SERVICE_UNAVAILABLE, "not ready")
+        }
+    }
+
+    /// 요청 헤더 이름과 값의 바이트 수 합계
+    fn request_header_size<B>(req: &Request<B>) -> u64 {
+        req.headers().iter()
+            .map(|(name, value)| (name.as_str().len() + value.len()) as u64)
+            .sum()
+    }
+
+    fn probe_response(status: StatusCode, body: &'static str) -> Response<Full<Bytes>> {
+        Response::builder()
+            .status(status)
+            .header(hyper::header::CACHE_CONTROL, "no-store")
+            .body(Full::new(Bytes::from(body)))
+            .unwrap()
+    }
+
+    /// 버전과 빌드 정보를 JSON으로 반환합니다. 빌드 정보는 `build.rs`에서 주입합니다.
+    fn version_response() -> Response<Full<Bytes>> {
+        let body = serde_json::json!({
+            "version": env!("CARGO_PKG_VERSION"),
+            "build_time": env!("VERGEN_BUILD_TIMESTAMP"),
+            "git_hash": env!("VERGEN_GIT_SHA"),
+            "rustc_version": env!("VERGEN_RUSTC_SEMVER"),
+            "target": env!("VERGEN_CARGO_TARGET_TRIPLE"),
+        });
+        Response::builder()
+            .status(StatusCode::OK)
+            .header(hyper::header::CONTENT_TYPE, "application/json")
+            .header(hyper::header::CACHE_CONTROL, "no-store")
+            .body(Full::new(Bytes::from(body.to_string())))
+            .unwrap()
+    }
+
+    /// Prometheus 텍스트 형식(0.0.4)으로 메트릭을 반환합니다.
+    fn metrics_response(&self) -> Response<Full<Bytes>> {
+        let retry_count = self.docker_retry_count
+            .as_ref()
+            .map_or(0, |count| count.load(Ordering::Relaxed));
+        let mut body = format!(
+            "# HELP retry_count_total Total number of retries while listing Docker container routes.\n\
+             # TYPE retry_count_total counter\n\
+             retry_count_total{{operation=\"docker_container_routes\"}} {}\n",
+            retry_count
+        );
+        body.push_str(&metrics::render());
+        Response::builder()
+            .status(StatusCode::OK)
+            .header(hyper::header::CONTENT_TYPE, "text/plain; version=0.0.4")
+            .header(hyper::header::CACHE_CONTROL, "no-store")
+            .body(Full::new(Bytes::from(body)))
+            .unwrap()
+    }
+
     fn create_routing_error_response(&self, error: RoutingError) -> Response<Full<Bytes>> {
         let status = match error {
             RoutingError::MissingHost | 
@@ -91,6 +434,7 @@ impl RequestHandler {
             RoutingError::HeaderParseError { .. } => StatusCode::BAD_REQUEST,
             RoutingError::BackendNotFound { .. } => StatusCode::NOT_FOUND,
             RoutingError::InvalidPathPattern { .. } => StatusCode::NOT_FOUND,
+            RoutingError::DuplicateRoute { .. } => StatusCode::INTERNAL_SERVER_ERROR,
         };
         
         Response::builder()
@@ -102,16 +446,116 @@ impl RequestHandler {
             })
     }
 
-    pub async fn handle_connection<I>(&self, io: I) -> std::result::Result<(), Box<dyn std::error::Error>>
+    pub async fn handle_connection<I>(
+        &self,
+        io: I,
+        connection: ConnectionInfo,
+    ) -> std::result::Result<(), Box<dyn std::error::Error>>
     where
         I: hyper::rt::Read + hyper::rt::Write + Send + Unpin + 'static,
     {
         http1::Builder::new()
             .serve_connection(
                 io,
-                service_fn(|req| self.handle_request(req)),
+                service_fn(|mut req: Request<Incoming>| {
+                    // 미들웨어에서 클라이언트 주소와 스킴을 사용할 수 있도록 기록
+                    req.extensions_mut().insert(connection);
+                    self.handle_request(req)
+                }),
             )
+            // CONNECT 터널링을 위해 연결 업그레이드 허용
+            .with_upgrades()
             .await
             .map_err(|e| e.into())
     }
-} 
\ No newline at end of file
+} 
+/// 바디를 로그에 남겨도 되는 텍스트 Content-Type인지 확인합니다.
+fn is_text_content(headers: &hyper::HeaderMap) -> bool {
+    let Some(content_type) = headers.get(hyper::header::CONTENT_TYPE).and_then(|v| v.to_str().ok()) else {
+        return false;
+    };
+    let mime = content_type.split(';').next().unwrap_or_default().trim().to_ascii_lowercase();
+
+    mime.starts_with("text/")
+        || mime.ends_with("+json")
+        || mime.ends_with("+xml")
+        || matches!(
+            mime.as_str(),
+            "application/json"
+                | "application/xml"
+                | "application/javascript"
+                | "application/x-www-form-urlencoded"
+                | "application/graphql"
+        )
+}
+
+fn hex_encode(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+/// 응답 미들웨어에 전달하기 위해 읽은 응답 바디
+enum CollectedBody {
+    /// 한도 안에서 모두 읽은 바디
+    Buffered(Bytes),
+    /// 한도를 넘어 클라이언트로 스트리밍할 바디
+    Streaming(ResponseBody),
+}
+
+/// 응답 바디를 `limit` 바이트까지 메모리에 모읍니다.
+///
+/// `Content-Length`나 읽은 크기가 한도를 넘으면 이미 읽은 프레임과 남은 바디를 이어 붙여 스트리밍합니다.
+async fn collect_response_body(body: ResponseBody, limit: usize) -> Result<CollectedBody, hyper::Error> {
+    let size_hint = body.size_hint();
+    if size_hint.lower() > limit as u64 {
+        return Ok(CollectedBody::Streaming(body));
+    }
+
+    let mut stream = BodyStream::new(body);
+    let mut frames = Vec::new();
+    let mut size = 0;
+    while let Some(frame) = stream.next().await {
+        let frame = frame?;
+        size += frame.data_ref().map_or(0, |data| data.len());
+        frames.push(frame);
+        if size > limit {
+            let body = ReplayBody {
+                frames: futures_util::stream::iter(frames.into_iter().map(Ok)).chain(stream),
+                size_hint,
+            };
+            return Ok(CollectedBody::Streaming(body.boxed_unsync()));
+        }
+    }
+
+    let mut bytes = Vec::with_capacity(size);
+    for data in frames.into_iter().filter_map(|frame| frame.into_data().ok()) {
+        bytes.extend_from_slice(&data);
+    }
+    Ok(CollectedBody::Buffered(Bytes::from(bytes)))
+}
+
+/// 이미 읽은 프레임과 남은 바디를 이어 붙인 바디
+///
+/// 업스트림 요청이 원래처럼 `Content-Length`를 사용하도록 원본 바디의 크기 정보를 유지합니다.
+struct ReplayBody<S> {
+    frames: S,
+    size_hint: SizeHint,
+}
+
+impl<S> Body for ReplayBody<S>
+where
+    S: Stream<Item = Result<Frame<Bytes>, hyper::Error>> + Unpin,
+{
+    type Data = Bytes;
+    type Error = hyper::Error;
+
+    fn poll_frame(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Frame<Self::Data>, Self::Error>>> {
+        self.frames.poll_next_unpin(cx)
+    }
+
+    fn size_hint(&self) -> SizeHint {
+        self.size_hint.clone()
+    }
+}
@@ -1,13 +1,105 @@
+use std::net::SocketAddr;
 use std::sync::Arc;
+use std::time::{Duration, Instant};
+use socket2::{Domain, Protocol, Socket, Type};
 use tokio::net::TcpListener;
 use hyper_util::rt::TokioIo;
 use crate::server::error::Error;
 use crate::settings::Settings;
 use crate::tls::TlsConfig;
+use crate::middleware::ConnectionInfo;
 use tracing::{debug, error, info};
 use super::handler::RequestHandler;
 use super::Result;
 
+/// TCP 리스너를 바인딩합니다.
+///
+/// `reuse_port`가 true면 바인딩 전에 SO_REUSEPORT를 설정해 다른 프로세스와 포트를 공유합니다.
+pub(crate) fn bind_tcp_listener(addr: &str, reuse_port: bool) -> std::io::Result<TcpListener> {
+    let addr: SocketAddr = addr.parse()
+        .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidInput, e))?;
+
+    let socket = Socket::new(Domain::for_address(addr), Type::STREAM, Some(Protocol::TCP))?;
+    // tokio의 TcpListener::bind와 동일하게 SO_REUSEADDR 설정
+    #[cfg(unix)]
+    socket.set_reuse_address(true)?;
+    if reuse_port {
+        #[cfg(all(unix, not(any(target_os = "solaris", target_os = "illumos"))))]
+        socket.set_reuse_port(true)?;
+        #[cfg(not(all(unix, not(any(target_os = "solaris", target_os = "illumos")))))]
+        tracing::warn!(addr = %addr, "이 플랫폼은 SO_REUSEPORT를 지원하지 않아 무시합니다");
+    }
+    socket.set_nonblocking(true)?;
+    socket.bind(&addr.into())?;
+    socket.listen(1024)?;
+
+    TcpListener::from_std(socket.into())
+}
+
+// EMFILE/ENFILE errno (Linux, macOS 공통)
+const EMFILE: i32 = 24;
+const ENFILE: i32 = 23;
+
+/// accept() 실패 누적 추적
+///
+/// 일시적인 에러는 재시도하고, 그 외 에러가 짧은 시간에 몰리면 서버를 종료하도록 판단합니다.
+struct AcceptErrorTracker {
+    window_start: Instant,
+    count: u32,
+}
+
+impl AcceptErrorTracker {
+    const WINDOW: Duration = Duration::from_secs(10);
+    const MAX_ERRORS: u32 = 100;
+    const WOULD_BLOCK_DELAY: Duration = Duration::from_millis(10);
+    const FD_EXHAUSTED_DELAY: Duration = Duration::from_millis(100);
+
+    fn new() -> Self {
+        Self { window_start: Instant::now(), count: 0 }
+    }
+
+    /// 에러를 기록하고 한도를 넘었는지 반환합니다.
+    fn record(&mut self, now: Instant) -> bool {
+        if now.duration_since(self.window_start) > Self::WINDOW {
+            self.window_start = now;
+            self.count = 0;
+        }
+        self.count += 1;
+        self.count > Self::MAX_ERRORS
+    }
+
+    /// accept() 에러를 처리합니다. 서버를 종료해야 하면 에러를 반환합니다.
+    async fn handle(&mut self, e: std::io::Error, listener: &str) -> Result<()> {
+        if e.kind() == std::io::ErrorKind::WouldBlock {
+            tokio::time::sleep(Self::WOULD_BLOCK_DELAY).await;
+            return Ok(());
+        }
+
+        if matches!(e.raw_os_error(), Some(EMFILE) | Some(ENFILE)) {
+            // 파일 디스크립터 고갈은 기존 연결이 닫히면 회복되므로 잠시 대기 후 재시도
+            tracing::warn!(error = %e, listener = listener, "파일 디스크립터 부족으로 연결 수락 실패, 잠시 후 재시도");
+            tokio::time::sleep(Self::FD_EXHAUSTED_DELAY).await;
+            return Ok(());
+        }
+
+        error!(error = %e, listener = listener, "연결 수락 실패");
+        if self.record(Instant::now()) {
+            error!(
+                listener = listener,
+                max_errors = Self::MAX_ERRORS,
+                window_secs = Self::WINDOW.as_secs(),
+                "연결 수락 실패가 한도를 초과하여 서버를 종료합니다"
+            );
+            return Err(Error::IoError(std::io::Error::new(
+                e.kind(),
+                format!("{} 연결 수락 실패 한도 초과: {}", listener, e),
+            )));
+        }
+
+        Ok(())
+    }
+}
+
 pub struct ServerListener {
     http_listener: TcpListener,
     https_config: Option<TlsConfig>,
@@ -18,13 +110,12 @@ impl ServerListener {
         // HTTP 리스너 초기화
         let http_addr = format!("0.0.0.0:{}", settings.server.http_port);
         debug!("HTTP 리스너 바인딩 시작: {}", http_addr);
-        let http_listener = TcpListener::bind(&http_addr)
-            .await
+        let http_listener = bind_tcp_listener(&http_addr, settings.server.reuse_port)
             .map_err(|e| {
                 error!(error = %e, addr = %http_addr, "HTTP 바인딩 실패");
                 e
             })?;
-        info!(addr = %http_addr, "HTTP 리스너 시작");
+        info!(addr = %http_addr, reuse_port = settings.server.reuse_port, "HTTP 리스너 시작");
 
         // HTTPS 설정 초기화
         let https_config = if settings.server.https_enabled {
@@ -40,7 +131,13 @@ impl ServerListener {
                 "TLS 인증서 로드 시작"
             );
 
-            let config = TlsConfig::new(cert_path, key_path, settings.server.https_port)
+            let config = TlsConfig::new(
+                cert_path,
+                key_path,
+                settings.server.https_port,
+                &settings.tls.alpn_protocols,
+                settings.server.reuse_port,
+            )
                 .await
                 .map_err(|e| {
                     error!(error = %e, "TLS 설정 초기화 실패");
@@ -64,7 +161,8 @@ impl ServerListener {
         handler: Arc<RequestHandler>,
     ) -> Result<()> {
         info!("서버 리스너 시작");
-        
+        let mut accept_errors = AcceptErrorTracker::new();
+
         loop {
             tokio::select! {
                 result = self.http_listener.accept() => {
@@ -73,15 +171,15 @@ impl ServerListener {
                             debug!(addr = %addr, "새로운 HTTP 연결 수락");
                             let handler = handler.clone();
                             tokio::spawn(async move {
+                                let local_addr = stream.local_addr().ok();
                                 let io = TokioIo::new(stream);
-                                if let Err(err) = handler.handle_connection(io).await {
+                                let connection = ConnectionInfo { remote_addr: addr, local_addr, is_tls: false };
+                                if let Err(err) = handler.handle_connection(io, connection).await {
                                     error!(error = %err, addr = %addr, "HTTP 연결 처리 실패");
                                 }
                             });
                         }
-                        Err(e) => {
-                            error!(error = %e, "HTTP 연결 수락 실패");
-                        }
+                        Err(e) => accept_errors.handle(e, "HTTP").await?,
                     }
                 }
                 
@@ -96,14 +194,16 @@ impl ServerListener {
                         Ok((stream, addr)) => {
                             debug!(addr = %addr, "새로운 HTTPS 연결 수락");
                             let handler = handler.clone();
-                            let acceptor = self.https_config.as_ref().unwrap().acceptor.clone();
+                            let acceptor = self.https_config.as_ref().unwrap().certificates.acceptor();
                             
                             tokio::spawn(async move {
+                                let local_addr = stream.local_addr().ok();
                                 match acceptor.accept(stream).await {
                                     Ok(tls_stream) => {
                                         debug!(addr = %addr, "TLS 핸드쉐이크 성공");
                                         let io = TokioIo::new(tls_stream);
-                                        if let Err(err) = handler.handle_connection(io).await {
+                                        let connection = ConnectionInfo { remote_addr: addr, local_addr, is_tls: true };
+                                        if let Err(err) = handler.handle_connection(io, connection).await {
                                             error!(error = %err, addr = %addr, "HTTPS 연결 처리 실패");
                                         }
                                     }
@@ -113,12 +213,56 @@ impl ServerListener {
                                 }
                             });
                         }
-                        Err(e) => {
-                            error!(error = %e, "HTTPS 연결 수락 실패");
-                        }
+                        Err(e) => accept_errors.handle(e, "HTTPS").await?,
                     }
                 }
             }
         }
     }
-} 
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_bind_tcp_listener_reuse_port() {
+        let first = bind_tcp_listener("127.0.0.1:0", true).unwrap();
+        let addr = first.local_addr().unwrap().to_string();
+
+        // SO_REUSEPORT가 설정되어 있으면 같은 포트에 다시 바인딩 가능
+        let second = bind_tcp_listener(&addr, true);
+        assert!(second.is_ok());
+
+        // 설정하지 않으면 바인딩 실패
+        assert!(bind_tcp_listener(&addr, false).is_err());
+    }
+
+    #[test]
+    fn test_accept_error_tracker_window() {
+        let mut tracker = AcceptErrorTracker::new();
+        let start = tracker.window_start;
+
+        for _ in 0..AcceptErrorTracker::MAX_ERRORS {
+            assert!(!tracker.record(start));
+        }
+        assert!(tracker.record(start + Duration::from_secs(1)));
+
+        // 윈도우가 지나면 카운터 초기화
+        assert!(!tracker.record(start + AcceptErrorTracker::WINDOW + Duration::from_secs(1)));
+        assert_eq!(tracker.count, 1);
+    }
+
+    #[tokio::test]
+    async fn test_accept_error_tracker_transient_errors() {
+        let mut tracker = AcceptErrorTracker::new();
+
+        let emfile = std::io::Error::from_raw_os_error(EMFILE);
+        assert!(tracker.handle(emfile, "HTTP").await.is_ok());
+        let would_block = std::io::Error::from(std::io::ErrorKind::WouldBlock);
+        assert!(tracker.handle(would_block, "HTTP").await.is_ok());
+
+        // 일시적인 에러는 카운트하지 않음
+        assert_eq!(tracker.count, 0);
+    }
+}
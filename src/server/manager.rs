@@ -2,7 +2,7 @@ use std::sync::Arc;
 use tokio::sync::RwLock;
 use tracing::{error, warn, info, debug, instrument};
 use crate::{
-    docker::DockerManager, middleware::MiddlewareManager, routing_v2::RoutingTable, settings::{watcher::{ConfigEvent, ConfigWatcher}, JsonConfig, Settings}
+    docker::{DockerEventLogger, DockerManager, HealthChecker, HealthStatus, HttpHealthChecker}, middleware::{MiddlewareManager, PluginRegistry, redirect::RedirectMiddleware}, routing_v2::{BackendService, RoutingTable}, settings::{docker::HealthCheckType, types::{ValidMiddlewareId, ValidPath, ValidTimeout}, watcher::{ConfigEvent, ConfigWatcher}, JsonConfig, Settings, SettingsError, parse_env_var}
 };
 use super::{
     handler::RequestHandler,
@@ -11,18 +11,34 @@ use super::{
     Result,
     error::Error,
 };
+use std::collections::HashSet;
 use std::env;
+use std::net::SocketAddr;
 use std::path::{Path, PathBuf};
 use std::time::Duration;
 use tokio::sync::mpsc;
+use hyper::header::HeaderName;
 
 /// Config file watcher settings
 #[derive(Debug)]
 struct WatcherConfig {
     enabled: bool,
-    debounce_timeout: Duration,
+    debounce_timeout: ValidTimeout,
     poll_interval: Duration,
-    config_path: PathBuf,
+    config_path: ValidPath,
+    retry_on_file_error: ValidTimeout,
+    /// etcd JSON gateway endpoint to watch in addition to the config file
+    etcd_endpoint: Option<String>,
+    etcd_key_prefix: String,
+}
+
+/// 시작 프로브 결과
+#[derive(Debug, Clone)]
+pub struct ProbeResult {
+    pub host: String,
+    pub address: SocketAddr,
+    pub healthy: bool,
+    pub message: String,
 }
 
 pub struct ServerManager {
@@ -35,6 +51,20 @@ pub struct ServerManager {
     shared_middleware_manager: Option<Arc<RwLock<MiddlewareManager>>>,
 }
 
+/// Prints table and chain sizes rather than their full contents
+impl std::fmt::Debug for ServerManager {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ServerManager")
+            .field("config", &self.config)
+            .field("docker_manager", &self.docker_manager)
+            .field("routing_table", &self.routing_table)
+            .field("middleware_manager", &self.middleware_manager)
+            .field("config_watcher", &self.config_watcher)
+            .field("watching", &self.shared_config.is_some())
+            .finish()
+    }
+}
+
 impl ServerManager {
     /// Create a new ServerManager with explicit dependencies
     /// 
@@ -58,8 +88,13 @@ impl ServerManager {
     }
 
     // Factory method for application use
-    #[instrument(skip(settings), level = "debug", err)]
-    pub async fn with_defaults(mut settings: Settings) -> Result<Self> {
+    pub async fn with_defaults(settings: Settings) -> Result<Self> {
+        Self::with_plugins(settings, PluginRegistry::default()).await
+    }
+
+    /// Same as `with_defaults`, but custom middleware types are built from `plugins`
+    #[instrument(skip(settings, plugins), level = "debug", err)]
+    pub async fn with_plugins(mut settings: Settings, plugins: PluginRegistry) -> Result<Self> {
         // 1. Initialize Docker manager
         let docker_manager = DockerManager::with_defaults(settings.docker.clone())
             .await
@@ -90,11 +125,21 @@ impl ServerManager {
         
         {
             let mut table = routing_table.write().await;
-            table.sync_docker_routes(initial_routes);
+            table.replace(initial_routes);
+            table.set_router_priorities(settings.router_priorities.clone());
+            if let Some(addr) = settings.server.default_backend {
+                table.set_default_backend(BackendService::new(addr));
+            }
         }
 
         // 6. Initialize middleware manager
-        let middleware_manager = MiddlewareManager::new(&settings.middleware, &settings.router_middlewares);
+        let tenant_header = HeaderName::from_bytes(settings.server.tenant_header.as_bytes())
+            .map_err(|e| Error::ConfigError(format!("Invalid tenant header: {}", e)))?;
+        let middleware_manager = MiddlewareManager::with_plugins(
+            &settings.middleware,
+            &settings.router_middlewares,
+            Arc::new(plugins),
+        ).with_tenant_header(tenant_header);
 
         Ok(Self::new(
             settings,
@@ -105,17 +150,16 @@ impl ServerManager {
     }
 
     /// Get config watcher settings from environment variables
-    fn get_watcher_config_from_env() -> WatcherConfig {
+    ///
+    /// Zero or non-numeric watch timeouts are rejected instead of falling back to the defaults.
+    fn get_watcher_config_from_env() -> Result<WatcherConfig> {
         // Check if watcher is enabled
         let enabled = env::var("PROXY_CONFIG_WATCH_ENABLED")
             .map(|val| val.to_lowercase() != "false")
             .unwrap_or(true);
         
         // Debounce timeout setting
-        let debounce_timeout_ms = env::var("PROXY_CONFIG_WATCH_TIMEOUT")
-            .ok()
-            .and_then(|v| v.parse::<u64>().ok())
-            .unwrap_or(300);
+        let debounce_timeout = Self::timeout_from_env("PROXY_CONFIG_WATCH_TIMEOUT", 300, ValidTimeout::from_ms)?;
         
         // Polling interval setting
         let poll_interval_ms = env::var("PROXY_CONFIG_WATCH_INTERVAL")
@@ -123,29 +167,65 @@ impl ServerManager {
             .and_then(|v| v.parse::<u64>().ok())
             .unwrap_or(200);
         
-        // Config file path
-        let config_path = env::var("PROXY_JSON_CONFIG")
-            .map(PathBuf::from)
-            .unwrap_or_else(|_| {
+        // Retry interval after a config file read error
+        let retry_on_file_error = Self::timeout_from_env("PROXY_CONFIG_WATCH_RETRY_SECS", 5, ValidTimeout::from_secs)?;
+        
+        // Config file path (an invalid value is a config error, unset uses the default)
+        let config_path = match env::var("PROXY_JSON_CONFIG") {
+            Ok(path) => ValidPath::new(path.as_str()).map_err(|e| SettingsError::EnvVarInvalid {
+                var_name: "PROXY_JSON_CONFIG".to_string(),
+                value: path,
+                reason: e.to_string(),
+            })?,
+            Err(_) => {
                 let mut path = env::current_dir().unwrap_or_else(|_| PathBuf::from("."));
                 path.push("config");
                 path.push("config.json");
-                path
-            });
+                ValidPath::new(path).expect("non-empty default")
+            }
+        };
         
-        WatcherConfig {
+        // etcd keys watched alongside the config file
+        let etcd_endpoint = env::var("PROXY_CONFIG_ETCD_ENDPOINT")
+            .ok()
+            .filter(|v| !v.trim().is_empty());
+        let etcd_key_prefix = env::var("PROXY_CONFIG_ETCD_PREFIX")
+            .unwrap_or_else(|_| "/roxy/config/".to_string());
+        
+        Ok(WatcherConfig {
             enabled,
-            debounce_timeout: Duration::from_millis(debounce_timeout_ms),
+            debounce_timeout,
             poll_interval: Duration::from_millis(poll_interval_ms),
             config_path,
-        }
+            retry_on_file_error,
+            etcd_endpoint,
+            etcd_key_prefix,
+        })
+    }
+
+    /// Read a non-zero timeout from an environment variable, using `default` when it is unset
+    fn timeout_from_env(
+        name: &str,
+        default: u64,
+        to_timeout: fn(u64) -> std::result::Result<ValidTimeout, SettingsError>,
+    ) -> Result<ValidTimeout> {
+        let value = parse_env_var(name, || default)?;
+        to_timeout(value).map_err(|e| SettingsError::EnvVarInvalid {
+            var_name: name.to_string(),
+            value: value.to_string(),
+            reason: e.to_string(),
+        }.into())
     }
 
     /// Initialize file watcher
     #[instrument(level = "debug", err)]
     async fn initialize_watcher(config: &WatcherConfig) -> Result<ConfigWatcher> {
         let mut watcher = ConfigWatcher::new();
-        watcher.add_path(&config.config_path);
+        watcher.add_path(config.config_path.as_path());
+        if let Some(endpoint) = &config.etcd_endpoint {
+            watcher.add_etcd_path(endpoint, &config.etcd_key_prefix)
+                .map_err(|e| Error::ConfigWatchError(format!("Invalid etcd watch settings: {}", e)))?;
+        }
         watcher.start_with_interval(config.poll_interval).await
             .map_err(|e| Error::ConfigWatchError(format!("Failed to start file watcher: {}", e)))?;
         Ok(watcher)
@@ -193,10 +273,11 @@ impl ServerManager {
     ) -> Result<()> {
         let config = shared_config.read().await;
         let mut middleware_lock = shared_middleware_manager.write().await;
-        *middleware_lock = MiddlewareManager::new(
+        *middleware_lock = MiddlewareManager::with_plugins(
             &config.middleware,
-            &config.router_middlewares
-        );
+            &config.router_middlewares,
+            middleware_lock.plugins().clone(),
+        ).with_tenant_header(middleware_lock.tenant_header().clone());
         
         debug!("Middleware manager updated successfully");
         Ok(())
@@ -207,7 +288,14 @@ impl ServerManager {
     async fn load_and_validate_json_config(path: &Path) -> Result<JsonConfig> {
         // Load JsonConfig
         let json_config = JsonConfig::from_file(path).await
-            .map_err(|e| Error::ConfigError(format!("Failed to load config file: {}: {}", path.display(), e)))?;
+            .map_err(|e| match e {
+                // Keep read failures distinguishable so the watcher can retry them
+                SettingsError::FileError { path, error } => Error::IoError(std::io::Error::new(
+                    error.kind(),
+                    format!("Failed to read config file: {}: {}", path, error),
+                )),
+                e => Error::ConfigError(format!("Failed to load config file: {}: {}", path.display(), e)),
+            })?;
         
         info!("JSON config loaded: {}", path.display());
         
@@ -229,11 +317,11 @@ impl ServerManager {
         
         // Update middleware settings
         for (name, middleware_config) in &json_config.middlewares {
-            let full_name = if name.contains('.') {
-                name.clone()
-            } else {
-                format!("{}.{}", config_id, name)
+            let Some(full_name) = ValidMiddlewareId::namespaced(config_id, name) else {
+                warn!("Skipping middleware with invalid id: {} (config: {})", name, config_id);
+                continue;
             };
+            let full_name = String::from(full_name);
             
             debug!("Updating middleware: {}, config: {:?}", full_name, middleware_config.settings);
             if let Some(settings) = &middleware_config.settings.get("users") {
@@ -274,6 +362,24 @@ impl ServerManager {
         updated
     }
     
+    /// Update router priorities
+    ///
+    /// Keyed by the bare router name, which is what `RoutingTable` matches routes against.
+    fn update_router_priorities(
+        config_lock: &mut Settings,
+        json_config: &JsonConfig,
+    ) -> bool {
+        let mut updated = false;
+
+        for (router_name, router_config) in &json_config.routers {
+            if let Some(priority) = router_config.priority {
+                updated |= config_lock.router_priorities.insert(router_name.clone(), priority) != Some(priority);
+            }
+        }
+
+        updated
+    }
+    
     /// Validate middleware manager and handle rollback if needed
     fn validate_middleware_manager(
         config_lock: &mut Settings,
@@ -332,9 +438,12 @@ impl ServerManager {
             
             // Update router-middleware mappings
             let router_updated = Self::update_router_middleware_mappings(&mut config_lock, &json_config, &config_id);
+
+            // Update router priorities
+            let priorities_updated = Self::update_router_priorities(&mut config_lock, &json_config);
             
             // Check if configuration was updated
-            let changes_detected = middleware_updated || router_updated;
+            let changes_detected = middleware_updated || router_updated || priorities_updated;
             
             // Validate middleware manager and handle rollback
             if changes_detected {
@@ -348,14 +457,16 @@ impl ServerManager {
     }
 
     /// Process multiple configuration files
-    #[instrument(skip(paths, shared_config, shared_middleware_manager), level = "debug", err, 
+    #[instrument(skip(paths, shared_config, shared_middleware_manager, routing_table), level = "debug", err, 
                 fields(file_count = paths.len()))]
     async fn process_config_files(
         paths: Vec<PathBuf>,
         shared_config: Arc<RwLock<Settings>>,
-        shared_middleware_manager: Arc<RwLock<MiddlewareManager>>
-    ) -> Result<bool> {
+        shared_middleware_manager: Arc<RwLock<MiddlewareManager>>,
+        routing_table: Arc<RwLock<RoutingTable>>,
+    ) -> Result<(bool, Vec<PathBuf>)> {
         let mut configs_updated = false;
+        let mut unreadable_files = Vec::new();
         
         // Process all changed files
         for path in paths {
@@ -365,18 +476,25 @@ impl ServerManager {
                         configs_updated = true;
                     }
                 },
+                Err(Error::IoError(e)) => {
+                    // The file may be mid-write by an editor; retry it later
+                    warn!("{}, will retry", e);
+                    unreadable_files.push(path);
+                },
                 Err(e) => {
                     error!("{}", e.to_string());
                 }
             }
         }
         
-        // If configuration was updated, update middleware manager as well
+        // If configuration was updated, update middleware manager and router priorities as well
         if configs_updated {
             Self::update_middleware_manager(&shared_config, &shared_middleware_manager).await?;
+            let priorities = shared_config.read().await.router_priorities.clone();
+            routing_table.write().await.set_router_priorities(priorities);
         }
         
-        Ok(configs_updated)
+        Ok((configs_updated, unreadable_files))
     }
 
     /// Send config update notification
@@ -404,15 +522,15 @@ impl ServerManager {
     #[instrument(skip(self), level = "debug", err)]
     pub async fn start_config_watcher(&mut self) -> Result<(tokio::sync::mpsc::Receiver<()>, tokio::task::JoinHandle<()>)> {
         // Get config from environment variables
-        let watcher_config = Self::get_watcher_config_from_env();
+        let watcher_config = Self::get_watcher_config_from_env()?;
         
         if !watcher_config.enabled {
             return Err(Error::ConfigWatchError("Config file watching is disabled".to_string()));
         }
 
         // Check if file exists
-        if !watcher_config.config_path.exists() {
-            return Err(Error::ConfigError(format!("Config file not found: {}", watcher_config.config_path.display())));
+        if !watcher_config.config_path.as_path().exists() {
+            return Err(Error::ConfigError(format!("Config file not found: {}", watcher_config.config_path)));
         }
         
         info!("Starting config file watcher");
@@ -433,38 +551,84 @@ impl ServerManager {
 
         // Transfer ownership of ConfigWatcher
         self.config_watcher = None;
+
+        // Health checks are paused while a reload is applied
+        let docker_manager = self.docker_manager.clone();
+        let routing_table = self.routing_table.clone();
         
         // Start config watching task
         let handle = tokio::spawn(async move {
             info!("Config watch task started (debounce timeout: {}ms, polling interval: {}ms)", 
-                  watcher_config.debounce_timeout.as_millis(), 
+                  watcher_config.debounce_timeout.as_duration().as_millis(), 
                   watcher_config.poll_interval.as_millis());
             
-            while let Some(events) = watcher.watch_debounced(watcher_config.debounce_timeout).await {
-                // Log events
-                ServerManager::log_config_events(&events);
+            let retry_interval: Duration = watcher_config.retry_on_file_error.into();
+            let mut pending_retry: Vec<PathBuf> = Vec::new();
+            
+            loop {
+                // While unreadable files are pending, wake up after the retry interval
+                // even if no new file event arrives. Only the first event races the retry
+                // timer; debouncing happens afterwards so collected events are never dropped.
+                let first_event = if pending_retry.is_empty() {
+                    match watcher.watch().await {
+                        Some(event) => Some(event),
+                        None => break,
+                    }
+                } else {
+                    match tokio::time::timeout(retry_interval, watcher.watch()).await {
+                        Ok(Some(event)) => Some(event),
+                        Ok(None) => break,
+                        Err(_) => None,
+                    }
+                };
+                let events = match first_event {
+                    Some(event) => Some(watcher.debounce(event, watcher_config.debounce_timeout.into()).await),
+                    None => None,
+                };
                 
-                // Classify events
-                let (files_to_process, has_deleted_files) = ServerManager::classify_events(events);
+                let mut files_to_process = std::mem::take(&mut pending_retry);
                 
-                if has_deleted_files {
-                    warn!("Some config files were deleted. No special handling implemented for this case.");
+                match events {
+                    Some(events) => {
+                        // Log events
+                        ServerManager::log_config_events(&events);
+                        
+                        // Classify events
+                        let (changed_files, has_deleted_files) = ServerManager::classify_events(events);
+                        
+                        if has_deleted_files {
+                            warn!("Some config files were deleted. No special handling implemented for this case.");
+                        }
+                        
+                        for path in changed_files {
+                            if !files_to_process.contains(&path) {
+                                files_to_process.push(path);
+                            }
+                        }
+                    }
+                    None => info!("Retrying reload of {} unreadable config file(s)", files_to_process.len()),
                 }
                 
                 // Process config files
                 if !files_to_process.is_empty() {
                     // Process config files and handle data flow
+                    docker_manager.pause_health_checks().await;
                     let should_notify = match ServerManager::process_config_files(
                         files_to_process, 
                         shared_config.clone(), 
-                        shared_middleware_manager.clone()
+                        shared_middleware_manager.clone(),
+                        routing_table.clone(),
                     ).await {
-                        Ok(updated) => updated,
+                        Ok((updated, unreadable_files)) => {
+                            pending_retry = unreadable_files;
+                            updated
+                        }
                         Err(e) => {
                             error!("Failed to process config file: {}", e.to_string());
                             false
                         }
                     };
+                    docker_manager.resume_health_checks();
                     
                     // Separate data processing from async call
                     if should_notify {
@@ -481,16 +645,105 @@ impl ServerManager {
         Ok((notify_rx, handle))
     }
 
+    /// Probe every backend in the routing table once before accepting traffic
+    ///
+    /// Sends a single GET to the health check path configured in `docker.health_check`.
+    /// Unreachable backends are logged as warnings; when `startup.require_all_healthy`
+    /// is set, any failure aborts startup.
+    pub async fn startup_probe(&self) -> Result<Vec<ProbeResult>> {
+        let check_type = match &self.config.docker.health_check.check_type {
+            HealthCheckType::Http { path, expected_status, .. } => HealthCheckType::Http {
+                path: path.clone(),
+                method: "GET".to_string(),
+                expected_status: *expected_status,
+            },
+            // TCP/Exec 체크가 설정된 경우에도 기본 HTTP 경로로 확인
+            HealthCheckType::Tcp { .. } | HealthCheckType::Exec { .. } => HealthCheckType::default(),
+        };
+        let timeout_secs = self.config.docker.health_check.timeout.as_secs();
+
+        let targets: Vec<(String, SocketAddr)> = {
+            let table = self.routing_table.read().await;
+            let mut seen = HashSet::new();
+            let mut targets = Vec::new();
+            for ((host, _), service) in &table.routes {
+                for addr in service.addresses() {
+                    if seen.insert(addr) {
+                        targets.push((host.clone(), addr));
+                    }
+                }
+            }
+            targets
+        };
+
+        let probes = targets.into_iter().map(|(host, address)| {
+            let check_type = check_type.clone();
+            async move {
+                let (healthy, message) = match HttpHealthChecker::new(address.to_string(), &check_type, timeout_secs) {
+                    Some(checker) => match checker.check().await {
+                        Ok(result) => (matches!(result.status, HealthStatus::Healthy), result.message),
+                        Err(e) => (false, e.to_string()),
+                    },
+                    None => (false, "HTTP health check not configured".to_string()),
+                };
+                ProbeResult { host, address, healthy, message }
+            }
+        });
+        let results = futures_util::future::join_all(probes).await;
+
+        for result in &results {
+            if result.healthy {
+                info!(host = %result.host, address = %result.address, message = %result.message, "Backend reachable");
+            } else {
+                warn!(host = %result.host, address = %result.address, message = %result.message, "Backend unreachable");
+            }
+        }
+
+        let failed: Vec<String> = results.iter()
+            .filter(|r| !r.healthy)
+            .map(|r| format!("{}({})", r.host, r.address))
+            .collect();
+        if self.config.startup.require_all_healthy && !failed.is_empty() {
+            return Err(Error::StartupProbeFailed(failed.join(", ")));
+        }
+
+        Ok(results)
+    }
+
     /// Run server
     #[instrument(skip(self), level = "info", err)]
     pub async fn run(mut self) -> Result<()> {
+        // Probe backends before accepting traffic
+        self.startup_probe().await?;
+
         // Start config file watcher
-        if let Err(e) = self.start_config_watcher().await {
-            error!("Failed to start config watcher: {}", e);
+        match self.start_config_watcher().await {
+            // Invalid watcher settings are rejected like any other invalid configuration
+            Err(e @ Error::Configuration(_)) => return Err(e),
+            Err(e) => error!("Failed to start config watcher: {}", e),
+            Ok(_) => {}
         }
 
+        // Open Docker event log if configured
+        let event_logger = match self.config.docker.event_log_file.as_deref() {
+            Some(path) => match DockerEventLogger::from_path(path) {
+                Ok(logger) => {
+                    info!(path = %path, "Docker event log enabled");
+                    Some(logger)
+                }
+                Err(e) => {
+                    error!(path = %path, error = %e, "Failed to open Docker event log");
+                    None
+                }
+            },
+            None => None,
+        };
+
         // Subscribe to Docker events
-        let mut event_rx = self.docker_manager.subscribe_to_events().await;
+        // Diff the initial Docker routes against the table so routes of vanished containers are removed
+        #[allow(clippy::mutable_key_type)]
+        let current_routes = self.routing_table.read().await.routes.clone();
+        let mut event_rx = self.docker_manager.subscribe_to_events(&current_routes).await;
         let event_handler = DockerEventHandler::new(
             self.routing_table.clone(),
             Arc::new(RwLock::new(self.middleware_manager.clone())),
@@ -499,6 +752,9 @@ impl ServerManager {
         // Start Docker event handling task
         tokio::spawn(async move {
             while let Some(event) = event_rx.recv().await {
+                if let Some(logger) = &event_logger {
+                    logger.log(&event);
+                }
                 if let Err(e) = event_handler.handle_event(event).await {
                     error!("Event handling error: {}", e);
                 }
@@ -510,10 +766,18 @@ impl ServerManager {
         let listener = ServerListener::new(&self.config).await?;
         
         // Create RequestHandler
-        let handler = Arc::new(RequestHandler::new(
+        let mut handler = RequestHandler::new(
             self.routing_table,
             self.middleware_manager,
-        ));
+        ).with_docker_status(self.docker_manager.connection_status())
+            .with_docker_retry_count(self.docker_manager.retry_count_total())
+            .with_request_body_logging(self.config.logging.request_body_max_bytes)
+            .with_max_request_header_size(self.config.server.max_request_header_size_bytes);
+        if self.config.server.https_enabled && self.config.server.auto_redirect_http {
+            info!(https_port = self.config.server.https_port, "HTTP → HTTPS auto redirect enabled");
+            handler = handler.with_http_redirect(RedirectMiddleware::https_upgrade(self.config.server.https_port));
+        }
+        let handler = Arc::new(handler);
 
         // Run listener
         listener.run(handler).await
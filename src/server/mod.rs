@@ -8,4 +8,4 @@ pub type Result<T> = std::result::Result<T, Error>;
 mod manager;
 
 use error::Error;
-pub use manager::ServerManager; 
\ No newline at end of file
+pub use manager::{ProbeResult, ServerManager}; 
\ No newline at end of file
@@ -109,13 +109,36 @@ pub fn label_key_to_json_path(label_key: &str) -> (String, Vec<String>) {
 
 /// 주어진 문자열이 미들웨어 타입인지 확인
 fn is_middleware_type(s: &str) -> bool {
-    matches!(s, "basicAuth" | "cors" | "rateLimit" | "headers" | "stripPrefix" | "addPrefix")
+    matches!(s, "basicAuth" | "cors" | "rateLimit" | "headers" | "stripPrefix" | "addPrefix" | "redirect" | "cache")
+}
+
+/// 미들웨어 목록 라벨인지 확인 (라우터의 middlewares 필드와 router_middlewares 매핑)
+fn is_middleware_list(key: &str) -> bool {
+    key.ends_with(".middlewares") || key.split('.').nth(2) == Some("router_middlewares")
+}
+
+/// JSON 스키마에서 숫자나 불리언 타입인 필드인지 확인
+///
+/// `path`는 `label_key_to_json_path`가 반환한 경로입니다. `health`를 제외한 루트는
+/// 첫 세그먼트가 리소스 이름이므로 그 뒤의 필드 경로로 판단합니다.
+fn is_typed_field(root_key: &str, path: &[String]) -> bool {
+    let field = match root_key {
+        "health" => path.join("."),
+        _ => path.get(1..).unwrap_or_default().join("."),
+    };
+    matches!(
+        (root_key, field.as_str()),
+        ("middlewares", "enabled" | "order")
+            | ("routers", "priority")
+            | ("services", "loadbalancer.server.port" | "loadbalancer.server.weight")
+            | ("health", "enabled" | "interval" | "timeout" | "max_failures")
+    )
 }
 
 /// 문자열 값을 적절한 타입으로 변환
 pub fn convert_value(value: &str, key: &str) -> Value {
     // 특수 경우 처리: 미들웨어 목록은 쉼표로 구분된 문자열
-    if key.ends_with(".middlewares") {
+    if is_middleware_list(key) {
         // 쉼표로 구분된 문자열을 배열로 변환
         let values: Vec<Value> = value
             .split(',')
@@ -163,8 +186,13 @@ pub fn labels_to_json(labels: &HashMap<String, String>, prefix: &str) -> Value {
             continue;
         }
         
-        // 값 변환 - 키도 함께 전달
-        let converted_value = convert_value(value, key);
+        // 값 변환 - 스키마상 숫자/불리언 필드와 미들웨어 목록만 변환하고
+        // 이름, 서비스 참조, 미들웨어 settings 등은 `8080`, `true` 같은 값이어도 문자열로 유지
+        let converted_value = if is_typed_field(&root_key, &path) || is_middleware_list(key) {
+            convert_value(value, key)
+        } else {
+            Value::String(value.clone())
+        };
         
         // 루트 객체에 해당 타입 맵이 없으면 생성
         if !root.contains_key(&root_key) {
@@ -221,9 +249,11 @@ pub fn json_to_labels(json: &Value, prefix: &str) -> HashMap<String, String> {
                                             "basic-auth" => "basicAuth",
                                             "cors" => "cors",
                                             "rate-limit" => "rateLimit",
-                                            "header" => "headers",
+                                            "header" | "headers" => "headers",
                                             "strip-prefix" => "stripPrefix",
                                             "add-prefix" => "addPrefix",
+                                            "redirect" => "redirect",
+                                            "cache" => "cache",
                                             _ => "unknown"
                                         };
                                         
@@ -685,4 +715,157 @@ mod tests {
         assert!(labels.contains_key("rproxy.http.routers.api.service"));
         assert_eq!(labels.get("rproxy.http.routers.api.service").unwrap(), "api_service");
     }
-} 
\ No newline at end of file
+
+    #[test]
+    fn test_labels_to_json_keeps_string_fields() {
+        let labels: HashMap<String, String> = [
+            ("rproxy.http.routers.api.service", "8080"),
+            ("rproxy.http.routers.api.rule", "true"),
+            ("rproxy.http.routers.api.priority", "10"),
+            ("rproxy.http.services.8080.loadbalancer.server.port", "8080"),
+            ("rproxy.http.middlewares.cors.enabled", "false"),
+        ]
+        .into_iter()
+        .map(|(k, v)| (k.to_string(), v.to_string()))
+        .collect();
+
+        let json = labels_to_json(&labels, "rproxy.http.");
+        assert_eq!(json["routers"]["api"]["service"], Value::String("8080".to_string()));
+        assert_eq!(json["routers"]["api"]["rule"], Value::String("true".to_string()));
+        assert_eq!(json["routers"]["api"]["priority"], Value::Number(10.into()));
+        assert_eq!(json["services"]["8080"]["loadbalancer"]["server"]["port"], Value::Number(8080.into()));
+        assert_eq!(json["middlewares"]["cors"]["enabled"], Value::Bool(false));
+    }
+
+    mod round_trip {
+        use super::*;
+        use crate::middleware::config::{MiddlewareConfig, MiddlewareType};
+        use crate::settings::json::{
+            HealthConfig, HttpHealthConfig, JsonConfig, LoadBalancerConfig, RouterConfig,
+            ServerConfig, ServiceConfig,
+        };
+        use crate::settings::types::ValidTimeout;
+        use proptest::prelude::*;
+
+        const PREFIX: &str = "rproxy.http.";
+
+        // 라벨 세그먼트로 쓸 수 있는 이름 (점/쉼표 없음). 숫자나 불리언처럼 보이는 이름도 포함
+        fn name() -> impl Strategy<Value = String> {
+            prop_oneof![
+                "[a-z0-9]{1,8}",
+                "[0-9]{1,5}",
+                Just("true".to_string()),
+                Just("false".to_string()),
+            ]
+        }
+
+        // 미들웨어 settings 키는 snake_case ↔ camelCase 변환을 거치므로 소문자 snake_case로 제한
+        fn setting_key() -> impl Strategy<Value = String> {
+            "[a-z]{1,6}(_[a-z]{1,6}){0,2}"
+        }
+
+        // 플러그인 타입(`Custom`)은 라벨 세그먼트로 표현할 수 없어 제외
+        fn middleware_type() -> impl Strategy<Value = MiddlewareType> {
+            prop_oneof![
+                Just(MiddlewareType::BasicAuth),
+                Just(MiddlewareType::Headers),
+                Just(MiddlewareType::Cors),
+                Just(MiddlewareType::RateLimit),
+                Just(MiddlewareType::AddPrefix),
+                Just(MiddlewareType::Redirect),
+                Just(MiddlewareType::Cache),
+            ]
+        }
+
+        prop_compose! {
+            fn middleware_config()(
+                middleware_type in middleware_type(),
+                enabled in any::<bool>(),
+                order in -100i32..100,
+                settings in prop::collection::hash_map(setting_key(), "[ -~]{0,16}", 0..4),
+            ) -> MiddlewareConfig {
+                MiddlewareConfig { middleware_type, enabled, order, settings }
+            }
+        }
+
+        prop_compose! {
+            fn router_config()(
+                host in name(),
+                middlewares in prop::option::of(prop::collection::vec(name(), 1..4)),
+                service in name(),
+                priority in prop::option::of(any::<u32>()),
+            ) -> RouterConfig {
+                RouterConfig {
+                    rule: format!("Host(`{}.local`)", host),
+                    middlewares,
+                    service,
+                    priority,
+                }
+            }
+        }
+
+        prop_compose! {
+            fn service_config()(port in any::<u16>(), weight in any::<u32>()) -> ServiceConfig {
+                ServiceConfig {
+                    loadbalancer: LoadBalancerConfig {
+                        server: ServerConfig { port, weight },
+                    },
+                    sticky_sessions: None,
+                }
+            }
+        }
+
+        prop_compose! {
+            fn health_config()(
+                enabled in any::<bool>(),
+                path in "/[a-z]{1,8}",
+                interval in 1u64..86_400,
+                timeout in 1u64..3_600,
+                max_failures in any::<u32>(),
+            ) -> HealthConfig {
+                HealthConfig {
+                    enabled,
+                    http: HttpHealthConfig { path },
+                    interval,
+                    timeout: ValidTimeout::from_secs(timeout).unwrap(),
+                    max_failures,
+                }
+            }
+        }
+
+        prop_compose! {
+            // version/id는 라벨 경로가 없는 파일 메타데이터이므로 기본값으로 고정
+            fn json_config()(
+                middlewares in prop::collection::hash_map(name(), middleware_config(), 0..4),
+                routers in prop::collection::hash_map(name(), router_config(), 0..4),
+                services in prop::collection::hash_map(name(), service_config(), 0..4),
+                router_middlewares in prop::collection::hash_map(
+                    name(), prop::collection::vec(name(), 1..4), 0..3),
+                health in prop::option::of(health_config()),
+            ) -> JsonConfig {
+                JsonConfig {
+                    middlewares,
+                    routers,
+                    services,
+                    router_middlewares,
+                    health,
+                    ..JsonConfig::default()
+                }
+            }
+        }
+
+        proptest! {
+            #[test]
+            fn test_labels_round_trip(config in json_config()) {
+                let labels = json_to_labels(&serde_json::to_value(&config).unwrap(), PREFIX);
+                let restored: JsonConfig = serde_json::from_value(labels_to_json(&labels, PREFIX))
+                    .map_err(|e| TestCaseError::fail(format!("역변환 실패: {} (labels: {:?})", e, labels)))?;
+
+                prop_assert_eq!(
+                    serde_json::to_value(&config).unwrap(),
+                    serde_json::to_value(&restored).unwrap()
+                );
+            }
+        }
+    }
+}
@@ -1,5 +1,5 @@
 use serde::Deserialize;
-use super::{SettingsError, parse_env_var};
+use super::{SettingsError, parse_env_var, types::{ValidLabel, ValidPath, ValidTimeout}};
 pub type Result<T> = std::result::Result<T, SettingsError>;
 
 /// 헬스 체크 타입
@@ -22,6 +22,11 @@ pub enum HealthCheckType {
         /// 체크할 포트
         port: u16,
     },
+    /// 컨테이너 내부 명령 실행 체크 (`docker exec`)
+    Exec {
+        /// 실행할 명령과 인자 (예: `["curl", "-f", "http://localhost/health"]`)
+        command: Vec<String>,
+    },
 }
 
 impl Default for HealthCheckType {
@@ -53,8 +58,8 @@ pub struct HealthCheckSettings {
     pub interval: u64,
 
     /// 체크 타임아웃 (초)
-    #[serde(default = "default_check_timeout")]
-    pub timeout: u64,
+    #[serde(default = "default_check_timeout", deserialize_with = "ValidTimeout::deserialize_secs")]
+    pub timeout: ValidTimeout,
 
     /// 헬스 체크 타입
     #[serde(default)]
@@ -81,8 +86,8 @@ fn default_check_interval() -> u64 {
     30 // 30초
 }
 
-fn default_check_timeout() -> u64 {
-    5 // 5초
+fn default_check_timeout() -> ValidTimeout {
+    ValidTimeout::from_secs(5).expect("기본 타임아웃은 0이 아님") // 5초
 }
 
 fn default_max_failures() -> u32 {
@@ -154,7 +159,13 @@ pub struct DockerSettings {
 
     /// 라벨 접두사
     #[serde(default = "default_label_prefix")]
-    pub label_prefix: String,
+    pub label_prefix: ValidLabel,
+
+    /// 추가 라벨 접두사 (`label_prefix` 다음 순서로 시도, 먼저 일치하는 접두사 사용)
+    ///
+    /// 하나의 프록시를 여러 라벨 네임스페이스(`rproxy.`, `traefik.` 등)가 공유할 때 사용합니다.
+    #[serde(default)]
+    pub additional_label_prefixes: Vec<String>,
 
     /// 헬스 체크 설정
     #[serde(default)]
@@ -171,28 +182,100 @@ pub struct DockerSettings {
     /// 초기 헬스체크 설정 여부
     #[serde(default)]
     pub setup_initial_health_checks: bool,
+
+    /// 프록시 컨테이너가 연결된 네트워크를 자동 감지할지 여부 (기본값: true)
+    ///
+    /// `network`가 기본값일 때만 감지된 네트워크로 대체합니다.
+    #[serde(default = "default_auto_detect_network")]
+    pub auto_detect_network: bool,
+
+    /// Docker 이벤트 로그 파일 경로 (`-`이면 표준 출력, 없으면 기록하지 않음)
+    #[serde(default)]
+    pub event_log_file: Option<String>,
+
+    /// Docker 데몬 주소 (`unix:///var/run/docker.sock`, `tcp://remote:2376` 등)
+    ///
+    /// 없으면 `DOCKER_HOST` 또는 로컬 기본 소켓을 사용합니다.
+    #[serde(default)]
+    pub host: Option<String>,
+
+    /// TCP 연결에 TLS를 사용하고 서버 인증서를 검증할지 여부
+    #[serde(default)]
+    pub tls_verify: bool,
+
+    /// TLS 인증서 디렉터리 (`ca.pem`, `cert.pem`, `key.pem`)
+    #[serde(default)]
+    pub cert_path: Option<ValidPath>,
 }
 
 impl DockerSettings {
     pub fn from_env() -> Result<Self> {
+        let settings = Self::read_env()?;
+        settings.validate()?;
+        Ok(settings)
+    }
+
+    /// `from_env`와 같지만 검증하지 않습니다.
+    ///
+    /// 설정 계층을 덮어쓴 뒤 한 번에 검증하는 `Settings::load`에서 사용합니다.
+    pub(crate) fn read_env() -> Result<Self> {
         let network = parse_env_var("PROXY_DOCKER_NETWORK", default_docker_network)?;
         let label_prefix = parse_env_var("PROXY_LABEL_PREFIX", default_label_prefix)?;
+        let additional_label_prefixes = std::env::var("PROXY_ADDITIONAL_LABEL_PREFIXES")
+            .map(|value| value.split(',')
+                .map(|s| s.trim().to_string())
+                .filter(|s| !s.is_empty())
+                .collect())
+            .unwrap_or_default();
         let health_check = HealthCheckSettings::default();
         let retry = RetrySettings::default();
         let load_balancer = LoadBalancerSettings::default();
+        let auto_detect_network = parse_env_var("PROXY_DOCKER_AUTO_DETECT_NETWORK", default_auto_detect_network)?;
+        let event_log_file = std::env::var("PROXY_EVENT_LOG_FILE")
+            .ok()
+            .filter(|path| !path.trim().is_empty());
+        let host = std::env::var("PROXY_DOCKER_HOST")
+            .ok()
+            .filter(|host| !host.trim().is_empty());
+        let tls_verify = parse_env_var("PROXY_DOCKER_TLS_VERIFY", || false)?;
+        let cert_path = std::env::var("PROXY_DOCKER_CERT_PATH")
+            .ok()
+            .map(|path| ValidPath::new(&path).map_err(|e| SettingsError::EnvVarInvalid {
+                var_name: "PROXY_DOCKER_CERT_PATH".to_string(),
+                value: path,
+                reason: e.to_string(),
+            }))
+            .transpose()?;
 
         let settings = Self {
             network,
             label_prefix,
+            additional_label_prefixes,
             health_check,
             retry,
             load_balancer,
             setup_initial_health_checks: false,
+            auto_detect_network,
+            event_log_file,
+            host,
+            tls_verify,
+            cert_path,
         };
-        settings.validate()?;
         Ok(settings)
     }
 
+    /// 라벨 접두사를 시도 순서대로 반환합니다 (`label_prefix` 다음 `additional_label_prefixes`).
+    pub fn label_prefixes(&self) -> Vec<&str> {
+        std::iter::once(self.label_prefix.as_str())
+            .chain(self.additional_label_prefixes.iter().map(String::as_str))
+            .collect()
+    }
+
+    /// 네트워크가 기본값 그대로인지 확인 (자동 감지 대상 여부)
+    pub fn uses_default_network(&self) -> bool {
+        self.network == default_docker_network()
+    }
+
    pub fn validate(&self) -> Result<()> {
         // 빈 네트워크 이름 검사
         if self.network.is_empty() {
@@ -204,10 +287,10 @@ impl DockerSettings {
         }
 
         // 라벨 접두사 길이 제한
-        if self.label_prefix.len() > 100 {
+        if self.label_prefix.as_str().len() > 100 {
             return Err(SettingsError::EnvVarInvalid {
                 var_name: "PROXY_LABEL_PREFIX".to_string(),
-                value: self.label_prefix.clone(),
+                value: self.label_prefix.to_string(),
                 reason: "라벨 접두사가 너무 깁니다 (최대 100자)".to_string(),
             });
         }
@@ -222,10 +305,26 @@ impl DockerSettings {
         }
 
         // 라벨 접두사 검증
-        if !self.label_prefix.ends_with('.') {
+        if !self.label_prefix.as_str().ends_with('.') {
             return Err(SettingsError::EnvVarInvalid {
                 var_name: "PROXY_LABEL_PREFIX".to_string(),
-                value: self.label_prefix.clone(),
+                value: self.label_prefix.to_string(),
+                reason: "라벨 접두사는 '.'으로 끝나야 합니다".to_string(),
+            });
+        }
+
+        for prefix in &self.additional_label_prefixes {
+            ValidLabel::new(prefix).map_err(|e| SettingsError::EnvVarInvalid {
+                var_name: "PROXY_ADDITIONAL_LABEL_PREFIXES".to_string(),
+                value: prefix.clone(),
+                reason: e.to_string(),
+            })?;
+        }
+
+        if let Some(prefix) = self.additional_label_prefixes.iter().find(|p| !p.ends_with('.')) {
+            return Err(SettingsError::EnvVarInvalid {
+                var_name: "PROXY_ADDITIONAL_LABEL_PREFIXES".to_string(),
+                value: prefix.clone(),
                 reason: "라벨 접두사는 '.'으로 끝나야 합니다".to_string(),
             });
         }
@@ -239,10 +338,16 @@ impl Default for DockerSettings {
         Self {
             network: default_docker_network(),
             label_prefix: default_label_prefix(),
+            additional_label_prefixes: Vec::new(),
             health_check: HealthCheckSettings::default(),
             retry: RetrySettings::default(),
             load_balancer: LoadBalancerSettings::default(),
             setup_initial_health_checks: false,
+            auto_detect_network: default_auto_detect_network(),
+            event_log_file: None,
+            host: None,
+            tls_verify: false,
+            cert_path: None,
         }
     }
 }
@@ -251,6 +356,10 @@ fn default_docker_network() -> String {
     "reverse-proxy-network".to_string()
 }
 
-fn default_label_prefix() -> String {
-    "rproxy.".to_string()
+fn default_auto_detect_network() -> bool {
+    true
+}
+
+fn default_label_prefix() -> ValidLabel {
+    ValidLabel::new("rproxy.").expect("기본 라벨 접두사는 유효함")
 } 
\ No newline at end of file
@@ -1,4 +1,40 @@
 use std::fmt;
+use crate::middleware::config::MiddlewareType;
+use super::types::InvalidUrl;
+
+/// 설정 검증 오류
+///
+/// `json_pointer`는 실패한 필드의 위치를 RFC 6901 형식으로 나타냅니다. (예: `/routers/api/service`)
+#[derive(Debug, Clone, PartialEq)]
+pub struct ValidationError {
+    pub json_pointer: String,
+    pub message: String,
+}
+
+impl ValidationError {
+    /// 경로 구성 요소로부터 검증 오류를 생성합니다.
+    pub fn new<S: AsRef<str>>(path: &[S], message: impl Into<String>) -> Self {
+        Self {
+            json_pointer: json_pointer(path),
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.json_pointer, self.message)
+    }
+}
+
+/// 경로 구성 요소를 RFC 6901 JSON 포인터로 변환합니다.
+///
+/// `~`는 `~0`, `/`는 `~1`로 이스케이프합니다. 빈 경로는 문서 전체(`""`)를 가리킵니다.
+pub fn json_pointer<S: AsRef<str>>(path: &[S]) -> String {
+    path.iter()
+        .map(|segment| format!("/{}", segment.as_ref().replace('~', "~0").replace('/', "~1")))
+        .collect()
+}
 
 #[derive(Debug)]
 pub enum SettingsError {
@@ -17,12 +53,35 @@ pub enum SettingsError {
     ParseError {
         source: toml::de::Error,
     },
+    TomlSerializeError {
+        source: toml::ser::Error,
+    },
     JsonParseError {
         source: serde_json::Error,
     },
     InvalidConfig(String),
-    DuplicateMiddleware(String),
+    /// 같은 이름의 미들웨어가 이미 등록됨
+    DuplicateMiddleware {
+        name: String,
+        existing_type: MiddlewareType,
+    },
     WatchError(String),
+    ValidationErrors(Vec<ValidationError>),
+    /// IP 주소 형식의 호스트를 소켓 주소로 변환할 수 없음
+    SocketAddrParseError {
+        value: String,
+        source: std::net::AddrParseError,
+    },
+    /// 호스트 이름을 DNS로 조회할 수 없음
+    DnsResolutionError {
+        host: String,
+        reason: String,
+    },
+    /// 허용되지 않는 URL (`url`은 인증 정보를 가린 값)
+    InvalidUrl {
+        url: String,
+        reason: InvalidUrl,
+    },
 }
 
 impl fmt::Display for SettingsError {
@@ -38,9 +97,25 @@ impl fmt::Display for SettingsError {
                 write!(f, "TOML 설정 파싱 오류: {}", source),
             Self::JsonParseError { source } => 
                 write!(f, "JSON 설정 파싱 오류: {}", source),
+            Self::TomlSerializeError { source } => 
+                write!(f, "TOML 설정 직렬화 오류: {}", source),
             Self::InvalidConfig(msg) => write!(f, "Invalid configuration: {}", msg),
-            Self::DuplicateMiddleware(name) => write!(f, "Duplicate middleware: {}", name),
+            Self::DuplicateMiddleware { name, existing_type } =>
+                write!(f, "Middleware '{}' already exists as type '{:?}'", name, existing_type),
             Self::WatchError(msg) => write!(f, "Watch error: {}", msg),
+            Self::SocketAddrParseError { value, source } =>
+                write!(f, "소켓 주소 변환 오류 {}: {}", value, source),
+            Self::DnsResolutionError { host, reason } =>
+                write!(f, "DNS 조회 실패 {}: {}", host, reason),
+            Self::InvalidUrl { url, reason } =>
+                write!(f, "잘못된 URL '{}': {}", url, reason),
+            Self::ValidationErrors(errors) => {
+                write!(f, "설정 검증 오류 {}건", errors.len())?;
+                for error in errors {
+                    write!(f, "\n  {}", error)?;
+                }
+                Ok(())
+            }
         }
     }
 }
@@ -50,8 +125,23 @@ impl std::error::Error for SettingsError {
         match self {
             Self::ParseError { source } => Some(source),
             Self::JsonParseError { source } => Some(source),
+            Self::TomlSerializeError { source } => Some(source),
             Self::FileError { error, .. } => Some(error),
+            Self::SocketAddrParseError { source, .. } => Some(source),
+            Self::InvalidUrl { reason, .. } => Some(reason),
             _ => None,
         }
     }
-} 
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_json_pointer_escaping() {
+        assert_eq!(json_pointer(&["routers", "api", "service"]), "/routers/api/service");
+        assert_eq!(json_pointer(&["middlewares", "a/b~c"]), "/middlewares/a~1b~0c");
+        assert_eq!(json_pointer::<&str>(&[]), "");
+    }
+}
@@ -0,0 +1,335 @@
+//! etcd 설정 감시
+//!
+//! etcd v3 JSON 게이트웨이(`/v3/kv/range`, `/v3/watch`)로 키 접두사 아래의 값을 감시합니다.
+//! 각 키의 값을 로컬 디렉토리의 파일로 반영한 뒤 `ConfigEvent`를 보내므로,
+//! 설정 재적용 경로는 파일 감시와 동일하게 파일을 읽어 처리합니다.
+
+use std::collections::HashSet;
+use std::path::{Component, Path, PathBuf};
+use std::time::Duration;
+use base64::{engine::general_purpose::STANDARD, Engine};
+use bytes::Bytes;
+use http_body_util::{BodyExt, Full};
+use hyper::body::Incoming;
+use hyper::{Request, Response, Uri};
+use hyper_util::client::legacy::{connect::HttpConnector, Client};
+use hyper_util::rt::TokioExecutor;
+use serde::Deserialize;
+use tokio::sync::mpsc;
+use tracing::{debug, info, warn};
+use super::watcher::ConfigEvent;
+use super::{Result, error::SettingsError};
+
+/// 감시 스트림이 끊겼을 때 재연결 간격
+const RETRY_INTERVAL: Duration = Duration::from_secs(5);
+
+type EtcdClient = Client<HttpConnector, Full<Bytes>>;
+
+/// 감시할 etcd 엔드포인트와 키 접두사
+#[derive(Debug, Clone)]
+pub(crate) struct EtcdSource {
+    endpoint: String,
+    key_prefix: String,
+}
+
+impl EtcdSource {
+    /// `http://host:2379` 형식의 엔드포인트와 키 접두사를 검증합니다.
+    pub(crate) fn new(endpoint: &str, key_prefix: &str) -> Result<Self> {
+        let invalid = |reason: &str| SettingsError::WatchError(format!("잘못된 etcd 설정 {} {}: {}", endpoint, key_prefix, reason));
+
+        let uri: Uri = endpoint.parse().map_err(|_| invalid("엔드포인트 URI 형식이 아닙니다"))?;
+        if uri.scheme_str() != Some("http") || uri.authority().is_none() {
+            return Err(invalid("http://호스트:포트 형식의 엔드포인트만 지원합니다"));
+        }
+        if key_prefix.is_empty() {
+            return Err(invalid("키 접두사가 비어 있습니다"));
+        }
+
+        Ok(Self {
+            endpoint: endpoint.trim_end_matches('/').to_string(),
+            key_prefix: key_prefix.to_string(),
+        })
+    }
+}
+
+/// etcd 값을 로컬 디렉토리에 파일로 반영하는 감시 작업
+pub(crate) struct EtcdMirror {
+    source: EtcdSource,
+    /// 키를 파일로 저장할 디렉토리
+    dir: PathBuf,
+    /// 현재 반영된 파일 목록
+    files: HashSet<PathBuf>,
+    event_tx: mpsc::Sender<ConfigEvent>,
+    /// 동기화 시 이벤트를 보낼지 여부 (첫 동기화는 `emit_initial_state`를 따름)
+    emit_sync_events: bool,
+}
+
+impl EtcdMirror {
+    pub(crate) fn new(source: EtcdSource, dir: PathBuf, event_tx: mpsc::Sender<ConfigEvent>, emit_initial_state: bool) -> Self {
+        Self {
+            source,
+            dir,
+            files: HashSet::new(),
+            event_tx,
+            emit_sync_events: emit_initial_state,
+        }
+    }
+
+    /// 전체 키를 동기화한 뒤 변경을 감시합니다. 연결이 끊기면 다시 동기화하고 감시를 재개합니다.
+    pub(crate) async fn run(mut self) {
+        let client: EtcdClient = Client::builder(TokioExecutor::new()).build_http();
+        loop {
+            match self.sync_and_watch(&client).await {
+                Ok(()) => debug!(endpoint = %self.source.endpoint, "etcd 감시 스트림 종료"),
+                Err(e) => warn!(endpoint = %self.source.endpoint, prefix = %self.source.key_prefix, error = %e, "etcd 감시 실패"),
+            }
+            if self.event_tx.is_closed() {
+                return;
+            }
+            self.emit_sync_events = true;
+            tokio::time::sleep(RETRY_INTERVAL).await;
+        }
+    }
+
+    async fn sync_and_watch(&mut self, client: &EtcdClient) -> std::result::Result<(), String> {
+        let revision = self.sync(client).await?;
+        info!(endpoint = %self.source.endpoint, prefix = %self.source.key_prefix, revision, "etcd 감시 시작");
+
+        let request = serde_json::json!({
+            "create_request": {
+                "key": STANDARD.encode(&self.source.key_prefix),
+                "range_end": STANDARD.encode(prefix_range_end(self.source.key_prefix.as_bytes())),
+                "start_revision": (revision + 1).to_string(),
+            }
+        });
+        let mut body = self.post(client, "/v3/watch", request).await?.into_body();
+
+        // 게이트웨이는 감시 응답을 줄바꿈으로 구분된 JSON으로 스트리밍합니다.
+        let mut buf = Vec::new();
+        while let Some(frame) = body.frame().await {
+            let frame = frame.map_err(|e| e.to_string())?;
+            if let Some(data) = frame.data_ref() {
+                buf.extend_from_slice(data);
+            }
+            while let Some(pos) = buf.iter().position(|&b| b == b'\n') {
+                let line: Vec<u8> = buf.drain(..=pos).collect();
+                if !line.iter().all(u8::is_ascii_whitespace) {
+                    self.handle_watch_message(&line).await?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// 접두사 아래의 모든 키를 파일로 반영하고, 사라진 키의 파일을 삭제합니다.
+    /// 조회 시점의 리비전을 반환합니다.
+    async fn sync(&mut self, client: &EtcdClient) -> std::result::Result<i64, String> {
+        let request = serde_json::json!({
+            "key": STANDARD.encode(&self.source.key_prefix),
+            "range_end": STANDARD.encode(prefix_range_end(self.source.key_prefix.as_bytes())),
+        });
+        let response = self.post(client, "/v3/kv/range", request).await?;
+        let body = response.into_body().collect().await.map_err(|e| e.to_string())?.to_bytes();
+        let range: RangeResponse = serde_json::from_slice(&body).map_err(|e| format!("range 응답 파싱 실패: {}", e))?;
+
+        let mut seen = HashSet::new();
+        for kv in &range.kvs {
+            if let Some(path) = self.write_key(kv).await? {
+                if self.emit_sync_events {
+                    self.send(ConfigEvent::Modified(path.clone())).await;
+                }
+                seen.insert(path);
+            }
+        }
+
+        let removed: Vec<PathBuf> = self.files.difference(&seen).cloned().collect();
+        for path in removed {
+            self.remove_file(&path).await;
+        }
+        self.files = seen;
+
+        Ok(range.header.revision())
+    }
+
+    async fn handle_watch_message(&mut self, line: &[u8]) -> std::result::Result<(), String> {
+        let message: WatchMessage = serde_json::from_slice(line).map_err(|e| format!("watch 응답 파싱 실패: {}", e))?;
+        if let Some(error) = message.error {
+            return Err(format!("watch 오류: {}", error));
+        }
+        let Some(result) = message.result else {
+            return Ok(());
+        };
+        if result.canceled {
+            return Err("watch가 취소되었습니다".to_string());
+        }
+
+        for event in &result.events {
+            if event.kind.as_deref() == Some("DELETE") {
+                if let Some(path) = self.key_path(&event.kv.key) {
+                    if self.files.remove(&path) {
+                        self.remove_file(&path).await;
+                    }
+                }
+            } else if let Some(path) = self.write_key(&event.kv).await? {
+                let event = if self.files.insert(path.clone()) {
+                    ConfigEvent::Created(path)
+                } else {
+                    ConfigEvent::Modified(path)
+                };
+                self.send(event).await;
+            }
+        }
+        Ok(())
+    }
+
+    /// 키 값을 파일에 기록합니다. 파일 경로로 쓸 수 없는 키는 건너뜁니다.
+    async fn write_key(&self, kv: &KeyValue) -> std::result::Result<Option<PathBuf>, String> {
+        let Some(path) = self.key_path(&kv.key) else {
+            return Ok(None);
+        };
+        let value = STANDARD.decode(&kv.value).map_err(|e| format!("값 디코딩 실패: {}", e))?;
+        if let Some(parent) = path.parent() {
+            tokio::fs::create_dir_all(parent).await.map_err(|e| e.to_string())?;
+        }
+        tokio::fs::write(&path, value).await.map_err(|e| format!("{}: {}", path.display(), e))?;
+        Ok(Some(path))
+    }
+
+    async fn remove_file(&self, path: &Path) {
+        if let Err(e) = tokio::fs::remove_file(path).await {
+            warn!(path = %path.display(), error = %e, "etcd 미러 파일 삭제 실패");
+        }
+        self.send(ConfigEvent::Deleted(path.to_path_buf())).await;
+    }
+
+    /// 키를 미러 디렉토리 안의 파일 경로로 변환합니다. (`/roxy/config/app.json` → `<dir>/app.json`)
+    fn key_path(&self, encoded_key: &str) -> Option<PathBuf> {
+        let key = STANDARD.decode(encoded_key).ok().and_then(|key| String::from_utf8(key).ok())?;
+        let relative = Path::new(key.strip_prefix(&self.source.key_prefix)?.trim_start_matches('/'));
+        let is_safe = relative.components().next().is_some()
+            && relative.components().all(|c| matches!(c, Component::Normal(_)));
+        if !is_safe {
+            debug!(key = %key, "파일 경로로 쓸 수 없는 etcd 키 무시");
+            return None;
+        }
+        Some(self.dir.join(relative))
+    }
+
+    async fn post(&self, client: &EtcdClient, path: &str, body: serde_json::Value) -> std::result::Result<Response<Incoming>, String> {
+        let request = Request::post(format!("{}{}", self.source.endpoint, path))
+            .header(hyper::header::CONTENT_TYPE, "application/json")
+            .body(Full::new(Bytes::from(body.to_string())))
+            .map_err(|e| e.to_string())?;
+        let response = client.request(request).await.map_err(|e| e.to_string())?;
+        if !response.status().is_success() {
+            return Err(format!("{} 응답 상태 {}", path, response.status()));
+        }
+        Ok(response)
+    }
+
+    async fn send(&self, event: ConfigEvent) {
+        debug!(event = ?event, "etcd 설정 이벤트");
+        let _ = self.event_tx.send(event).await;
+    }
+}
+
+/// 접두사 조회용 `range_end` (접두사의 마지막 바이트를 1 증가)
+fn prefix_range_end(prefix: &[u8]) -> Vec<u8> {
+    let mut end = prefix.to_vec();
+    while let Some(last) = end.pop() {
+        if last < 0xff {
+            end.push(last + 1);
+            return end;
+        }
+    }
+    // 모든 바이트가 0xff이면 접두사 이후 전체 키
+    vec![0]
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct ResponseHeader {
+    /// 게이트웨이는 int64를 문자열로 직렬화합니다.
+    #[serde(default)]
+    revision: Option<serde_json::Value>,
+}
+
+impl ResponseHeader {
+    fn revision(&self) -> i64 {
+        match &self.revision {
+            Some(serde_json::Value::String(s)) => s.parse().unwrap_or(0),
+            Some(serde_json::Value::Number(n)) => n.as_i64().unwrap_or(0),
+            _ => 0,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct KeyValue {
+    key: String,
+    #[serde(default)]
+    value: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct RangeResponse {
+    #[serde(default)]
+    header: ResponseHeader,
+    #[serde(default)]
+    kvs: Vec<KeyValue>,
+}
+
+#[derive(Debug, Deserialize)]
+struct WatchMessage {
+    result: Option<WatchResponse>,
+    error: Option<serde_json::Value>,
+}
+
+#[derive(Debug, Deserialize)]
+struct WatchResponse {
+    #[serde(default)]
+    events: Vec<WatchEvent>,
+    #[serde(default)]
+    canceled: bool,
+}
+
+#[derive(Debug, Deserialize)]
+struct WatchEvent {
+    /// PUT 이벤트는 기본값이라 생략됩니다.
+    #[serde(rename = "type")]
+    kind: Option<String>,
+    kv: KeyValue,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_prefix_range_end() {
+        assert_eq!(prefix_range_end(b"/roxy/"), b"/roxy0".to_vec());
+        assert_eq!(prefix_range_end(&[b'a', 0xff]), b"b".to_vec());
+        assert_eq!(prefix_range_end(&[0xff]), vec![0]);
+    }
+
+    #[test]
+    fn test_etcd_source_validation() {
+        assert!(EtcdSource::new("http://127.0.0.1:2379", "/roxy/").is_ok());
+        assert!(EtcdSource::new("https://etcd:2379", "/roxy/").is_err());
+        assert!(EtcdSource::new("etcd:2379", "/roxy/").is_err());
+        assert!(EtcdSource::new("http://etcd:2379", "").is_err());
+    }
+
+    #[test]
+    fn test_key_path() {
+        let (tx, _rx) = mpsc::channel(1);
+        let source = EtcdSource::new("http://127.0.0.1:2379", "/roxy/").unwrap();
+        let mirror = EtcdMirror::new(source, PathBuf::from("/mirror"), tx, true);
+        let key = |k: &str| STANDARD.encode(k);
+
+        assert_eq!(mirror.key_path(&key("/roxy/app.json")), Some(PathBuf::from("/mirror/app.json")));
+        assert_eq!(mirror.key_path(&key("/roxy/sub/app.json")), Some(PathBuf::from("/mirror/sub/app.json")));
+        assert_eq!(mirror.key_path(&key("/roxy/../escape.json")), None);
+        assert_eq!(mirror.key_path(&key("/roxy/")), None);
+        assert_eq!(mirror.key_path(&key("/other/app.json")), None);
+    }
+}
@@ -1,9 +1,11 @@
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 use crate::middleware::config::{MiddlewareConfig, MiddlewareType};
-use super::error::SettingsError;
+use crate::routing_v2::StickyCookieConfig;
+use super::error::{SettingsError, ValidationError};
+use super::types::ValidTimeout;
 use super::Result;
 use super::converter::{labels_to_json, json_to_labels};
 
@@ -51,12 +53,23 @@ pub struct RouterConfig {
     
     /// 서비스 이름
     pub service: String,
+
+    /// 라우트 매칭 우선순위
+    /// 여러 라우터가 같은 요청에 매칭되면 높은 쪽이 우선합니다.
+    /// 지정하지 않으면 경로 패턴 길이(`PathMatcher::priority`)를 사용합니다.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub priority: Option<u32>,
 }
 
 /// 서비스 설정
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ServiceConfig {
     pub loadbalancer: LoadBalancerConfig,
+
+    /// 스티키 세션 쿠키 설정
+    /// 지정하면 선택된 백엔드 주소를 쿠키에 기록하고 이후 요청을 같은 백엔드로 보냅니다.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sticky_sessions: Option<StickyCookieConfig>,
 }
 
 /// 로드밸런서 설정
@@ -86,8 +99,12 @@ pub struct HealthConfig {
     #[serde(default = "default_interval")]
     pub interval: u64,
     
-    #[serde(default = "default_timeout")]
-    pub timeout: u64,
+    #[serde(
+        default = "default_timeout",
+        deserialize_with = "ValidTimeout::deserialize_secs",
+        serialize_with = "ValidTimeout::serialize_secs"
+    )]
+    pub timeout: ValidTimeout,
     
     #[serde(default = "default_max_failures")]
     pub max_failures: u32,
@@ -117,8 +134,8 @@ fn default_interval() -> u64 {
     30
 }
 
-fn default_timeout() -> u64 {
-    5
+fn default_timeout() -> ValidTimeout {
+    ValidTimeout::from_secs(5).expect("기본 타임아웃은 0이 아님")
 }
 
 fn default_max_failures() -> u32 {
@@ -151,7 +168,16 @@ impl JsonConfig {
             error: e,
         })?;
 
-        let mut config: Self = serde_json::from_str(&content)
+        let mut value: serde_json::Value = serde_json::from_str(&content)
+            .map_err(|e| SettingsError::JsonParseError { 
+                source: e 
+            })?;
+
+        // 다른 파일의 미들웨어를 참조하는 `$ref` 항목 해석
+        let base_dir = path.as_ref().parent().unwrap_or_else(|| Path::new("."));
+        RefResolver::new(base_dir).resolve(&mut value).await?;
+
+        let mut config: Self = serde_json::from_value(value)
             .map_err(|e| SettingsError::JsonParseError { 
                 source: e 
             })?;
@@ -252,6 +278,13 @@ impl JsonConfig {
         json_to_labels(&json_value, prefix)
     }
     
+    /// 설정을 TOML 문자열로 내보냅니다.
+    pub fn to_toml_string(&self) -> Result<String> {
+        toml::to_string_pretty(self).map_err(|e| SettingsError::TomlSerializeError {
+            source: e,
+        })
+    }
+    
     /// Docker 라벨에서 JSON 설정 생성
     pub fn from_docker_labels(labels: &HashMap<String, String>, prefix: &str) -> Self {
         let json = labels_to_json(labels, prefix);
@@ -274,13 +307,8 @@ impl JsonConfig {
                         let parts: Vec<&str> = key.split('.').collect();
                         if parts.len() >= 5 {
                             let middleware_name = parts[3];
-                            let middleware_type = match value.as_str() {
-                                "cors" => MiddlewareType::Cors,
-                                "basic-auth" => MiddlewareType::BasicAuth,
-                                "ratelimit" => MiddlewareType::RateLimit,
-                                "headers" => MiddlewareType::Headers,
-                                _ => MiddlewareType::Headers,
-                            };
+                            let middleware_type = value.parse()
+                                .unwrap_or(MiddlewareType::Headers);
                             
                             // 미들웨어 생성 또는 업데이트
                             if !config.middlewares.contains_key(middleware_name) {
@@ -306,6 +334,7 @@ impl JsonConfig {
                                     rule: value.clone(),
                                     middlewares: None,
                                     service: "default".to_string(),
+                                    priority: None,
                                 });
                             } else if let Some(router) = config.routers.get_mut(router_name) {
                                 router.rule = value.clone();
@@ -342,6 +371,18 @@ impl JsonConfig {
                         }
                     }
                     
+                    // 라우터 우선순위 추출
+                    if key.ends_with(".priority") && key.contains(".routers.") {
+                        let parts: Vec<&str> = key.split('.').collect();
+                        if parts.len() >= 5 {
+                            let router_name = parts[3];
+
+                            if let Some(router) = config.routers.get_mut(router_name) {
+                                router.priority = value.trim().parse().ok();
+                            }
+                        }
+                    }
+
                     // 서비스 설정 추출
                     if key.contains(".service") && key.contains(".routers.") {
                         let parts: Vec<&str> = key.split('.').collect();
@@ -375,38 +416,182 @@ impl JsonConfig {
     }
     
     /// 설정 유효성 검증
+    ///
+    /// 발견한 모든 오류를 실패한 필드의 JSON 포인터와 함께 `SettingsError::ValidationErrors`로 반환합니다.
     pub fn validate(&self) -> Result<()> {
+        let mut errors = Vec::new();
+
         // 1. 버전 검증
         if !["1.0"].contains(&self.version.as_str()) {
-            return Err(SettingsError::InvalidConfig(
-                format!("지원하지 않는 버전: {}", self.version)
+            errors.push(ValidationError::new(
+                &["version"],
+                format!("지원하지 않는 버전: {}", self.version),
             ));
         }
         
         // 2. 라우터-서비스 참조 검증
         for (router_name, router) in &self.routers {
             if !self.services.contains_key(&router.service) {
-                return Err(SettingsError::InvalidConfig(
+                errors.push(ValidationError::new(
+                    &["routers", router_name, "service"],
                     format!("라우터 '{}'가 존재하지 않는 서비스 '{}'를 참조합니다", 
-                            router_name, router.service)
+                            router_name, router.service),
                 ));
             }
             
             // 3. 라우터-미들웨어 참조 검증
             if let Some(middlewares) = &router.middlewares {
-                for middleware in middlewares {
+                for (index, middleware) in middlewares.iter().enumerate() {
                     if !self.middlewares.contains_key(middleware) {
-                        return Err(SettingsError::InvalidConfig(
+                        errors.push(ValidationError::new(
+                            &["routers", router_name, "middlewares", &index.to_string()],
                             format!("라우터 '{}'가 존재하지 않는 미들웨어 '{}'를 참조합니다", 
-                                    router_name, middleware)
+                                    router_name, middleware),
                         ));
                     }
                 }
             }
         }
+
+        // 4. 스티키 세션 쿠키 이름 검증
+        for (service_name, service) in &self.services {
+            if let Some(sticky) = service.sticky_sessions.as_ref()
+                .filter(|sticky| !StickyCookieConfig::is_valid_cookie_name(&sticky.cookie_name))
+            {
+                errors.push(ValidationError::new(
+                    &["services", service_name, "sticky_sessions", "cookie_name"],
+                    format!("서비스 '{}'의 쿠키 이름 '{}'이 올바른 토큰이 아닙니다", service_name, sticky.cookie_name),
+                ));
+            }
+        }
+
+        // 5. 라우터 간 중복 규칙 검증 (같은 규칙이면 어느 라우터로 갈지 정해지지 않음)
+        let mut routers_by_rule: HashMap<&str, Vec<&str>> = HashMap::new();
+        for (router_name, router) in &self.routers {
+            routers_by_rule.entry(router.rule.trim()).or_default().push(router_name);
+        }
+        for (rule, mut router_names) in routers_by_rule {
+            if router_names.len() < 2 {
+                continue;
+            }
+            router_names.sort();
+            for router_name in &router_names {
+                errors.push(ValidationError::new(
+                    &["routers", router_name, "rule"],
+                    format!("라우터 {:?}가 같은 규칙 '{}'을 사용합니다", router_names, rule),
+                ));
+            }
+        }
         
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            // HashMap 순회 순서와 무관하게 일정한 순서로 보고
+            errors.sort_by(|a, b| a.json_pointer.cmp(&b.json_pointer));
+            Err(SettingsError::ValidationErrors(errors))
+        }
+    }
+}
+
+/// 라우터 미들웨어 목록의 `$ref` 참조를 해석합니다.
+///
+/// `{"$ref": "shared.json#/middlewares/cors"}` 항목은 참조한 파일의 미들웨어 정의를
+/// 현재 설정의 `middlewares`로 가져오고, 목록에는 미들웨어 이름(`cors`)만 남깁니다.
+/// 파일 경로는 참조하는 설정 파일의 디렉토리 기준이며, 같은 파일은 한 번만 읽습니다.
+/// 같은 이름의 미들웨어가 이미 있는데 정의가 다르면 `SettingsError::InvalidConfig`를 반환합니다.
+struct RefResolver {
+    base_dir: PathBuf,
+    cache: HashMap<PathBuf, serde_json::Value>,
+}
+
+impl RefResolver {
+    const REF_KEY: &'static str = "$ref";
+
+    fn new(base_dir: &Path) -> Self {
+        Self {
+            base_dir: base_dir.to_path_buf(),
+            cache: HashMap::new(),
+        }
+    }
+
+    async fn resolve(&mut self, config: &mut serde_json::Value) -> Result<()> {
+        let mut resolved = Vec::new();
+
+        if let Some(routers) = config.get_mut("routers").and_then(|r| r.as_object_mut()) {
+            for router in routers.values_mut() {
+                let Some(entries) = router.get_mut("middlewares").and_then(|m| m.as_array_mut()) else {
+                    continue;
+                };
+                for entry in entries.iter_mut() {
+                    let Some(reference) = entry.get(Self::REF_KEY).and_then(|r| r.as_str()) else {
+                        continue;
+                    };
+                    let reference = reference.to_string();
+                    let (name, middleware) = self.load(&reference).await?;
+                    *entry = serde_json::Value::String(name.clone());
+                    resolved.push((reference, name, middleware));
+                }
+            }
+        }
+
+        if resolved.is_empty() {
+            return Ok(());
+        }
+
+        let middlewares = config.as_object_mut()
+            .ok_or_else(|| SettingsError::InvalidConfig("JSON 설정 최상위가 객체가 아님".to_string()))?
+            .entry("middlewares")
+            .or_insert_with(|| serde_json::Value::Object(Default::default()));
+        let middlewares = middlewares.as_object_mut()
+            .ok_or_else(|| SettingsError::InvalidConfig("middlewares가 객체가 아님".to_string()))?;
+        for (reference, name, middleware) in resolved {
+            // 같은 이름의 정의가 이미 있으면 내용이 같을 때만 허용
+            match middlewares.get(&name) {
+                Some(existing) if *existing != middleware => {
+                    return Err(SettingsError::InvalidConfig(format!(
+                        "$ref '{}'의 미들웨어 '{}'가 같은 이름의 다른 정의와 충돌함", reference, name
+                    )));
+                }
+                Some(_) => {}
+                None => {
+                    middlewares.insert(name, middleware);
+                }
+            }
+        }
+
         Ok(())
     }
+
+    /// `파일#/JSON 포인터` 형식의 참조를 읽어 (미들웨어 이름, 정의)를 반환합니다.
+    async fn load(&mut self, reference: &str) -> Result<(String, serde_json::Value)> {
+        let (file, pointer) = reference.split_once('#')
+            .ok_or_else(|| SettingsError::InvalidConfig(format!("$ref '{}'에 '#' 포인터가 없음", reference)))?;
+        let name = pointer.strip_prefix("/middlewares/")
+            .filter(|name| !name.is_empty() && !name.contains('/'))
+            .map(|name| name.replace("~1", "/").replace("~0", "~"))
+            .ok_or_else(|| SettingsError::InvalidConfig(format!(
+                "$ref '{}'는 '#/middlewares/<이름>' 형식이어야 함", reference
+            )))?;
+
+        let path = self.base_dir.join(file);
+        if !self.cache.contains_key(&path) {
+            let content = tokio::fs::read_to_string(&path).await.map_err(|e| SettingsError::FileError {
+                path: path.to_string_lossy().to_string(),
+                error: e,
+            })?;
+            let document: serde_json::Value = serde_json::from_str(&content)
+                .map_err(|e| SettingsError::JsonParseError { source: e })?;
+            self.cache.insert(path.clone(), document);
+        }
+
+        let middleware = self.cache[&path].pointer(pointer)
+            .cloned()
+            .ok_or_else(|| SettingsError::InvalidConfig(format!(
+                "$ref '{}'가 가리키는 미들웨어를 찾을 수 없음", reference
+            )))?;
+
+        Ok((name, middleware))
+    }
 }
 
 #[cfg(test)]
@@ -416,6 +601,85 @@ mod tests {
     use super::*;
     use std::path::PathBuf;
 
+    #[test]
+    fn test_to_toml_string_round_trip() {
+        let mut config = JsonConfig::default();
+        let mut middleware = MiddlewareConfig::new(MiddlewareType::BasicAuth);
+        middleware.settings.insert("basicAuth.users".to_string(), "admin:hash".to_string());
+        config.middlewares.insert("auth".to_string(), middleware);
+        config.routers.insert("api".to_string(), RouterConfig {
+            rule: "Host(`api.example.com`)".to_string(),
+            middlewares: Some(vec!["auth".to_string()]),
+            service: "api".to_string(),
+            priority: Some(10),
+        });
+
+        let toml_str = config.to_toml_string().unwrap();
+        assert!(toml_str.contains("middleware_type = \"basic-auth\""));
+
+        let parsed: JsonConfig = toml::from_str(&toml_str).unwrap();
+        assert_eq!(parsed.version, config.version);
+        assert_eq!(parsed.middlewares["auth"].middleware_type, MiddlewareType::BasicAuth);
+        assert_eq!(parsed.middlewares["auth"].settings["basicAuth.users"], "admin:hash");
+        assert_eq!(parsed.routers["api"].rule, "Host(`api.example.com`)");
+        assert_eq!(parsed.routers["api"].middlewares, Some(vec!["auth".to_string()]));
+        assert_eq!(parsed.routers["api"].priority, Some(10));
+    }
+
+    #[test]
+    fn test_service_sticky_sessions() {
+        let config: JsonConfig = serde_json::from_str(r#"{
+            "services": {
+                "web": {
+                    "loadbalancer": { "server": { "port": 8080 } },
+                    "sticky_sessions": { "cookie_name": "srv", "secure": true, "same_site": "Strict" }
+                },
+                "api": {
+                    "loadbalancer": { "server": { "port": 9090 } }
+                }
+            }
+        }"#).unwrap();
+
+        let sticky = config.services["web"].sticky_sessions.as_ref().unwrap();
+        assert_eq!(sticky.cookie_name, "srv");
+        assert!(sticky.http_only);
+        assert!(sticky.secure);
+        assert_eq!(sticky.same_site, crate::routing_v2::SameSite::Strict);
+        assert!(config.services["api"].sticky_sessions.is_none());
+        assert!(config.validate().is_ok());
+
+        let config: JsonConfig = serde_json::from_str(r#"{
+            "services": {
+                "web": {
+                    "loadbalancer": { "server": { "port": 8080 } },
+                    "sticky_sessions": { "cookie_name": "srv; Path=/admin" }
+                }
+            }
+        }"#).unwrap();
+        let Err(SettingsError::ValidationErrors(errors)) = config.validate() else {
+            panic!("잘못된 쿠키 이름이 검증을 통과함");
+        };
+        assert_eq!(errors[0].json_pointer, "/services/web/sticky_sessions/cookie_name");
+    }
+
+    #[test]
+    fn test_health_timeout() {
+        let config: JsonConfig = serde_json::from_str(r#"{
+            "health": { "http": { "path": "/health" }, "timeout": 3 }
+        }"#).unwrap();
+        let health = config.health.unwrap();
+        assert_eq!(health.timeout.as_secs(), 3);
+        assert_eq!(serde_json::to_value(&health).unwrap()["timeout"], 3);
+
+        let config: JsonConfig = serde_json::from_str(r#"{ "health": { "http": { "path": "/health" } } }"#).unwrap();
+        assert_eq!(config.health.unwrap().timeout.as_secs(), 5);
+
+        // 0초 타임아웃은 거부
+        assert!(serde_json::from_str::<JsonConfig>(r#"{
+            "health": { "http": { "path": "/health" }, "timeout": 0 }
+        }"#).is_err());
+    }
+
     #[test]
     fn test_json_config_default() {
         let config = JsonConfig::default();
@@ -447,11 +711,12 @@ mod tests {
         let mut config = JsonConfig::default();
         config.version = "2.0".to_string();
         let result = config.validate();
-        assert!(result.is_err());
-        if let Err(SettingsError::InvalidConfig(_)) = result {
-            // 예상대로 오류 발생
-        } else {
-            panic!("Expected InvalidConfig error");
+        match result {
+            Err(SettingsError::ValidationErrors(errors)) => {
+                assert_eq!(errors.len(), 1);
+                assert_eq!(errors[0].json_pointer, "/version");
+            }
+            other => panic!("Expected ValidationErrors, got {:?}", other),
         }
     }
 
@@ -464,14 +729,16 @@ mod tests {
             rule: "Host(`example.com`)".to_string(),
             middlewares: None,
             service: "non-existent-service".to_string(),
+            priority: None,
         });
         
         let result = config.validate();
-        assert!(result.is_err());
-        if let Err(SettingsError::InvalidConfig(_)) = result {
-            // 예상대로 오류 발생
-        } else {
-            panic!("Expected InvalidConfig error");
+        match result {
+            Err(SettingsError::ValidationErrors(errors)) => {
+                assert_eq!(errors.len(), 1);
+                assert_eq!(errors[0].json_pointer, "/routers/test-router/service");
+            }
+            other => panic!("Expected ValidationErrors, got {:?}", other),
         }
     }
 
@@ -486,7 +753,8 @@ mod tests {
                     port: 80,
                     weight: 1,
                 }
-            }
+            },
+            sticky_sessions: None,
         });
         
         // 존재하지 않는 미들웨어를 참조하는 라우터 추가
@@ -494,14 +762,16 @@ mod tests {
             rule: "Host(`example.com`)".to_string(),
             middlewares: Some(vec!["non-existent-middleware".to_string()]),
             service: "test-service".to_string(),
+            priority: None,
         });
         
         let result = config.validate();
-        assert!(result.is_err());
-        if let Err(SettingsError::InvalidConfig(_)) = result {
-            // 예상대로 오류 발생
-        } else {
-            panic!("Expected InvalidConfig error");
+        match result {
+            Err(SettingsError::ValidationErrors(errors)) => {
+                assert_eq!(errors.len(), 1);
+                assert_eq!(errors[0].json_pointer, "/routers/test-router/middlewares/0");
+            }
+            other => panic!("Expected ValidationErrors, got {:?}", other),
         }
     }
 
@@ -524,7 +794,8 @@ mod tests {
                     port: 80,
                     weight: 1,
                 }
-            }
+            },
+            sticky_sessions: None,
         });
         
         // 라우터 추가
@@ -532,6 +803,7 @@ mod tests {
             rule: "Host(`example.com`)".to_string(),
             middlewares: Some(vec!["test-middleware".to_string()]),
             service: "test-service".to_string(),
+            priority: None,
         });
         
         // 유효한 설정이므로 오류가 없어야 함
@@ -588,6 +860,7 @@ mod tests {
             rule: "Host(`api.example.com`)".to_string(),
             middlewares: Some(vec!["cors".to_string()]),
             service: "api-service".to_string(),
+            priority: None,
         });
         
         // Docker 라벨로 변환
@@ -638,4 +911,139 @@ mod tests {
             panic!("api router not found");
         }
     }
-} 
\ No newline at end of file
+
+    #[test]
+    fn test_validate_collects_all_errors() {
+        let mut config = JsonConfig {
+            version: "2.0".to_string(),
+            ..Default::default()
+        };
+        config.routers.insert("api/v1".to_string(), RouterConfig {
+            rule: "Host(`example.com`)".to_string(),
+            middlewares: None,
+            service: "missing".to_string(),
+            priority: None,
+        });
+
+        let Err(SettingsError::ValidationErrors(errors)) = config.validate() else {
+            panic!("Expected ValidationErrors");
+        };
+        let pointers: Vec<_> = errors.iter().map(|e| e.json_pointer.as_str()).collect();
+        assert_eq!(pointers, vec!["/routers/api~1v1/service", "/version"]);
+    }
+
+    #[tokio::test]
+    async fn test_from_file_resolves_middleware_refs() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("shared.json"), r#"{
+            "middlewares": {
+                "cors": { "middleware_type": "cors", "enabled": true, "settings": {} }
+            }
+        }"#).unwrap();
+        let path = dir.path().join("app.json");
+        std::fs::write(&path, r#"{
+            "routers": {
+                "api": {
+                    "rule": "Host(`api.local`)",
+                    "service": "api",
+                    "middlewares": [{ "$ref": "shared.json#/middlewares/cors" }]
+                },
+                "web": {
+                    "rule": "Host(`web.local`)",
+                    "service": "web",
+                    "middlewares": ["local", { "$ref": "shared.json#/middlewares/cors" }]
+                }
+            }
+        }"#).unwrap();
+
+        let config = JsonConfig::from_file(&path).await.unwrap();
+        assert_eq!(config.routers["api"].middlewares, Some(vec!["cors".to_string()]));
+        assert_eq!(
+            config.routers["web"].middlewares,
+            Some(vec!["local".to_string(), "cors".to_string()])
+        );
+        assert_eq!(config.middlewares["cors"].middleware_type, MiddlewareType::Cors);
+
+        // 존재하지 않는 미들웨어 참조는 오류
+        std::fs::write(&path, r#"{
+            "routers": {
+                "api": {
+                    "rule": "Host(`api.local`)",
+                    "service": "api",
+                    "middlewares": [{ "$ref": "shared.json#/middlewares/missing" }]
+                }
+            }
+        }"#).unwrap();
+        assert!(matches!(
+            JsonConfig::from_file(&path).await,
+            Err(SettingsError::InvalidConfig(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_from_file_rejects_conflicting_middleware_refs() {
+        let dir = tempfile::tempdir().unwrap();
+        let cors = |enabled: bool| format!(
+            r#"{{ "middlewares": {{ "cors": {{ "middleware_type": "cors", "enabled": {}, "settings": {{}} }} }} }}"#,
+            enabled
+        );
+        std::fs::write(dir.path().join("a.json"), cors(true)).unwrap();
+        std::fs::write(dir.path().join("b.json"), cors(false)).unwrap();
+        std::fs::write(dir.path().join("same.json"), cors(true)).unwrap();
+        let path = dir.path().join("app.json");
+        let app = |refs: &[&str], local: &str| format!(
+            r#"{{
+                "routers": {{
+                    "api": {{ "rule": "Host(`api.local`)", "service": "api", "middlewares": [{}] }}
+                }},
+                "middlewares": {{ {} }}
+            }}"#,
+            refs.iter().map(|r| format!(r#"{{ "$ref": "{}" }}"#, r)).collect::<Vec<_>>().join(", "),
+            local
+        );
+
+        // 서로 다른 파일의 같은 이름, 다른 정의는 오류
+        std::fs::write(&path, app(&["a.json#/middlewares/cors", "b.json#/middlewares/cors"], "")).unwrap();
+        let Err(SettingsError::InvalidConfig(message)) = JsonConfig::from_file(&path).await else {
+            panic!("Expected InvalidConfig");
+        };
+        assert!(message.contains("b.json#/middlewares/cors"), "{}", message);
+
+        // 현재 파일의 다른 정의와 충돌해도 오류
+        let local = r#""cors": { "middleware_type": "cors", "enabled": false, "settings": {} }"#;
+        std::fs::write(&path, app(&["a.json#/middlewares/cors"], local)).unwrap();
+        assert!(matches!(JsonConfig::from_file(&path).await, Err(SettingsError::InvalidConfig(_))));
+
+        // 정의가 같으면 허용
+        std::fs::write(&path, app(&["a.json#/middlewares/cors", "same.json#/middlewares/cors"], "")).unwrap();
+        let config = JsonConfig::from_file(&path).await.unwrap();
+        assert_eq!(config.routers["api"].middlewares, Some(vec!["cors".to_string(), "cors".to_string()]));
+    }
+
+    #[test]
+    fn test_validate_duplicate_router_rules() {
+        let mut config = JsonConfig::default();
+        config.services.insert("api".to_string(), ServiceConfig {
+            loadbalancer: LoadBalancerConfig {
+                server: ServerConfig { port: 8080, weight: 1 },
+            },
+            sticky_sessions: None,
+        });
+        let rule = "Host(`example.com`) && PathPrefix(`/api`)";
+        for (name, rule) in [("api-a", rule), ("api-b", rule), ("web", "Host(`example.com`)")] {
+            config.routers.insert(name.to_string(), RouterConfig {
+                rule: rule.to_string(),
+                middlewares: None,
+                service: "api".to_string(),
+                priority: None,
+            });
+        }
+
+        let Err(SettingsError::ValidationErrors(errors)) = config.validate() else {
+            panic!("Expected ValidationErrors");
+        };
+        let pointers: Vec<_> = errors.iter().map(|e| e.json_pointer.as_str()).collect();
+        assert_eq!(pointers, vec!["/routers/api-a/rule", "/routers/api-b/rule"]);
+        assert!(errors[0].message.contains(r#"["api-a", "api-b"]"#), "{}", errors[0].message);
+    }
+}
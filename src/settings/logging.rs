@@ -57,6 +57,11 @@ pub struct LogSettings {
     pub format: LogFormat,
     pub level: Level,
     pub output: LogOutput,
+    /// DEBUG 이하 로그의 샘플링 비율 (0.0–1.0, 기본값: 1.0)
+    /// INFO 이상은 샘플링하지 않습니다.
+    pub debug_sample_rate: f32,
+    /// TRACE 레벨에서 기록할 요청 바디 최대 바이트 수 (0이면 기록하지 않음, 기본값: 1024)
+    pub request_body_max_bytes: usize,
 }
 
 impl LogSettings {
@@ -65,6 +70,8 @@ impl LogSettings {
             format: parse_env_var("PROXY_LOG_FORMAT", || LogFormat::Text)?,
             level: parse_log_level(env::var("PROXY_LOG_LEVEL").unwrap_or_else(|_| "info".to_string()))?,
             output: parse_log_output()?,
+            debug_sample_rate: parse_debug_sample_rate()?,
+            request_body_max_bytes: parse_env_var("PROXY_LOG_REQUEST_BODY_MAX_BYTES", default_request_body_max_bytes)?,
         })
     }
 }
@@ -75,6 +82,8 @@ impl Default for LogSettings {
             format: LogFormat::default(),
             level: Level::INFO,
             output: LogOutput::default(),
+            debug_sample_rate: default_debug_sample_rate(),
+            request_body_max_bytes: default_request_body_max_bytes(),
         }
     }
 }
@@ -94,6 +103,18 @@ fn parse_log_level(level: String) -> Result<Level, SettingsError> {
     }
 }
 
+fn parse_debug_sample_rate() -> Result<f32, SettingsError> {
+    let rate = parse_env_var("PROXY_LOG_DEBUG_SAMPLE_RATE", default_debug_sample_rate)?;
+    if !(0.0..=1.0).contains(&rate) {
+        return Err(SettingsError::EnvVarInvalid {
+            var_name: "PROXY_LOG_DEBUG_SAMPLE_RATE".to_string(),
+            value: rate.to_string(),
+            reason: "샘플링 비율은 0.0에서 1.0 사이여야 합니다".to_string(),
+        });
+    }
+    Ok(rate)
+}
+
 fn parse_log_output() -> Result<LogOutput, SettingsError> {
     match env::var("PROXY_LOG_OUTPUT") {
         Ok(output) => match output.to_lowercase().as_str() {
@@ -122,6 +143,10 @@ impl<'de> Deserialize<'de> for LogSettings {
             level: String,
             #[serde(default)]
             output: LogOutput,
+            #[serde(default = "default_debug_sample_rate")]
+            debug_sample_rate: f32,
+            #[serde(default = "default_request_body_max_bytes")]
+            request_body_max_bytes: usize,
         }
 
         let helper = Helper::deserialize(deserializer)?;
@@ -138,10 +163,20 @@ impl<'de> Deserialize<'de> for LogSettings {
             format: helper.format,
             level,
             output: helper.output,
+            debug_sample_rate: helper.debug_sample_rate.clamp(0.0, 1.0),
+            request_body_max_bytes: helper.request_body_max_bytes,
         })
     }
 }
 
 fn default_log_level_string() -> String {
     "info".to_string()
-} 
\ No newline at end of file
+}
+
+fn default_debug_sample_rate() -> f32 {
+    1.0
+}
+
+fn default_request_body_max_bytes() -> usize {
+    1024
+}
@@ -1,22 +1,30 @@
-use std::{collections::HashMap, env, fs, path::Path};
+use std::{collections::{HashMap, HashSet}, env, fs, path::Path};
 use serde::Deserialize;
 use tracing::{debug, info};
 use crate::middleware::config::{MiddlewareConfig, MiddlewareType};
+use crate::middleware::rate_limit::RateLimitStrategy;
+use crate::middleware::cache::CacheConfig;
 
 mod server;
 pub mod logging;
-mod tls;
+pub(crate) mod tls;
+mod startup;
 mod error;
 pub mod docker;
 pub mod json;
 pub mod watcher;
+mod etcd;
 pub mod converter;
+pub mod types;
+pub mod overlay;
 
 pub use server::ServerSettings;
 pub use logging::LogSettings;
 pub use tls::TlsSettings;
+pub use startup::StartupSettings;
+pub use overlay::PartialSettings;
 pub use docker::DockerSettings;
-pub use error::SettingsError;
+pub use error::{SettingsError, ValidationError};
 pub use json::JsonConfig;
 pub use converter::{label_key_to_json_path, convert_value, labels_to_json, json_to_labels};
 
@@ -39,6 +47,10 @@ pub struct Settings {
 
     #[serde(default)]
     pub docker: DockerSettings,
+
+    // 시작 설정
+    #[serde(default)]
+    pub startup: StartupSettings,
     
     /// 미들웨어 설정
     #[serde(default)]
@@ -47,6 +59,31 @@ pub struct Settings {
     /// 라우터-미들웨어 매핑
     #[serde(default)]
     pub router_middlewares: HashMap<String, Vec<String>>,
+
+    /// 라우터별 라우트 매칭 우선순위 (JSON 설정의 `priority`)
+    ///
+    /// 라우트의 라우터 이름(`BackendService::router_name`)으로 찾으므로 설정 ID 없이 라우터 이름을 키로 사용합니다.
+    #[serde(default)]
+    pub router_priorities: HashMap<String, u32>,
+
+    /// 마지막으로 병합한 컨테이너 라벨
+    ///
+    /// 다음 병합 때 `previous_labels`로 넘겨 삭제된 라벨의 미들웨어를 제거합니다.
+    #[serde(skip)]
+    pub docker_labels: HashMap<String, String>,
+}
+
+/// Docker 라벨 병합 결과
+///
+/// 어떤 라벨이 현재 설정에 반영되었는지 추적할 수 있도록 이름을 정렬해 기록합니다.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LabelMergeDiff {
+    /// 라벨로 추가되거나 갱신된 미들웨어
+    pub added_middlewares: Vec<String>,
+    /// 같은 설정이 이미 있어 건너뛴 미들웨어
+    pub skipped_middlewares: Vec<String>,
+    /// 라벨로 새로 매핑된 라우터
+    pub added_routers: Vec<String>,
 }
 
 impl Default for Settings {
@@ -56,8 +93,11 @@ impl Default for Settings {
             logging: LogSettings::default(),
             tls: TlsSettings::default(),
             docker: DockerSettings::default(),
+            startup: StartupSettings::default(),
             middleware: HashMap::new(),
             router_middlewares: HashMap::new(),
+            router_priorities: HashMap::new(),
+            docker_labels: HashMap::new(),
         }
     }
 }
@@ -65,13 +105,39 @@ impl Default for Settings {
 impl Settings {
     pub async fn load() -> Result<Self> {
         // 기본 설정만 로드 (Docker 라벨은 ServerManager에서 처리)
-        if let Ok(config_path) = env::var("PROXY_CONFIG_FILE") {
-            Self::from_toml_file(&config_path).await
+        let settings = if let Ok(config_path) = env::var("PROXY_CONFIG_FILE") {
+            Self::from_toml_file(&config_path).await?
         } else {
-            Self::from_env().await
+            Self::read_env()?
+        };
+
+        // ConfigMap/Secret 등 추가 설정 계층을 순서대로 덮어쓴 뒤 최종 설정을 검증
+        // (계층이 기본 설정의 잘못된 값을 고칠 수 있으므로 병합 전에는 검증하지 않음)
+        match env::var("PROXY_CONFIG_OVERLAYS") {
+            Ok(paths) => settings.overlay_files(paths.split(',').map(str::trim).filter(|p| !p.is_empty())).await,
+            Err(_) => {
+                settings.validate().await?;
+                Ok(settings)
+            }
         }
     }
 
+    /// TOML 설정 계층 파일들을 순서대로 덮어쓴 뒤 검증합니다.
+    pub async fn overlay_files<P: AsRef<Path>>(&self, paths: impl IntoIterator<Item = P>) -> Result<Self> {
+        let mut settings = self.clone();
+        for path in paths {
+            let content = fs::read_to_string(&path).map_err(|e| SettingsError::FileError {
+                path: path.as_ref().to_string_lossy().to_string(),
+                error: e,
+            })?;
+            settings = settings.overlay(&PartialSettings::from_toml_str(&content)?)?;
+            info!(path = %path.as_ref().display(), "설정 계층 적용");
+        }
+
+        settings.validate().await?;
+        Ok(settings)
+    }
+
     pub async fn from_toml_file<P: AsRef<Path>>(path: P) -> Result<Self> {
         let content = fs::read_to_string(&path).map_err(|e| SettingsError::FileError {
             path: path.as_ref().to_string_lossy().to_string(),
@@ -85,34 +151,64 @@ impl Settings {
     }
 
     pub async fn from_env() -> Result<Self> {
-        let settings = Self {
-            server: ServerSettings::from_env()?,
+        let settings = Self::read_env()?;
+
+        // 설정 생성 시점에 바로 검증 (파일 검사는 `validate_async`에서 수행)
+        settings.validate_sync()?;
+        Ok(settings)
+    }
+
+    /// 환경 변수에서 설정을 읽기만 하고 검증하지 않습니다.
+    ///
+    /// 설정 계층이 기본 설정의 잘못된 값을 고칠 수 있으므로 `load`는 계층을 덮어쓴 뒤에 검증합니다.
+    fn read_env() -> Result<Self> {
+        // 인라인 PEM 임시 파일 경로를 서버 설정에 넘기기 위해 TLS 설정을 먼저 읽음
+        let tls = TlsSettings::from_env()?;
+        Ok(Self {
+            server: ServerSettings::read_env_with_tls(&tls)?,
             logging: LogSettings::from_env()?,
-            tls: TlsSettings::from_env()?,
-            docker: DockerSettings::from_env()?,
+            tls,
+            docker: DockerSettings::read_env()?,
+            startup: StartupSettings::from_env()?,
             middleware: HashMap::new(),
             router_middlewares: HashMap::new(),
-        };
-
-        // 설정 생성 시점에 바로 검증
-        settings.validate().await?;
-        Ok(settings)
+            router_priorities: HashMap::new(),
+            docker_labels: HashMap::new(),
+        })
     }
 
-    /// 설정 유효성 검증
+    /// 설정 유효성 검증 (`validate_sync` 후 `validate_async`)
     pub async fn validate(&self) -> Result<()> {
+        self.validate_sync()?;
+        self.validate_async().await
+    }
+
+    /// 파일 시스템 접근이 필요한 검증 (TLS 인증서/키 파일)
+    pub async fn validate_async(&self) -> Result<()> {
+        self.tls.validate_files().await
+    }
+
+    /// 파일 시스템에 접근하지 않는 검증 (포트, 네트워크, 미들웨어 설정 등)
+    pub fn validate_sync(&self) -> Result<()> {
         self.server.validate()?;
-        self.tls.validate().await?;
+        self.tls.validate_sync()?;
         self.docker.validate()?;
 
         // 미들웨어 설정 검증
         for (name, middleware) in &self.middleware {
             if middleware.enabled {
-                match middleware.middleware_type {
+                match &middleware.middleware_type {
                     MiddlewareType::BasicAuth => {
-                        if !middleware.settings.contains_key("users") {
+                        // 사용자 목록이나 htpasswd 파일 중 하나는 있어야 함
+                        const CREDENTIAL_KEYS: [&str; 4] = [
+                            "basicAuth.users",
+                            "users",
+                            "basicAuth.htpasswdFile",
+                            "basicAuth.htpasswd.path",
+                        ];
+                        if !CREDENTIAL_KEYS.iter().any(|key| middleware.settings.contains_key(*key)) {
                             return Err(SettingsError::EnvVarMissing {
-                                var_name: format!("{}.users", name),
+                                var_name: format!("{}.basicAuth.users", name),
                             });
                         }
                     }
@@ -128,8 +224,10 @@ impl Settings {
                         }
                     }
                     MiddlewareType::RateLimit => {
-                        // Rate Limit 필수 설정 검증
-                        if !middleware.settings.contains_key("rateLimit.average") {
+                        // average는 period와 함께 지정할 때만 필수 (burst만 지정하는 것도 유효)
+                        if middleware.settings.contains_key("rateLimit.period")
+                            && !middleware.settings.contains_key("rateLimit.average")
+                        {
                             return Err(SettingsError::EnvVarMissing {
                                 var_name: format!("{}.rateLimit.average", name),
                             });
@@ -152,6 +250,43 @@ impl Settings {
                                 ));
                             }
                         }
+
+                        if let Some(period) = middleware.settings.get("rateLimit.period") {
+                            if !matches!(period.parse::<u64>(), Ok(secs) if secs > 0) {
+                                return Err(SettingsError::InvalidConfig(
+                                    format!("Invalid period value for rate limit: {}", period)
+                                ));
+                            }
+                        }
+
+                        if let Some(strategy) = middleware.settings.get("rateLimit.strategy") {
+                            strategy.parse::<RateLimitStrategy>()
+                                .map_err(SettingsError::InvalidConfig)?;
+                        }
+                    }
+                    MiddlewareType::AddPrefix => {
+                        if !middleware.settings.contains_key("addPrefix.prefix") {
+                            return Err(SettingsError::EnvVarMissing {
+                                var_name: format!("{}.addPrefix.prefix", name),
+                            });
+                        }
+                    }
+                    MiddlewareType::Redirect => {
+                        for key in ["redirect.from", "redirect.to"] {
+                            if !middleware.settings.contains_key(key) {
+                                return Err(SettingsError::EnvVarMissing {
+                                    var_name: format!("{}.{}", name, key),
+                                });
+                            }
+                        }
+                    }
+                    MiddlewareType::Cache => {
+                        // 캐시 설정은 모두 선택 항목이므로 값 형식만 검증
+                        CacheConfig::from_labels(&middleware.settings)
+                            .map_err(|e| SettingsError::InvalidConfig(e.to_string()))?;
+                    }
+                    MiddlewareType::Custom(_) => {
+                        // 플러그인 설정 검증은 팩토리가 생성 시 수행
                     }
                 }
             }
@@ -161,57 +296,133 @@ impl Settings {
     }
 
     // Docker 라벨을 통해 설정을 추가
-    pub fn merge_docker_labels(&mut self, labels: &HashMap<String, String>) -> Result<()> {
+    //
+    // `previous_labels`가 주어지면 이전 라벨에만 있던 미들웨어는 제거하고,
+    // 이전에도 있던 미들웨어는 새 라벨 값으로 갱신합니다.
+    // 이미 같은 설정으로 정의된 미들웨어는 중복으로 보지 않고 건너뜁니다.
+    pub fn merge_docker_labels(
+        &mut self,
+        labels: &HashMap<String, String>,
+        previous_labels: Option<&HashMap<String, String>>,
+    ) -> Result<LabelMergeDiff> {
         // 미들웨어 설정 파싱
-        let label_middlewares = MiddlewareConfig::from_labels(labels)
+        let prefixes = self.docker.label_prefixes();
+        let label_middlewares = MiddlewareConfig::from_labels(labels, &prefixes)
             .map_err(|e| SettingsError::InvalidConfig(e))?;
-        // 미들웨어 추가
+        let previous_names = previous_labels
+            .map(|previous| MiddlewareConfig::from_labels(previous, &prefixes)
+                .map_err(SettingsError::InvalidConfig))
+            .transpose()?
+            .map(|previous| previous.into_iter().map(|(name, _)| name).collect::<HashSet<_>>())
+            .unwrap_or_default();
+
+        // 라벨에서 삭제된 미들웨어 제거
+        for name in &previous_names {
+            if !label_middlewares.iter().any(|(current, _)| current == name) {
+                debug!(middleware = %name, "라벨에서 삭제된 미들웨어 제거");
+                self.remove_middleware(name);
+            }
+        }
+
+        // 미들웨어 추가 (이전 라벨에서 온 미들웨어는 갱신)
+        let mut diff = LabelMergeDiff::default();
         for (name, config) in label_middlewares {
-            self.add_middleware(name, config)?;
+            if self.middleware.get(&name) == Some(&config) {
+                diff.skipped_middlewares.push(name);
+                continue;
+            }
+            let override_existing = previous_names.contains(&name);
+            self.add_middleware_with_override(name.clone(), config, override_existing)?;
+            diff.added_middlewares.push(name);
         }
 
         // 라우터-미들웨어 매핑 파싱
-        self.router_middlewares = Self::parse_router_middlewares(labels);
-        
-        Ok(())
+        let router_middlewares = Self::parse_router_middlewares(labels, &self.docker.label_prefixes());
+        diff.added_routers = router_middlewares.keys()
+            .filter(|router| !self.router_middlewares.contains_key(*router))
+            .cloned()
+            .collect();
+        self.router_middlewares = router_middlewares;
+
+        diff.added_middlewares.sort();
+        diff.skipped_middlewares.sort();
+        diff.added_routers.sort();
+        debug!(
+            added_middlewares = ?diff.added_middlewares,
+            skipped_middlewares = ?diff.skipped_middlewares,
+            added_routers = ?diff.added_routers,
+            "Docker 라벨 병합 결과"
+        );
+
+        Ok(diff)
+    }
+
+    /// 마지막으로 병합한 컨테이너 라벨을 이전 라벨로 사용해 Docker 라벨을 병합하고 캐시를 갱신
+    pub fn merge_container_labels(&mut self, labels: &HashMap<String, String>) -> Result<LabelMergeDiff> {
+        let previous = std::mem::take(&mut self.docker_labels);
+        let result = self.merge_docker_labels(labels, Some(&previous));
+        self.docker_labels = if result.is_ok() { labels.clone() } else { previous };
+        result
     }
 
     pub fn add_middleware(&mut self, name: String, config: MiddlewareConfig) -> Result<()> {
-        if self.middleware.contains_key(&name) {
-            return Err(SettingsError::DuplicateMiddleware(name));
+        if let Some(existing) = self.middleware.get(&name) {
+            return Err(SettingsError::DuplicateMiddleware {
+                existing_type: existing.middleware_type.clone(),
+                name,
+            });
         }
         self.middleware.insert(name, config);
         Ok(())
     }
 
+    /// 미들웨어를 제거하고 제거된 설정을 반환합니다.
+    pub fn remove_middleware(&mut self, name: &str) -> Option<MiddlewareConfig> {
+        self.middleware.remove(name)
+    }
+
+    /// JSON 설정 파일의 미들웨어 이름을 설정 ID가 붙은 미들웨어 ID로 변환합니다.
+    fn json_middleware_id(config_id: &str, name: &str) -> Result<types::ValidMiddlewareId> {
+        types::ValidMiddlewareId::namespaced(config_id, name).ok_or_else(|| SettingsError::InvalidConfig(
+            format!("잘못된 미들웨어 이름 '{}' (설정 ID: {}): 비어 있지 않아야 하며 . , 공백은 사용할 수 없음", name, config_id)
+        ))
+    }
+
     /// 미들웨어 추가 (덮어쓰기 옵션)
     pub fn add_middleware_with_override(&mut self, name: String, config: MiddlewareConfig, override_existing: bool) -> Result<()> {
-        if self.middleware.contains_key(&name) && !override_existing {
-            return Err(SettingsError::DuplicateMiddleware(name));
+        if let Some(existing) = self.middleware.get(&name).filter(|_| !override_existing) {
+            return Err(SettingsError::DuplicateMiddleware {
+                existing_type: existing.middleware_type.clone(),
+                name,
+            });
         }
         self.middleware.insert(name, config);
         Ok(())
     }
 
-    fn parse_router_middlewares(labels: &HashMap<String, String>) -> HashMap<String, Vec<String>> {
+    /// 라벨 접두사를 순서대로 시도해 라우터-미들웨어 매핑을 파싱합니다. 같은 라우터는 앞선 접두사가 우선합니다.
+    fn parse_router_middlewares(labels: &HashMap<String, String>, prefixes: &[&str]) -> HashMap<String, Vec<String>> {
         let mut router_middlewares = HashMap::new();
         
-        for (key, value) in labels {
-            // rproxy.http.routers.{router}.middlewares=middleware1,middleware2
-            if let Some(router_config) = key.strip_prefix("rproxy.http.routers.") {
-                if router_config.ends_with(".middlewares") {
-                    let router_name = router_config.trim_end_matches(".middlewares");
-                    let middlewares: Vec<String> = value.split(',')
-                        .map(|s| s.trim().to_string())
-                        .collect();
-                    
-                    debug!(
-                        router = %router_name,
-                        middlewares = ?middlewares,
-                        "라우터 미들웨어 매핑 파싱"
-                    );
-                    
-                    router_middlewares.insert(router_name.to_string(), middlewares);
+        for prefix in prefixes {
+            let router_prefix = format!("{}http.routers.", prefix);
+            for (key, value) in labels {
+                // rproxy.http.routers.{router}.middlewares=middleware1,middleware2
+                if let Some(router_config) = key.strip_prefix(&router_prefix) {
+                    if router_config.ends_with(".middlewares") {
+                        let router_name = router_config.trim_end_matches(".middlewares");
+                        let middlewares: Vec<String> = value.split(',')
+                            .map(|s| s.trim().to_string())
+                            .collect();
+                        
+                        debug!(
+                            router = %router_name,
+                            middlewares = ?middlewares,
+                            "라우터 미들웨어 매핑 파싱"
+                        );
+                        
+                        router_middlewares.entry(router_name.to_string()).or_insert(middlewares);
+                    }
                 }
             }
         }
@@ -232,25 +443,27 @@ impl Settings {
         
         // 미들웨어 설정 병합
         for (name, middleware_config) in config.middlewares {
-            let full_name = if name.contains('.') {
-                name
-            } else {
-                format!("{}.{}", config_id, name)
-            };
+            let full_name = Self::json_middleware_id(&config_id, &name)?;
             
             debug!("미들웨어 추가: {}", full_name);
-            self.add_middleware_with_override(full_name, middleware_config, override_existing)?;
+            self.add_middleware_with_override(full_name.into(), middleware_config, override_existing)?;
         }
         
-        // 라우터-미들웨어 매핑 병합
+        // 라우터 우선순위와 라우터-미들웨어 매핑 병합
         for (router_name, router_config) in config.routers {
+            // 라우팅 테이블은 라우트의 라우터 이름으로 우선순위를 찾으므로 설정 ID 없이 등록
+            if let Some(priority) = router_config.priority {
+                debug!(router = %router_name, priority = priority, "라우터 우선순위 추가");
+                self.router_priorities.insert(router_name.clone(), priority);
+            }
+
+            let full_name = if router_name.contains('.') {
+                router_name
+            } else {
+                format!("{}.{}", config_id, router_name)
+            };
+
             if let Some(middlewares) = router_config.middlewares {
-                let full_name = if router_name.contains('.') {
-                    router_name
-                } else {
-                    format!("{}.{}", config_id, router_name)
-                };
-                
                 debug!(
                     router = %full_name,
                     middlewares = ?middlewares,
@@ -347,10 +560,10 @@ impl Settings {
             // 라벨이 우선순위가 높은 경우: JSON을 먼저 로드하고 라벨로 덮어씀
             self.load_json_from_env().await?;
             self.load_json_from_labels(labels).await?;
-            self.merge_docker_labels(labels)?;
+            self.merge_container_labels(labels)?;
         } else {
             // JSON이 우선순위가 높은 경우: 라벨을 먼저 로드하고 JSON으로 덮어씀
-            self.merge_docker_labels(labels)?;
+            self.merge_container_labels(labels)?;
             self.load_json_from_labels(labels).await?;
             
             // 환경변수에서 JSON 로드 시 덮어쓰기 활성화
@@ -383,22 +596,21 @@ impl Settings {
                 
                 // 미들웨어 설정 적용
                 for (name, middleware_config) in config.middlewares {
-                    let full_name = if name.contains('.') {
-                        name
-                    } else {
-                        format!("{}.{}", config_id, name)
-                    };
-                    settings.add_middleware(full_name, middleware_config)?;
+                    let full_name = Self::json_middleware_id(&config_id, &name)?;
+                    settings.add_middleware(full_name.into(), middleware_config)?;
                 }
                 
                 // 라우터-미들웨어 매핑 적용
                 for (router_name, router_config) in config.routers {
+                    if let Some(priority) = router_config.priority {
+                        settings.router_priorities.insert(router_name.clone(), priority);
+                    }
+                    let full_name = if router_name.contains('.') {
+                        router_name
+                    } else {
+                        format!("{}.{}", config_id, router_name)
+                    };
                     if let Some(middlewares) = router_config.middlewares {
-                        let full_name = if router_name.contains('.') {
-                            router_name
-                        } else {
-                            format!("{}.{}", config_id, router_name)
-                        };
                         settings.router_middlewares.insert(full_name, middlewares);
                     }
                 }
@@ -419,6 +631,7 @@ impl Settings {
         // 3. 설정 적용
         self.middleware = new_settings.middleware;
         self.router_middlewares = new_settings.router_middlewares;
+        self.router_priorities = new_settings.router_priorities;
         
         debug!("설정 리로드 완료");
         Ok(())
@@ -459,6 +672,213 @@ mod tests {
         assert_eq!(settings.middleware.len(), 1);
     }
 
+    #[test]
+    fn test_add_duplicate_middleware() {
+        let mut settings = Settings::default();
+        settings.add_middleware("cors".to_string(), MiddlewareConfig::new(MiddlewareType::Cors)).unwrap();
+
+        let err = settings
+            .add_middleware("cors".to_string(), MiddlewareConfig::new(MiddlewareType::Headers))
+            .unwrap_err();
+        assert!(matches!(
+            &err,
+            SettingsError::DuplicateMiddleware { name, existing_type: MiddlewareType::Cors } if name == "cors"
+        ));
+        assert_eq!(err.to_string(), "Middleware 'cors' already exists as type 'Cors'");
+
+        // 덮어쓰기를 허용하면 성공
+        settings.add_middleware_with_override(
+            "cors".to_string(),
+            MiddlewareConfig::new(MiddlewareType::Headers),
+            true,
+        ).unwrap();
+        assert_eq!(settings.middleware["cors"].middleware_type, MiddlewareType::Headers);
+    }
+
+    #[test]
+    fn test_merge_docker_labels_removes_deleted_middleware() {
+        let previous: HashMap<String, String> = [
+            ("rproxy.http.middlewares.cors.type", "cors"),
+            ("rproxy.http.middlewares.cors.cors.allowOrigins", "*"),
+            ("rproxy.http.middlewares.headers.type", "headers"),
+            ("rproxy.http.middlewares.headers.headers.customResponseHeaders.X-Old", "1"),
+        ].into_iter().map(|(k, v)| (k.to_string(), v.to_string())).collect();
+
+        let mut settings = Settings::default();
+        settings.add_middleware("json-only".to_string(), MiddlewareConfig::new(MiddlewareType::Headers)).unwrap();
+        settings.merge_docker_labels(&previous, None).unwrap();
+        assert_eq!(settings.middleware.len(), 3);
+
+        // cors 라벨 삭제, headers 라벨 값 변경
+        let current: HashMap<String, String> = [
+            ("rproxy.http.middlewares.headers.type", "headers"),
+            ("rproxy.http.middlewares.headers.headers.customResponseHeaders.X-New", "2"),
+        ].into_iter().map(|(k, v)| (k.to_string(), v.to_string())).collect();
+        let diff = settings.merge_docker_labels(&current, Some(&previous)).unwrap();
+        assert_eq!(diff.added_middlewares, vec!["headers".to_string()]);
+
+        assert!(!settings.middleware.contains_key("cors"));
+        assert!(settings.middleware.contains_key("json-only"));
+        let headers = &settings.middleware["headers"].settings;
+        assert!(headers.contains_key("headers.customResponseHeaders.X-New"));
+        assert!(!headers.contains_key("headers.customResponseHeaders.X-Old"));
+    }
+
+    #[test]
+    fn test_merge_container_labels_uses_cached_labels() {
+        let previous: HashMap<String, String> = [
+            ("rproxy.http.middlewares.cors.type", "cors"),
+            ("rproxy.http.middlewares.headers.type", "headers"),
+        ].into_iter().map(|(k, v)| (k.to_string(), v.to_string())).collect();
+
+        let mut settings = Settings::default();
+        settings.merge_container_labels(&previous).unwrap();
+        assert_eq!(settings.docker_labels, previous);
+
+        // cors 라벨이 삭제된 컨테이너 라벨을 다시 병합
+        let current: HashMap<String, String> = [
+            ("rproxy.http.middlewares.headers.type", "headers"),
+        ].into_iter().map(|(k, v)| (k.to_string(), v.to_string())).collect();
+        settings.merge_container_labels(&current).unwrap();
+
+        assert!(!settings.middleware.contains_key("cors"));
+        assert!(settings.middleware.contains_key("headers"));
+        assert_eq!(settings.docker_labels, current);
+    }
+
+    #[test]
+    fn test_merge_docker_labels_diff() {
+        let labels: HashMap<String, String> = [
+            ("rproxy.http.middlewares.cors.type", "cors"),
+            ("rproxy.http.middlewares.cors.cors.allowOrigins", "*"),
+            ("rproxy.http.middlewares.auth.type", "basic-auth"),
+            ("rproxy.http.routers.api.middlewares", "cors,auth"),
+        ].into_iter().map(|(k, v)| (k.to_string(), v.to_string())).collect();
+
+        let mut settings = Settings::default();
+        let diff = settings.merge_docker_labels(&labels, None).unwrap();
+        assert_eq!(diff, LabelMergeDiff {
+            added_middlewares: vec!["auth".to_string(), "cors".to_string()],
+            skipped_middlewares: vec![],
+            added_routers: vec!["api".to_string()],
+        });
+
+        // 같은 라벨을 다시 병합하면 중복 오류 없이 건너뜀
+        let diff = settings.merge_docker_labels(&labels, None).unwrap();
+        assert_eq!(diff, LabelMergeDiff {
+            added_middlewares: vec![],
+            skipped_middlewares: vec!["auth".to_string(), "cors".to_string()],
+            added_routers: vec![],
+        });
+    }
+
+    #[test]
+    fn test_merge_docker_labels_additional_prefixes() {
+        let labels: HashMap<String, String> = [
+            ("rproxy.http.middlewares.cors.type", "cors"),
+            ("traefik.http.middlewares.cors.type", "headers"),
+            ("traefik.http.middlewares.auth.type", "basic-auth"),
+            ("traefik.http.routers.api.middlewares", "cors,auth"),
+        ].into_iter().map(|(k, v)| (k.to_string(), v.to_string())).collect();
+
+        let mut settings = Settings::default();
+        settings.docker.additional_label_prefixes = vec!["traefik.".to_string()];
+        settings.merge_docker_labels(&labels, None).unwrap();
+
+        // 같은 이름은 기본 접두사가 우선
+        assert_eq!(settings.middleware["cors"].middleware_type, MiddlewareType::Cors);
+        assert_eq!(settings.middleware["auth"].middleware_type, MiddlewareType::BasicAuth);
+        assert_eq!(settings.router_middlewares["api"], vec!["cors".to_string(), "auth".to_string()]);
+    }
+
+    #[tokio::test]
+    async fn test_validate_sync_and_async() {
+        let dir = tempdir().unwrap();
+        let mut settings = Settings::default();
+        settings.tls.enabled = true;
+        settings.tls.cert_path = Some(types::ValidPath::new(dir.path().join("cert.pem")).unwrap());
+        settings.tls.key_path = Some(types::ValidPath::new(dir.path().join("key.pem")).unwrap());
+
+        // 파일이 없어도 동기 검증은 통과하고, 파일 검사는 비동기 검증에서 실패
+        assert!(settings.validate_sync().is_ok());
+        assert!(matches!(settings.validate_async().await, Err(SettingsError::FileError { .. })));
+        assert!(settings.validate().await.is_err());
+
+        // 경로 누락과 잘못된 미들웨어 설정은 동기 검증에서 발견
+        settings.tls.key_path = None;
+        assert!(matches!(settings.validate_sync(), Err(SettingsError::EnvVarMissing { .. })));
+        settings.tls.enabled = false;
+        let mut auth = MiddlewareConfig::new(MiddlewareType::BasicAuth);
+        auth.enabled = true;
+        settings.middleware.insert("auth".to_string(), auth);
+        assert!(settings.validate_sync().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_overlay_files_validates_merged_settings() {
+        let dir = tempdir().unwrap();
+        let mut settings = Settings::default();
+        let mut auth = MiddlewareConfig::new(MiddlewareType::BasicAuth);
+        auth.enabled = true;
+        settings.middleware.insert("auth".to_string(), auth);
+        assert!(settings.validate_sync().is_err());
+
+        // 계층이 잘못된 기본 설정을 고치면 병합 결과는 유효
+        let overlay = dir.path().join("overlay.toml");
+        std::fs::write(&overlay, "[middleware]\n").unwrap();
+        let merged = settings.overlay_files([&overlay]).await.unwrap();
+        assert!(merged.middleware.is_empty());
+    }
+
+    #[test]
+    fn test_validate_basic_auth_credentials() {
+        let basic_auth = |key: &str| {
+            let mut settings = Settings::default();
+            let mut config = MiddlewareConfig::new(MiddlewareType::BasicAuth);
+            config.enabled = true;
+            config.settings.insert(key.to_string(), "value".to_string());
+            settings.middleware.insert("auth".to_string(), config);
+            settings.validate_sync()
+        };
+
+        // htpasswd 파일만 지정해도 유효
+        assert!(basic_auth("basicAuth.htpasswdFile").is_ok());
+        assert!(basic_auth("basicAuth.htpasswd.path").is_ok());
+        assert!(basic_auth("basicAuth.users").is_ok());
+        assert!(basic_auth("users").is_ok());
+        assert!(matches!(
+            basic_auth("basicAuth.realm"),
+            Err(SettingsError::EnvVarMissing { var_name }) if var_name == "auth.basicAuth.users"
+        ));
+    }
+
+    #[test]
+    fn test_validate_rate_limit_settings() {
+        let rate_limit = |pairs: &[(&str, &str)]| {
+            let mut settings = Settings::default();
+            let mut config = MiddlewareConfig::new(MiddlewareType::RateLimit);
+            config.enabled = true;
+            for (key, value) in pairs {
+                config.settings.insert(key.to_string(), value.to_string());
+            }
+            settings.middleware.insert("limit".to_string(), config);
+            settings.validate_sync()
+        };
+
+        // burst만 지정해도 유효
+        assert!(rate_limit(&[("rateLimit.burst", "10")]).is_ok());
+        assert!(rate_limit(&[("rateLimit.average", "5"), ("rateLimit.period", "60")]).is_ok());
+        assert!(rate_limit(&[("rateLimit.strategy", "sliding_window")]).is_ok());
+
+        // period를 지정하면 average 필요
+        assert!(matches!(
+            rate_limit(&[("rateLimit.period", "60")]),
+            Err(SettingsError::EnvVarMissing { .. })
+        ));
+        assert!(rate_limit(&[("rateLimit.average", "5"), ("rateLimit.period", "0")]).is_err());
+        assert!(rate_limit(&[("rateLimit.strategy", "leaky_bucket")]).is_err());
+    }
+
     #[tokio::test]
     async fn test_load_json_config() {
         let dir = tempdir().unwrap();
@@ -479,7 +899,8 @@ mod tests {
                 "test-router": {
                     "rule": "Host(`example.com`)",
                     "middlewares": ["test-middleware"],
-                    "service": "test-service"
+                    "service": "test-service",
+                    "priority": 500
                 }
             },
             "services": {
@@ -502,8 +923,11 @@ mod tests {
             logging: LogSettings::default(),
             tls: TlsSettings::default(),
             docker: DockerSettings::default(),
+            startup: StartupSettings::default(),
             middleware: HashMap::new(),
             router_middlewares: HashMap::new(),
+            router_priorities: HashMap::new(),
+            docker_labels: HashMap::new(),
         };
         
         // JSON 설정 로드
@@ -522,6 +946,9 @@ mod tests {
         assert_eq!(settings.router_middlewares.len(), 1);
         assert!(settings.router_middlewares.contains_key("test-config.test-router"));
         assert_eq!(settings.router_middlewares["test-config.test-router"], vec!["test-middleware"]);
+
+        // 라우터 우선순위 검증
+        assert_eq!(settings.router_priorities.get("test-router"), Some(&500));
     }
     
     #[tokio::test]
@@ -590,8 +1017,11 @@ mod tests {
             logging: LogSettings::default(),
             tls: TlsSettings::default(),
             docker: DockerSettings::default(),
+            startup: StartupSettings::default(),
             middleware: HashMap::new(),
             router_middlewares: HashMap::new(),
+            router_priorities: HashMap::new(),
+            docker_labels: HashMap::new(),
         };
         
         settings.load_config_directory(dir.path()).await.unwrap();
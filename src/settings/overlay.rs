@@ -0,0 +1,231 @@
+//! 설정 계층 병합
+//!
+//! Kubernetes ConfigMap/Secret처럼 여러 계층으로 나뉜 설정을 구조적으로 덮어씁니다.
+//! 계층은 `PartialSettings`로 표현하며, 명시적으로 지정한 값만 `Some`이 됩니다.
+
+use std::collections::HashMap;
+use serde::Deserialize;
+use crate::middleware::config::MiddlewareConfig;
+use super::{
+    docker::{HealthCheckSettings, LoadBalancerSettings, RetrySettings},
+    logging::{LogFormat, LogOutput},
+    types::{ValidLabel, ValidPath},
+    Settings, SettingsError,
+};
+
+/// 덮어쓸 설정 계층
+///
+/// 지정하지 않은 항목은 `None`으로 남아 기존 값을 유지합니다.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct PartialSettings {
+    pub server: PartialServerSettings,
+    pub logging: PartialLogSettings,
+    pub tls: PartialTlsSettings,
+    pub docker: PartialDockerSettings,
+    pub startup: PartialStartupSettings,
+    /// 지정하면 미들웨어 목록 전체를 대체합니다.
+    pub middleware: Option<HashMap<String, MiddlewareConfig>>,
+    /// 지정하면 라우터-미들웨어 매핑 전체를 대체합니다.
+    pub router_middlewares: Option<HashMap<String, Vec<String>>>,
+    /// 지정하면 라우터 우선순위 전체를 대체합니다.
+    pub router_priorities: Option<HashMap<String, u32>>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct PartialServerSettings {
+    pub http_port: Option<u16>,
+    pub https_enabled: Option<bool>,
+    pub https_port: Option<u16>,
+    pub tls_cert_path: Option<String>,
+    pub tls_key_path: Option<String>,
+    pub auto_redirect_http: Option<bool>,
+    pub reuse_port: Option<bool>,
+    pub max_request_header_size_bytes: Option<u64>,
+    pub tenant_header: Option<String>,
+    pub default_backend: Option<std::net::SocketAddr>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct PartialLogSettings {
+    pub format: Option<LogFormat>,
+    pub level: Option<String>,
+    pub output: Option<LogOutput>,
+    pub debug_sample_rate: Option<f32>,
+    pub request_body_max_bytes: Option<usize>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct PartialTlsSettings {
+    pub enabled: Option<bool>,
+    pub port: Option<u16>,
+    pub cert_path: Option<ValidPath>,
+    pub key_path: Option<ValidPath>,
+    pub alpn_protocols: Option<Vec<String>>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct PartialDockerSettings {
+    pub network: Option<String>,
+    pub label_prefix: Option<ValidLabel>,
+    pub additional_label_prefixes: Option<Vec<String>>,
+    pub health_check: Option<HealthCheckSettings>,
+    pub retry: Option<RetrySettings>,
+    pub load_balancer: Option<LoadBalancerSettings>,
+    pub setup_initial_health_checks: Option<bool>,
+    pub auto_detect_network: Option<bool>,
+    pub event_log_file: Option<String>,
+    pub host: Option<String>,
+    pub tls_verify: Option<bool>,
+    pub cert_path: Option<ValidPath>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct PartialStartupSettings {
+    pub require_all_healthy: Option<bool>,
+}
+
+impl PartialSettings {
+    /// TOML 문자열에서 설정 계층을 파싱합니다.
+    pub fn from_toml_str(content: &str) -> Result<Self, SettingsError> {
+        toml::from_str(content).map_err(|e| SettingsError::ParseError { source: e })
+    }
+}
+
+/// `Some`인 필드만 대상에 복사합니다.
+macro_rules! overlay_fields {
+    ($target:expr, $source:expr, $($field:ident),+ $(,)?) => {
+        $(
+            if let Some(value) = &$source.$field {
+                $target.$field = value.clone();
+            }
+        )+
+    };
+}
+
+impl Settings {
+    /// 설정 계층을 덮어쓴 새 설정을 반환합니다.
+    ///
+    /// `overrides`에서 명시적으로 지정한 값만 대체하는 구조적 병합입니다.
+    /// 미들웨어를 추가하는 `merge_with_json_config`와 달리 지정된 미들웨어 목록은 통째로 대체됩니다.
+    /// 문자열로 지정한 값을 해석할 수 없으면 `SettingsError::InvalidConfig`를 반환합니다.
+    pub fn overlay(&self, overrides: &PartialSettings) -> Result<Settings, SettingsError> {
+        let mut settings = self.clone();
+
+        let server = &overrides.server;
+        overlay_fields!(settings.server, server,
+            http_port, https_enabled, https_port, auto_redirect_http, reuse_port,
+            max_request_header_size_bytes, tenant_header);
+        if server.tls_cert_path.is_some() {
+            settings.server.tls_cert_path = server.tls_cert_path.clone();
+        }
+        if server.tls_key_path.is_some() {
+            settings.server.tls_key_path = server.tls_key_path.clone();
+        }
+        if server.default_backend.is_some() {
+            settings.server.default_backend = server.default_backend;
+        }
+
+        let logging = &overrides.logging;
+        overlay_fields!(settings.logging, logging, format, output, debug_sample_rate, request_body_max_bytes);
+        if let Some(level) = logging.level.as_deref() {
+            settings.logging.level = level.parse().map_err(|_| SettingsError::InvalidConfig(
+                format!("유효하지 않은 로그 레벨: {}", level)
+            ))?;
+        }
+
+        let tls = &overrides.tls;
+        overlay_fields!(settings.tls, tls, enabled, port, alpn_protocols);
+        if tls.cert_path.is_some() {
+            settings.tls.cert_path = tls.cert_path.clone();
+        }
+        if tls.key_path.is_some() {
+            settings.tls.key_path = tls.key_path.clone();
+        }
+
+        overlay_fields!(settings.docker, overrides.docker,
+            network, label_prefix, additional_label_prefixes, health_check,
+            retry, load_balancer, setup_initial_health_checks, auto_detect_network, tls_verify);
+        if overrides.docker.event_log_file.is_some() {
+            settings.docker.event_log_file = overrides.docker.event_log_file.clone();
+        }
+        if overrides.docker.host.is_some() {
+            settings.docker.host = overrides.docker.host.clone();
+        }
+        if overrides.docker.cert_path.is_some() {
+            settings.docker.cert_path = overrides.docker.cert_path.clone();
+        }
+
+        overlay_fields!(settings.startup, overrides.startup, require_all_healthy);
+
+        overlay_fields!(settings, overrides, middleware, router_middlewares, router_priorities);
+
+        Ok(settings)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tracing::Level;
+
+    #[test]
+    fn test_overlay_replaces_only_explicit_values() {
+        let mut base = Settings::default();
+        base.server.http_port = 8080;
+        base.server.https_port = 8443;
+        base.docker.network = "base-network".to_string();
+        base.router_middlewares.insert("api".to_string(), vec!["auth".to_string()]);
+
+        let overrides = PartialSettings::from_toml_str(r#"
+            [server]
+            http_port = 80
+
+            [logging]
+            level = "debug"
+
+            [docker]
+            network = "prod-network"
+
+            [router_middlewares]
+            web = ["cors"]
+        "#).unwrap();
+
+        let settings = base.overlay(&overrides).unwrap();
+
+        // 기본값과 같은 값도 명시적으로 지정하면 대체
+        assert_eq!(settings.server.http_port, 80);
+        assert_eq!(settings.logging.level, Level::DEBUG);
+        assert_eq!(settings.docker.network, "prod-network");
+        // 맵은 통째로 대체
+        assert_eq!(settings.router_middlewares.len(), 1);
+        assert!(settings.router_middlewares.contains_key("web"));
+
+        // 지정하지 않은 값은 유지
+        assert_eq!(settings.server.https_port, 8443);
+        assert_eq!(settings.docker.label_prefix, base.docker.label_prefix);
+
+        // 빈 계층은 변경 없음
+        let unchanged = base.overlay(&PartialSettings::default()).unwrap();
+        assert_eq!(unchanged.server.http_port, 8080);
+        assert_eq!(unchanged.router_middlewares, base.router_middlewares);
+    }
+
+    #[test]
+    fn test_overlay_rejects_invalid_log_level() {
+        let overrides = PartialSettings::from_toml_str(r#"
+            [logging]
+            level = "verbose"
+        "#).unwrap();
+
+        let Err(SettingsError::InvalidConfig(message)) = Settings::default().overlay(&overrides) else {
+            panic!("Expected InvalidConfig");
+        };
+        assert!(message.contains("verbose"), "{}", message);
+    }
+}
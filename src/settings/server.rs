@@ -1,6 +1,8 @@
 use serde::Deserialize;
 use std::env;
-use super::SettingsError;
+use std::net::SocketAddr;
+use tracing::warn;
+use super::{tls::TlsSettings, types::ValidPath, SettingsError};
 
 #[derive(Clone, Debug, Deserialize)]
 pub struct ServerSettings {
@@ -21,12 +23,40 @@ pub struct ServerSettings {
 
     /// TLS 키 경로
     pub tls_key_path: Option<String>,
+
+    /// HTTPS 활성화 시 HTTP 요청을 HTTPS로 자동 리다이렉트할지 여부 (기본값: true)
+    #[serde(default = "default_auto_redirect_http")]
+    pub auto_redirect_http: bool,
+
+    /// 리스너 소켓에 SO_REUSEPORT 설정 여부 (기본값: false)
+    ///
+    /// 여러 프록시 프로세스가 같은 포트를 공유할 수 있어 블루-그린 배포에 사용합니다.
+    /// Linux 3.9 이상이 필요하며, 프로세스 간 연결 분배는 커널이 담당합니다.
+    #[serde(default)]
+    pub reuse_port: bool,
+
+    /// 요청 헤더 이름과 값의 바이트 수 합계 최대값 (기본값: 8KB, 0이면 제한 없음)
+    ///
+    /// 초과하면 미들웨어 처리 전에 `431 Request Header Fields Too Large`로 응답합니다.
+    #[serde(default = "default_max_request_header_size_bytes")]
+    pub max_request_header_size_bytes: u64,
+
+    /// 테넌트별 미들웨어 체인을 고르는 요청 헤더 이름 (기본값: X-Tenant-ID)
+    #[serde(default = "default_tenant_header")]
+    pub tenant_header: String,
+
+    /// 라우트가 없는 호스트의 요청을 보낼 기본 백엔드 주소 (`ip:port`, 기본값: 없음)
+    #[serde(default)]
+    pub default_backend: Option<SocketAddr>,
 }
 
 fn default_http_port() -> u16 { 80 }
 fn default_https_port() -> u16 { 443 }
 
 fn default_https_disabled() -> bool { false }
+fn default_auto_redirect_http() -> bool { true }
+fn default_max_request_header_size_bytes() -> u64 { 8 * 1024 }
+fn default_tenant_header() -> String { "X-Tenant-ID".to_string() }
 
 pub fn parse_env_var<T: std::str::FromStr, F: FnOnce() -> T>(name: &str, default: F) -> Result<T, SettingsError>
 where
@@ -51,6 +81,8 @@ impl ServerSettings {
     // 포트 범위 상수 추가
     const MIN_PORT: u16 = 1;
     const MAX_PORT: u16 = 65535;
+    // 이 포트 미만은 root 권한이 있어야 바인딩할 수 있음
+    const PRIVILEGED_PORT_LIMIT: u16 = 1024;
     
     // 포트 파싱 전용 함수
     fn parse_port(name: &str, value: &str) -> Result<u16, SettingsError> {
@@ -72,6 +104,38 @@ impl ServerSettings {
     }
 
     pub fn from_env() -> Result<Self, SettingsError> {
+        let settings = Self::from_env_with_tls_paths(env::var("PROXY_TLS_CERT").ok(), env::var("PROXY_TLS_KEY").ok())?;
+        settings.validate()?;
+        Ok(settings)
+    }
+
+    /// 환경 변수에서 서버 설정을 읽되, 인증서/키 경로는 `TlsSettings`에서 가져옵니다.
+    ///
+    /// `PROXY_TLS_CERT_PEM`/`PROXY_TLS_KEY_PEM`으로 받은 인라인 PEM은 `TlsSettings`가 임시 파일로
+    /// 기록하므로, 리스너와 검증이 그 경로를 보도록 여기로 전달합니다.
+    pub fn from_env_with_tls(tls: &TlsSettings) -> Result<Self, SettingsError> {
+        let settings = Self::read_env_with_tls(tls)?;
+        settings.validate()?;
+        Ok(settings)
+    }
+
+    /// `from_env_with_tls`와 같지만 검증하지 않습니다.
+    ///
+    /// 설정 계층을 덮어쓴 뒤 한 번에 검증하는 `Settings::load`에서 사용합니다.
+    pub(crate) fn read_env_with_tls(tls: &TlsSettings) -> Result<Self, SettingsError> {
+        let path_string = |path: &Option<ValidPath>| {
+            path.as_ref().map(|path| path.as_path().to_string_lossy().into_owned())
+        };
+        Self::from_env_with_tls_paths(
+            path_string(&tls.cert_path).or_else(|| env::var("PROXY_TLS_CERT").ok()),
+            path_string(&tls.key_path).or_else(|| env::var("PROXY_TLS_KEY").ok()),
+        )
+    }
+
+    fn from_env_with_tls_paths(
+        tls_cert_path: Option<String>,
+        tls_key_path: Option<String>,
+    ) -> Result<Self, SettingsError> {
         let http_port = Self::parse_port(
             "PROXY_HTTP_PORT",
             &env::var("PROXY_HTTP_PORT").unwrap_or_else(|_| default_http_port().to_string())
@@ -86,15 +150,36 @@ impl ServerSettings {
             http_port,
             https_port,
             https_enabled: parse_env_var::<bool, _>("PROXY_HTTPS_ENABLED", default_https_disabled)?,
-            tls_cert_path: env::var("PROXY_TLS_CERT").ok(),
-            tls_key_path: env::var("PROXY_TLS_KEY").ok(),
+            tls_cert_path,
+            tls_key_path,
+            auto_redirect_http: parse_env_var::<bool, _>("PROXY_AUTO_REDIRECT_HTTP", default_auto_redirect_http)?,
+            reuse_port: parse_env_var::<bool, _>("PROXY_REUSE_PORT", || false)?,
+            max_request_header_size_bytes: parse_env_var::<u64, _>(
+                "PROXY_MAX_REQUEST_HEADER_SIZE_BYTES",
+                default_max_request_header_size_bytes,
+            )?,
+            tenant_header: env::var("PROXY_TENANT_HEADER").unwrap_or_else(|_| default_tenant_header()),
+            default_backend: env::var("PROXY_DEFAULT_BACKEND").ok()
+                .map(|value| value.parse().map_err(|e: std::net::AddrParseError| SettingsError::EnvVarInvalid {
+                    var_name: "PROXY_DEFAULT_BACKEND".to_string(),
+                    value,
+                    reason: e.to_string(),
+                }))
+                .transpose()?,
         };
         
-        settings.validate()?;
         Ok(settings)
     }
 
     pub fn validate(&self) -> Result<(), SettingsError> {
+        // 특권 포트는 root가 아니면 바인딩에 실패할 수 있으므로 경고만 남김
+        if self.http_port < Self::PRIVILEGED_PORT_LIMIT && !is_running_as_root() {
+            warn!(
+                http_port = self.http_port,
+                "HTTP 포트가 1024 미만입니다. root 권한 없이 실행하면 바인딩에 실패할 수 있습니다"
+            );
+        }
+
         // HTTPS가 활성화된 경우 인증서/키 파일 필수 검사
         if self.https_enabled {
             if self.tls_cert_path.is_none() {
@@ -118,10 +203,32 @@ impl ServerSettings {
             }
         }
 
+        if let Err(e) = hyper::header::HeaderName::from_bytes(self.tenant_header.as_bytes()) {
+            return Err(SettingsError::EnvVarInvalid {
+                var_name: "PROXY_TENANT_HEADER".to_string(),
+                value: self.tenant_header.clone(),
+                reason: e.to_string(),
+            });
+        }
+
         Ok(())
     }
 }
 
+/// 현재 프로세스가 root 권한으로 실행 중인지 확인합니다.
+#[cfg(unix)]
+fn is_running_as_root() -> bool {
+    use std::os::unix::fs::MetadataExt;
+    std::fs::metadata("/proc/self")
+        .map(|m| m.uid() == 0)
+        .unwrap_or(false)
+}
+
+#[cfg(not(unix))]
+fn is_running_as_root() -> bool {
+    false
+}
+
 impl Default for ServerSettings {
     fn default() -> Self {
         Self {
@@ -130,6 +237,11 @@ impl Default for ServerSettings {
             https_port: default_https_port(),
             tls_cert_path: None,
             tls_key_path: None,
+            auto_redirect_http: default_auto_redirect_http(),
+            reuse_port: false,
+            max_request_header_size_bytes: default_max_request_header_size_bytes(),
+            tenant_header: default_tenant_header(),
+            default_backend: None,
         }
     }
 } 
\ No newline at end of file
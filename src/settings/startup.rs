@@ -0,0 +1,18 @@
+use serde::Deserialize;
+use super::{server::parse_env_var, SettingsError};
+
+/// 서버 시작 시 동작 설정
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct StartupSettings {
+    /// 시작 프로브에서 하나라도 실패하면 서버 시작을 중단할지 여부 (기본값: false)
+    #[serde(default)]
+    pub require_all_healthy: bool,
+}
+
+impl StartupSettings {
+    pub fn from_env() -> Result<Self, SettingsError> {
+        Ok(Self {
+            require_all_healthy: parse_env_var("PROXY_STARTUP_REQUIRE_ALL_HEALTHY", || false)?,
+        })
+    }
+}
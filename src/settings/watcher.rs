@@ -1,8 +1,10 @@
-use std::path::PathBuf;
+use std::collections::VecDeque;
+use std::path::{Path, PathBuf};
 use std::time::{Duration, Instant};
 use tokio::sync::mpsc;
 use notify::{RecursiveMode, Watcher, Config, Event, Result as NotifyResult, PollWatcher};
 use crate::settings::{Result, error::SettingsError};
+use crate::settings::etcd::{EtcdMirror, EtcdSource};
 use tracing::{debug, error};
 
 /// 설정 파일 변경 이벤트 타입
@@ -26,6 +28,18 @@ pub struct ConfigWatcher {
     event_rx: mpsc::Receiver<ConfigEvent>,
     /// 파일 시스템 감시자
     watcher: Option<PollWatcher>,
+    /// 감시 시작 시 이미 존재하는 파일마다 `Modified` 이벤트를 발생시킬지 여부
+    ///
+    /// 시작 시점의 설정 파일을 런타임 변경과 같은 경로로 처리할 수 있습니다.
+    pub emit_initial_state: bool,
+    /// 아직 전달되지 않은 초기 상태 이벤트
+    initial_events: VecDeque<ConfigEvent>,
+    /// 감시할 etcd 키 접두사 목록
+    etcd_sources: Vec<EtcdSource>,
+    /// etcd 값을 파일로 반영하는 디렉토리 (감시자와 함께 삭제)
+    etcd_dir: Option<tempfile::TempDir>,
+    /// 실행 중인 etcd 감시 작업
+    etcd_tasks: Vec<tokio::task::JoinHandle<()>>,
 }
 
 impl ConfigWatcher {
@@ -37,6 +51,11 @@ impl ConfigWatcher {
             event_tx,
             event_rx,
             watcher: None,
+            emit_initial_state: true,
+            initial_events: VecDeque::new(),
+            etcd_sources: Vec::new(),
+            etcd_dir: None,
+            etcd_tasks: Vec::new(),
         }
     }
 
@@ -45,6 +64,16 @@ impl ConfigWatcher {
         self.paths.push(path.into());
     }
 
+    /// 감시할 etcd 키 접두사 추가
+    ///
+    /// `endpoint`는 etcd v3 JSON 게이트웨이 주소(`http://etcd:2379`)입니다.
+    /// 접두사 아래의 각 키는 임시 디렉토리의 파일로 반영되며, PUT/DELETE 이벤트는
+    /// 파일 감시와 같은 채널로 해당 파일 경로의 `ConfigEvent`로 전달됩니다.
+    pub fn add_etcd_path(&mut self, endpoint: &str, key_prefix: &str) -> Result<()> {
+        self.etcd_sources.push(EtcdSource::new(endpoint, key_prefix)?);
+        Ok(())
+    }
+
     /// 테스트용 이벤트 송신자 반환
     #[cfg(test)]
     pub fn get_sender(&self) -> mpsc::Sender<ConfigEvent> {
@@ -106,45 +135,111 @@ impl ConfigWatcher {
         }
 
         self.watcher = Some(watcher);
+
+        if self.emit_initial_state {
+            let mut files = Vec::new();
+            for path in &self.paths {
+                collect_files(path, &mut files);
+            }
+            debug!("기존 파일 {}개에 대한 초기 이벤트 생성", files.len());
+            self.initial_events.extend(files.into_iter().map(ConfigEvent::Modified));
+        }
+
+        self.start_etcd()?;
+
+        Ok(())
+    }
+
+    /// etcd 감시 작업 시작 (초기 상태 이벤트는 첫 동기화 때 채널로 전달)
+    fn start_etcd(&mut self) -> Result<()> {
+        for task in self.etcd_tasks.drain(..) {
+            task.abort();
+        }
+        if self.etcd_sources.is_empty() {
+            return Ok(());
+        }
+
+        if self.etcd_dir.is_none() {
+            let dir = tempfile::Builder::new()
+                .prefix("roxy-etcd-")
+                .tempdir()
+                .map_err(|e| SettingsError::WatchError(format!("etcd 미러 디렉토리 생성 실패: {}", e)))?;
+            self.etcd_dir = Some(dir);
+        }
+        let base = self.etcd_dir.as_ref().map(|dir| dir.path().to_path_buf()).unwrap_or_default();
+
+        for (index, source) in self.etcd_sources.iter().enumerate() {
+            debug!("etcd 감시 시작: {:?}", source);
+            let mirror = EtcdMirror::new(
+                source.clone(),
+                base.join(format!("etcd-{}", index)),
+                self.event_tx.clone(),
+                self.emit_initial_state,
+            );
+            self.etcd_tasks.push(tokio::spawn(mirror.run()));
+        }
         Ok(())
     }
 
     /// 이벤트 수신 대기
+    ///
+    /// 초기 상태 이벤트가 남아 있으면 먼저 반환합니다.
     pub async fn watch(&mut self) -> Option<ConfigEvent> {
+        if let Some(event) = self.initial_events.pop_front() {
+            return Some(event);
+        }
         self.event_rx.recv().await
     }
     
     /// 이벤트 수신 대기 (디바운싱 적용)
     /// 지정된 시간 동안 발생한 모든 이벤트를 수집하여 반환합니다.
     pub async fn watch_debounced(&mut self, timeout: Duration) -> Option<Vec<ConfigEvent>> {
-        let start = Instant::now();
-        let mut events = Vec::new();
-        
         // 첫 번째 이벤트를 기다림
-        if let Some(event) = self.watch().await {
+        let first = self.watch().await?;
+        Some(self.debounce(first, timeout).await)
+    }
+
+    /// 이미 받은 첫 이벤트 이후 지정된 시간 동안 발생한 이벤트를 모아 함께 반환합니다.
+    ///
+    /// 첫 이벤트만 다른 타이머와 경쟁시키고 디바운싱은 그 밖에서 수행할 때 사용합니다.
+    /// (`watch_debounced` 전체를 취소하면 이미 수집한 이벤트가 사라짐)
+    pub async fn debounce(&mut self, first: ConfigEvent, timeout: Duration) -> Vec<ConfigEvent> {
+        let start = Instant::now();
+        let mut events = vec![first];
+
+        // timeout 동안 추가 이벤트 수집
+        while let Ok(Some(event)) = tokio::time::timeout(
+            timeout.saturating_sub(start.elapsed()),
+            self.watch()
+        ).await {
             events.push(event);
-            
-            // timeout 동안 추가 이벤트 수집
-            while let Ok(Some(event)) = tokio::time::timeout(
-                timeout.saturating_sub(start.elapsed()),
-                self.watch()
-            ).await {
-                events.push(event);
-                
-                // 최대 이벤트 수 제한
-                if events.len() >= 10 {
-                    debug!("최대 이벤트 수(10개) 도달, 이벤트 수집 종료");
-                    break;
-                }
+
+            // 최대 이벤트 수 제한
+            if events.len() >= 10 {
+                debug!("최대 이벤트 수(10개) 도달, 이벤트 수집 종료");
+                break;
             }
         }
-        
-        if events.is_empty() {
-            None
-        } else {
-            debug!("{} 이벤트 수집됨, 경과 시간: {:?}", events.len(), start.elapsed());
-            Some(events)
-        }
+
+        debug!("{} 이벤트 수집됨, 경과 시간: {:?}", events.len(), start.elapsed());
+        events
+    }
+}
+
+/// 경로가 파일이면 그대로, 디렉토리면 하위 파일을 재귀적으로 수집합니다.
+fn collect_files(path: &Path, files: &mut Vec<PathBuf>) {
+    if path.is_file() {
+        files.push(path.to_path_buf());
+        return;
+    }
+
+    let Ok(entries) = std::fs::read_dir(path) else {
+        return;
+    };
+    let mut children: Vec<PathBuf> = entries.filter_map(|e| e.ok().map(|e| e.path())).collect();
+    children.sort();
+    for child in children {
+        collect_files(&child, files);
     }
 }
 
@@ -154,6 +249,25 @@ impl Default for ConfigWatcher {
     }
 }
 
+impl std::fmt::Debug for ConfigWatcher {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ConfigWatcher")
+            .field("paths", &self.paths)
+            .field("etcd_sources", &self.etcd_sources)
+            .field("started", &self.watcher.is_some())
+            .field("emit_initial_state", &self.emit_initial_state)
+            .finish()
+    }
+}
+
+impl Drop for ConfigWatcher {
+    fn drop(&mut self) {
+        for task in &self.etcd_tasks {
+            task.abort();
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -295,6 +409,31 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn test_emit_initial_state() {
+        let temp_dir = tempdir().unwrap();
+        let sub_dir = temp_dir.path().join("configs");
+        std::fs::create_dir(&sub_dir).unwrap();
+        std::fs::write(temp_dir.path().join("a.json"), "{}").unwrap();
+        std::fs::write(sub_dir.join("b.json"), "{}").unwrap();
+
+        let mut watcher = ConfigWatcher::new();
+        watcher.add_path(temp_dir.path());
+        watcher.start().await.unwrap();
+
+        let first = watcher.watch().await.unwrap();
+        let second = watcher.watch().await.unwrap();
+        assert_eq!(first, ConfigEvent::Modified(temp_dir.path().join("a.json")));
+        assert_eq!(second, ConfigEvent::Modified(sub_dir.join("b.json")));
+
+        // 비활성화하면 초기 이벤트 없음
+        let mut watcher = ConfigWatcher::new();
+        watcher.emit_initial_state = false;
+        watcher.add_path(temp_dir.path());
+        watcher.start().await.unwrap();
+        assert!(tokio::time::timeout(Duration::from_millis(300), watcher.watch()).await.is_err());
+    }
+
     #[tokio::test]
     async fn test_recursive_watch() {
         // 임시 디렉토리 생성
@@ -464,4 +603,73 @@ mod tests {
             .any(|e| matches!(e, ConfigEvent::Modified(_)));
         assert!(has_modify_event);
     }
-} 
\ No newline at end of file
+
+    /// etcd JSON 게이트웨이를 흉내 내는 서버 (range 응답 후 watch 이벤트를 스트리밍)
+    async fn spawn_etcd_gateway(watch_lines: Vec<String>) -> std::net::SocketAddr {
+        use base64::{engine::general_purpose::STANDARD, Engine};
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            while let Ok((mut stream, _)) = listener.accept().await {
+                let watch_lines = watch_lines.clone();
+                tokio::spawn(async move {
+                    let mut buf = [0u8; 4096];
+                    let n = stream.read(&mut buf).await.unwrap_or(0);
+                    let request = String::from_utf8_lossy(&buf[..n]).to_string();
+                    if request.starts_with("POST /v3/kv/range") {
+                        let body = serde_json::json!({
+                            "header": {"revision": "5"},
+                            "kvs": [{"key": STANDARD.encode("/roxy/app.json"), "value": STANDARD.encode("{}")}],
+                        }).to_string();
+                        let response = format!("HTTP/1.1 200 OK\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{}", body.len(), body);
+                        let _ = stream.write_all(response.as_bytes()).await;
+                    } else {
+                        let _ = stream.write_all(b"HTTP/1.1 200 OK\r\nconnection: close\r\n\r\n").await;
+                        for line in watch_lines {
+                            let _ = stream.write_all(format!("{}\n", line).as_bytes()).await;
+                        }
+                        sleep(Duration::from_secs(5)).await;
+                    }
+                });
+            }
+        });
+        addr
+    }
+
+    #[tokio::test]
+    async fn test_etcd_watch_events() {
+        use base64::{engine::general_purpose::STANDARD, Engine};
+
+        let watch_lines = vec![
+            serde_json::json!({"result": {"header": {"revision": "5"}, "created": true}}).to_string(),
+            serde_json::json!({"result": {"events": [
+                {"kv": {"key": STANDARD.encode("/roxy/new.json"), "value": STANDARD.encode(r#"{"version":"1.0"}"#)}},
+            ]}}).to_string(),
+            serde_json::json!({"result": {"events": [
+                {"type": "DELETE", "kv": {"key": STANDARD.encode("/roxy/app.json")}},
+            ]}}).to_string(),
+        ];
+        let addr = spawn_etcd_gateway(watch_lines).await;
+
+        let mut watcher = ConfigWatcher::new();
+        assert!(watcher.add_etcd_path("https://127.0.0.1:2379", "/roxy/").is_err());
+        watcher.add_etcd_path(&format!("http://{}", addr), "/roxy/").unwrap();
+        watcher.start().await.unwrap();
+
+        let mut events = Vec::new();
+        for _ in 0..3 {
+            events.push(tokio::time::timeout(Duration::from_secs(2), watcher.watch()).await.unwrap().unwrap());
+        }
+
+        // 초기 동기화 → PUT → DELETE 순서로 파일 경로 이벤트 전달
+        let ConfigEvent::Modified(app) = &events[0] else { panic!("초기 동기화 이벤트 없음: {:?}", events) };
+        assert_eq!(app.file_name().unwrap(), "app.json");
+        let ConfigEvent::Created(new) = &events[1] else { panic!("PUT 이벤트 없음: {:?}", events) };
+        assert_eq!(new.parent(), app.parent());
+        assert_eq!(std::fs::read_to_string(new).unwrap(), r#"{"version":"1.0"}"#);
+        assert_eq!(events[2], ConfigEvent::Deleted(app.clone()));
+        assert!(!app.exists());
+    }
+}
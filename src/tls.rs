@@ -1,51 +1,304 @@
-use std::fs::File;
 use std::io::BufReader;
-use std::sync::Arc;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
 use tokio::net::TcpListener;
+use tokio::task::JoinHandle;
 use tokio_rustls::rustls::{self, Certificate, PrivateKey};
 use tokio_rustls::TlsAcceptor;
-use tracing::{error, info};
+use crate::server::listener::bind_tcp_listener;
+use crate::settings::{watcher::ConfigWatcher, SettingsError};
+use crate::settings::tls::{is_der, is_pem};
+use rustls_pemfile::Item;
+use tracing::{debug, error, info, warn};
 
 pub struct TlsConfig {
-    pub acceptor: TlsAcceptor,
+    pub certificates: CertificateManager,
     pub listener: TcpListener,
 }
 
 impl TlsConfig {
-    pub async fn new(cert_path: &str, key_path: &str, port: u16) -> Result<Self, Box<dyn std::error::Error>> {
-        let tls_config = Self::load_tls_config(cert_path, key_path)?;
-        let acceptor = TlsAcceptor::from(Arc::new(tls_config));
-        
-        let listener = TcpListener::bind(format!("0.0.0.0:{}", port)).await
+    pub async fn new(
+        cert_path: &str,
+        key_path: &str,
+        port: u16,
+        alpn_protocols: &[String],
+        reuse_port: bool,
+    ) -> Result<Self, Box<dyn std::error::Error>> {
+        if alpn_protocols.iter().any(|p| p == "h2") {
+            // 연결 처리는 HTTP/1.1 기반이므로 h2를 광고하면 클라이언트가 협상 후 실패할 수 있음
+            warn!(protocols = ?alpn_protocols, "ALPN에 h2가 포함됨: 현재 연결 처리는 HTTP/1.1만 지원");
+        }
+        let certificates = CertificateManager::new(cert_path, key_path, alpn_protocols)?;
+        if !alpn_protocols.is_empty() {
+            info!(protocols = ?alpn_protocols, "ALPN 프로토콜 설정");
+        }
+
+        // 인증서 교체 감시 (실패해도 기존 인증서로 계속 동작)
+        if let Err(e) = certificates.hot_reload().await {
+            warn!(error = %e, "인증서 파일 감시 시작 실패, 인증서 자동 교체 비활성화");
+        }
+
+        let listener = bind_tcp_listener(&format!("0.0.0.0:{}", port), reuse_port)
             .map_err(|e| {
                 error!(error = %e, port = port, "HTTPS 포트 바인딩 실패");
                 e
             })?;
 
         info!(port = port, "HTTPS 리스너 시작");
-        Ok(Self { acceptor, listener })
+        Ok(Self { certificates, listener })
     }
+}
 
-    fn load_tls_config(cert_path: &str, key_path: &str) -> Result<rustls::ServerConfig, Box<dyn std::error::Error>> {
-        let cert_file = File::open(cert_path)?;
-        let mut cert_reader = BufReader::new(cert_file);
-        let certs = rustls_pemfile::certs(&mut cert_reader)?
-            .into_iter()
-            .map(Certificate)
+/// TLS 인증서 관리자
+///
+/// 현재 `rustls::ServerConfig`를 보관하고, 인증서 파일이 바뀌면 새 설정으로 교체합니다.
+/// 교체 후 새 핸드쉐이크부터 새 인증서를 사용하며, 기존 연결은 영향을 받지 않습니다.
+#[derive(Clone)]
+pub struct CertificateManager {
+    cert_path: PathBuf,
+    key_path: PathBuf,
+    alpn_protocols: Vec<Vec<u8>>,
+    config: Arc<RwLock<Arc<rustls::ServerConfig>>>,
+}
+
+impl CertificateManager {
+    /// 인증서 파일 변경 후 추가 변경을 모으는 시간
+    const RELOAD_DEBOUNCE: Duration = Duration::from_millis(500);
+
+    pub fn new(
+        cert_path: impl Into<PathBuf>,
+        key_path: impl Into<PathBuf>,
+        alpn_protocols: &[String],
+    ) -> Result<Self, Box<dyn std::error::Error>> {
+        let cert_path = cert_path.into();
+        let key_path = key_path.into();
+        let alpn_protocols: Vec<Vec<u8>> = alpn_protocols.iter()
+            .map(|p| p.as_bytes().to_vec())
             .collect();
+        let config = load_tls_config(&cert_path, &key_path, &alpn_protocols)?;
 
-        let key_file = File::open(key_path)?;
-        let mut key_reader = BufReader::new(key_file);
-        let key = rustls_pemfile::pkcs8_private_keys(&mut key_reader)?
-            .first()
-            .ok_or("개인키를 찾을 수 없음")?
-            .clone();
+        Ok(Self {
+            cert_path,
+            key_path,
+            alpn_protocols,
+            config: Arc::new(RwLock::new(Arc::new(config))),
+        })
+    }
+
+    /// 현재 인증서로 TLS 핸드쉐이크를 수행하는 acceptor를 반환합니다.
+    pub fn acceptor(&self) -> TlsAcceptor {
+        TlsAcceptor::from(self.current())
+    }
 
-        let config = rustls::ServerConfig::builder()
-            .with_safe_defaults()
-            .with_no_client_auth()
-            .with_single_cert(certs, PrivateKey(key))?;
+    /// 현재 사용 중인 TLS 설정
+    pub fn current(&self) -> Arc<rustls::ServerConfig> {
+        self.config.read()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .clone()
+    }
 
-        Ok(config)
+    /// 인증서와 키를 다시 읽어 검증한 뒤 교체합니다.
+    /// 실패하면 기존 설정을 유지합니다.
+    pub fn reload(&self) -> Result<(), Box<dyn std::error::Error>> {
+        let config = load_tls_config(&self.cert_path, &self.key_path, &self.alpn_protocols)?;
+        *self.config.write().unwrap_or_else(|poisoned| poisoned.into_inner()) = Arc::new(config);
+        Ok(())
     }
-} 
\ No newline at end of file
+
+    /// 인증서/키 파일을 감시하여 변경 시 자동으로 다시 로드합니다.
+    pub async fn hot_reload(&self) -> Result<JoinHandle<()>, SettingsError> {
+        let mut watcher = ConfigWatcher::new();
+        // 생성 시 이미 인증서를 읽었으므로 초기 이벤트 불필요
+        watcher.emit_initial_state = false;
+        watcher.add_path(&self.cert_path);
+        watcher.add_path(&self.key_path);
+        watcher.start().await?;
+
+        let manager = self.clone();
+        info!(cert_path = %self.cert_path.display(), key_path = %self.key_path.display(), "인증서 파일 감시 시작");
+
+        Ok(tokio::spawn(async move {
+            // 인증서와 키가 연달아 교체되는 경우를 하나의 재로드로 처리
+            while let Some(events) = watcher.watch_debounced(Self::RELOAD_DEBOUNCE).await {
+                debug!(events = ?events, "인증서 파일 변경 감지");
+                match manager.reload() {
+                    Ok(()) => info!(cert_path = %manager.cert_path.display(), "TLS 인증서 교체 완료"),
+                    Err(e) => error!(error = %e, "TLS 인증서 재로드 실패, 기존 인증서 유지"),
+                }
+            }
+        }))
+    }
+}
+
+fn load_tls_config(
+    cert_path: &Path,
+    key_path: &Path,
+    alpn_protocols: &[Vec<u8>],
+) -> Result<rustls::ServerConfig, Box<dyn std::error::Error>> {
+    let certs = read_certificates(cert_path)?;
+    if certs.is_empty() {
+        return Err("인증서를 찾을 수 없음".into());
+    }
+
+    let key = read_private_key(key_path)?.ok_or("개인키를 찾을 수 없음")?;
+    verify_key_matches_cert(&certs[0], &key)?;
+
+    let mut config = rustls::ServerConfig::builder()
+        .with_safe_defaults()
+        .with_no_client_auth()
+        .with_single_cert(certs, key)?;
+    config.alpn_protocols = alpn_protocols.to_vec();
+
+    Ok(config)
+}
+
+/// 인증서 파일을 읽습니다.
+///
+/// PEM이면 모든 `CERTIFICATE` 블록을, DER이면 파일 전체를 인증서 하나로 사용합니다.
+/// 허용하는 형식은 `TlsSettings::validate_files`와 같습니다.
+fn read_certificates(path: &Path) -> Result<Vec<Certificate>, Box<dyn std::error::Error>> {
+    let data = std::fs::read(path)?;
+    if is_pem(&data) {
+        let certs = rustls_pemfile::certs(&mut BufReader::new(data.as_slice()))?;
+        return Ok(certs.into_iter().map(Certificate).collect());
+    }
+    Ok(if is_der(&data) { vec![Certificate(data)] } else { Vec::new() })
+}
+
+/// 개인키 파일을 읽습니다.
+///
+/// PEM이면 첫 번째 PKCS#8(`PRIVATE KEY`), PKCS#1(`RSA PRIVATE KEY`), SEC1(`EC PRIVATE KEY`) 블록을,
+/// DER이면 파일 전체를 사용합니다. 키를 찾지 못하면 `None`을 반환합니다.
+fn read_private_key(path: &Path) -> Result<Option<PrivateKey>, Box<dyn std::error::Error>> {
+    let data = std::fs::read(path)?;
+    if is_pem(&data) {
+        let key = rustls_pemfile::read_all(&mut BufReader::new(data.as_slice()))?
+            .into_iter()
+            .find_map(|item| match item {
+                Item::PKCS8Key(key) | Item::RSAKey(key) | Item::ECKey(key) => Some(PrivateKey(key)),
+                _ => None,
+            });
+        return Ok(key);
+    }
+    Ok(is_der(&data).then_some(PrivateKey(data)))
+}
+
+/// 개인키로 서명한 값을 인증서의 공개키로 검증하여 키와 인증서가 한 쌍인지 확인합니다.
+///
+/// rustls는 인증서와 키가 서로 맞는지 검사하지 않으므로, 교체 중 한쪽 파일만 바뀐 상태로
+/// 설정을 만들면 모든 핸드쉐이크가 실패합니다.
+fn verify_key_matches_cert(
+    cert: &Certificate,
+    key: &PrivateKey,
+) -> Result<(), Box<dyn std::error::Error>> {
+    use rustls::SignatureScheme;
+
+    const PROBE: &[u8] = b"roxy certificate key probe";
+    let schemes: [(SignatureScheme, &webpki::SignatureAlgorithm); 5] = [
+        (SignatureScheme::ECDSA_NISTP256_SHA256, &webpki::ECDSA_P256_SHA256),
+        (SignatureScheme::ECDSA_NISTP384_SHA384, &webpki::ECDSA_P384_SHA384),
+        (SignatureScheme::ED25519, &webpki::ED25519),
+        (SignatureScheme::RSA_PSS_SHA256, &webpki::RSA_PSS_2048_8192_SHA256_LEGACY_KEY),
+        (SignatureScheme::RSA_PKCS1_SHA256, &webpki::RSA_PKCS1_2048_8192_SHA256),
+    ];
+
+    let signing_key = rustls::sign::any_supported_type(key)
+        .map_err(|_| "지원하지 않는 개인키 형식")?;
+    let offered: Vec<SignatureScheme> = schemes.iter().map(|(scheme, _)| *scheme).collect();
+    let signer = signing_key.choose_scheme(&offered)
+        .ok_or("개인키에 맞는 서명 방식을 찾을 수 없음")?;
+    let algorithm = schemes.iter()
+        .find(|(scheme, _)| *scheme == signer.scheme())
+        .map(|(_, algorithm)| *algorithm)
+        .ok_or("개인키에 맞는 서명 방식을 찾을 수 없음")?;
+    let signature = signer.sign(PROBE)?;
+
+    let end_entity = webpki::EndEntityCert::try_from(cert.0.as_slice())
+        .map_err(|e| format!("인증서 파싱 실패: {:?}", e))?;
+    end_entity.verify_signature(algorithm, PROBE, &signature)
+        .map_err(|_| "개인키가 인증서와 일치하지 않음")?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FIXTURES: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/tls");
+
+    fn copy_fixture(name: &str, dest: &Path) {
+        std::fs::copy(Path::new(FIXTURES).join(name), dest).unwrap();
+    }
+
+    #[test]
+    fn test_certificate_reload() {
+        let dir = tempfile::tempdir().unwrap();
+        let cert_path = dir.path().join("cert.pem");
+        let key_path = dir.path().join("key.pem");
+        copy_fixture("cert_a.pem", &cert_path);
+        copy_fixture("key_a.pem", &key_path);
+
+        let manager = CertificateManager::new(&cert_path, &key_path, &["http/1.1".to_string()]).unwrap();
+        let initial = manager.current();
+        assert_eq!(initial.alpn_protocols, vec![b"http/1.1".to_vec()]);
+
+        // 새 인증서로 교체
+        copy_fixture("cert_b.pem", &cert_path);
+        copy_fixture("key_b.pem", &key_path);
+        manager.reload().unwrap();
+        let reloaded = manager.current();
+        assert!(!Arc::ptr_eq(&initial, &reloaded));
+        assert_eq!(reloaded.alpn_protocols, vec![b"http/1.1".to_vec()]);
+
+        // 잘못된 파일이면 기존 설정 유지
+        std::fs::write(&key_path, "not a key").unwrap();
+        assert!(manager.reload().is_err());
+        assert!(Arc::ptr_eq(&reloaded, &manager.current()));
+    }
+
+    #[test]
+    fn test_certificate_reload_rejects_mismatched_key() {
+        let dir = tempfile::tempdir().unwrap();
+        let cert_path = dir.path().join("cert.pem");
+        let key_path = dir.path().join("key.pem");
+        copy_fixture("cert_a.pem", &cert_path);
+        copy_fixture("key_a.pem", &key_path);
+
+        let manager = CertificateManager::new(&cert_path, &key_path, &[]).unwrap();
+        let initial = manager.current();
+
+        // 인증서만 교체된 상태: 키가 맞지 않으므로 기존 설정 유지
+        copy_fixture("cert_b.pem", &cert_path);
+        let err = manager.reload().unwrap_err();
+        assert!(err.to_string().contains("일치하지 않음"), "{}", err);
+        assert!(Arc::ptr_eq(&initial, &manager.current()));
+
+        // 처음부터 맞지 않는 쌍이면 생성 실패
+        assert!(CertificateManager::new(&cert_path, &key_path, &[]).is_err());
+    }
+
+    #[test]
+    fn test_certificate_formats_accepted_by_validation() {
+        let dir = tempfile::tempdir().unwrap();
+        let cert_path = dir.path().join("cert.pem");
+        let key_path = dir.path().join("key.pem");
+
+        // SEC1(`EC PRIVATE KEY`) PEM 개인키
+        copy_fixture("cert_a.pem", &cert_path);
+        copy_fixture("key_a_sec1.pem", &key_path);
+        CertificateManager::new(&cert_path, &key_path, &[]).unwrap();
+
+        // DER 인증서와 DER 개인키
+        let pem_body = |name: &str| {
+            let data = std::fs::read(Path::new(FIXTURES).join(name)).unwrap();
+            match rustls_pemfile::read_one(&mut data.as_slice()).unwrap() {
+                Some(Item::X509Certificate(der) | Item::PKCS8Key(der)) => der,
+                other => panic!("unexpected PEM item: {:?}", other),
+            }
+        };
+        std::fs::write(&cert_path, pem_body("cert_a.pem")).unwrap();
+        std::fs::write(&key_path, pem_body("key_a.pem")).unwrap();
+        CertificateManager::new(&cert_path, &key_path, &[]).unwrap();
+    }
+}
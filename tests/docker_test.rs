@@ -1,10 +1,10 @@
 use bollard::secret::{ContainerSummaryNetworkSettings, EndpointSettings, NetworkSettings};
 use reverse_proxy_traefik::docker::container::ContainerInfo;
-use reverse_proxy_traefik::docker::{DockerManager, DockerError, DockerClient, ContainerInfoExtractor};
+use reverse_proxy_traefik::docker::{DockerManager, DockerError, DockerClient, DockerEvent, ContainerInfoExtractor, DefaultExtractor, resolve_backend_addr};
 use bollard::container::ListContainersOptions;
-use bollard::models::{ContainerSummary, EventMessage};
+use bollard::models::{ContainerConfig, ContainerInspectResponse, ContainerSummary, EventMessage};
 use futures_util::Stream;
-use reverse_proxy_traefik::routing_v2::{BackendService, PathMatcher};
+use reverse_proxy_traefik::routing_v2::{BackendService, PathMatcher, SameSite, XAccelRedirect};
 use std::pin::Pin;
 use std::sync::Arc;
 use tokio::sync::{Mutex, RwLock};
@@ -23,6 +23,8 @@ use reverse_proxy_traefik::routing_v2::RoutingTable;
 #[derive(Clone)]
 struct MockDockerClient {
     containers: Arc<Mutex<Vec<ContainerSummary>>>,
+    /// 컨테이너 ID별 환경 변수 (`KEY=VALUE`)
+    env: HashMap<String, Vec<String>>,
 }
 
 #[async_trait::async_trait]
@@ -44,6 +46,25 @@ impl DockerClient for MockDockerClient {
     ) -> Pin<Box<dyn Stream<Item = Result<EventMessage, DockerError>> + Send>> {
         Box::pin(futures_util::stream::empty())
     }
+
+    async fn inspect_container(&self, id: &str) -> Result<ContainerInspectResponse, DockerError> {
+        self.containers.lock().await.iter()
+            .find(|container| container.id.as_deref() == Some(id))
+            .map(|container| ContainerInspectResponse {
+                id: container.id.clone(),
+                config: Some(ContainerConfig {
+                    labels: container.labels.clone(),
+                    env: self.env.get(id).cloned(),
+                    ..Default::default()
+                }),
+                ..Default::default()
+            })
+            .ok_or_else(|| DockerError::ContainerConfigError {
+                container_id: id.to_string(),
+                reason: "컨테이너를 찾을 수 없음".to_string(),
+                context: None,
+            })
+    }
 }
 
 // Mock Container Info Extractor
@@ -62,6 +83,7 @@ impl MockExtractor {
     }
 }
 
+#[async_trait::async_trait]
 impl ContainerInfoExtractor for MockExtractor {
     fn clone_box(&self) -> Box<dyn ContainerInfoExtractor> {
         Box::new(self.clone())
@@ -110,10 +132,21 @@ impl ContainerInfoExtractor for MockExtractor {
             router_name: Some("web".to_string()),  // 테스트용 고정 라우터 이름
             health_check: None,
             load_balancer: None,
+            passthrough: false,
+            priority: BackendService::DOCKER_PRIORITY,
+            env_header_names: Vec::new(),
+            env_headers: HashMap::new(),
+            compose_project: None,
+            compose_service: None,
+            weight: None,
+            trusted_proxy_cidrs: Vec::new(),
+            rfc7239_forwarded: false,
+            sticky_cookie: None,
+            x_accel_redirect: None,
         })
     }
 
-    fn create_backend(&self, info: &ContainerInfo) -> Result<BackendService, DockerError> {
+    async fn create_backend(&self, info: &ContainerInfo) -> Result<BackendService, DockerError> {
         let addr = format!("{}:{}", info.ip, info.port).parse().unwrap();
         let mut service = BackendService::with_router(addr, info.router_name.clone());
         
@@ -129,7 +162,7 @@ impl ContainerInfoExtractor for MockExtractor {
 fn create_test_settings() -> DockerSettings {
     DockerSettings {
         network: "reverse-proxy-network".to_string(),
-        label_prefix: "reverse-proxy.".to_string(),
+        label_prefix: "reverse-proxy.".parse().unwrap(),
         ..Default::default()
     }
 }
@@ -139,8 +172,9 @@ async fn test_docker_manager_initialization() {
     let settings = create_test_settings();
     let client = MockDockerClient {
         containers: Arc::new(Mutex::new(vec![])),
+        env: HashMap::new(),
     };
-    let extractor = MockExtractor::new(settings.network.clone(), settings.label_prefix.clone());
+    let extractor = MockExtractor::new(settings.network.clone(), settings.label_prefix.to_string());
 
     let manager = DockerManager::new(
         Box::new(client),
@@ -151,6 +185,79 @@ async fn test_docker_manager_initialization() {
     assert!(manager.get_container_routes().await.is_ok());
 }
 
+/// 처음 `failures`번은 일시적인 네트워크 오류를 반환하는 클라이언트
+#[derive(Clone)]
+struct FlakyDockerClient {
+    inner: MockDockerClient,
+    failures: Arc<std::sync::atomic::AtomicU32>,
+}
+
+#[async_trait::async_trait]
+impl DockerClient for FlakyDockerClient {
+    fn clone_box(&self) -> Box<dyn DockerClient> {
+        Box::new(self.clone())
+    }
+
+    async fn list_containers(
+        &self,
+        options: Option<ListContainersOptions<String>>,
+    ) -> Result<Vec<ContainerSummary>, DockerError> {
+        let remaining = self.failures.load(std::sync::atomic::Ordering::SeqCst);
+        if remaining > 0 {
+            self.failures.store(remaining - 1, std::sync::atomic::Ordering::SeqCst);
+            return Err(DockerError::NetworkError {
+                container_id: String::new(),
+                network: "test".to_string(),
+                reason: "timeout".to_string(),
+                context: None,
+            });
+        }
+        self.inner.list_containers(options).await
+    }
+
+    fn events(
+        &self,
+        options: Option<bollard::system::EventsOptions<String>>,
+    ) -> Pin<Box<dyn Stream<Item = Result<EventMessage, DockerError>> + Send>> {
+        self.inner.events(options)
+    }
+
+    async fn inspect_container(&self, id: &str) -> Result<ContainerInspectResponse, DockerError> {
+        self.inner.inspect_container(id).await
+    }
+}
+
+#[tokio::test]
+async fn test_container_routes_retry_stats() {
+    let mut settings = create_test_settings();
+    settings.retry.interval = 0;
+    let client = FlakyDockerClient {
+        inner: MockDockerClient {
+            containers: Arc::new(Mutex::new(vec![])),
+            env: HashMap::new(),
+        },
+        failures: Arc::new(std::sync::atomic::AtomicU32::new(2)),
+    };
+    let extractor = MockExtractor::new(settings.network.clone(), settings.label_prefix.to_string());
+    let manager = DockerManager::new(Box::new(client.clone()), Box::new(extractor), settings).await;
+    let retry_count = manager.retry_count_total();
+
+    let (routes, stats) = manager.get_container_routes_with_retry_stats().await.unwrap();
+    assert!(routes.is_empty());
+    assert_eq!(stats.attempts, 3);
+    assert_eq!(retry_count.load(std::sync::atomic::Ordering::Relaxed), 2);
+
+    // 재시도 없이 성공하면 카운터는 그대로
+    let (_, stats) = manager.get_container_routes_with_retry_stats().await.unwrap();
+    assert_eq!(stats.attempts, 1);
+    assert_eq!(retry_count.load(std::sync::atomic::Ordering::Relaxed), 2);
+
+    // 최대 시도 횟수를 넘기면 실패하지만 재시도 횟수는 누적
+    client.failures.store(5, std::sync::atomic::Ordering::SeqCst);
+    assert!(manager.get_container_routes().await.is_err());
+    assert_eq!(retry_count.load(std::sync::atomic::Ordering::Relaxed), 4);
+}
+
 #[tokio::test]
 async fn test_container_routes() {
     let settings = create_test_settings();
@@ -176,8 +283,9 @@ async fn test_container_routes() {
 
     let client = MockDockerClient {
         containers: Arc::new(Mutex::new(containers)),
+        env: HashMap::new(),
     };
-    let extractor = MockExtractor::new(settings.network.clone(), settings.label_prefix.clone());
+    let extractor = MockExtractor::new(settings.network.clone(), settings.label_prefix.to_string());
 
     let manager = DockerManager::new(
         Box::new(client),
@@ -212,8 +320,9 @@ async fn test_container_routes_with_missing_host() {
 
     let client = MockDockerClient {
         containers: Arc::new(Mutex::new(containers)),
+        env: HashMap::new(),
     };
-    let extractor = MockExtractor::new(settings.network.clone(), settings.label_prefix.clone());
+    let extractor = MockExtractor::new(settings.network.clone(), settings.label_prefix.to_string());
 
     let manager = DockerManager::new(
         Box::new(client),
@@ -244,8 +353,9 @@ async fn test_container_routes_with_missing_network() {
 
     let client = MockDockerClient {
         containers: Arc::new(Mutex::new(containers)),
+        env: HashMap::new(),
     };
-    let extractor = MockExtractor::new(settings.network.clone(), settings.label_prefix.clone());
+    let extractor = MockExtractor::new(settings.network.clone(), settings.label_prefix.to_string());
 
     let manager = DockerManager::new(
         Box::new(client),
@@ -305,8 +415,9 @@ async fn test_path_based_routing() {
 
     let client = MockDockerClient {
         containers: Arc::new(Mutex::new(containers)),
+        env: HashMap::new(),
     };
-    let extractor = MockExtractor::new(settings.network.clone(), settings.label_prefix.clone());
+    let extractor = MockExtractor::new(settings.network.clone(), settings.label_prefix.to_string());
 
     let manager = DockerManager::new(
         Box::new(client),
@@ -416,6 +527,193 @@ fn test_container_path_patterns_regex() {
 }
 
 // 미들웨어 테스트 추가
+#[test]
+fn test_additional_label_prefixes() {
+    let container_with = |labels: &[(&str, &str)]| ContainerSummary {
+        id: Some("test_container".to_string()),
+        labels: Some(labels.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect()),
+        network_settings: Some(ContainerSummaryNetworkSettings {
+            networks: Some(HashMap::from([(
+                "test_network".to_string(),
+                EndpointSettings {
+                    ip_address: Some("172.17.0.2".to_string()),
+                    ..Default::default()
+                },
+            )])),
+        }),
+        ..Default::default()
+    };
+
+    let extractor = DefaultExtractor::new("test_network".to_string(), "rproxy.".to_string())
+        .with_additional_prefixes(vec!["traefik.".to_string(), "team.".to_string()]);
+
+    // 추가 접두사 라벨만 있는 컨테이너
+    let info = extractor.extract_info(&container_with(&[("traefik.host", "traefik.local")])).unwrap();
+    assert_eq!(info.host, "traefik.local");
+
+    // 여러 접두사가 일치하면 먼저 설정된 접두사 사용
+    let info = extractor.extract_info(&container_with(&[
+        ("rproxy.host", "rproxy.local"),
+        ("traefik.host", "traefik.local"),
+    ])).unwrap();
+    assert_eq!(info.host, "rproxy.local");
+
+    let info = extractor.extract_info(&container_with(&[
+        ("team.host", "team.local"),
+        ("traefik.host", "traefik.local"),
+    ])).unwrap();
+    assert_eq!(info.host, "traefik.local");
+
+    // 일치하는 접두사가 없으면 에러
+    assert!(extractor.extract_info(&container_with(&[("other.host", "other.local")])).is_err());
+}
+
+#[test]
+fn test_compose_default_router_name() {
+    let container_with = |labels: &[(&str, &str)]| ContainerSummary {
+        id: Some("test_container".to_string()),
+        labels: Some(labels.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect()),
+        network_settings: Some(ContainerSummaryNetworkSettings {
+            networks: Some(HashMap::from([(
+                "test_network".to_string(),
+                EndpointSettings {
+                    ip_address: Some("172.17.0.2".to_string()),
+                    ..Default::default()
+                },
+            )])),
+        }),
+        ..Default::default()
+    };
+    let extractor = DefaultExtractor::new("test_network".to_string(), "rproxy.".to_string());
+
+    let info = extractor.extract_info(&container_with(&[
+        ("rproxy.host", "shop.local"),
+        ("com.docker.compose.project", "shop"),
+        ("com.docker.compose.service", "api"),
+    ])).unwrap();
+    assert_eq!(info.compose_project.as_deref(), Some("shop"));
+    assert_eq!(info.compose_service.as_deref(), Some("api"));
+    assert_eq!(info.router_name.as_deref(), Some("shop-api"));
+    assert_eq!(info.weight, None);
+
+    // 가중치 라벨
+    let info = extractor.extract_info(&container_with(&[
+        ("rproxy.host", "shop.local"),
+        ("rproxy.weight", "3"),
+    ])).unwrap();
+    assert_eq!(info.weight, Some(3));
+
+    // 명시적인 라우터 라벨이 우선
+    let info = extractor.extract_info(&container_with(&[
+        ("rproxy.http.routers.web.rule", "Host(`shop.local`)"),
+        ("com.docker.compose.project", "shop"),
+        ("com.docker.compose.service", "api"),
+    ])).unwrap();
+    assert_eq!(info.router_name.as_deref(), Some("web"));
+
+    // Compose 라벨이 없으면 라우터 이름 없음
+    let info = extractor.extract_info(&container_with(&[("rproxy.host", "shop.local")])).unwrap();
+    assert!(info.router_name.is_none());
+}
+
+#[test]
+fn test_sticky_cookie_labels() {
+    let container = ContainerSummary {
+        id: Some("test_container".to_string()),
+        labels: Some(HashMap::from([
+            ("rproxy.http.routers.web.rule".to_string(), "Host(`shop.local`)".to_string()),
+            ("rproxy.http.services.web.loadbalancer.sticky.cookie.name".to_string(), "srv".to_string()),
+            ("rproxy.http.services.web.loadbalancer.sticky.cookie.secure".to_string(), "true".to_string()),
+            ("rproxy.http.services.web.loadbalancer.sticky.cookie.sameSite".to_string(), "strict".to_string()),
+        ])),
+        network_settings: Some(ContainerSummaryNetworkSettings {
+            networks: Some(HashMap::from([(
+                "test_network".to_string(),
+                EndpointSettings {
+                    ip_address: Some("172.17.0.2".to_string()),
+                    ..Default::default()
+                },
+            )])),
+        }),
+        ..Default::default()
+    };
+    let extractor = DefaultExtractor::new("test_network".to_string(), "rproxy.".to_string());
+
+    let sticky = extractor.extract_info(&container).unwrap().sticky_cookie.unwrap();
+    assert_eq!(sticky.cookie_name, "srv");
+    assert!(sticky.http_only);
+    assert!(sticky.secure);
+    assert_eq!(sticky.same_site, SameSite::Strict);
+}
+
+#[test]
+fn test_x_accel_redirect_labels() {
+    let container_with = |labels: &[(&str, &str)]| ContainerSummary {
+        id: Some("test_container".to_string()),
+        labels: Some(labels.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect()),
+        network_settings: Some(ContainerSummaryNetworkSettings {
+            networks: Some(HashMap::from([(
+                "test_network".to_string(),
+                EndpointSettings {
+                    ip_address: Some("172.17.0.2".to_string()),
+                    ..Default::default()
+                },
+            )])),
+        }),
+        ..Default::default()
+    };
+    let extractor = DefaultExtractor::new("test_network".to_string(), "rproxy.".to_string());
+    let rule = ("rproxy.http.routers.files.rule", "Host(`files.local`)");
+
+    let info = extractor.extract_info(&container_with(&[rule])).unwrap();
+    assert!(info.x_accel_redirect.is_none());
+
+    let info = extractor.extract_info(&container_with(&[
+        rule,
+        ("rproxy.http.routers.files.x_accel_redirect", "true"),
+    ])).unwrap();
+    assert_eq!(info.x_accel_redirect, Some(XAccelRedirect { base_path: None }));
+
+    // 기준 디렉터리만 지정해도 활성화
+    let info = extractor.extract_info(&container_with(&[
+        rule,
+        ("rproxy.http.routers.files.x_accel_redirect.base_path", "/srv/protected"),
+    ])).unwrap();
+    assert_eq!(info.x_accel_redirect, Some(XAccelRedirect { base_path: Some("/srv/protected".to_string()) }));
+}
+
+#[test]
+fn test_sticky_cookie_labels_use_router_service() {
+    let container_with = |cookie_name: &str| ContainerSummary {
+        id: Some("test_container".to_string()),
+        labels: Some(HashMap::from([
+            ("rproxy.http.routers.web.rule".to_string(), "Host(`shop.local`)".to_string()),
+            ("rproxy.http.routers.web.service".to_string(), "shop".to_string()),
+            ("rproxy.http.services.shop.loadbalancer.sticky.cookie.name".to_string(), cookie_name.to_string()),
+        ])),
+        network_settings: Some(ContainerSummaryNetworkSettings {
+            networks: Some(HashMap::from([(
+                "test_network".to_string(),
+                EndpointSettings {
+                    ip_address: Some("172.17.0.2".to_string()),
+                    ..Default::default()
+                },
+            )])),
+        }),
+        ..Default::default()
+    };
+    let extractor = DefaultExtractor::new("test_network".to_string(), "rproxy.".to_string());
+
+    let sticky = extractor.extract_info(&container_with("srv")).unwrap().sticky_cookie.unwrap();
+    assert_eq!(sticky.cookie_name, "srv");
+
+    // 쿠키 토큰이 아닌 이름은 무시
+    for cookie_name in ["srv; Path=/", "a=b", "my cookie"] {
+        let info = extractor.extract_info(&container_with(cookie_name)).unwrap();
+        assert!(info.sticky_cookie.is_none(), "{cookie_name}");
+    }
+}
+
 #[tokio::test]
 async fn test_container_with_middleware() {
     let settings = create_test_settings();
@@ -442,8 +740,9 @@ async fn test_container_with_middleware() {
 
     let client = MockDockerClient {
         containers: Arc::new(Mutex::new(containers)),
+        env: HashMap::new(),
     };
-    let extractor = MockExtractor::new(settings.network.clone(), settings.label_prefix.clone());
+    let extractor = MockExtractor::new(settings.network.clone(), settings.label_prefix.to_string());
 
     let manager = DockerManager::new(
         Box::new(client),
@@ -465,6 +764,63 @@ async fn test_container_with_middleware() {
     );
 }
 
+#[tokio::test]
+async fn test_container_env_headers() {
+    let settings = create_test_settings();
+    let containers = vec![ContainerSummary {
+        id: Some("app-container".to_string()),
+        labels: Some(HashMap::from([
+            ("reverse-proxy.host".to_string(), "app.localhost".to_string()),
+            ("reverse-proxy.env.headers".to_string(), "APP_VERSION, GIT_COMMIT,MISSING".to_string()),
+        ])),
+        network_settings: Some(ContainerSummaryNetworkSettings {
+            networks: Some(HashMap::from([(
+                settings.network.clone(),
+                EndpointSettings {
+                    ip_address: Some("172.17.0.2".to_string()),
+                    ..Default::default()
+                },
+            )])),
+        }),
+        ..Default::default()
+    }];
+
+    let client = MockDockerClient {
+        containers: Arc::new(Mutex::new(containers)),
+        env: HashMap::from([(
+            "app-container".to_string(),
+            vec![
+                "APP_VERSION=1.2.3".to_string(),
+                "GIT_COMMIT=abc=def".to_string(),
+                "DB_PASSWORD=secret".to_string(),
+            ],
+        )]),
+    };
+    let extractor = DefaultExtractor::new(settings.network.clone(), settings.label_prefix.to_string());
+
+    let manager = DockerManager::new(
+        Box::new(client),
+        Box::new(extractor),
+        settings,
+    ).await;
+
+    let routes = manager.get_container_routes().await.unwrap();
+    let backend = routes.get(&("app.localhost".to_string(), PathMatcher::from_str("/").unwrap())).unwrap();
+
+    // 라벨에 지정된 변수만 포함
+    assert_eq!(backend.env_headers, HashMap::from([
+        ("APP_VERSION".to_string(), "1.2.3".to_string()),
+        ("GIT_COMMIT".to_string(), "abc=def".to_string()),
+    ]));
+
+    let mut headers: Vec<(String, &str)> = backend.upstream_env_headers().collect();
+    headers.sort();
+    assert_eq!(headers, vec![
+        ("X-App-App-Version".to_string(), "1.2.3"),
+        ("X-App-Git-Commit".to_string(), "abc=def"),
+    ]);
+}
+
 #[tokio::test]
 async fn test_load_balancer_grouping() {
     let containers = vec![
@@ -514,12 +870,13 @@ async fn test_load_balancer_grouping() {
 
     let client = MockDockerClient {
         containers: Arc::new(Mutex::new(containers)),
+        env: HashMap::new(),
     };
 
     let settings = create_test_settings();
     let manager = DockerManager::new(
         Box::new(client),
-        Box::new(MockExtractor::new(settings.network.clone(), settings.label_prefix.clone())),
+        Box::new(MockExtractor::new(settings.network.clone(), settings.label_prefix.to_string())),
         settings,
     ).await;
 
@@ -529,3 +886,228 @@ async fn test_load_balancer_grouping() {
     let ((host, _), service) = routes.iter().next().unwrap();
     assert_eq!(host, "web.example.com");
 }
+
+#[tokio::test]
+async fn test_resolve_backend_addr() {
+    // IP 리터럴은 DNS 조회 없이 파싱
+    let addr = resolve_backend_addr("172.17.0.2", 8080).await.unwrap();
+    assert_eq!(addr, "172.17.0.2:8080".parse().unwrap());
+
+    let addr = resolve_backend_addr("::1", 8080).await.unwrap();
+    assert_eq!(addr, "[::1]:8080".parse().unwrap());
+
+    // DNS 이름은 조회
+    let addr = resolve_backend_addr("localhost", 8080).await.unwrap();
+    assert!(addr.ip().is_loopback());
+    assert_eq!(addr.port(), 8080);
+
+    // 조회할 수 없는 이름은 에러
+    assert!(resolve_backend_addr("no-such-host.invalid", 8080).await.is_err());
+}
+
+#[test]
+fn test_routes_diff() {
+    let key = |host: &str| (host.to_string(), PathMatcher::from_str("/").unwrap());
+    let service = |addr: &str| BackendService::new(addr.parse().unwrap());
+
+    let mut previous = HashMap::new();
+    previous.insert(key("same.local"), service("10.0.0.1:80"));
+    previous.insert(key("changed.local"), service("10.0.0.2:80"));
+    previous.insert(key("removed.local"), service("10.0.0.3:80"));
+
+    let mut current = HashMap::new();
+    current.insert(key("same.local"), service("10.0.0.1:80"));
+    current.insert(key("changed.local"), service("10.0.0.9:80"));
+    current.insert(key("added.local"), service("10.0.0.4:80"));
+
+    match DockerEvent::routes_diff(&previous, current) {
+        DockerEvent::RoutesDiff { added, removed, changed } => {
+            assert_eq!(added.len(), 1);
+            assert_eq!(added[0].0.0, "added.local");
+            assert_eq!(removed, vec![key("removed.local")]);
+            assert_eq!(changed.len(), 1);
+            assert_eq!(changed[0].0.0, "changed.local");
+            assert_eq!(changed[0].1.address, "10.0.0.9:80".parse().unwrap());
+        }
+        other => panic!("unexpected event: {:?}", other),
+    }
+
+    // 변경이 없으면 빈 diff
+    let unchanged: HashMap<_, _> = previous.iter()
+        .map(|(k, v)| (k.clone(), v.clone()))
+        .collect();
+    assert!(DockerEvent::routes_diff(&previous, unchanged).is_empty_diff());
+
+    // 신뢰할 프록시 대역만 바뀌어도 변경으로 감지
+    let current: HashMap<_, _> = previous.iter()
+        .map(|(k, v)| {
+            let mut service = v.clone();
+            if k.0 == "same.local" {
+                service.set_trusted_proxy_cidrs(vec!["10.0.0.0/8".parse().unwrap()]);
+            }
+            (k.clone(), service)
+        })
+        .collect();
+    match DockerEvent::routes_diff(&previous, current) {
+        DockerEvent::RoutesDiff { added, removed, changed } => {
+            assert!(added.is_empty());
+            assert!(removed.is_empty());
+            assert_eq!(changed.len(), 1);
+            assert_eq!(changed[0].0.0, "same.local");
+        }
+        other => panic!("unexpected event: {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn test_subscribe_removes_routes_of_vanished_containers() {
+    let settings = create_test_settings();
+    let client = MockDockerClient {
+        containers: Arc::new(Mutex::new(vec![])),
+        env: HashMap::new(),
+    };
+    let extractor = MockExtractor::new(settings.network.clone(), settings.label_prefix.to_string());
+    let manager = DockerManager::new(Box::new(client), Box::new(extractor), settings).await;
+
+    // 연결이 끊긴 동안 사라진 컨테이너의 라우트가 테이블에 남아 있는 상황
+    let key = ("gone.local".to_string(), PathMatcher::from_str("/").unwrap());
+    let current = HashMap::from([(key.clone(), BackendService::new("10.0.0.1:80".parse().unwrap()))]);
+
+    let mut rx = manager.subscribe_to_events(&current).await;
+    match timeout(Duration::from_secs(1), rx.recv()).await.unwrap() {
+        Some(DockerEvent::RoutesDiff { added, removed, changed }) => {
+            assert!(added.is_empty());
+            assert_eq!(removed, vec![key]);
+            assert!(changed.is_empty());
+        }
+        other => panic!("unexpected event: {:?}", other),
+    }
+}
+
+// API 버전을 보고하는 Mock Docker Client
+#[derive(Clone)]
+struct VersionedDockerClient {
+    api_version: Option<String>,
+}
+
+#[async_trait::async_trait]
+impl DockerClient for VersionedDockerClient {
+    fn clone_box(&self) -> Box<dyn DockerClient> {
+        Box::new(self.clone())
+    }
+
+    async fn list_containers(
+        &self,
+        _options: Option<ListContainersOptions<String>>,
+    ) -> Result<Vec<ContainerSummary>, DockerError> {
+        Ok(vec![])
+    }
+
+    fn events(
+        &self,
+        _options: Option<bollard::system::EventsOptions<String>>,
+    ) -> Pin<Box<dyn Stream<Item = Result<EventMessage, DockerError>> + Send>> {
+        Box::pin(futures_util::stream::empty())
+    }
+
+    async fn inspect_container(&self, id: &str) -> Result<ContainerInspectResponse, DockerError> {
+        Err(DockerError::ContainerConfigError {
+            container_id: id.to_string(),
+            reason: "컨테이너를 찾을 수 없음".to_string(),
+            context: None,
+        })
+    }
+
+    async fn api_version(&self) -> Result<Option<String>, DockerError> {
+        Ok(self.api_version.clone())
+    }
+}
+
+#[tokio::test]
+async fn test_check_api_compatibility() {
+    let manager_with = |api_version: Option<&str>| {
+        let settings = create_test_settings();
+        let extractor = MockExtractor::new(settings.network.clone(), settings.label_prefix.to_string());
+        DockerManager::new(
+            Box::new(VersionedDockerClient { api_version: api_version.map(String::from) }),
+            Box::new(extractor),
+            settings,
+        )
+    };
+
+    assert!(manager_with(Some("1.43")).await.check_api_compatibility().await.is_ok());
+    assert!(manager_with(Some("1.45")).await.check_api_compatibility().await.is_ok());
+    // 버전을 알 수 없으면 검사 생략
+    assert!(manager_with(None).await.check_api_compatibility().await.is_ok());
+
+    match manager_with(Some("1.41")).await.check_api_compatibility().await {
+        Err(DockerError::IncompatibleApiVersion { required, found }) => {
+            assert_eq!(required, "1.43");
+            assert_eq!(found, "1.41");
+        }
+        other => panic!("unexpected result: {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn test_pause_and_resume_health_checks() {
+    let settings = create_test_settings();
+    let client = MockDockerClient {
+        containers: Arc::new(Mutex::new(vec![])),
+        env: HashMap::new(),
+    };
+    let extractor = DefaultExtractor::new(settings.network.clone(), settings.label_prefix.to_string());
+    let manager = DockerManager::new(Box::new(client), Box::new(extractor), settings).await;
+    assert!(!manager.health_checks_paused());
+
+    manager.pause_health_checks().await;
+    assert!(manager.health_checks_paused());
+    // 복제된 매니저도 같은 상태를 공유
+    assert!(manager.clone().health_checks_paused());
+
+    manager.resume_health_checks();
+    assert!(!manager.health_checks_paused());
+}
+
+#[tokio::test]
+async fn test_middleware_configs_deduplicated() {
+    let container = |id: &str, created: i64, labels: &[(&str, &str)]| ContainerSummary {
+        id: Some(id.to_string()),
+        created: Some(created),
+        labels: Some(labels.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect()),
+        ..Default::default()
+    };
+    let shared_cors = [
+        ("reverse-proxy.http.middlewares.cors.type", "cors"),
+        ("reverse-proxy.http.middlewares.cors.cors.allowOrigins", "https://a.example.com"),
+    ];
+    // 목록 순서와 관계없이 생성 시각이 가장 늦은 컨테이너 설정 사용
+    let containers = vec![
+        container("newest", 30, &[
+            ("reverse-proxy.http.middlewares.cors.type", "cors"),
+            ("reverse-proxy.http.middlewares.cors.cors.allowOrigins", "https://b.example.com"),
+        ]),
+        container("first", 10, &shared_cors),
+        container("second", 20, &[
+            shared_cors[0],
+            shared_cors[1],
+            ("reverse-proxy.http.middlewares.auth.type", "basic-auth"),
+        ]),
+    ];
+
+    let client = MockDockerClient {
+        containers: Arc::new(Mutex::new(containers)),
+        env: HashMap::new(),
+    };
+    let settings = create_test_settings();
+    let manager = DockerManager::new(
+        Box::new(client),
+        Box::new(MockExtractor::new(settings.network.clone(), settings.label_prefix.to_string())),
+        settings,
+    ).await;
+
+    let configs = manager.get_middleware_configs().await.unwrap();
+    let names: Vec<&str> = configs.iter().map(|(name, _)| name.as_str()).collect();
+    assert_eq!(names, vec!["auth", "cors"]);
+    assert_eq!(configs[1].1.settings["cors.allowOrigins"], "https://b.example.com");
+}
@@ -1,10 +1,11 @@
 use std::sync::atomic::AtomicUsize;
 use reverse_proxy_traefik::routing_v2::{
     BackendService,
-    backend::LoadBalancerStrategy,
+    backend::{BackendState, LoadBalancerStrategy},
     error::BackendError,
 };
 use std::net::SocketAddr;
+use std::time::Duration;
 
 #[test]
 fn test_basic_backend_service() {
@@ -43,6 +44,83 @@ fn test_round_robin_strategy() {
     assert_eq!(service.get_next_address().unwrap(), addr1); // 다시 처음으로
 }
 
+#[tokio::test]
+async fn test_address_metadata() {
+    let addr1: SocketAddr = "127.0.0.1:8001".parse().unwrap();
+    let addr2: SocketAddr = "127.0.0.1:8002".parse().unwrap();
+    let addr3: SocketAddr = "127.0.0.1:8003".parse().unwrap();
+    let zone = |name: &str| std::collections::HashMap::from([("zone".to_string(), name.to_string())]);
+
+    let mut service = BackendService::new(addr1);
+    assert_eq!(service.addresses(), vec![addr1]);
+    // 로드밸런서 없이는 주소 추가 불가
+    assert!(matches!(
+        service.add_address_with_metadata(addr2, 1, zone("a")),
+        Err(BackendError::LoadBalancerNotEnabled)
+    ));
+
+    service.enable_load_balancer(LoadBalancerStrategy::RoundRobin {
+        current_index: AtomicUsize::new(0),
+    });
+    service.add_address_with_metadata(addr2, 1, zone("a")).unwrap();
+    service.add_address_with_metadata(addr3, 2, zone("b")).unwrap();
+    assert_eq!(service.addresses(), vec![addr1, addr2, addr3]);
+
+    assert_eq!(service.find_address_by_label("zone", "a"), Some(addr2));
+    assert_eq!(service.find_address_by_label("zone", "b"), Some(addr3));
+    assert_eq!(service.find_address_by_label("zone", "c"), None);
+    assert_eq!(service.find_address_by_label("dc", "a"), None);
+
+    // 교체된 주소의 메타데이터는 제거
+    service.drain_and_replace(vec![(addr1, 1), (addr3, 1)], Duration::from_secs(1)).await.unwrap();
+    assert_eq!(service.find_address_by_label("zone", "a"), None);
+    assert_eq!(service.find_address_by_label("zone", "b"), Some(addr3));
+    assert_eq!(service.addresses(), vec![addr1, addr3]);
+}
+
+#[test]
+fn test_set_weight() {
+    let addr1: SocketAddr = "127.0.0.1:8001".parse().unwrap();
+    let addr2: SocketAddr = "127.0.0.1:8002".parse().unwrap();
+    let mut service = BackendService::new(addr1);
+    assert!(matches!(service.set_weight(addr1, 2), Err(BackendError::LoadBalancerNotEnabled)));
+
+    service.enable_load_balancer(LoadBalancerStrategy::Weighted {
+        current_index: AtomicUsize::new(0),
+        total_weight: 1,
+    });
+    service.add_address(addr2, 1).unwrap();
+
+    service.set_weight(addr1, 0).unwrap();
+    service.set_weight(addr2, 4).unwrap();
+    assert_eq!(service.load_balancer.as_ref().unwrap().addresses, vec![(addr1, 1), (addr2, 4)]);
+
+    let unknown: SocketAddr = "127.0.0.1:9999".parse().unwrap();
+    assert!(matches!(
+        service.set_weight(unknown, 1),
+        Err(BackendError::AddressNotFound { addr }) if addr == unknown
+    ));
+}
+
+#[test]
+fn test_reset_load_balancer_state() {
+    let addr1: SocketAddr = "127.0.0.1:8001".parse().unwrap();
+    let addr2: SocketAddr = "127.0.0.1:8002".parse().unwrap();
+    let mut service = BackendService::new(addr1);
+    service.enable_load_balancer(LoadBalancerStrategy::RoundRobin {
+        current_index: AtomicUsize::new(0),
+    });
+    service.add_address(addr2, 1).unwrap();
+
+    assert_eq!(service.get_next_address().unwrap(), addr1);
+    service.reset_load_balancer_state();
+    assert_eq!(service.get_next_address().unwrap(), addr1);
+    assert_eq!(service.get_next_address().unwrap(), addr2);
+
+    // 로드밸런서가 없어도 안전하게 무시
+    BackendService::new(addr1).reset_load_balancer_state();
+}
+
 #[test]
 fn test_weighted_strategy() {
     // 1. 백엔드 서비스 생성
@@ -93,4 +171,140 @@ fn test_load_balancer_error_cases() {
         current_index: AtomicUsize::new(0)
     });
     assert!(service.add_address("127.0.0.1:8081".parse().unwrap(), 1).is_ok());
-} 
\ No newline at end of file
+} 
+#[test]
+fn test_weight_factor() {
+    let addr1: SocketAddr = "127.0.0.1:8001".parse().unwrap();
+    let addr2: SocketAddr = "127.0.0.1:8002".parse().unwrap();
+    let addr3: SocketAddr = "127.0.0.1:8003".parse().unwrap();
+    let mut service = BackendService::new(addr1);
+    service.enable_load_balancer(LoadBalancerStrategy::Weighted {
+        current_index: AtomicUsize::new(0),
+        total_weight: 1,
+    });
+    service.add_address(addr2, 4).unwrap();
+    service.add_address(addr3, 3).unwrap();
+
+    // 가중치 절반: 1 -> 1 (최소값), 4 -> 2, 3 -> 2 (반올림)
+    service.weight_factor(0.5);
+    let weights: Vec<usize> = service.load_balancer.as_ref().unwrap()
+        .addresses.iter().map(|(_, w)| *w).collect();
+    assert_eq!(weights, vec![1, 2, 2]);
+
+    // 전체 가중치도 갱신되어 분배 비율이 1:2:2
+    let mut counts = std::collections::HashMap::new();
+    for _ in 0..50 {
+        *counts.entry(service.get_next_address().unwrap()).or_insert(0) += 1;
+    }
+    assert_eq!(counts[&addr1], 10);
+    assert_eq!(counts[&addr2], 20);
+    assert_eq!(counts[&addr3], 20);
+
+    // 로드밸런서가 없으면 변화 없음
+    let mut single = BackendService::new(addr1);
+    single.weight_factor(0.5);
+    assert!(single.load_balancer.is_none());
+}
+
+#[tokio::test]
+async fn test_drain_and_replace() {
+    let blue: SocketAddr = "127.0.0.1:8080".parse().unwrap();
+    let green_a: SocketAddr = "127.0.0.1:9090".parse().unwrap();
+    let green_b: SocketAddr = "127.0.0.1:9091".parse().unwrap();
+    let mut service = BackendService::new(blue);
+    let in_flight = service.track_connection(blue);
+
+    let switch = service.drain_and_replace(vec![(green_a, 1), (green_b, 1)], Duration::from_secs(5));
+
+    // 새 요청은 즉시 새 백엔드로 전달
+    let next = service.get_next_address().unwrap();
+    assert!(next == green_a || next == green_b);
+    assert_eq!(service.connections.state(blue), BackendState::Draining);
+    assert_eq!(service.connections.state(green_a), BackendState::Healthy);
+
+    // 진행 중인 요청이 끝나야 교체 완료
+    let switch = tokio::spawn(switch);
+    tokio::time::sleep(Duration::from_millis(20)).await;
+    assert!(!switch.is_finished());
+    drop(in_flight);
+    switch.await.unwrap().unwrap();
+    assert_eq!(service.connections.active(blue), 0);
+}
+
+#[tokio::test]
+async fn test_drain_and_replace_timeout() {
+    let blue: SocketAddr = "127.0.0.1:8080".parse().unwrap();
+    let mut service = BackendService::new(blue);
+    let _in_flight = service.track_connection(blue);
+
+    let result = service
+        .drain_and_replace(vec![("127.0.0.1:9090".parse().unwrap(), 1)], Duration::from_millis(20))
+        .await;
+    assert!(matches!(result, Err(BackendError::DrainTimeout { remaining: 1 })));
+
+    let result = service.drain_and_replace(vec![], Duration::from_millis(20)).await;
+    assert!(matches!(result, Err(BackendError::NoAddresses)));
+}
+
+#[test]
+fn test_trusted_proxy_cidrs() {
+    let mut service = BackendService::new("127.0.0.1:8080".parse().unwrap());
+    assert!(!service.is_trusted_proxy(&"10.0.0.1".parse().unwrap()));
+
+    service.set_trusted_proxy_cidrs(vec!["10.0.0.0/8".parse().unwrap()]);
+    assert!(service.is_trusted_proxy(&"10.1.2.3".parse().unwrap()));
+    assert!(!service.is_trusted_proxy(&"192.168.0.1".parse().unwrap()));
+    // IPv4-mapped IPv6 주소는 IPv4 대역으로 비교
+    assert!(service.is_trusted_proxy(&"::ffff:10.1.2.3".parse().unwrap()));
+}
+
+#[test]
+fn test_client_ip_from_trusted_proxy_only() {
+    let mut service = BackendService::new("127.0.0.1:8080".parse().unwrap());
+    let headers = |xff: &str| {
+        let mut headers = hyper::HeaderMap::new();
+        headers.insert("x-forwarded-for", xff.parse().unwrap());
+        headers
+    };
+    let remote = "10.0.0.5".parse().unwrap();
+
+    // 신뢰 대역이 없으면 위조 가능한 헤더를 무시
+    assert_eq!(service.client_ip(remote, &headers("1.2.3.4")), remote);
+
+    service.set_trusted_proxy_cidrs(vec!["10.0.0.0/8".parse().unwrap()]);
+    assert_eq!(service.client_ip(remote, &headers("1.2.3.4")), "1.2.3.4".parse::<std::net::IpAddr>().unwrap());
+    // 체인 앞쪽 값은 클라이언트가 위조할 수 있으므로 가장 오른쪽의 신뢰하지 않는 주소를 사용
+    assert_eq!(
+        service.client_ip(remote, &headers("9.9.9.9, 1.2.3.4, 10.0.0.7")),
+        "1.2.3.4".parse::<std::net::IpAddr>().unwrap()
+    );
+    // 신뢰 대역 밖에서 온 요청은 여전히 연결 주소
+    let outside = "203.0.113.1".parse().unwrap();
+    assert_eq!(service.client_ip(outside, &headers("1.2.3.4")), outside);
+}
+
+#[test]
+fn test_backend_snapshot() {
+    let addr1: SocketAddr = "127.0.0.1:8001".parse().unwrap();
+    let addr2: SocketAddr = "127.0.0.1:8002".parse().unwrap();
+
+    let service = BackendService::with_router(addr1, Some("web".to_string()));
+    let snapshot = service.snapshot();
+    assert_eq!(snapshot.addresses, vec![(addr1, 1)]);
+    assert_eq!(snapshot.strategy, None);
+    assert_eq!(snapshot.router_name.as_deref(), Some("web"));
+
+    let mut service = BackendService::new(addr1);
+    service.enable_load_balancer(LoadBalancerStrategy::Weighted {
+        current_index: AtomicUsize::new(0),
+        total_weight: 1,
+    });
+    service.add_address(addr2, 3).unwrap();
+    let snapshot = service.snapshot();
+    assert_eq!(snapshot.addresses, vec![(addr1, 1), (addr2, 3)]);
+    assert_eq!(snapshot.strategy.as_deref(), Some("weighted"));
+
+    // 직렬화 후 복원 가능
+    let json = serde_json::to_string(&snapshot).unwrap();
+    assert_eq!(serde_json::from_str::<reverse_proxy_traefik::routing_v2::BackendServiceSnapshot>(&json).unwrap(), snapshot);
+}
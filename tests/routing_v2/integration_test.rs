@@ -36,7 +36,7 @@ fn test_complete_routing_flow() {
             host.to_string(),
             BackendService::new(addr.parse().unwrap()),
             Some(PathMatcher::from_str(pattern).unwrap()),
-        );
+        ).unwrap();
     }
 
     // 2. 다양한 요청 시나리오 테스트
@@ -116,7 +116,7 @@ fn test_error_handling_flow() {
         "example.com".to_string(),
         BackendService::new("127.0.0.1:8080".parse().unwrap()),
         Some(PathMatcher::from_str("/api/*").unwrap()),
-    );
+    ).unwrap();
 
     // 2. 다양한 에러 시나리오 테스트
     let error_cases = vec![
@@ -171,7 +171,7 @@ async fn test_load_balanced_routing() {
         "api.example.com".to_string(),
         service,
         Some(PathMatcher::from_str("/").unwrap()),
-    );
+    ).unwrap();
 
     // 5. 요청 테스트
     let request = create_request("api.example.com", "/users");
@@ -203,7 +203,7 @@ async fn test_weighted_load_balancing() {
         "api.example.com".to_string(),
         service,
         Some(PathMatcher::from_str("/").unwrap()),
-    );
+    ).unwrap();
 
     // 4. 요청 생성
     let request = create_request("api.example.com", "/users");
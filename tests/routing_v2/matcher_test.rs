@@ -111,4 +111,20 @@ fn test_path_matcher_trailing_slash() {
     assert!(prefix_matcher.matches("/api/"));
     assert!(prefix_matcher.matches("/api/users"));
     assert!(prefix_matcher.matches("/api/users/"));
-} 
\ No newline at end of file
+} 
+#[test]
+fn test_path_matcher_display() {
+    let test_cases = vec![
+        ("/api", "EXACT:/api"),
+        ("/api*", "PREFIX:/api*"),
+        ("/api/*", "PREFIX:/api/*"),
+        ("^/api/.*", "REGEX:/api/.*"),
+        ("/", "ANY"),
+        ("/*", "ANY"),
+    ];
+
+    for (pattern, expected) in test_cases {
+        let matcher = PathMatcher::from_str(pattern).unwrap();
+        assert_eq!(matcher.to_string(), expected, "패턴 '{}'의 출력이 다름", pattern);
+    }
+}
@@ -1,4 +1,6 @@
-use reverse_proxy_traefik::routing_v2::{RoutingTable, BackendService, HostInfo, PathMatcher, RoutingError};
+use reverse_proxy_traefik::routing_v2::{RoutingTable, BackendService, HostConfig, HostInfo, PathMatcher, RouteChange, RoutingError, StickyCookieConfig};
+use std::time::Duration;
+use std::collections::HashMap;
 use std::net::SocketAddr;
 use hyper::{Request, Method};
 use http_body_util::Empty;
@@ -24,7 +26,7 @@ fn test_routing_table_basic() {
     let mut table = RoutingTable::new();
     let backend = BackendService::new("127.0.0.1:8080".parse().unwrap());
 
-    table.add_route("example.com".to_string(), backend, None);
+    table.add_route("example.com".to_string(), backend, None).unwrap();
 
     let req = create_request(Some("example.com"), "/");
     assert!(table.route_request(&req).is_ok());
@@ -45,7 +47,7 @@ fn test_routing_table_multiple_hosts() {
             host.to_string(),
             BackendService::new(addr.parse().unwrap()),
             path,
-        );
+        ).unwrap();
     }
 
     for (host, addr, path) in backends {
@@ -67,12 +69,12 @@ fn test_routing_table_path_based() {
         "example.com".to_string(),
         BackendService::new("127.0.0.1:8080".parse().unwrap()),
         Some(PathMatcher::from_str("/api/*").unwrap()),
-    );
+    ).unwrap();
     table.add_route(
         "example.com".to_string(),
         BackendService::new("127.0.0.1:8081".parse().unwrap()),
         Some(PathMatcher::from_str("/web/*").unwrap()),
-    );
+    ).unwrap();
 
     // API 경로 테스트
     let req = create_request(Some("example.com"), "/api/users");
@@ -102,11 +104,11 @@ fn test_routing_table_load_balancing() {
     ];
 
     for addr in backends.iter() {
-        table.add_route(
+        table.merge_route(
             "example.com".to_string(),
             BackendService::new(addr.parse().unwrap()),
             Some(PathMatcher::from_str("/api/*").unwrap()),
-        );
+        ).unwrap();
     }
 
     let req = create_request(Some("example.com"), "/api/users");
@@ -156,14 +158,16 @@ fn test_routing_table_remove_route() {
         "example.com".to_string(),
         BackendService::new("127.0.0.1:8080".parse().unwrap()),
         None,
-    );
+    ).unwrap();
 
     // 라우트 제거 전 테스트
     let req = create_request(Some("example.com"), "/");
     assert!(table.route_request(&req).is_ok());
 
     // 라우트 제거
-    table.remove_route("example.com");
+    assert_eq!(table.remove_route("example.com"), 1);
+    // 이미 제거된 호스트
+    assert_eq!(table.remove_route("example.com"), 0);
 
     // 라우트 제거 후 테스트
     let req = create_request(Some("example.com"), "/");
@@ -207,7 +211,7 @@ fn test_routing_table_round_robin() {
     service.add_address(addr2, 1).unwrap();
     service.add_address(addr3, 1).unwrap();
     
-    table.add_route("example.com".to_string(), service, None);
+    table.add_route("example.com".to_string(), service, None).unwrap();
 
     let req = create_request(Some("example.com"), "/");
     let backend = table.route_request(&req).unwrap();
@@ -235,7 +239,7 @@ fn test_routing_table_weighted() {
     let addr2 = "127.0.0.1:8081".parse().unwrap();
     service.add_address(addr2, 2).unwrap();
     
-    table.add_route("example.com".to_string(), service, None);
+    table.add_route("example.com".to_string(), service, None).unwrap();
 
     let req = create_request(Some("example.com"), "/");
     let backend = table.route_request(&req).unwrap();
@@ -266,11 +270,11 @@ fn test_routing_table_load_balancer_activation() {
     
     // 로드밸런서 없이 시작
     let service = BackendService::new(addr1);
-    table.add_route("example.com".to_string(), service, None);
+    table.add_route("example.com".to_string(), service, None).unwrap();
     
     // 두 번째 백엔드 추가 시 자동으로 라운드 로빈 활성화
     let service2 = BackendService::new(addr2);
-    table.add_route("example.com".to_string(), service2, None);
+    table.merge_route("example.com".to_string(), service2, None).unwrap();
     
     let req = create_request(Some("example.com"), "/");
     let backend = table.route_request(&req).unwrap();
@@ -282,4 +286,557 @@ fn test_routing_table_load_balancer_activation() {
     }
     
     assert_eq!(addresses.len(), 2, "두 백엔드가 모두 사용되어야 함");
-} 
\ No newline at end of file
+} 
+#[test]
+fn test_routing_table_duplicate_route() {
+    let mut table = RoutingTable::new();
+    let api = || Some(PathMatcher::from_str("/api/*").unwrap());
+    let service = |addr: &str, router: &str, priority: u32| {
+        let mut service = BackendService::with_router(addr.parse().unwrap(), Some(router.to_string()));
+        service.set_priority(priority);
+        service
+    };
+
+    table.add_route(
+        "example.com".to_string(),
+        service("127.0.0.1:8080", "docker", BackendService::DOCKER_PRIORITY),
+        api(),
+    ).unwrap();
+
+    // 다른 라우터의 우선순위가 낮거나 같은 동일 (호스트, 경로)는 거부
+    for priority in [BackendService::ENV_PRIORITY, BackendService::DOCKER_PRIORITY] {
+        let err = table.add_route(
+            "example.com".to_string(),
+            service("127.0.0.1:8081", "static", priority),
+            api(),
+        ).unwrap_err();
+        assert_eq!(err, RoutingError::DuplicateRoute {
+            host: "example.com".to_string(),
+            path: "/api/".to_string(),
+        });
+    }
+
+    // 기존 라우트는 그대로 유지
+    let req = create_request(Some("example.com"), "/api/users");
+    assert_eq!(
+        table.route_request(&req).unwrap().get_next_address().unwrap().to_string(),
+        "127.0.0.1:8080"
+    );
+
+    // 우선순위가 더 높으면 교체
+    table.add_route(
+        "example.com".to_string(),
+        service("127.0.0.1:8082", "static", BackendService::DOCKER_PRIORITY + 1),
+        api(),
+    ).unwrap();
+    assert_eq!(
+        table.route_request(&req).unwrap().get_next_address().unwrap().to_string(),
+        "127.0.0.1:8082"
+    );
+
+    // upsert_route와 merge_route도 같은 우선순위 규칙을 따름
+    assert!(table.upsert_route(
+        "example.com".to_string(),
+        service("127.0.0.1:8083", "docker", BackendService::DOCKER_PRIORITY),
+        api(),
+    ).is_err());
+    assert!(table.merge_route(
+        "example.com".to_string(),
+        service("127.0.0.1:8084", "docker", BackendService::DOCKER_PRIORITY),
+        api(),
+    ).is_err());
+    let backend = table.route_request(&req).unwrap();
+    assert!(backend.load_balancer.is_none());
+    assert_eq!(backend.get_next_address().unwrap().to_string(), "127.0.0.1:8082");
+
+    // 우선순위가 더 높으면 병합하지 않고 교체
+    table.merge_route(
+        "example.com".to_string(),
+        service("127.0.0.1:8085", "docker", BackendService::DOCKER_PRIORITY + 2),
+        api(),
+    ).unwrap();
+    let backend = table.route_request(&req).unwrap();
+    assert!(backend.load_balancer.is_none());
+    assert_eq!(backend.get_next_address().unwrap().to_string(), "127.0.0.1:8085");
+
+    // 같은 라우터가 우선순위를 낮춰 다시 등록하면 이전 라우트를 교체
+    table.upsert_route(
+        "example.com".to_string(),
+        service("127.0.0.1:8086", "docker", BackendService::ENV_PRIORITY),
+        api(),
+    ).unwrap();
+    let backend = table.route_request(&req).unwrap();
+    assert_eq!(backend.get_next_address().unwrap().to_string(), "127.0.0.1:8086");
+    assert_eq!(backend.priority, BackendService::ENV_PRIORITY);
+
+    // replace_route는 우선순위를 비교하지 않음
+    table.replace_route(
+        "example.com".to_string(),
+        service("127.0.0.1:8087", "static", BackendService::ENV_PRIORITY),
+        api(),
+    );
+    assert_eq!(
+        table.route_request(&req).unwrap().get_next_address().unwrap().to_string(),
+        "127.0.0.1:8087"
+    );
+}
+
+#[test]
+fn test_routing_table_upsert_route() {
+    let mut table = RoutingTable::new();
+
+    table.upsert_route(
+        "example.com".to_string(),
+        BackendService::new("127.0.0.1:8080".parse().unwrap()),
+        None,
+    ).unwrap();
+    table.upsert_route(
+        "example.com".to_string(),
+        BackendService::new("127.0.0.1:8081".parse().unwrap()),
+        None,
+    ).unwrap();
+
+    assert_eq!(table.routes.len(), 1);
+    let req = create_request(Some("example.com"), "/");
+    let backend = table.route_request(&req).unwrap();
+    assert!(backend.load_balancer.is_none());
+    assert_eq!(backend.get_next_address().unwrap().to_string(), "127.0.0.1:8081");
+}
+
+#[test]
+fn test_routing_table_replace_and_clear() {
+    let mut table = RoutingTable::new();
+    table.add_route(
+        "old.example.com".to_string(),
+        BackendService::new("127.0.0.1:8080".parse().unwrap()),
+        None,
+    ).unwrap();
+
+    let mut new_routes = std::collections::HashMap::new();
+    new_routes.insert(
+        ("new.example.com".to_string(), PathMatcher::from_str("/").unwrap()),
+        BackendService::new("127.0.0.1:8081".parse().unwrap()),
+    );
+    table.replace(new_routes);
+
+    // 기존 라우트는 사라지고 새 라우트만 남음
+    assert!(table.route_request(&create_request(Some("old.example.com"), "/")).is_err());
+    assert!(table.route_request(&create_request(Some("new.example.com"), "/")).is_ok());
+
+    table.clear();
+    assert!(table.routes.is_empty());
+}
+
+#[test]
+fn test_routing_table_lookup_returns_matcher() {
+    let mut table = RoutingTable::new();
+    table.add_route(
+        "example.com".to_string(),
+        BackendService::new("127.0.0.1:8080".parse().unwrap()),
+        Some(PathMatcher::from_str("/api/*").unwrap()),
+    ).unwrap();
+
+    let host_info = HostInfo {
+        name: "example.com".to_string(),
+        port: None,
+        path: Some("/api/users".to_string()),
+    };
+    let (backend, matcher) = table.lookup(&host_info).unwrap();
+    assert_eq!(backend.address, "127.0.0.1:8080".parse().unwrap());
+    assert_eq!(matcher.pattern, "/api/");
+
+    let unknown = HostInfo {
+        name: "unknown.com".to_string(),
+        port: None,
+        path: None,
+    };
+    assert!(table.lookup(&unknown).is_none());
+}
+
+#[test]
+fn test_routing_table_lookup_prefers_specific_prefix() {
+    let mut table = RoutingTable::new();
+    for (pattern, port) in [("/*", 8080), ("/api/*", 8081), ("/api/v2/*", 8082)] {
+        table.add_route(
+            "example.com".to_string(),
+            BackendService::new(format!("127.0.0.1:{}", port).parse().unwrap()),
+            Some(PathMatcher::from_str(pattern).unwrap()),
+        ).unwrap();
+    }
+
+    let lookup_port = |path: &str| {
+        let host_info = HostInfo {
+            name: "example.com".to_string(),
+            port: None,
+            path: Some(path.to_string()),
+        };
+        table.lookup(&host_info).unwrap().0.address.port()
+    };
+
+    assert_eq!(lookup_port("/api/v2/users"), 8082);
+    assert_eq!(lookup_port("/api/v1/users"), 8081);
+    assert_eq!(lookup_port("/static/app.js"), 8080);
+
+    assert!(PathMatcher::from_str("/api/v2/*").unwrap().priority()
+        > PathMatcher::from_str("/api/*").unwrap().priority());
+    assert_eq!(PathMatcher::from_str("/").unwrap().priority(), 0);
+}
+
+#[test]
+fn test_routing_table_set_backend_weight() {
+    let addr1: SocketAddr = "127.0.0.1:8001".parse().unwrap();
+    let addr2: SocketAddr = "127.0.0.1:8002".parse().unwrap();
+    let mut service = BackendService::new(addr1);
+    service.enable_load_balancer(LoadBalancerStrategy::Weighted {
+        current_index: AtomicUsize::new(0),
+        total_weight: 1,
+    });
+    service.add_address(addr2, 1).unwrap();
+
+    let mut table = RoutingTable::new();
+    table.add_route("example.com".to_string(), service, None).unwrap();
+    let mut changes = table.subscribe();
+
+    assert_eq!(table.set_backend_weight("example.com", addr2, 3), 1);
+    assert_eq!(changes.try_recv().unwrap(), RouteChange::Updated { host: "example.com".into(), path: "/".into() });
+
+    // 가중치 1:3으로 분배
+    let request = create_request(Some("example.com"), "/");
+    let backend = table.route_request(&request).unwrap();
+    let addr2_count = (0..8).filter(|_| backend.get_next_address().unwrap() == addr2).count();
+    assert_eq!(addr2_count, 6);
+
+    // 등록되지 않은 주소나 호스트는 변경 없음
+    assert_eq!(table.set_backend_weight("example.com", "127.0.0.1:9999".parse().unwrap(), 2), 0);
+    assert_eq!(table.set_backend_weight("other.com", addr2, 2), 0);
+    assert!(changes.try_recv().is_err());
+}
+
+#[test]
+fn test_routing_table_weights_survive_merge_and_update() {
+    let addr1: SocketAddr = "127.0.0.1:8001".parse().unwrap();
+    let addr2: SocketAddr = "127.0.0.1:8002".parse().unwrap();
+    let weighted = |addr: SocketAddr, weight: u32| {
+        let mut service = BackendService::with_router(addr, Some("web".to_string()));
+        service.use_weighted_strategy();
+        service.set_weight(addr, weight).unwrap();
+        service
+    };
+    let request = create_request(Some("example.com"), "/");
+
+    // 병합 시 새 컨테이너의 가중치 라벨 적용
+    let mut table = RoutingTable::new();
+    table.merge_route("example.com".to_string(), BackendService::with_router(addr1, Some("web".to_string())), None).unwrap();
+    table.merge_route("example.com".to_string(), weighted(addr2, 3), None).unwrap();
+    let backend = table.route_request(&request).unwrap();
+    assert!(backend.is_weighted());
+    assert_eq!(backend.load_balancer.as_ref().unwrap().addresses, vec![(addr1, 1), (addr2, 3)]);
+
+    // 컨테이너 하나의 설정 변경은 다른 주소를 유지하고 가중치만 갱신
+    table.update_route("example.com".to_string(), weighted(addr2, 5), None);
+    let backend = table.route_request(&request).unwrap();
+    assert_eq!(backend.load_balancer.as_ref().unwrap().addresses, vec![(addr1, 1), (addr2, 5)]);
+
+    // 라운드로빈 라우트의 가중치를 바꾸면 가중치 기반으로 전환
+    let mut table = RoutingTable::new();
+    table.merge_route("example.com".to_string(), BackendService::new(addr1), None).unwrap();
+    table.merge_route("example.com".to_string(), BackendService::new(addr2), None).unwrap();
+    assert!(!table.route_request(&request).unwrap().is_weighted());
+    assert_eq!(table.set_backend_weight("example.com", addr2, 3), 1);
+    let backend = table.route_request(&request).unwrap();
+    assert!(backend.is_weighted());
+    backend.reset_load_balancer_state();
+    let addr2_count = (0..8).filter(|_| backend.get_next_address().unwrap() == addr2).count();
+    assert_eq!(addr2_count, 6);
+}
+
+#[test]
+fn test_routing_table_lookup_priority_rules() {
+    let mut table = RoutingTable::new();
+    for (pattern, port) in [
+        ("/", 8000),
+        ("^/api/v[0-9]+/.*", 8001),
+        ("^/api/.*", 8002),
+        ("/api/*", 8003),
+        ("/api", 8004),
+    ] {
+        table.add_route(
+            "example.com".to_string(),
+            BackendService::new(format!("127.0.0.1:{}", port).parse().unwrap()),
+            Some(PathMatcher::from_str(pattern).unwrap()),
+        ).unwrap();
+    }
+    table.add_route(
+        "other.com".to_string(),
+        BackendService::new("127.0.0.1:9000".parse().unwrap()),
+        Some(PathMatcher::from_str("^/docs/.*").unwrap()),
+    ).unwrap();
+    table.add_route(
+        "other.com".to_string(),
+        BackendService::new("127.0.0.1:9001".parse().unwrap()),
+        Some(PathMatcher::from_str("^/docs/v[0-9]+/.*").unwrap()),
+    ).unwrap();
+
+    let lookup_port = |host: &str, path: &str| {
+        let host_info = HostInfo {
+            name: host.to_string(),
+            port: None,
+            path: Some(path.to_string()),
+        };
+        table.lookup(&host_info).map(|(backend, _)| backend.address.port())
+    };
+
+    // 1. 정확 매칭이 같은 경로의 접두사보다 우선
+    assert_eq!(lookup_port("example.com", "/api"), Some(8004));
+    // 2. 접두사가 정규식보다 우선
+    assert_eq!(lookup_port("example.com", "/api/v1/users"), Some(8003));
+    // 4. 매칭되는 것이 없으면 모든 경로
+    assert_eq!(lookup_port("example.com", "/web"), Some(8000));
+    // 3. 정규식끼리는 먼저 등록된 매처 우선 (패턴 길이와 무관)
+    assert_eq!(lookup_port("other.com", "/docs/v1/intro"), Some(9000));
+    assert_eq!(lookup_port("other.com", "/blog"), None);
+}
+
+/// 매칭 규칙을 그대로 구현한 참조 모델
+fn expected_match<'a>(matchers: &'a [(PathMatcher, u16)], path: &str) -> Option<&'a (PathMatcher, u16)> {
+    use reverse_proxy_traefik::routing_v2::matcher::PathMatcherKind;
+
+    let matching: Vec<_> = matchers.iter().filter(|(m, _)| m.matches(path)).collect();
+    let non_root = |kind: PathMatcherKind| {
+        matching.iter().copied().filter(move |(m, _)| m.kind == kind && m.pattern != "/")
+    };
+
+    non_root(PathMatcherKind::Exact).next()
+        .or_else(|| non_root(PathMatcherKind::Prefix)
+            .max_by_key(|(m, _)| m.pattern.trim_end_matches('/').len()))
+        .or_else(|| non_root(PathMatcherKind::Regex).next())
+        .or_else(|| matching.iter().copied().find(|(m, _)| m.pattern == "/"))
+}
+
+proptest::proptest! {
+    #![proptest_config(proptest::prelude::ProptestConfig::with_cases(1000))]
+
+    #[test]
+    fn prop_lookup_follows_priority_rules(path in "(/(api|web|v[0-9]|users|[a-z]{1,3})){0,4}/?") {
+        let matchers: Vec<(PathMatcher, u16)> = [
+            ("/", 8000),
+            ("/api", 8001),
+            ("/api/*", 8002),
+            ("/api/v1/*", 8003),
+            ("/web/*", 8004),
+            ("/web", 8005),
+            ("^/[a-z]+/users", 8006),
+            ("^/.*users", 8007),
+            ("^/api/v[0-9]/.*", 8008),
+        ].into_iter()
+            .map(|(pattern, port)| (PathMatcher::from_str(pattern).unwrap(), port))
+            .collect();
+
+        let mut table = RoutingTable::new();
+        for (matcher, port) in &matchers {
+            table.add_route(
+                "example.com".to_string(),
+                BackendService::new(format!("127.0.0.1:{}", port).parse().unwrap()),
+                Some(matcher.clone()),
+            ).unwrap();
+        }
+
+        let path = if path.is_empty() { "/".to_string() } else { path };
+        let host_info = HostInfo {
+            name: "example.com".to_string(),
+            port: None,
+            path: Some(path.clone()),
+        };
+        let (backend, matcher) = table.lookup(&host_info).unwrap();
+        let (expected_matcher, expected_port) = expected_match(&matchers, &path).unwrap();
+
+        // 선택된 매처는 하나뿐이며 해당 매처의 백엔드가 반환됨
+        proptest::prop_assert_eq!(matcher, expected_matcher, "path: {}", path);
+        proptest::prop_assert_eq!(backend.address.port(), *expected_port, "path: {}", path);
+    }
+}
+
+#[test]
+fn test_routing_table_router_priority_overrides_specificity() {
+    let mut table = RoutingTable::new();
+    for (pattern, port, router) in [("/*", 8080, "catch-all"), ("/api/*", 8081, "api")] {
+        table.add_route(
+            "example.com".to_string(),
+            BackendService::with_router(format!("127.0.0.1:{}", port).parse().unwrap(), Some(router.to_string())),
+            Some(PathMatcher::from_str(pattern).unwrap()),
+        ).unwrap();
+    }
+    let host_info = HostInfo {
+        name: "example.com".to_string(),
+        port: None,
+        path: Some("/api/users".to_string()),
+    };
+    assert_eq!(table.lookup(&host_info).unwrap().0.address.port(), 8081);
+
+    // 명시적 우선순위가 경로 구체성보다 우선
+    table.set_router_priorities(HashMap::from([("catch-all".to_string(), 100)]));
+    assert_eq!(table.lookup(&host_info).unwrap().0.address.port(), 8080);
+}
+
+#[test]
+fn test_routing_table_host_config() {
+    let mut table = RoutingTable::new();
+    assert!(table.host_config("example.com").is_none());
+
+    let config = HostConfig {
+        max_connections: Some(100),
+        timeout_override: Some(Duration::from_secs(5)),
+        access_log_enabled: false,
+    };
+    table.set_host_config("example.com", config.clone());
+    assert_eq!(table.host_config("example.com"), Some(&config));
+
+    // 라우트 교체와 무관하게 유지
+    table.add_route(
+        "example.com".to_string(),
+        BackendService::new("127.0.0.1:8080".parse().unwrap()),
+        None,
+    ).unwrap();
+    table.sync_docker_routes(Default::default());
+    assert_eq!(table.host_config("example.com"), Some(&config));
+
+    assert_eq!(table.remove_host_config("example.com"), Some(config));
+    assert!(table.host_config("example.com").is_none());
+    assert!(HostConfig::default().access_log_enabled);
+}
+
+#[test]
+fn test_routing_table_route_change_events() {
+    let mut table = RoutingTable::new();
+    let mut changes = table.subscribe();
+    let backend = |port: u16| BackendService::new(SocketAddr::from(([127, 0, 0, 1], port)));
+    let change = |host: &str, path: &str| (host.to_string(), path.to_string());
+
+    table.add_route("a.com".to_string(), backend(8080), None).unwrap();
+    table.add_route("a.com".to_string(), backend(8081), None).unwrap();
+    table.remove_route("a.com");
+    assert_eq!(changes.try_recv().unwrap(), RouteChange::Added { host: "a.com".into(), path: "/".into() });
+    assert_eq!(changes.try_recv().unwrap(), RouteChange::Updated { host: "a.com".into(), path: "/".into() });
+    assert_eq!(changes.try_recv().unwrap(), RouteChange::Removed { host: "a.com".into(), path: "/".into() });
+    assert!(changes.try_recv().is_err());
+
+    // Docker 동기화는 이전 라우트와 비교한 차이만 전달
+    let api = PathMatcher::from_str("/api").unwrap();
+    table.add_route("b.com".to_string(), backend(9000), None).unwrap();
+    table.add_route("c.com".to_string(), backend(9001), None).unwrap();
+    let _ = changes.try_recv();
+    let _ = changes.try_recv();
+
+    let mut routes = HashMap::new();
+    routes.insert(("b.com".to_string(), PathMatcher::from_str("/").unwrap()), backend(9000));
+    routes.insert(("c.com".to_string(), PathMatcher::from_str("/").unwrap()), backend(9002));
+    routes.insert(("d.com".to_string(), api), backend(9003));
+    table.sync_docker_routes(routes);
+
+    let mut received = Vec::new();
+    while let Ok(event) = changes.try_recv() {
+        received.push(match event {
+            RouteChange::Added { host, path } => ("added", change(&host, &path)),
+            RouteChange::Removed { host, path } => ("removed", change(&host, &path)),
+            RouteChange::Updated { host, path } => ("updated", change(&host, &path)),
+        });
+    }
+    received.sort();
+    assert_eq!(received, vec![
+        ("added", change("d.com", "/api")),
+        ("updated", change("c.com", "/")),
+    ]);
+}
+
+#[test]
+fn test_routing_table_snapshot() {
+    let mut table = RoutingTable::new();
+    table.add_route(
+        "b.example.com".to_string(),
+        BackendService::new("127.0.0.1:8002".parse().unwrap()),
+        None,
+    ).unwrap();
+    table.add_route(
+        "a.example.com".to_string(),
+        BackendService::with_router("127.0.0.1:8001".parse().unwrap(), Some("api".to_string())),
+        Some(PathMatcher::from_str("/api/*").unwrap()),
+    ).unwrap();
+
+    let snapshot = table.snapshot();
+    let routes: Vec<_> = snapshot.iter().map(|r| (r.host.as_str(), r.path.as_str())).collect();
+    assert_eq!(routes, vec![("a.example.com", "PREFIX:/api/*"), ("b.example.com", "ANY")]);
+    assert_eq!(snapshot[0].backend.router_name.as_deref(), Some("api"));
+    assert_eq!(snapshot[1].backend.addresses, vec![("127.0.0.1:8002".parse().unwrap(), 1)]);
+}
+
+#[test]
+fn test_routing_table_default_backend() {
+    let mut table = RoutingTable::new();
+    table.add_route(
+        "api.local".to_string(),
+        BackendService::new("127.0.0.1:8001".parse().unwrap()),
+        Some(PathMatcher::from_str("/api").unwrap()),
+    ).unwrap();
+    table.set_default_backend(BackendService::new("127.0.0.1:9000".parse().unwrap()));
+    assert_eq!(table.default_backend().unwrap().address, "127.0.0.1:9000".parse().unwrap());
+    assert!(format!("{:?}", table).contains("127.0.0.1:9000"));
+
+    // 스냅샷은 기본 백엔드를 마지막 항목으로 포함
+    let snapshot = table.snapshot();
+    assert_eq!(snapshot.len(), 2);
+    assert_eq!(snapshot[1].host, "*");
+    assert_eq!(snapshot[1].backend.addresses, vec![("127.0.0.1:9000".parse().unwrap(), 1)]);
+
+    // 등록되지 않은 호스트는 포트와 관계없이 기본 백엔드로
+    for host in ["unknown.local", "unknown.local:3000"] {
+        let backend = table.route_request(&create_request(Some(host), "/anything")).unwrap();
+        assert_eq!(backend.address, "127.0.0.1:9000".parse().unwrap());
+    }
+
+    // 등록된 호스트는 기존 라우트를 사용하고, 경로가 맞지 않으면 기본 백엔드로 넘기지 않음
+    let backend = table.route_request(&create_request(Some("api.local"), "/api")).unwrap();
+    assert_eq!(backend.address, "127.0.0.1:8001".parse().unwrap());
+    assert!(table.route_request(&create_request(Some("api.local"), "/web")).is_err());
+
+    // 제거하면 다시 오류
+    table.clear_default_backend();
+    assert_eq!(table.snapshot().len(), 1);
+    assert!(matches!(
+        table.route_request(&create_request(Some("unknown.local"), "/")).unwrap_err(),
+        RoutingError::BackendNotFound { .. }
+    ));
+}
+
+#[test]
+fn test_routing_table_lookup_with_cookie() {
+    let addr1: SocketAddr = "127.0.0.1:8001".parse().unwrap();
+    let addr2: SocketAddr = "127.0.0.1:8002".parse().unwrap();
+    let mut service = BackendService::new(addr1);
+    service.enable_load_balancer(LoadBalancerStrategy::RoundRobin { current_index: AtomicUsize::new(0) });
+    service.add_address(addr2, 1).unwrap();
+    service.set_sticky_cookie(Some(StickyCookieConfig::new("srv")));
+
+    let mut table = RoutingTable::new();
+    table.add_route("sticky.local".to_string(), service, None).unwrap();
+    table.add_route("plain.local".to_string(), BackendService::new(addr1), None).unwrap();
+
+    let host_info = |name: &str| HostInfo { name: name.to_string(), port: None, path: Some("/".to_string()) };
+    let cookie = |value: &str| {
+        let mut headers = hyper::HeaderMap::new();
+        headers.insert(hyper::header::COOKIE, value.parse().unwrap());
+        headers
+    };
+    let sticky = |host: &str, headers: &hyper::HeaderMap| table.lookup_with_cookie(&host_info(host), headers).unwrap().2;
+
+    let pinned = cookie(&format!("a=1; srv={}", StickyCookieConfig::encode_address(addr2)));
+    assert_eq!(sticky("sticky.local", &pinned), Some(addr2));
+
+    // 쿠키가 없거나, 형식이 잘못되었거나, 풀에 없는 주소면 일반 선택으로
+    assert_eq!(sticky("sticky.local", &hyper::HeaderMap::new()), None);
+    assert_eq!(sticky("sticky.local", &cookie("srv=not-an-address")), None);
+    let removed = cookie(&format!("srv={}", StickyCookieConfig::encode_address("127.0.0.1:9999".parse().unwrap())));
+    assert_eq!(sticky("sticky.local", &removed), None);
+
+    // 스티키 세션이 설정되지 않은 서비스는 쿠키를 무시
+    assert_eq!(sticky("plain.local", &pinned), None);
+}
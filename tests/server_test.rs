@@ -1,15 +1,17 @@
 use reverse_proxy_traefik::{
-    settings::Settings,
+    settings::{Settings, types::ValidTimeout},
     server::ServerManager,
-    docker::{DockerClient, DockerError, DockerManager, container::DefaultExtractor},
+    docker::{DockerClient, DockerError, DockerEvent, DockerManager, container::DefaultExtractor},
     routing_v2::RoutingTable,
-    middleware::MiddlewareManager,
+    middleware::{Middleware, MiddlewareError, MiddlewareFactory, MiddlewareManager, PluginRegistry},
+    middleware::config::{MiddlewareConfig, MiddlewareType},
 };
 use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
 use tokio::sync::RwLock;
 use async_trait::async_trait;
 use bollard::container::ListContainersOptions;
-use bollard::models::{ContainerSummary, EventMessage};
+use bollard::models::{ContainerConfig, ContainerInspectResponse, ContainerSummary, EventMessage};
 use bollard::system::EventsOptions;
 use futures_util::stream::{self, Stream};
 use std::pin::Pin;
@@ -18,6 +20,14 @@ use serial_test::serial;
 use std::collections::HashMap;
 use std::sync::Mutex;
 use bollard::secret::{ContainerSummaryNetworkSettings, EndpointSettings};
+use reverse_proxy_traefik::middleware::ConnectionInfo;
+use reverse_proxy_traefik::routing_v2::{BackendService, KeepaliveConfig, LoadBalancerStrategy, StickyCookieConfig};
+use reverse_proxy_traefik::server::handler::RequestHandler;
+use reverse_proxy_traefik::server::docker::DockerEventHandler;
+use reverse_proxy_traefik::proxy::upstream::{AsyncReadWrite, UpstreamConnector};
+use hyper_util::rt::TokioIo;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
 
 // Mock Docker 클라이언트 구현
 #[derive(Clone)]
@@ -50,6 +60,24 @@ impl DockerClient for MockDockerClient {
     {
         Box::pin(stream::empty())
     }
+
+    async fn inspect_container(&self, id: &str) -> Result<ContainerInspectResponse, DockerError> {
+        self.containers.lock().unwrap().iter()
+            .find(|container| container.id.as_deref() == Some(id))
+            .map(|container| ContainerInspectResponse {
+                id: container.id.clone(),
+                config: Some(ContainerConfig {
+                    labels: container.labels.clone(),
+                    ..Default::default()
+                }),
+                ..Default::default()
+            })
+            .ok_or_else(|| DockerError::ContainerConfigError {
+                container_id: id.to_string(),
+                reason: "컨테이너를 찾을 수 없음".to_string(),
+                context: None,
+            })
+    }
 }
 
 #[cfg(test)]
@@ -94,7 +122,7 @@ mod tests {
             Box::new(MockDockerClient::empty()),
             Box::new(DefaultExtractor::new(
                 settings.docker.network.clone(),
-                settings.docker.label_prefix.clone(),
+                settings.docker.label_prefix.to_string(),
             )),
             settings.docker.clone(),
         ).await;
@@ -133,7 +161,7 @@ mod tests {
             Box::new(MockDockerClient::empty()),
             Box::new(DefaultExtractor::new(
                 settings.docker.network.clone(),
-                settings.docker.label_prefix.clone(),
+                settings.docker.label_prefix.to_string(),
             )),
             settings.docker.clone(),
         ).await;
@@ -153,6 +181,42 @@ mod tests {
         teardown();
     }
 
+    #[tokio::test]
+    #[serial]
+    async fn test_server_manager_debug() {
+        setup().await;
+
+        let settings = Settings::from_env().await.unwrap();
+        let docker_manager = DockerManager::new(
+            Box::new(MockDockerClient::empty()),
+            Box::new(DefaultExtractor::new(
+                settings.docker.network.clone(),
+                settings.docker.label_prefix.to_string(),
+            )),
+            settings.docker.clone(),
+        ).await;
+        let mut table = RoutingTable::new();
+        table.add_route("a.local".to_string(), BackendService::new("127.0.0.1:8080".parse().unwrap()), None).unwrap();
+        table.add_route("b.local".to_string(), BackendService::new("127.0.0.1:8081".parse().unwrap()), None).unwrap();
+
+        let server = ServerManager::new(
+            settings,
+            docker_manager,
+            Arc::new(RwLock::new(table)),
+            MiddlewareManager::new(&HashMap::new(), &HashMap::new()),
+        );
+
+        // 라우트 내용 대신 개수만 표시
+        let debug = format!("{:?}", server);
+        assert!(debug.starts_with("ServerManager {"), "{}", debug);
+        assert!(debug.contains("routes: 2 entries"), "{}", debug);
+        assert!(debug.contains("router_chains: 0 entries"), "{}", debug);
+        assert!(debug.contains("client: \"<dyn DockerClient>\""), "{}", debug);
+        assert!(!debug.contains("127.0.0.1:8080"), "{}", debug);
+
+        teardown();
+    }
+
     #[tokio::test]
     #[serial]
     async fn test_server_with_container_routes() {
@@ -191,7 +255,7 @@ mod tests {
             Box::new(mock_client),
             Box::new(DefaultExtractor::new(
                 settings.docker.network.clone(),
-                settings.docker.label_prefix.clone(),
+                settings.docker.label_prefix.to_string(),
             )),
             settings.docker.clone(),
         ).await;
@@ -244,7 +308,7 @@ mod tests {
             Box::new(MockDockerClient::empty()),
             Box::new(DefaultExtractor::new(
                 settings.docker.network.clone(),
-                settings.docker.label_prefix.clone(),
+                settings.docker.label_prefix.to_string(),
             )),
             settings.docker.clone(),
         ).await;
@@ -316,7 +380,7 @@ mod tests {
             Box::new(mock_client),
             Box::new(DefaultExtractor::new(
                 settings.docker.network.clone(),
-                settings.docker.label_prefix.clone(),
+                settings.docker.label_prefix.to_string(),
             )),
             settings.docker.clone(),
         ).await;
@@ -407,7 +471,7 @@ mod tests {
             Box::new(client),
             Box::new(DefaultExtractor::new(
                 settings.docker.network.clone(),
-                settings.docker.label_prefix.clone(),
+                settings.docker.label_prefix.to_string(),
             )),
             settings.docker.clone(),
         ).await;
@@ -494,7 +558,7 @@ mod tests {
             Box::new(client),
             Box::new(DefaultExtractor::new(
                 settings.docker.network.clone(),
-                settings.docker.label_prefix.clone(),
+                settings.docker.label_prefix.to_string(),
             )),
             settings.docker.clone(),
         ).await;
@@ -582,7 +646,7 @@ mod tests {
             Box::new(client),
             Box::new(DefaultExtractor::new(
                 settings.docker.network.clone(),
-                settings.docker.label_prefix.clone(),
+                settings.docker.label_prefix.to_string(),
             )),
             settings.docker.clone(),
         ).await;
@@ -593,6 +657,9 @@ mod tests {
             &settings.middleware,
             &router_middlewares
         );
+        // 미들웨어가 없는 라우터는 체인 실행을 건너뜀
+        assert!(!middleware_manager.has_middlewares(Some("test")));
+        assert!(!middleware_manager.has_middlewares(None));
 
         let server = ServerManager::new(
             settings,
@@ -625,4 +692,1073 @@ mod tests {
 
         teardown();
     }
-} 
\ No newline at end of file
+
+    /// 클라이언트 요청을 처리하는 프록시 서버를 띄우고 주소를 반환합니다.
+    async fn spawn_proxy(routing_table: RoutingTable) -> std::net::SocketAddr {
+        spawn_handler(RequestHandler::new(
+            Arc::new(RwLock::new(routing_table)),
+            MiddlewareManager::default(),
+        )).await
+    }
+
+    async fn spawn_handler(handler: RequestHandler) -> std::net::SocketAddr {
+        let handler = Arc::new(handler);
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+
+        tokio::spawn(async move {
+            while let Ok((stream, remote_addr)) = listener.accept().await {
+                let handler = handler.clone();
+                tokio::spawn(async move {
+                    let connection = ConnectionInfo { remote_addr, local_addr: stream.local_addr().ok(), is_tls: false };
+                    let _ = handler.handle_connection(TokioIo::new(stream), connection).await;
+                });
+            }
+        });
+
+        addr
+    }
+
+    /// CONNECT 요청을 보내고 응답 헤더를 읽습니다.
+    async fn send_connect(proxy_addr: std::net::SocketAddr, target: &str) -> (TcpStream, String) {
+        let mut stream = TcpStream::connect(proxy_addr).await.unwrap();
+        stream.write_all(format!(
+            "CONNECT {target} HTTP/1.1\r\nHost: {target}\r\n\r\n"
+        ).as_bytes()).await.unwrap();
+
+        let mut head = Vec::new();
+        let mut byte = [0u8; 1];
+        while !head.ends_with(b"\r\n\r\n") {
+            stream.read_exact(&mut byte).await.unwrap();
+            head.push(byte[0]);
+        }
+        (stream, String::from_utf8(head).unwrap())
+    }
+
+    /// 모든 요청에 주어진 상태 코드로 응답하는 백엔드를 띄웁니다.
+    async fn spawn_status_backend(status: &'static str) -> std::net::SocketAddr {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            while let Ok((mut stream, _)) = listener.accept().await {
+                tokio::spawn(async move {
+                    let mut buf = [0u8; 1024];
+                    let _ = stream.read(&mut buf).await;
+                    let response = format!("HTTP/1.1 {status}\r\ncontent-length: 0\r\n\r\n");
+                    let _ = stream.write_all(response.as_bytes()).await;
+                });
+            }
+        });
+        addr
+    }
+
+    async fn create_probe_server(settings: Settings, table: RoutingTable) -> ServerManager {
+        let docker_manager = DockerManager::new(
+            Box::new(MockDockerClient::empty()),
+            Box::new(DefaultExtractor::new(
+                settings.docker.network.clone(),
+                settings.docker.label_prefix.to_string(),
+            )),
+            settings.docker.clone(),
+        ).await;
+
+        ServerManager::new(
+            settings,
+            docker_manager,
+            Arc::new(RwLock::new(table)),
+            MiddlewareManager::default(),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_startup_probe() {
+        let healthy_addr = spawn_status_backend("200 OK").await;
+        // 바인딩 후 바로 닫아 연결이 거부되는 주소
+        let closed_addr = TcpListener::bind("127.0.0.1:0").await.unwrap().local_addr().unwrap();
+
+        let mut table = RoutingTable::new();
+        table.add_route("up.local".to_string(), BackendService::new(healthy_addr), None).unwrap();
+        table.add_route("down.local".to_string(), BackendService::new(closed_addr), None).unwrap();
+
+        let mut settings = Settings::default();
+        settings.docker.health_check.timeout = ValidTimeout::from_secs(1).unwrap();
+        let server = create_probe_server(settings.clone(), table.clone()).await;
+
+        let mut results = server.startup_probe().await.unwrap();
+        results.sort_by(|a, b| a.host.cmp(&b.host));
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].host, "down.local");
+        assert!(!results[0].healthy);
+        assert_eq!(results[1].host, "up.local");
+        assert_eq!(results[1].address, healthy_addr);
+        assert!(results[1].healthy, "probe failed: {}", results[1].message);
+
+        // require_all_healthy가 켜져 있으면 실패한 백엔드가 있을 때 에러
+        settings.startup.require_all_healthy = true;
+        let server = create_probe_server(settings, table).await;
+        assert!(server.startup_probe().await.is_err());
+    }
+
+    /// `/download` 요청에 X-Accel-Redirect로 응답하고 그 외 경로는 경로를 바디로 돌려주는 백엔드
+    async fn spawn_accel_backend() -> std::net::SocketAddr {
+        spawn_named_accel_backend("").await
+    }
+
+    /// `spawn_accel_backend`와 같지만 경로 앞에 `name`을 붙여 돌려주는 백엔드
+    async fn spawn_named_accel_backend(name: &'static str) -> std::net::SocketAddr {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            while let Ok((mut stream, _)) = listener.accept().await {
+                tokio::spawn(async move {
+                    let mut buf = [0u8; 1024];
+                    let n = stream.read(&mut buf).await.unwrap_or(0);
+                    let request = String::from_utf8_lossy(&buf[..n]);
+                    let path = request.split_whitespace().nth(1).unwrap_or("/").to_string();
+                    let response = if path == "/download" {
+                        "HTTP/1.1 200 OK\r\ncontent-type: text/plain\r\nx-accel-redirect: /internal/file.txt\r\ncontent-length: 7\r\nconnection: close\r\n\r\nignored".to_string()
+                    } else {
+                        let body = format!("{}{}", name, path);
+                        format!("HTTP/1.1 200 OK\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{}", body.len(), body)
+                    };
+                    let _ = stream.write_all(response.as_bytes()).await;
+                });
+            }
+        });
+        addr
+    }
+
+    async fn get_via_proxy(proxy_addr: std::net::SocketAddr, host: &str, path: &str) -> reqwest::Response {
+        reqwest::Client::new()
+            .get(format!("http://{}{}", proxy_addr, path))
+            .header("host", host)
+            .send()
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_x_accel_redirect() {
+        let backend_addr = spawn_accel_backend().await;
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir(dir.path().join("internal")).unwrap();
+        std::fs::write(dir.path().join("internal/file.txt"), "from disk").unwrap();
+
+        let mut table = RoutingTable::new();
+        // 같은 백엔드로 다시 요청
+        let mut service = BackendService::new(backend_addr);
+        service.enable_x_accel_redirect(None);
+        table.add_route("backend.local".to_string(), service, None).unwrap();
+        // 로컬 파일 제공
+        let mut service = BackendService::new(backend_addr);
+        service.enable_x_accel_redirect(Some(dir.path().to_string_lossy().to_string()));
+        table.add_route("files.local".to_string(), service, None).unwrap();
+        // 비활성화된 경우 원래 응답 유지
+        table.add_route("plain.local".to_string(), BackendService::new(backend_addr), None).unwrap();
+        let proxy_addr = spawn_proxy(table).await;
+
+        let response = get_via_proxy(proxy_addr, "backend.local", "/download").await;
+        assert!(response.headers().get("x-accel-redirect").is_none());
+        assert_eq!(response.text().await.unwrap(), "/internal/file.txt");
+
+        let response = get_via_proxy(proxy_addr, "files.local", "/download").await;
+        assert_eq!(response.status(), 200);
+        assert_eq!(response.headers()["content-type"], "text/plain");
+        assert_eq!(response.text().await.unwrap(), "from disk");
+
+        let response = get_via_proxy(proxy_addr, "plain.local", "/download").await;
+        assert!(response.headers().get("x-accel-redirect").is_some());
+        assert_eq!(response.text().await.unwrap(), "ignored");
+    }
+
+    #[tokio::test]
+    async fn test_x_accel_redirect_uses_responding_replica() {
+        let first = spawn_named_accel_backend("first").await;
+        let second = spawn_named_accel_backend("second").await;
+
+        let mut service = BackendService::new(first);
+        service.enable_load_balancer(LoadBalancerStrategy::RoundRobin { current_index: AtomicUsize::new(0) });
+        service.add_address(second, 1).unwrap();
+        service.enable_x_accel_redirect(None);
+        let mut table = RoutingTable::new();
+        table.add_route("backend.local".to_string(), service, None).unwrap();
+        let proxy_addr = spawn_proxy(table).await;
+
+        // 라운드 로빈이 넘어가도 내부 요청은 X-Accel-Redirect를 보낸 복제본으로 보냄
+        let mut bodies = Vec::new();
+        for _ in 0..2 {
+            let response = get_via_proxy(proxy_addr, "backend.local", "/download").await;
+            bodies.push(response.text().await.unwrap());
+        }
+        bodies.sort();
+        assert_eq!(bodies, vec!["first/internal/file.txt", "second/internal/file.txt"]);
+    }
+
+    #[tokio::test]
+    async fn test_connect_tunnel_to_passthrough_backend() {
+        // 받은 데이터를 그대로 돌려주는 백엔드
+        let backend = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let backend_addr = backend.local_addr().unwrap();
+        tokio::spawn(async move {
+            let (mut stream, _) = backend.accept().await.unwrap();
+            let (mut reader, mut writer) = stream.split();
+            let _ = tokio::io::copy(&mut reader, &mut writer).await;
+        });
+
+        let mut service = BackendService::new(backend_addr);
+        service.set_passthrough(true);
+        let mut table = RoutingTable::new();
+        table.add_route("secure.local".to_string(), service, None).unwrap();
+        let proxy_addr = spawn_proxy(table).await;
+
+        let (mut stream, head) = send_connect(proxy_addr, "secure.local:443").await;
+        assert!(head.starts_with("HTTP/1.1 200"), "unexpected response: {}", head);
+
+        // 터널을 통해 바이트가 그대로 전달되는지 확인
+        stream.write_all(b"ping").await.unwrap();
+        let mut buf = [0u8; 4];
+        stream.read_exact(&mut buf).await.unwrap();
+        assert_eq!(&buf, b"ping");
+    }
+
+    #[tokio::test]
+    async fn test_connect_rejected_without_passthrough() {
+        let mut table = RoutingTable::new();
+        table.add_route(
+            "plain.local".to_string(),
+            BackendService::new("127.0.0.1:9".parse().unwrap()),
+            None,
+        ).unwrap();
+        let proxy_addr = spawn_proxy(table).await;
+
+        let (_, head) = send_connect(proxy_addr, "plain.local:443").await;
+        assert!(head.starts_with("HTTP/1.1 405"), "unexpected response: {}", head);
+    }
+
+    #[tokio::test]
+    async fn test_readiness_and_liveness_probes() {
+        let routing_table = Arc::new(RwLock::new(RoutingTable::new()));
+        let docker_connected = Arc::new(AtomicBool::new(true));
+        let proxy_addr = spawn_handler(
+            RequestHandler::new(routing_table.clone(), MiddlewareManager::default())
+                .with_docker_status(docker_connected.clone()),
+        ).await;
+
+        // 라우트가 없으면 준비되지 않음
+        assert_eq!(get_via_proxy(proxy_addr, "any.local", "/__roxy/live").await.status(), 200);
+        assert_eq!(get_via_proxy(proxy_addr, "any.local", "/__roxy/ready").await.status(), 503);
+
+        let backend_addr = spawn_status_backend("200 OK").await;
+        routing_table.write().await
+            .add_route("app.local".to_string(), BackendService::new(backend_addr), None)
+            .unwrap();
+        assert_eq!(get_via_proxy(proxy_addr, "any.local", "/__roxy/ready").await.status(), 200);
+
+        // Docker 연결이 끊기면 준비되지 않음, 생존 프로브는 영향 없음
+        docker_connected.store(false, Ordering::Relaxed);
+        assert_eq!(get_via_proxy(proxy_addr, "any.local", "/__roxy/ready").await.status(), 503);
+        assert_eq!(get_via_proxy(proxy_addr, "any.local", "/__roxy/live").await.status(), 200);
+    }
+
+    #[tokio::test]
+    async fn test_version_endpoint() {
+        let routing_table = Arc::new(RwLock::new(RoutingTable::new()));
+        let proxy_addr = spawn_handler(
+            RequestHandler::new(routing_table, MiddlewareManager::default()),
+        ).await;
+
+        // 라우트가 없어도 응답
+        let response = get_via_proxy(proxy_addr, "any.local", "/__roxy/version").await;
+        assert_eq!(response.status(), 200);
+        assert_eq!(response.headers()["content-type"], "application/json");
+        let body: serde_json::Value = response.json().await.unwrap();
+        assert_eq!(body["version"], env!("CARGO_PKG_VERSION"));
+        for field in ["build_time", "git_hash", "rustc_version", "target"] {
+            assert!(body[field].as_str().is_some_and(|value| !value.is_empty()), "{field}");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_metrics_endpoint() {
+        let routing_table = Arc::new(RwLock::new(RoutingTable::new()));
+        let retry_count = Arc::new(std::sync::atomic::AtomicU64::new(2));
+        let proxy_addr = spawn_handler(
+            RequestHandler::new(routing_table, MiddlewareManager::default())
+                .with_docker_retry_count(retry_count.clone()),
+        ).await;
+
+        let metric = "retry_count_total{operation=\"docker_container_routes\"}";
+        let response = get_via_proxy(proxy_addr, "any.local", "/__roxy/metrics").await;
+        assert_eq!(response.status(), 200);
+        assert!(response.headers()["content-type"].to_str().unwrap().starts_with("text/plain"));
+        let body = response.text().await.unwrap();
+        assert!(body.contains("# TYPE retry_count_total counter"), "{}", body);
+        assert!(body.contains(&format!("{} 2\n", metric)), "{}", body);
+
+        retry_count.fetch_add(1, Ordering::Relaxed);
+        let body = get_via_proxy(proxy_addr, "any.local", "/__roxy/metrics").await.text().await.unwrap();
+        assert!(body.contains(&format!("{} 3\n", metric)), "{}", body);
+    }
+
+    #[tokio::test]
+    async fn test_metrics_endpoint_middleware_duration() {
+        let mut headers = MiddlewareConfig::new(MiddlewareType::Headers);
+        headers.enabled = true;
+        headers.settings.insert("headers.response.set.X-Test".to_string(), "1".to_string());
+        let middlewares = HashMap::from([("api-headers".to_string(), headers)]);
+        let router_middlewares = HashMap::from([("api".to_string(), vec!["api-headers".to_string()])]);
+
+        let backend_addr = spawn_status_backend("200 OK").await;
+        let mut table = RoutingTable::new();
+        table.add_route(
+            "metrics.local".to_string(),
+            BackendService::with_router(backend_addr, Some("api".to_string())),
+            None,
+        ).unwrap();
+        let manager = MiddlewareManager::new(&middlewares, &router_middlewares);
+        let proxy_addr = spawn_handler(RequestHandler::new(Arc::new(RwLock::new(table)), manager)).await;
+
+        assert_eq!(get_via_proxy(proxy_addr, "metrics.local", "/").await.status(), 200);
+
+        let body = get_via_proxy(proxy_addr, "metrics.local", "/__roxy/metrics").await.text().await.unwrap();
+        assert!(body.contains("# TYPE roxy_middleware_duration_seconds histogram\n"), "{}", body);
+        assert!(body.contains("roxy_middleware_duration_seconds_bucket{middleware=\"headers\",le=\"+Inf\"}"), "{}", body);
+        assert!(body.contains("roxy_middleware_duration_seconds_count{middleware=\"headers\"}"), "{}", body);
+    }
+
+    #[tokio::test]
+    async fn test_metrics_endpoint_upstream_response_duration() {
+        let backend_addr = spawn_status_backend("200 OK").await;
+        let mut table = RoutingTable::new();
+        table.add_route("upstream.local".to_string(), BackendService::new(backend_addr), None).unwrap();
+        let proxy_addr = spawn_proxy(table).await;
+
+        assert_eq!(get_via_proxy(proxy_addr, "upstream.local", "/").await.status(), 200);
+
+        let body = get_via_proxy(proxy_addr, "upstream.local", "/__roxy/metrics").await.text().await.unwrap();
+        assert!(body.contains("# TYPE roxy_upstream_response_duration_seconds histogram\n"), "{}", body);
+        assert!(body.contains("roxy_upstream_response_duration_seconds_bucket{le=\"+Inf\"}"), "{}", body);
+        assert!(body.contains("roxy_upstream_response_duration_seconds_sum "), "{}", body);
+        let count = body.lines()
+            .find_map(|line| line.strip_prefix("roxy_upstream_response_duration_seconds_count "))
+            .and_then(|value| value.parse::<u64>().ok());
+        assert!(count.is_some_and(|count| count >= 1), "{}", body);
+    }
+
+    /// 받은 요청 헤더를 소문자로 바디에 담아 돌려주는 백엔드
+    async fn spawn_echo_headers_backend() -> std::net::SocketAddr {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            while let Ok((mut stream, _)) = listener.accept().await {
+                tokio::spawn(async move {
+                    let mut buf = [0u8; 1024];
+                    let n = stream.read(&mut buf).await.unwrap_or(0);
+                    let head = String::from_utf8_lossy(&buf[..n]).to_lowercase();
+                    let response = format!("HTTP/1.1 200 OK\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{}", head.len(), head);
+                    let _ = stream.write_all(response.as_bytes()).await;
+                });
+            }
+        });
+        addr
+    }
+
+    #[tokio::test]
+    async fn test_upstream_keepalive_disabled() {
+        let backend_addr = spawn_echo_headers_backend().await;
+
+        let mut table = RoutingTable::new();
+        table.add_route("default.local".to_string(), BackendService::new(backend_addr), None).unwrap();
+        let mut service = BackendService::new(backend_addr);
+        service.set_keepalive(KeepaliveConfig {
+            enabled: false,
+            ..Default::default()
+        });
+        table.add_route("close.local".to_string(), service, None).unwrap();
+        let proxy_addr = spawn_proxy(table).await;
+
+        let head = get_via_proxy(proxy_addr, "close.local", "/").await.text().await.unwrap();
+        assert!(head.contains("connection: close\r\n"), "{}", head);
+
+        let head = get_via_proxy(proxy_addr, "default.local", "/").await.text().await.unwrap();
+        assert!(!head.contains("connection: close"), "{}", head);
+    }
+
+    #[tokio::test]
+    async fn test_forwarded_for_from_untrusted_client_replaced() {
+        let backend_addr = spawn_echo_headers_backend().await;
+
+        let mut table = RoutingTable::new();
+        table.add_route("untrusted.local".to_string(), BackendService::new(backend_addr), None).unwrap();
+        let mut service = BackendService::new(backend_addr);
+        service.set_trusted_proxy_cidrs(vec!["127.0.0.0/8".parse().unwrap()]);
+        table.add_route("trusted.local".to_string(), service, None).unwrap();
+        let proxy_addr = spawn_proxy(table).await;
+
+        let forwarded_for = |host: &'static str| async move {
+            let head = reqwest::Client::new()
+                .get(format!("http://{}/", proxy_addr))
+                .header("host", host)
+                .header("x-forwarded-for", "1.2.3.4")
+                .send()
+                .await
+                .unwrap()
+                .text()
+                .await
+                .unwrap();
+            head.lines()
+                .find_map(|line| line.strip_prefix("x-forwarded-for: "))
+                .map(str::to_string)
+        };
+
+        // 신뢰하지 않는 클라이언트가 보낸 체인은 클라이언트 IP로 대체
+        assert_eq!(forwarded_for("untrusted.local").await.as_deref(), Some("127.0.0.1"));
+        // 신뢰하는 프록시에서 온 체인에는 클라이언트 IP를 덧붙임
+        assert_eq!(forwarded_for("trusted.local").await.as_deref(), Some("1.2.3.4, 127.0.0.1"));
+    }
+
+    #[tokio::test]
+    async fn test_rfc7239_forwarded_header() {
+        let backend_addr = spawn_echo_headers_backend().await;
+
+        let mut table = RoutingTable::new();
+        table.add_route("legacy.local".to_string(), BackendService::new(backend_addr), None).unwrap();
+        let mut service = BackendService::new(backend_addr);
+        service.set_rfc7239_forwarded(true);
+        table.add_route("rfc7239.local".to_string(), service, None).unwrap();
+        let proxy_addr = spawn_proxy(table).await;
+
+        let forwarded = |host: &'static str| async move {
+            let head = reqwest::Client::new()
+                .get(format!("http://{}/", proxy_addr))
+                .header("host", host)
+                .header("forwarded", "for=1.2.3.4")
+                .send()
+                .await
+                .unwrap()
+                .text()
+                .await
+                .unwrap();
+            head.lines()
+                .find_map(|line| line.strip_prefix("forwarded: "))
+                .map(str::to_string)
+        };
+
+        // 비활성화된 백엔드에는 신뢰하지 않는 클라이언트가 보낸 Forwarded 헤더도 보내지 않음
+        assert_eq!(forwarded("legacy.local").await, None);
+        // 활성화하면 신뢰하지 않는 클라이언트의 값을 대체하고 프록시 주소를 by로 기록
+        assert_eq!(
+            forwarded("rfc7239.local").await,
+            Some(format!("for=\"127.0.0.1\";proto=http;by=\"{}\"", proxy_addr))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_upstream_response_time_header() {
+        let backend_addr = spawn_status_backend("200 OK").await;
+
+        let mut table = RoutingTable::new();
+        table.add_route("timing.local".to_string(), BackendService::new(backend_addr), None).unwrap();
+        let proxy_addr = spawn_proxy(table).await;
+
+        let response = get_via_proxy(proxy_addr, "timing.local", "/").await;
+        let value = response.headers()["x-upstream-response-time"].to_str().unwrap();
+        let millis = value.strip_suffix("ms").expect(value);
+        assert!(millis.parse::<u64>().is_ok(), "{}", value);
+    }
+
+    /// 응답에 고정 헤더를 추가하는 플러그인 미들웨어
+    struct PluginHeaderMiddleware {
+        value: String,
+    }
+
+    #[async_trait]
+    impl Middleware for PluginHeaderMiddleware {
+        fn name(&self) -> &'static str {
+            "plugin-header"
+        }
+
+        async fn handle_request(&self, req: reverse_proxy_traefik::middleware::Request) -> Result<reverse_proxy_traefik::middleware::Request, MiddlewareError> {
+            Ok(req)
+        }
+
+        async fn handle_response(&self, mut res: reverse_proxy_traefik::middleware::Response) -> Result<reverse_proxy_traefik::middleware::Response, MiddlewareError> {
+            res.headers_mut().insert("x-plugin", self.value.parse().unwrap());
+            Ok(res)
+        }
+    }
+
+    struct PluginHeaderFactory;
+
+    impl MiddlewareFactory for PluginHeaderFactory {
+        fn create(&self, config: &MiddlewareConfig) -> Result<Box<dyn Middleware>, MiddlewareError> {
+            let value = config.settings.get("value").cloned().unwrap_or_default();
+            Ok(Box::new(PluginHeaderMiddleware { value }))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_custom_plugin_middleware() {
+        let backend_addr = spawn_status_backend("200 OK").await;
+
+        let mut middleware = MiddlewareConfig::new(MiddlewareType::Custom("plugin-header".to_string()));
+        middleware.enabled = true;
+        middleware.settings.insert("value".to_string(), "from-plugin".to_string());
+        let middlewares = HashMap::from([("plugin".to_string(), middleware)]);
+        let router_middlewares = HashMap::from([("api".to_string(), vec!["plugin".to_string()])]);
+
+        // 등록소가 없으면 플러그인 미들웨어를 만들 수 없음
+        let manager = MiddlewareManager::new(&middlewares, &router_middlewares);
+        assert!(!manager.has_middlewares(Some("api")));
+
+        let mut plugins = PluginRegistry::new();
+        plugins.register_middleware("plugin-header", Box::new(PluginHeaderFactory));
+        let manager = MiddlewareManager::with_plugins(&middlewares, &router_middlewares, Arc::new(plugins));
+        assert!(manager.has_middlewares(Some("api")));
+
+        let mut table = RoutingTable::new();
+        table.add_route(
+            "api.local".to_string(),
+            BackendService::with_router(backend_addr, Some("api".to_string())),
+            None,
+        ).unwrap();
+        let proxy_addr = spawn_handler(RequestHandler::new(Arc::new(RwLock::new(table)), manager)).await;
+
+        let response = get_via_proxy(proxy_addr, "api.local", "/").await;
+        assert_eq!(response.headers()["x-plugin"], "from-plugin");
+    }
+
+    /// 받은 요청 바디를 그대로 돌려주는 백엔드
+    async fn spawn_echo_body_backend() -> std::net::SocketAddr {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            while let Ok((mut stream, _)) = listener.accept().await {
+                tokio::spawn(async move {
+                    let mut request = Vec::new();
+                    let mut buf = [0u8; 1024];
+                    let body = loop {
+                        let n = stream.read(&mut buf).await.unwrap_or(0);
+                        if n == 0 {
+                            return;
+                        }
+                        request.extend_from_slice(&buf[..n]);
+                        let text = String::from_utf8_lossy(&request).to_string();
+                        if let Some((head, body)) = text.split_once("\r\n\r\n") {
+                            let length: usize = head.lines()
+                                .find_map(|line| line.to_lowercase().strip_prefix("content-length:").map(|v| v.trim().parse().unwrap()))
+                                .unwrap_or(0);
+                            if body.len() >= length {
+                                break body.to_string();
+                            }
+                        }
+                    };
+                    let response = format!("HTTP/1.1 200 OK\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{}", body.len(), body);
+                    let _ = stream.write_all(response.as_bytes()).await;
+                });
+            }
+        });
+        addr
+    }
+
+    /// 로그 출력을 메모리에 모으는 writer
+    #[derive(Clone, Default)]
+    struct CapturedLogs(Arc<std::sync::Mutex<Vec<u8>>>);
+
+    impl std::io::Write for CapturedLogs {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.lock().unwrap().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_request_body_trace_logging() {
+        let logs = CapturedLogs::default();
+        let writer = logs.clone();
+        let subscriber = tracing_subscriber::fmt()
+            .with_max_level(tracing::Level::TRACE)
+            .with_ansi(false)
+            .with_writer(move || writer.clone())
+            .finish();
+        let _guard = tracing::subscriber::set_default(subscriber);
+
+        let backend_addr = spawn_echo_body_backend().await;
+        let mut table = RoutingTable::new();
+        table.add_route("echo.local".to_string(), BackendService::new(backend_addr), None).unwrap();
+        let proxy_addr = spawn_handler(RequestHandler::new(
+            Arc::new(RwLock::new(table)),
+            MiddlewareManager::default(),
+        ).with_request_body_logging(4)).await;
+
+        let post = |content_type: &'static str, body: &'static str| {
+            reqwest::Client::new()
+                .post(format!("http://{}/", proxy_addr))
+                .header("host", "echo.local")
+                .header("content-type", content_type)
+                .body(body)
+                .send()
+        };
+
+        // 일부만 기록해도 업스트림에는 바디 전체가 전달됨
+        let response = post("application/json; charset=utf-8", "{\"a\":1}").await.unwrap();
+        assert_eq!(response.text().await.unwrap(), "{\"a\":1}");
+        let output = String::from_utf8(logs.0.lock().unwrap().clone()).unwrap();
+        assert!(output.contains("body_hex=7b226122"), "{}", output);
+        assert!(output.contains("truncated=true"), "{}", output);
+
+        // 바이너리 바디는 기록하지 않음
+        logs.0.lock().unwrap().clear();
+        let response = post("application/octet-stream", "binary").await.unwrap();
+        assert_eq!(response.text().await.unwrap(), "binary");
+        let output = String::from_utf8(logs.0.lock().unwrap().clone()).unwrap();
+        assert!(!output.contains("body_hex"), "{}", output);
+    }
+
+    #[tokio::test]
+    async fn test_head_response_has_no_body() {
+        let proxy_addr = spawn_proxy(RoutingTable::new()).await;
+        let get_body = get_via_proxy(proxy_addr, "unknown.local", "/").await.bytes().await.unwrap();
+
+        let mut stream = TcpStream::connect(proxy_addr).await.unwrap();
+        stream.write_all(b"HEAD / HTTP/1.1\r\nHost: unknown.local\r\nConnection: close\r\n\r\n").await.unwrap();
+        let mut response = Vec::new();
+        stream.read_to_end(&mut response).await.unwrap();
+        let response = String::from_utf8(response).unwrap().to_lowercase();
+
+        assert!(response.starts_with("http/1.1 404"), "{}", response);
+        // GET과 같은 Content-Length를 보고하되 본문은 없음
+        assert!(response.contains(&format!("content-length: {}\r\n", get_body.len())), "{}", response);
+        assert!(response.ends_with("\r\n\r\n"), "{}", response);
+    }
+
+    #[tokio::test]
+    async fn test_head_upstream_response_keeps_backend_headers() {
+        // Content-Length 없이 응답하는 백엔드
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let backend_addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            let (mut stream, _) = listener.accept().await.unwrap();
+            let mut buf = [0u8; 1024];
+            let _ = stream.read(&mut buf).await;
+            let _ = stream.write_all(b"HTTP/1.1 200 OK\r\nx-backend: head\r\n\r\n").await;
+        });
+
+        let mut table = RoutingTable::new();
+        table.add_route("app.local".to_string(), BackendService::new(backend_addr), None).unwrap();
+        let proxy_addr = spawn_proxy(table).await;
+
+        let mut stream = TcpStream::connect(proxy_addr).await.unwrap();
+        stream.write_all(b"HEAD / HTTP/1.1\r\nHost: app.local\r\nConnection: close\r\n\r\n").await.unwrap();
+        let mut response = Vec::new();
+        stream.read_to_end(&mut response).await.unwrap();
+        let response = String::from_utf8(response).unwrap().to_lowercase();
+
+        assert!(response.starts_with("http/1.1 200"), "{}", response);
+        // 백엔드 본문 길이를 모르므로 Content-Length: 0을 만들어 내지 않음
+        assert!(response.contains("x-backend: head"), "{}", response);
+        assert!(!response.contains("content-length"), "{}", response);
+    }
+
+    /// 현재 프로세스의 RSS (KiB)
+    #[cfg(target_os = "linux")]
+    fn rss_kib() -> u64 {
+        std::fs::read_to_string("/proc/self/status").unwrap()
+            .lines()
+            .find_map(|line| line.strip_prefix("VmRSS:"))
+            .and_then(|value| value.trim().trim_end_matches("kB").trim().parse().ok())
+            .unwrap()
+    }
+
+    #[cfg(target_os = "linux")]
+    #[tokio::test]
+    async fn test_large_response_streamed_without_buffering() {
+        const CHUNK: usize = 1024 * 1024;
+        const TOTAL: usize = 100 * CHUNK;
+
+        // 100MB를 1MB씩 나눠 보내는 백엔드
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let backend_addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            let (mut stream, _) = listener.accept().await.unwrap();
+            let mut buf = [0u8; 1024];
+            let _ = stream.read(&mut buf).await;
+            let head = format!("HTTP/1.1 200 OK\r\ncontent-length: {TOTAL}\r\nconnection: close\r\n\r\n");
+            stream.write_all(head.as_bytes()).await.unwrap();
+            let chunk = vec![b'x'; CHUNK];
+            for _ in 0..TOTAL / CHUNK {
+                if stream.write_all(&chunk).await.is_err() {
+                    return;
+                }
+            }
+        });
+
+        let mut table = RoutingTable::new();
+        table.add_route("large.local".to_string(), BackendService::new(backend_addr), None).unwrap();
+        let proxy_addr = spawn_proxy(table).await;
+
+        let baseline = rss_kib();
+        let mut response = get_via_proxy(proxy_addr, "large.local", "/").await;
+        assert_eq!(response.status(), 200);
+        assert_eq!(response.content_length(), Some(TOTAL as u64));
+
+        let mut received = 0;
+        let mut peak = baseline;
+        while let Some(chunk) = response.chunk().await.unwrap() {
+            received += chunk.len();
+            peak = peak.max(rss_kib());
+        }
+
+        assert_eq!(received, TOTAL);
+        // 바디를 모았다면 100MB 이상 증가
+        let growth_mib = peak.saturating_sub(baseline) / 1024;
+        assert!(growth_mib < 32, "RSS grew by {growth_mib} MiB while streaming");
+    }
+
+    #[tokio::test]
+    async fn test_max_request_header_size() {
+        let backend_addr = spawn_status_backend("200 OK").await;
+        let mut table = RoutingTable::new();
+        table.add_route("limit.local".to_string(), BackendService::new(backend_addr), None).unwrap();
+        let proxy_addr = spawn_handler(RequestHandler::new(
+            Arc::new(RwLock::new(table)),
+            MiddlewareManager::default(),
+        ).with_max_request_header_size(256)).await;
+
+        let response = get_via_proxy(proxy_addr, "limit.local", "/").await;
+        assert_eq!(response.status(), 200);
+
+        let response = reqwest::Client::new()
+            .get(format!("http://{}/", proxy_addr))
+            .header("host", "limit.local")
+            .header("x-large", "a".repeat(300))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), 431);
+    }
+
+    /// 실제 네트워크 대신 메모리 스트림으로 고정 응답을 돌려주는 커넥터
+    #[derive(Default)]
+    struct MockConnector {
+        connected: Mutex<Vec<std::net::SocketAddr>>,
+        fail: bool,
+    }
+
+    #[async_trait]
+    impl UpstreamConnector for MockConnector {
+        async fn connect(&self, addr: std::net::SocketAddr) -> std::io::Result<Box<dyn AsyncReadWrite>> {
+            self.connected.lock().unwrap().push(addr);
+            if self.fail {
+                return Err(std::io::Error::new(std::io::ErrorKind::ConnectionRefused, "mock refused"));
+            }
+
+            let (client, mut server) = tokio::io::duplex(4096);
+            tokio::spawn(async move {
+                let mut request = Vec::new();
+                let mut buf = [0u8; 1024];
+                while !request.windows(4).any(|w| w == b"\r\n\r\n") {
+                    match server.read(&mut buf).await {
+                        Ok(0) | Err(_) => return,
+                        Ok(n) => request.extend_from_slice(&buf[..n]),
+                    }
+                }
+                let _ = server.write_all(
+                    b"HTTP/1.1 200 OK\r\nContent-Length: 6\r\nConnection: close\r\n\r\nmocked"
+                ).await;
+            });
+            Ok(Box::new(client))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_upstream_connector_injection() {
+        // 연결할 수 없는 주소여도 커넥터가 응답을 제공
+        let backend_addr: std::net::SocketAddr = "192.0.2.1:8080".parse().unwrap();
+        let mut table = RoutingTable::new();
+        table.add_route("mock.local".to_string(), BackendService::new(backend_addr), None).unwrap();
+        let table = Arc::new(RwLock::new(table));
+
+        let connector = Arc::new(MockConnector::default());
+        let proxy_addr = spawn_handler(RequestHandler::new(table.clone(), MiddlewareManager::default())
+            .with_upstream_connector(connector.clone())).await;
+
+        let response = get_via_proxy(proxy_addr, "mock.local", "/").await;
+        assert_eq!(response.status(), 200);
+        assert_eq!(response.text().await.unwrap(), "mocked");
+        assert_eq!(*connector.connected.lock().unwrap(), vec![backend_addr]);
+
+        // 커넥터 연결 실패는 502
+        let failing = Arc::new(MockConnector { fail: true, ..Default::default() });
+        let proxy_addr = spawn_handler(RequestHandler::new(table, MiddlewareManager::default())
+            .with_upstream_connector(failing.clone())).await;
+        let response = get_via_proxy(proxy_addr, "mock.local", "/").await;
+        assert_eq!(response.status(), 502);
+        assert_eq!(failing.connected.lock().unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_container_stopped_without_route() {
+        let table = Arc::new(RwLock::new(RoutingTable::new()));
+        let handler = DockerEventHandler::new(table.clone(), Arc::new(RwLock::new(MiddlewareManager::default())));
+
+        // 등록되지 않은 컨테이너의 중지 이벤트는 오류 없이 무시
+        handler.handle_event(DockerEvent::ContainerStopped {
+            container_id: "never-started".to_string(),
+            host: "missing.local".to_string(),
+        }).await.unwrap();
+        assert!(table.read().await.routes.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_tenant_middleware_chain_selection() {
+        let headers_middleware = |value: &str| {
+            let mut config = MiddlewareConfig::new(MiddlewareType::Headers);
+            config.enabled = true;
+            config.settings.insert("headers.response.set.X-Chain".to_string(), value.to_string());
+            config
+        };
+        let middlewares = HashMap::from([
+            ("default-headers".to_string(), headers_middleware("default")),
+            ("acme-headers".to_string(), headers_middleware("acme")),
+        ]);
+        let router_middlewares = HashMap::from([
+            ("api".to_string(), vec!["default-headers".to_string()]),
+            ("api:acme".to_string(), vec!["acme-headers".to_string()]),
+        ]);
+
+        let backend_addr = spawn_status_backend("200 OK").await;
+        let mut table = RoutingTable::new();
+        table.add_route(
+            "tenant.local".to_string(),
+            BackendService::with_router(backend_addr, Some("api".to_string())),
+            None,
+        ).unwrap();
+        let manager = MiddlewareManager::new(&middlewares, &router_middlewares)
+            .with_tenant_header(hyper::header::HeaderName::from_static("x-org"));
+        let proxy_addr = spawn_handler(RequestHandler::new(Arc::new(RwLock::new(table)), manager)).await;
+
+        let get_with_tenant = |tenant: Option<&'static str>| async move {
+            let mut request = reqwest::Client::new()
+                .get(format!("http://{}/", proxy_addr))
+                .header("host", "tenant.local");
+            if let Some(tenant) = tenant {
+                request = request.header("x-org", tenant);
+            }
+            request.send().await.unwrap()
+        };
+
+        // 헤더가 없거나 전용 체인이 없는 테넌트는 라우터 체인 사용
+        assert_eq!(get_with_tenant(None).await.headers()["x-chain"], "default");
+        assert_eq!(get_with_tenant(Some("other")).await.headers()["x-chain"], "default");
+        assert_eq!(get_with_tenant(Some("acme")).await.headers()["x-chain"], "acme");
+    }
+
+    /// 요청 수를 세어 바디로 돌려주는 백엔드. `/nostore` 경로는 `Cache-Control: no-store`로 응답합니다.
+    async fn spawn_counting_backend() -> (std::net::SocketAddr, Arc<std::sync::atomic::AtomicUsize>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let count = Arc::new(std::sync::atomic::AtomicUsize::new(0));
+        let counter = count.clone();
+        tokio::spawn(async move {
+            while let Ok((mut stream, _)) = listener.accept().await {
+                let counter = counter.clone();
+                tokio::spawn(async move {
+                    let mut buf = [0u8; 4096];
+                    let n = stream.read(&mut buf).await.unwrap_or(0);
+                    let request = String::from_utf8_lossy(&buf[..n]);
+                    let path = request.split_whitespace().nth(1).unwrap_or("/");
+                    let mut body = (counter.fetch_add(1, Ordering::SeqCst) + 1).to_string();
+                    let extra_headers = match path {
+                        "/nostore" => "cache-control: no-store\r\n",
+                        "/vary" => "vary: accept-language\r\n",
+                        "/vary-star" => "vary: *\r\n",
+                        _ => "",
+                    };
+                    if path.starts_with("/big") {
+                        body = format!("{body:0>16}");
+                    }
+                    let response = if path == "/big-chunked" {
+                        // 길이를 알 수 없는 바디는 청크 두 개로 나눠 전송
+                        let (first, second) = body.split_at(8);
+                        format!(
+                            "HTTP/1.1 200 OK\r\ntransfer-encoding: chunked\r\nconnection: close\r\n\r\n8\r\n{first}\r\n8\r\n{second}\r\n0\r\n\r\n"
+                        )
+                    } else {
+                        format!(
+                            "HTTP/1.1 200 OK\r\n{extra_headers}content-length: {}\r\nconnection: close\r\n\r\n{body}",
+                            body.len()
+                        )
+                    };
+                    let _ = stream.write_all(response.as_bytes()).await;
+                });
+            }
+        });
+        (addr, count)
+    }
+
+    #[tokio::test]
+    async fn test_sticky_session_cookie() {
+        let (addr1, count1) = spawn_counting_backend().await;
+        let (addr2, count2) = spawn_counting_backend().await;
+        let mut service = BackendService::new(addr1);
+        service.enable_load_balancer(LoadBalancerStrategy::RoundRobin { current_index: AtomicUsize::new(0) });
+        service.add_address(addr2, 1).unwrap();
+        service.set_sticky_cookie(Some(StickyCookieConfig::new("srv")));
+
+        let mut table = RoutingTable::new();
+        table.add_route("sticky.local".to_string(), service, None).unwrap();
+        let proxy_addr = spawn_proxy(table).await;
+
+        // 첫 요청은 로드밸런서가 고른 주소를 쿠키로 기록
+        let response = get_via_proxy(proxy_addr, "sticky.local", "/").await;
+        let set_cookie = response.headers()["set-cookie"].to_str().unwrap().to_string();
+        assert!(set_cookie.starts_with("srv="), "{}", set_cookie);
+        assert!(set_cookie.contains("HttpOnly") && set_cookie.contains("SameSite=Lax"), "{}", set_cookie);
+        let cookie = set_cookie.split(';').next().unwrap().to_string();
+
+        // 쿠키가 있으면 라운드 로빈 대신 같은 백엔드로 보내고 쿠키를 다시 쓰지 않음
+        for _ in 0..4 {
+            let response = reqwest::Client::new()
+                .get(format!("http://{}/", proxy_addr))
+                .header("host", "sticky.local")
+                .header("cookie", &cookie)
+                .send()
+                .await
+                .unwrap();
+            assert!(!response.headers().contains_key("set-cookie"));
+        }
+        let counts = (count1.load(Ordering::SeqCst), count2.load(Ordering::SeqCst));
+        assert!(counts == (5, 0) || counts == (0, 5), "{:?}", counts);
+
+        // 풀에 없는 주소를 가리키는 쿠키는 무시하고 새 쿠키를 발급
+        let stale = format!("srv={}", StickyCookieConfig::encode_address("127.0.0.1:1".parse().unwrap()));
+        let response = reqwest::Client::new()
+            .get(format!("http://{}/", proxy_addr))
+            .header("host", "sticky.local")
+            .header("cookie", stale)
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), 200);
+        assert!(response.headers().contains_key("set-cookie"));
+    }
+
+    #[tokio::test]
+    async fn test_rate_limit_ignores_spoofed_forwarded_for() {
+        let mut rate_limit = MiddlewareConfig::new(MiddlewareType::RateLimit);
+        rate_limit.enabled = true;
+        rate_limit.settings.insert("rateLimit.average".to_string(), "1".to_string());
+        rate_limit.settings.insert("rateLimit.burst".to_string(), "2".to_string());
+        let middlewares = HashMap::from([("limit".to_string(), rate_limit)]);
+        let router_middlewares = HashMap::from([
+            ("api".to_string(), vec!["limit".to_string()]),
+            ("trusted".to_string(), vec!["limit".to_string()]),
+        ]);
+
+        let backend_addr = spawn_status_backend("200 OK").await;
+        let mut table = RoutingTable::new();
+        table.add_route(
+            "limit.local".to_string(),
+            BackendService::with_router(backend_addr, Some("api".to_string())),
+            None,
+        ).unwrap();
+        let mut trusted = BackendService::with_router(backend_addr, Some("trusted".to_string()));
+        trusted.set_trusted_proxy_cidrs(vec!["127.0.0.0/8".parse().unwrap()]);
+        table.add_route("trusted.local".to_string(), trusted, None).unwrap();
+        let manager = MiddlewareManager::new(&middlewares, &router_middlewares);
+        let proxy_addr = spawn_handler(RequestHandler::new(Arc::new(RwLock::new(table)), manager)).await;
+
+        let send = |host: &'static str, forwarded_for: String| async move {
+            reqwest::Client::new()
+                .get(format!("http://{}/", proxy_addr))
+                .header("host", host)
+                .header("x-forwarded-for", forwarded_for)
+                .send()
+                .await
+                .unwrap()
+                .status()
+        };
+
+        // 신뢰하지 않는 클라이언트가 X-Forwarded-For를 바꿔도 같은 버킷을 사용
+        for i in 0..2 {
+            assert_eq!(send("limit.local", format!("10.0.0.{}", i)).await, 200);
+        }
+        assert_eq!(send("limit.local", "10.0.0.9".to_string()).await, 429);
+
+        // 신뢰하는 프록시를 거친 요청은 전달된 클라이언트 IP별로 제한
+        for i in 0..3 {
+            assert_eq!(send("trusted.local", format!("192.0.2.{}", i)).await, 200);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_response_cache_middleware() {
+        let mut cache = MiddlewareConfig::new(MiddlewareType::Cache);
+        cache.enabled = true;
+        cache.settings.insert("cache.defaultTtlSecs".to_string(), "60".to_string());
+        cache.settings.insert("cache.maxBodyBytes".to_string(), "8".to_string());
+        let middlewares = HashMap::from([("cache".to_string(), cache)]);
+        let router_middlewares = HashMap::from([("api".to_string(), vec!["cache".to_string()])]);
+
+        let (backend_addr, count) = spawn_counting_backend().await;
+        let mut table = RoutingTable::new();
+        table.add_route(
+            "cache.local".to_string(),
+            BackendService::with_router(backend_addr, Some("api".to_string())),
+            None,
+        ).unwrap();
+        let manager = MiddlewareManager::new(&middlewares, &router_middlewares);
+        let proxy_addr = spawn_handler(RequestHandler::new(Arc::new(RwLock::new(table)), manager)).await;
+
+        let client = reqwest::Client::new();
+        let send = |method: reqwest::Method, path: &'static str, headers: &'static [(&'static str, &'static str)]| {
+            let mut request = client.request(method, format!("http://{}{}", proxy_addr, path))
+                .header("host", "cache.local");
+            for (name, value) in headers {
+                request = request.header(*name, *value);
+            }
+            async move {
+                let response = request.send().await.unwrap();
+                let x_cache = response.headers().get("x-cache")
+                    .map(|v| v.to_str().unwrap().to_string());
+                (x_cache, response.text().await.unwrap())
+            }
+        };
+
+        // 두 번째 GET은 백엔드를 거치지 않음
+        assert_eq!(send(reqwest::Method::GET, "/a", &[]).await, (Some("MISS".to_string()), "1".to_string()));
+        assert_eq!(send(reqwest::Method::GET, "/a", &[]).await, (Some("HIT".to_string()), "1".to_string()));
+        assert_eq!(count.load(Ordering::SeqCst), 1);
+
+        // 쿼리 문자열은 캐시 키에 포함
+        assert_eq!(send(reqwest::Method::GET, "/a?page=2", &[]).await.0.as_deref(), Some("MISS"));
+        // Authorization/Cookie 요청과 POST는 캐시하지 않음
+        assert_eq!(send(reqwest::Method::GET, "/a", &[("authorization", "Basic dXNlcjpwYXNz")]).await, (None, "3".to_string()));
+        assert_eq!(send(reqwest::Method::GET, "/a", &[("cookie", "session=abc")]).await, (None, "4".to_string()));
+        assert_eq!(send(reqwest::Method::POST, "/a", &[]).await, (None, "5".to_string()));
+        // no-store 응답은 저장하지 않음
+        send(reqwest::Method::GET, "/nostore", &[]).await;
+        assert_eq!(send(reqwest::Method::GET, "/nostore", &[]).await.0.as_deref(), Some("MISS"));
+        assert_eq!(count.load(Ordering::SeqCst), 7);
+
+        // Vary 요청 헤더 값별로 저장
+        assert_eq!(send(reqwest::Method::GET, "/vary", &[("accept-language", "en")]).await.0.as_deref(), Some("MISS"));
+        assert_eq!(send(reqwest::Method::GET, "/vary", &[("accept-language", "en")]).await.0.as_deref(), Some("HIT"));
+        assert_eq!(send(reqwest::Method::GET, "/vary", &[("accept-language", "ko")]).await.0.as_deref(), Some("MISS"));
+        // Vary: * 응답은 저장하지 않음
+        send(reqwest::Method::GET, "/vary-star", &[]).await;
+        assert_eq!(send(reqwest::Method::GET, "/vary-star", &[]).await.0.as_deref(), Some("MISS"));
+        assert_eq!(count.load(Ordering::SeqCst), 11);
+
+        // 최대 크기를 넘는 바디는 저장하지 않고 그대로 스트리밍
+        assert_eq!(send(reqwest::Method::GET, "/big", &[]).await, (Some("MISS".to_string()), "0000000000000012".to_string()));
+        assert_eq!(send(reqwest::Method::GET, "/big", &[]).await, (Some("MISS".to_string()), "0000000000000013".to_string()));
+        assert_eq!(send(reqwest::Method::GET, "/big-chunked", &[]).await, (Some("MISS".to_string()), "0000000000000014".to_string()));
+        assert_eq!(send(reqwest::Method::GET, "/big-chunked", &[]).await, (Some("MISS".to_string()), "0000000000000015".to_string()));
+    }
+}
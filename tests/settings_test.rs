@@ -1,4 +1,4 @@
-use reverse_proxy_traefik::settings::Settings;
+use reverse_proxy_traefik::settings::{Settings, TlsSettings};
 use std::sync::Once;
 
 #[cfg(test)]
@@ -28,6 +28,11 @@ mod tests {
         std::env::remove_var("PROXY_LOG_LEVEL");
         std::env::remove_var("PROXY_DOCKER_NETWORK");
         std::env::remove_var("PROXY_LABEL_PREFIX");
+        std::env::remove_var("PROXY_TLS_ENABLED");
+        std::env::remove_var("PROXY_TLS_CERT_PEM");
+        std::env::remove_var("PROXY_TLS_KEY_PEM");
+        std::env::remove_var("PROXY_DEFAULT_BACKEND");
+        std::env::remove_var("PROXY_CONFIG_OVERLAYS");
     }
 
     // 테스트용 임시 TOML 파일 생성 헬퍼
@@ -127,6 +132,7 @@ mod tests {
         std::env::set_var("PROXY_LOG_LEVEL", "debug");
         std::env::set_var("PROXY_DOCKER_NETWORK", "custom-network");
         std::env::set_var("PROXY_LABEL_PREFIX", "custom.");
+        std::env::set_var("PROXY_DEFAULT_BACKEND", "127.0.0.1:9000");
 
         // 설정 로드 및 검증
         let settings = Settings::from_env().await.unwrap();
@@ -137,6 +143,7 @@ mod tests {
         assert_eq!(settings.logging.level, tracing::Level::DEBUG);
         assert_eq!(settings.docker.network, "custom-network");
         assert_eq!(settings.docker.label_prefix, "custom.");
+        assert_eq!(settings.server.default_backend, Some("127.0.0.1:9000".parse().unwrap()));
 
         teardown();
     }
@@ -176,6 +183,91 @@ mod tests {
         let result = Settings::from_env().await;
         assert!(result.is_err(), "너무 긴 라벨 접두사는 허용되지 않아야 함");
 
+        // 5. 잘못된 기본 백엔드 주소
+        teardown();
+        std::env::set_var("PROXY_DEFAULT_BACKEND", "localhost");
+        let result = Settings::from_env().await;
+        assert!(result.is_err(), "ip:port가 아닌 기본 백엔드는 허용되지 않아야 함");
+
+        teardown();
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_tls_settings_inline_pem() {
+        setup();
+
+        let fixtures = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/tls");
+        let cert_pem = std::fs::read_to_string(fixtures.join("cert_a.pem")).unwrap();
+        let key_pem = std::fs::read_to_string(fixtures.join("key_a.pem")).unwrap();
+        std::env::set_var("PROXY_TLS_ENABLED", "true");
+        // 인라인 PEM이 경로보다 우선
+        std::env::set_var("PROXY_TLS_CERT", "/path/to/cert.pem");
+        std::env::set_var("PROXY_TLS_CERT_PEM", &cert_pem);
+        std::env::set_var("PROXY_TLS_KEY_PEM", &key_pem);
+
+        let settings = TlsSettings::from_env().unwrap();
+        let cert_path = settings.cert_path.clone().unwrap();
+        let key_path = settings.key_path.clone().unwrap();
+        assert_eq!(std::fs::read_to_string(cert_path.as_path()).unwrap(), cert_pem);
+        assert_eq!(std::fs::read_to_string(key_path.as_path()).unwrap(), key_pem);
+        settings.validate().await.unwrap();
+
+        // 복제본이 남아 있는 동안 임시 파일 유지
+        let cloned = settings.clone();
+        drop(settings);
+        assert!(cert_path.as_path().exists());
+        drop(cloned);
+        assert!(!cert_path.as_path().exists());
+        assert!(!key_path.as_path().exists());
+
+        teardown();
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_https_enabled_with_inline_pem_only() {
+        setup();
+
+        let fixtures = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/tls");
+        let cert_pem = std::fs::read_to_string(fixtures.join("cert_a.pem")).unwrap();
+        let key_pem = std::fs::read_to_string(fixtures.join("key_a.pem")).unwrap();
+        std::env::set_var("PROXY_HTTPS_ENABLED", "true");
+        std::env::set_var("PROXY_TLS_CERT_PEM", &cert_pem);
+        std::env::set_var("PROXY_TLS_KEY_PEM", &key_pem);
+
+        // PROXY_TLS_CERT/PROXY_TLS_KEY 없이도 서버 설정이 인라인 PEM 임시 파일을 사용해야 함
+        let settings = Settings::from_env().await.unwrap();
+        let cert_path = settings.server.tls_cert_path.clone().unwrap();
+        let key_path = settings.server.tls_key_path.clone().unwrap();
+        assert_eq!(std::fs::read_to_string(&cert_path).unwrap(), cert_pem);
+        assert_eq!(std::fs::read_to_string(&key_path).unwrap(), key_pem);
+
+        teardown();
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_load_validates_after_overlays() {
+        setup();
+
+        // 환경변수만으로는 인증서/키 경로가 없어 유효하지 않은 TLS 설정
+        std::env::set_var("PROXY_TLS_ENABLED", "true");
+        assert!(Settings::from_env().await.is_err());
+        assert!(Settings::load().await.is_err());
+
+        // Secret 계층이 인증서/키 경로를 채우면 병합된 설정은 유효
+        let fixtures = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/tls");
+        let (overlay_path, _temp_dir) = create_test_toml(&format!(
+            "[tls]\ncert_path = {:?}\nkey_path = {:?}\n",
+            fixtures.join("cert_a.pem").to_str().unwrap(),
+            fixtures.join("key_a.pem").to_str().unwrap(),
+        ));
+        std::env::set_var("PROXY_CONFIG_OVERLAYS", &overlay_path);
+        let settings = Settings::load().await.unwrap();
+        assert!(settings.tls.enabled);
+        assert!(settings.tls.cert_path.is_some());
+
         teardown();
     }
-} 
\ No newline at end of file
+}